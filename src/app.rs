@@ -1,789 +1,10947 @@
 use std::sync::Arc;
-use std::io;
-use tokio::sync::Mutex;
+use std::io::{self, Write};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
 
 use reqwest::Client;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use tracing::Instrument;
 
-use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 use ratatui::{
-    backend::CrosstermBackend,
+    backend::{Backend, CrosstermBackend},
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Margin, Position, Rect},
     prelude::Alignment,
-    style::{Style, Stylize},
+    style::{Style, Styled, Stylize},
     symbols::border,
     text::Line,
-    widgets::{Block, Cell, Clear, Paragraph, Row, Table, Widget},
+    widgets::{
+        Block, Cell, Clear, Gauge, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Sparkline, StatefulWidget, Table, TableState, Tabs, Widget,
+    },
     Frame, Terminal,
 };
 
 // RPC Client
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::{pubkey::Pubkey, signature::Signature};
-use solana_transaction_status_client_types::{
-    EncodedTransaction::Json, UiMessage::Raw, UiTransactionEncoding,
+use crate::bookmarks::{Bookmark, Bookmarks};
+use crate::command;
+use crate::error::AppError;
+use crate::fees::{self, FeeEstimate};
+use crate::fixtures::Transport;
+use crate::format::{
+    format_duration, format_lamport_change, format_lamports, format_lamports_short, format_ratio, format_relative,
+    format_stake, format_token_amount, format_token_change, grapheme_count, group_digits, group_digits_u128,
+    pop_grapheme, ratio, truncate, CurrencyUnit,
 };
+use crate::history::QueryHistory;
+use crate::instruction::{self, ComputeBudget, ListedInstruction};
+use crate::json_tree::{JsonTree, JsonTreeView};
+use crate::keymap::{AppAction, Keymap, ACTION_NAMES};
+use crate::labels;
+use crate::live::{self, SlotEvent, WatchEvent};
+use crate::logging::LogBuffer;
+use crate::query::{normalize_query, parse_slot, QueryKind};
+use crate::rpc::{self, ChainApi, LatencyTracker, RateLimiter, ResponseCache, RpcApi, RpcResult};
+use crate::stake;
+use crate::storage;
+use crate::theme::Theme;
+use crate::token;
+
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status_client_types::{TransactionConfirmationStatus, TransactionStatus};
 use std::str::FromStr;
+use unicode_segmentation::UnicodeSegmentation;
 
 const DEVNET_RPC: &str = "https://rpc.devnet.soo.network/rpc";
 const TESTNET_RPC: &str = "https://rpc.testnet.soo.network/rpc";
+const MAINNET_RPC: &str = "https://rpc.mainnet.soo.network/rpc";
+const SOLANA_MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+// Default interval between dashboard refreshes
+pub const DEFAULT_REFRESH_SECS: u64 = 10;
+
+// How long to wait for input before redrawing
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Redraw at least this often with nothing changed, for clocks and relative times
+const IDLE_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+// Number of performance samples used for the TPS sparkline
+const PERFORMANCE_SAMPLES: usize = 10;
+
+// Slots the newest snapshot may trail the tip before it is shown as lagging; full
+// snapshots are taken every 25,000 slots by default
+const SNAPSHOT_LAG_ALERT: u64 = 50_000;
+
+// How long status messages such as "Copied!" stay visible
+const STATUS_DURATION: Duration = Duration::from_secs(1);
+const STATUS_ERROR_DURATION: Duration = Duration::from_secs(4);
+
+// Below this size the layout can't fit, so only a warning is drawn
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
+
+// Two clicks on the same row within this interval open it
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+// Rows moved per scroll wheel step
+const MOUSE_SCROLL_ROWS: usize = 3;
+
+// Number of recent blocks listed on the Blocks tab
+const RECENT_BLOCKS: usize = 10;
+
+// Completed epochs whose inflation rewards are loaded with 'R'
+const REWARD_EPOCHS: u64 = 5;
+
+// Frames of the spinner shown while a query is being fetched
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+// Signatures per page of account history (the RPC maximum)
+const HISTORY_PAGE_SIZE: usize = 1000;
+
+// Loaded history rows are capped to keep memory bounded
+const MAX_HISTORY_ROWS: usize = 10_000;
+
+// How often a watched account is polled when its RPC has no WebSocket
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Pause before a query goes out, so one replaced right away never reaches the RPC
+const FETCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+// Waits before asking again for a transaction the node doesn't have yet; a signature
+// pasted right after sending is often known to getSignatureStatuses first
+const TRANSACTION_RETRY_DELAYS: [Duration; 2] = [Duration::from_millis(400), Duration::from_millis(1200)];
+
+// How often a watched transaction's status is polled
+const SIGNATURE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// How long transactions found by a watch stay highlighted
+const NEW_ROW_HIGHLIGHT: Duration = Duration::from_secs(6);
+
+// Token holdings listed before collapsing the rest into a "+N more" row
+const MAX_TOKEN_HOLDING_ROWS: usize = 50;
+
+// Maximum number of previous views kept for the Back key
+const MAX_VIEW_HISTORY: usize = 20;
+
+// Query results kept for repeated lookups
+const QUERY_CACHE_SIZE: usize = 100;
+
+// How long a cached account (or skipped slot) is reused; finalized blocks and
+// transactions never change and are kept until evicted
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RpcNetwork {
     Devnet,
     Testnet,
+    Mainnet,        // SOON mainnet
+    SolanaMainnet,  // Solana L1 mainnet-beta
+    Custom(String), // URL entered with 'N'
 }
 
 impl RpcNetwork {
+    // Order in which 'n' cycles through the preset networks
+    pub const ALL: [RpcNetwork; 4] = [
+        RpcNetwork::Devnet,
+        RpcNetwork::Testnet,
+        RpcNetwork::Mainnet,
+        RpcNetwork::SolanaMainnet,
+    ];
+
     // Method to get the RPC URL for the current network
-    pub fn get_url(&self) -> &'static str {
+    pub fn get_url(&self) -> &str {
         match self {
             RpcNetwork::Devnet => DEVNET_RPC,
             RpcNetwork::Testnet => TESTNET_RPC,
+            RpcNetwork::Mainnet => MAINNET_RPC,
+            RpcNetwork::SolanaMainnet => SOLANA_MAINNET_RPC,
+            RpcNetwork::Custom(url) => url,
         }
     }
 
-    // Method to display the network name
-    pub fn name(&self) -> &'static str {
+    // Method to display the network name; custom networks show their host
+    pub fn name(&self) -> &str {
         match self {
             RpcNetwork::Devnet => "Devnet",
             RpcNetwork::Testnet => "Testnet",
+            RpcNetwork::Mainnet => "Mainnet",
+            RpcNetwork::SolanaMainnet => "Solana Mainnet",
+            RpcNetwork::Custom(url) => rpc_host(url),
         }
     }
-}
-
-#[derive(Debug)]
-pub struct App {
-    pub query: String,
-    pub input_mode: InputMode,
-    pub slot_info: Option<i64>,
-    pub transaction_info: Option<i64>,
-    pub supply_info: Option<Value>,
-    pub json_response: Option<Value>,
-    pub address_sign: Option<Value>,
-    pub exit: bool,
-    pub show_popup: bool,
-    pub current_rpc_network: RpcNetwork,  // Changed from String to RpcNetwork
-    client: Client,
-}
-
-
-#[derive(Debug)]
-pub enum InputMode {
-    Normal,
-    Editing,
-}
 
-impl Default for App {
-    fn default() -> Self {
-        Self {
-            query: String::new(),
-            input_mode: InputMode::Normal,
-            slot_info: None,
-            transaction_info: None,
-            supply_info: None,
-            json_response: None,
-            address_sign: None,
-            exit: false,
-            show_popup: false,
-            current_rpc_network: RpcNetwork::Devnet,
-            client: Client::new(),
+    // Name saved in bookmarks; custom networks are saved by URL
+    pub fn saved_name(&self) -> &str {
+        match self {
+            RpcNetwork::Custom(url) => url,
+            _ => self.name(),
         }
     }
-}
 
-impl App {
-    //toggle RPCs
-     pub fn toggle_rpc_network(&mut self) {
-        // Toggle between Devnet and Testnet
-        self.current_rpc_network = match self.current_rpc_network {
-            RpcNetwork::Devnet => RpcNetwork::Testnet,
-            RpcNetwork::Testnet => RpcNetwork::Devnet,
-        };
+    pub fn from_name(name: &str) -> Option<Self> {
+        RpcNetwork::ALL
+            .into_iter()
+            .find(|network| network.name() == name)
+            .or_else(|| parse_rpc_url(name).ok().map(RpcNetwork::Custom))
     }
 
-    pub fn get_current_rpc_url(&self) -> &str {
-        self.current_rpc_network.get_url()
-    }        
+    // The preset after this one, wrapping around; a custom network goes back to the first preset
+    pub fn next(&self) -> RpcNetwork {
+        let index = RpcNetwork::ALL
+            .iter()
+            .position(|network| network == self)
+            .map_or(0, |index| (index + 1) % RpcNetwork::ALL.len());
+        RpcNetwork::ALL[index].clone()
+    }
+}
 
-    //Fetch Intial Blockchain data
-    pub async fn fetch_initial_blockchain_data(
-        &mut self,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        
-        let current_rpc_url = self.get_current_rpc_url();
-        // Fetch slot Info
-        let slot_payload = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getSlot",
-        });
-
-        let slot_response = self
-            .client
-            .post(current_rpc_url)
-            .header("Content-Type", "application/json")
-            .json(&slot_payload)
-            .send()
-            .await?;
+// Bookmark popups: typing a label for the current query, or picking a saved bookmark
+#[derive(Debug, Clone)]
+pub enum BookmarkPopup {
+    Label(String),
+    List(usize),
+}
 
-        if slot_response.status().is_success() {
-            let slot_json: Value = slot_response.json().await?;
-            self.slot_info = slot_json.get("result").and_then(|r| r.as_i64());
-        }
+// Raw data of the queried account, shown 16 bytes per line
+#[derive(Debug, Clone, Default)]
+pub struct HexViewer {
+    pub data: Vec<u8>,
+    pub top_line: usize, // First visible line
+    page_lines: usize,  // Visible lines in the last frame
+    goto: Option<String>, // Offset being typed after 'g'
+}
 
-        // Fetch Supply Info
-        let supply_payload = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getSupply"
-        });
+impl HexViewer {
+    fn line_count(&self) -> usize {
+        self.data.len().div_ceil(16)
+    }
 
-        let current_rpc_url = self.get_current_rpc_url();
-        let supply_response = self
-            .client
-            .post(current_rpc_url)
-            .header("Content-Type", "application/json")
-            .json(&supply_payload)
-            .send()
-            .await?;
+    fn scroll_to(&mut self, line: usize) {
+        self.top_line = line.min(self.line_count().saturating_sub(self.page_lines.max(1)));
+    }
+}
 
-        if supply_response.status().is_success() {
-            let supply_json: Value = supply_response.json().await?;
-            self.supply_info = supply_json.get("result").cloned();
-        }
+// What the last frame measured: where things went, for mouse hit-testing, and how
+// far each text on screen can scroll. Drawing only reads the app; the coordinator
+// takes this in once the frame is out.
+#[derive(Debug, Default, Clone, Copy)]
+struct FrameLayout {
+    input_area: Rect,
+    results_area: Rect,
+    other_pane_area: Rect, // Pane of a split without focus
+    logs_area: Rect,
+    max_popup_scroll: Option<u16>, // Of the help or instruction popup; None while neither is open
+    max_logs_scroll: Option<u16>,
+}
 
-        // to get transaction count
-        let transcation_payload = serde_json::json!({
-            "jsonrpc":"2.0",
-            "id":1,
-            "method":"getTransactionCount"
-        });
+// Text on the system clipboard; there is none without a display or clipboard tool
+fn clipboard_contents() -> Result<String, AppError> {
+    cli_clipboard::get_contents().map_err(|err| AppError::ClipboardUnavailable(err.to_string()))
+}
 
-        let transaction_response = self
-            .client
-            .post("https://rpc.devnet.soo.network/rpc")
-            .header("Content-Type", "application/json")
-            .json(&transcation_payload)
-            .send()
-            .await?;
+// Pasted text as one line: signatures copied from explorers often carry a
+// trailing newline
+fn paste_text(text: &str) -> String {
+    text.trim().replace(['\r', '\n'], "")
+}
 
-        if transaction_response.status().is_success() {
-            let transaction_json: Value = transaction_response.json().await?;
-            self.transaction_info = transaction_json.get("result").and_then(|r| r.as_i64());
+// History rows as CSV with a header, quoting fields that need it
+fn history_csv(entries: &[&HistoryEntry]) -> String {
+    fn field(text: &str) -> String {
+        if text.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
         }
-
-        Ok(())
     }
+    let mut csv = String::from("signature,slot,block_time,confirmation_status,result,error,memo\n");
+    for entry in entries {
+        let block_time = entry
+            .block_time
+            .and_then(|time| chrono::DateTime::from_timestamp(time, 0))
+            .map_or(String::new(), |time| time.to_rfc3339());
+        let fields = [
+            entry.signature.clone(),
+            entry.slot.to_string(),
+            block_time,
+            entry.confirmation_status.clone().unwrap_or_default(),
+            if entry.err.is_some() { "failed" } else { "success" }.to_string(),
+            entry.err.as_ref().map_or(String::new(), Value::to_string),
+            entry.memo.clone().unwrap_or_default(),
+        ];
+        csv.push_str(&fields.iter().map(|text| field(text)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
 
-    pub async fn run(
-        app: Arc<Mutex<App>>,
-        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    ) -> io::Result<()> {
-        // Fetch initial data
-        {
-            let mut app = app.lock().await;
-            app.fetch_initial_blockchain_data()
-                .await
-                .unwrap_or_else(|e| eprintln!("Error fetching initial data: {}", e));
-        }
+// Parse a goto offset, in hex with a 0x prefix or in decimal
+fn parse_offset(input: &str) -> Option<usize> {
+    let input = input.trim();
+    match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => input.parse().ok(),
+    }
+}
 
-        loop {
-            {
-                let app = app.lock().await;
-                if app.exit {
-                    break;
-                }
-                terminal.draw(|frame| app.draw(frame))?;
-            }
+// Popup for typing a custom RPC URL, with the error from the last attempt
+#[derive(Debug, Clone, Default)]
+pub struct RpcPrompt {
+    pub input: String,
+    pub error: Option<String>,
+}
 
-            if let Ok(should_break) = App::handle_events(Arc::clone(&app)).await {
-                if should_break {
-                    break;
-                }
-            }
-        }
-        Ok(())
-    }
+// The query input, and the prompts that take the keyboard from it while open
+#[derive(Debug, Default)]
+pub struct QueryInput {
+    pub mode: InputMode,
+    cursor: usize, // Cursor position in the query, in chars
+    history_index: Option<usize>, // History entry shown in the input while browsing
+    history_draft: String, // What was typed before browsing the history
+    editing_from: Option<ViewSnapshot>, // View shown when editing started
+    prompt: Option<Prompt>,
+}
 
-    fn draw(&self, frame: &mut Frame) {
-        let chunks = Layout::vertical([
-            Constraint::Length(3), // Input field
-            Constraint::Min(1),    // Results area
-        ])
-        .split(frame.area());
+// A question asked on a line of its own, or in a popup for the RPC URL
+#[derive(Debug)]
+enum Prompt {
+    Command(String), // Text typed after ':'
+    Date(String), // Date filter being typed
+    Rpc(RpcPrompt),
+    ConfirmExport(String), // Existing file an export waits to overwrite, answered with y/n
+}
 
-        // Create a layout for bottom instructions
-        let bottom_layout =
-            Layout::horizontal([Constraint::Percentage(40),Constraint::Percentage(20), Constraint::Percentage(40)])
-                .split(chunks[0]);
+// A popup over the screen, which takes every key while open
+#[derive(Debug)]
+enum Popup {
+    Help { scroll: u16 }, // Scrolled to the line at `scroll`
+    DebugLog,
+    Bookmarks(BookmarkPopup),
+    HistoryDetail(HistoryEntry),
+    InstructionDetail { index: usize, scroll: u16 },
+    HexViewer(HexViewer),
+}
 
-        // Toggle with the N button
-        let input_title = match self.current_rpc_network {
-            RpcNetwork::Devnet => format!(" SOONSCAN {} ", " 🌐 Devnet ".green()),
-            RpcNetwork::Testnet => format!(" SOONSCAN {} ", " 🌐 Testnet ".blue()),
-        };        
+#[derive(Debug)]
+pub struct App {
+    pub pane: Pane, // The results pane that has focus
+    split: Option<Pane>, // The other pane while the results are split
+    pub input: QueryInput,
+    pub query_history: QueryHistory,
+    pub bookmarks: Bookmarks,
+    signature_watches: Vec<SignatureWatch>, // Listed in the watch panel
+    finished_watch: Option<String>, // Signature whose outcome the banner shows
+    bell: bool, // Ring the terminal bell on the next draw
+    dirty: bool, // Something changed since the last draw
+    popup: Option<Popup>,
+    pub refresh_interval: Duration,
+    pub health: ClusterHealth,
+    pub last_contact: Option<Instant>, // Last successful response from the RPC
+    pub live_slots: bool, // Follow the slot over WebSocket when the RPC allows it
+    pub transport: Transport, // Record the RPC answers, or replay recorded ones
+    pub slot_feed: SlotFeed,
+    updates: mpsc::UnboundedSender<AppUpdate>, // For background tasks to report back
+    update_queue: Option<mpsc::UnboundedReceiver<AppUpdate>>, // Until the coordinator takes it
+    slot_task: Option<JoinHandle<()>>,
+    dashboard_task: Option<JoinHandle<()>>, // The dashboard refetch in flight
+    latencies: Arc<std::sync::Mutex<LatencyTracker>>,
+    pub rate_limiter: Arc<RateLimiter>, // Shared by every request to the current network
+    genesis_hash: Option<String>, // Of the current network, fetched with the dashboard
+    query_cache: Arc<std::sync::Mutex<ResponseCache<CachedQuery>>>,
+    pub cache_ttl: Duration, // Lifetime of cached accounts
+    pub search: Option<ViewSearch>,
+    pub exit: bool,
+    pub shutdown: Shutdown, // Triggered on quit so background fetches stop waiting
+    pub log: LogBuffer, // Latest log lines, for the debug popup
+    pub log_file: Option<std::path::PathBuf>, // Where the log is also written
+    pub current_rpc_network: RpcNetwork,  // Changed from String to RpcNetwork
+    pub error: Option<ErrorBanner>, // Last error, cleared on the next key press
+    pub status: Option<StatusMessage>,
+    pub theme: Theme,
+    pub keymap: Keymap,
+    fetch_generation: u64, // Bumped by every query fetch
+    pub commitment: CommitmentConfig, // Commitment of search queries
+    pub currency_unit: CurrencyUnit,
+    pub relative_times: bool, // History timestamps as "3 min ago" instead of dates
+    pub tab: Tab,
+    screens: [Screen; Tab::ALL.len()], // One per tab, in the order of Tab::ALL
+    layout: FrameLayout,
+    last_click: Option<(Instant, usize)>, // Last clicked history row, for double-click
+    client: Client,
+    // Answers every request in place of the current network's RPC, such as a test double
+    pub chain: Option<Arc<dyn ChainApi>>,
+}
 
-        let input = Paragraph::new(self.query.as_str())
-            .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().yellow(),
-            })
-            .block(Block::bordered().title(input_title));
+// One results pane of the Search tab: its query, what was fetched for it and how
+// it is scrolled. Splitting the results shows two of them side by side.
+#[derive(Debug, Default)]
+pub struct Pane {
+    id: usize, // 0 for the left pane, 1 for the right one
+    pub query: String,
+    pub json_response: Option<Value>,
+    pub address_sign: Option<Vec<HistoryEntry>>,
+    pub token_holdings: TabData<Vec<TokenHolding>>, // Loaded on demand with 'T'
+    pub largest_holders: TabData<Vec<TokenHolder>>, // Of the mint on screen, also loaded with 'T'
+    pub inflation_rewards: TabData<Vec<EpochReward>>, // Of the stake or vote account on screen, loaded with 'R'
+    pub network_search: TabData<Vec<NetworkPresence>>, // Other networks asked for a query not found, with 'a'
+    pub holders_view: bool, // The mint's holders ranked instead of its details, toggled with 'h'
+    pub leader_lookup: bool, // The leader of the block on screen is being looked up, with 'P'
+    pub history_paging: HistoryPaging,
+    pub scroll_offset: usize,
+    pub selected_history: Option<usize>, // Selected row in the transaction history or instruction list
+    pub view_history: Vec<ViewSnapshot>, // Previous views, most recent last
+    pub fetch_task: Option<FetchTask>,
+    pub state: QueryState,
+    results_height: usize, // Visible rows in the results table in the last frame
+    watch: Option<AddressWatch>, // New activity of the account on screen, toggled with 'w'
+    arrivals: HashMap<String, Instant>, // When the watch added each signature, for the highlight
+    raw_view: Option<JsonTree>, // Raw JSON shown instead of the table, toggled with 'J'
+}
 
-        frame.render_widget(input, chunks[0]);
+// Where the search of a pane stands; the results table is drawn from this alone
+#[derive(Debug, Default)]
+pub enum QueryState {
+    #[default]
+    Idle, // Nothing searched yet, or the results were cleared
+    Loading {
+        query: String,
+        started_at: Instant,
+    },
+    Loaded, // The result is in json_response
+    Failed {
+        query: String,
+        error: AppError,
+    },
+}
 
-        // Bottom right instructions
-        let instructions = Paragraph::new(match self.input_mode {
-            InputMode::Normal => " Press 'e' to edit ".blue().bold(),
-            InputMode::Editing => " Enter: Submit, Esc: Cancel ".blue().bold(),
-        })
-        .alignment(Alignment::Right);
+// A running watch for new transactions of the account in a pane
+#[derive(Debug)]
+pub struct AddressWatch {
+    handle: JoinHandle<()>,
+    pub live: bool, // Notified over WebSocket; polling otherwise
+}
 
-        frame.render_widget(instructions, bottom_layout[2]);
+// A transaction followed until it confirms, on the network it was started on
+#[derive(Debug)]
+pub struct SignatureWatch {
+    pub signature: String,
+    network: RpcNetwork,
+    pub progress: SignatureProgress,
+    slot: Option<u64>,
+    handle: JoinHandle<()>,
+}
 
-        // Render results area
-        frame.render_widget(self, chunks[1]);
-        // Render popup if active
-        if self.show_popup {
-            let popup_area = centered_rect(60, 40, frame.area());
-            let popup_block = Block::bordered()
-                .title("SoonScan - Help & Guide")
-                .border_style(Style::default().red());
-
-            let help_text = vec![
-                Line::from(vec![" Retrieve transaction information".blue()]),
-                Line::from(vec![
-                    " View account balances, transaction status, and more".blue()
-                ]),
-                Line::from(vec!["".into()]),
-                Line::from(vec![" ⌨️ Keystrokes:".blue().bold()]),
-                Line::from(vec![" e      : Enter edit mode for query input".blue()]),
-                Line::from(vec![" Enter  : Submit query (account/transaction)".blue()]),
-                Line::from(vec![" Esc    : Cancel editing/close popup".blue()]),
-                Line::from(vec![" Ctrl+V : Paste content from clipboard".blue()]),
-                Line::from(vec![" ?      : Toggle this help popup".blue()]),
-                Line::from(vec![" n      : Toggle between Devnet and Testnet".blue()]),
-                Line::from(vec![" q      : Quit application".blue()]),
-            ];
-
-            let popup_text = Paragraph::new(help_text)
-                .block(popup_block)
-                .wrap(ratatui::widgets::Wrap { trim: true });
+// How far a watched transaction has got
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureProgress {
+    NotFound,
+    Processed,
+    Confirmed,
+    Finalized,
+    Failed(String), // Confirmed with this error
+}
 
-            frame.render_widget(Clear, popup_area);
-            frame.render_widget(popup_text, popup_area);
+impl SignatureProgress {
+    fn from_status(status: Option<&TransactionStatus>) -> Self {
+        let Some(status) = status else {
+            return SignatureProgress::NotFound;
+        };
+        // Nodes that don't report a level only say whether the slot is rooted
+        let level = status.confirmation_status.clone().unwrap_or(match status.confirmations {
+            None => TransactionConfirmationStatus::Finalized,
+            Some(_) => TransactionConfirmationStatus::Processed,
+        });
+        match (level, &status.err) {
+            (TransactionConfirmationStatus::Processed, _) => SignatureProgress::Processed,
+            (_, Some(err)) => SignatureProgress::Failed(err.to_string()),
+            (TransactionConfirmationStatus::Confirmed, None) => SignatureProgress::Confirmed,
+            (TransactionConfirmationStatus::Finalized, None) => SignatureProgress::Finalized,
         }
     }
 
-    async fn handle_events(app: Arc<Mutex<App>>) -> io::Result<bool> {
-        if let Event::Key(key_event) = event::read()? {
-            if key_event.kind == KeyEventKind::Press {
-                match key_event.code {
-                    KeyCode::Char('q') => {
-                        let mut app = app.lock().await;
-                        app.exit = true;
-                        return Ok(true);
-                    }
-                    KeyCode::Char('e') => {
-                        let mut app = app.lock().await;
-                        if matches!(app.input_mode, InputMode::Normal) {
-                            app.input_mode = InputMode::Editing;
-                        }
-                    }
-                    KeyCode::Char('n') => {
-                        let mut app = app.lock().await;
-                        app.toggle_rpc_network();
-                    }
-                    KeyCode::Esc => {
-                        let mut app = app.lock().await;
-                        app.input_mode = InputMode::Normal;
-                    }
-                    KeyCode::Enter => {
-                        let mut app = app.lock().await;
-                        if matches!(app.input_mode, InputMode::Editing) {
-                            app.input_mode = InputMode::Normal;
-                            if !app.query.is_empty() {
-                                app.fetch_data()
-                                    .await
-                                    .unwrap_or_else(|e| eprintln!("Error: {}", e));
-                            }
-                        }
-                    }
-                    KeyCode::Char('?') => {
-                        let mut app = app.lock().await;
-                        app.show_popup = !app.show_popup;
-                    }
-                    // Handle paste events (Ctrl+V)
-                    KeyCode::Char('v') => {
-                        let mut app = app.lock().await;
-                        if matches!(app.input_mode, InputMode::Editing)
-                            && key_event.modifiers.contains(event::KeyModifiers::CONTROL)
-                        {
-                            if let Ok(clipboard_content) = cli_clipboard::get_contents() {
-                                app.query.push_str(&clipboard_content);
-                            }
-                        } else if matches!(app.input_mode, InputMode::Editing) {
-                            app.query.push('v');
-                        }
-                    }
-                    KeyCode::Char(c) => {
-                        let mut app = app.lock().await;
-                        if matches!(app.input_mode, InputMode::Editing) {
-                            app.query.push(c);
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        let mut app = app.lock().await;
-                        if matches!(app.input_mode, InputMode::Editing) {
-                            app.query.pop();
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        Ok(false)
+    // The watch ends once the outcome can no longer change
+    fn is_done(&self) -> bool {
+        !matches!(self, SignatureProgress::NotFound | SignatureProgress::Processed)
     }
 
-    async fn fetch_data(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Define the RPC URL
-        let url = DEVNET_RPC;
-        let client = RpcClient::new(url.to_string());
-
-        // Check if the query is a valid public key
-        if let Ok(pubkey) = Pubkey::from_str(&self.query) {
-            // println!("Valid public key detected: {}", pubkey);
+    fn label(&self) -> &str {
+        match self {
+            SignatureProgress::NotFound => "not found yet",
+            SignatureProgress::Processed => "processed",
+            SignatureProgress::Confirmed => "confirmed",
+            SignatureProgress::Finalized => "finalized",
+            SignatureProgress::Failed(_) => "failed",
+        }
+    }
+}
 
-            // Fetch account information using Solana RPC client
-            match client.get_account(&pubkey) {
-                Ok(account) => {
-                    // println!("Account found: {:?}", account);
-                    let account_info = serde_json::json!({
-                        "lamports": account.lamports,
-                        "owner": account.owner.to_string(),
-                        "space": account.data.len(),
-                        "executable": account.executable,
-                    });
-                    self.json_response = Some(account_info);
+impl Pane {
+    // Stop loading history pages for a view that is being replaced
+    fn reset_history_paging(&mut self) {
+        if let HistoryPaging::Loading(handle) = std::mem::take(&mut self.history_paging) {
+            handle.abort();
+        }
+    }
 
-                    // Fetch signatures related to an account
-                    match client.get_signatures_for_address(&pubkey) {
-                        Ok(signatures) => {
-                            self.address_sign = Some(serde_json::json!(signatures));
-                        }
-                        Err(err) => {
-                            eprintln!("Failed to fetch signatures: {}", err);
-                            self.address_sign = None;
-                        }
-                    }
-                }
-                Err(err) => {
-                    eprintln!("Failed to fetch account info: {}", err);
-                    self.json_response = None;
-                    self.address_sign = None;
-                }
-            }
-
-        } else if let Ok(signature) = Signature::from_str(&self.query) {
-            // println!("Valid transaction signature detected: {}", signature);
-            // Fetch transaction details using Solana RPC client
-            match client.get_transaction(&signature, UiTransactionEncoding::Json) {
-                Ok(transaction) => {
-                    let transaction_info = serde_json::json!({
-                        "slot": transaction.slot,
-                        "blockTime": transaction.block_time,
-                        "meta": {
-                            "status": transaction.transaction.meta.as_ref().map(|m| format!("{:?}", m.status)),
-                            "err": transaction.transaction.meta.as_ref().and_then(|m| m.err.clone()),
-                            "fee": transaction.transaction.meta.as_ref().map(|m| m.fee).unwrap_or(0),
-                            "preBalances": transaction.transaction.meta.as_ref().map(|m| m.pre_balances.clone()),
-                            "postBalances": transaction.transaction.meta.as_ref().map(|m| m.post_balances.clone()),
-                            "signatures": match &transaction.transaction.transaction {
-                                                    Json(ui_transaction) => ui_transaction.signatures.clone(),
-                                                    _ => vec![]
-                            },
-                            "accountKeys": match &transaction.transaction.transaction {
-                                                    Json(ui_transaction) => match &ui_transaction.message {
-                                                        Raw(raw_message) => raw_message.account_keys.clone(),
-                                                        _ => vec![]
-                                                    },
-                                                    _ => vec![]
-                                                },
-                                                "recentBlockhash": match &transaction.transaction.transaction {
-                                                    Json(ui_transaction) => match &ui_transaction.message {
-                                                        Raw(raw_message) => raw_message.recent_blockhash.clone(),
-                                                        _ => String::new()
-                                                    },
-                                                    _ => String::new()
-                                                },
-                                                "instructions": match &transaction.transaction.transaction {
-                                                    Json(ui_transaction) => match &ui_transaction.message {
-                                                        Raw(raw_message) => raw_message.instructions.clone(),
-                                                        _ => vec![]
-                                                    },
-                                                    _ => vec![]
-                                                },
-                            "logMessages": transaction.transaction.meta.as_ref().and_then(|m| Some(m.log_messages.clone())),
-                            "computeUnitsConsumed": transaction.transaction.meta.as_ref().and_then(|m| Some(m.compute_units_consumed.clone()))
-                        },
-                    });
-                    self.json_response = Some(transaction_info);
-                }
-                Err(err) => {
-                    eprintln!("Failed to fetch transaction info: {}", err);
-                    self.json_response = None;
-                }
-            }
-        } else {
-            eprintln!("Query is neither a valid public key nor a transaction signature.");
-            self.json_response = None;
+    // Abort everything still being fetched for this pane
+    fn stop_tasks(&mut self) {
+        if let Some(task) = self.fetch_task.take() {
+            task.handle.abort();
+            self.settle_state();
         }
+        self.reset_history_paging();
+        self.stop_watch();
+    }
 
-        Ok(())
+    // Leave the loading state for whatever the pane now holds, keeping a failure
+    // of the same query
+    fn settle_state(&mut self) {
+        if self.json_response.is_some() {
+            self.state = QueryState::Loaded;
+        } else if !matches!(&self.state, QueryState::Failed { query, .. } if *query == self.query) {
+            self.state = QueryState::Idle;
+        }
     }
-    fn format_timestamp(&self, timestamp: i64) -> String {
-        use chrono::{DateTime, TimeZone, Utc};
-        let dt: DateTime<Utc> = Utc.timestamp_opt(timestamp, 0).unwrap();
-        dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+
+    // Forget the result and the views before it, keeping the query so it can be
+    // searched again; used when they came from another network
+    fn clear_results(&mut self) {
+        self.stop_tasks();
+        self.json_response = None;
+        self.address_sign = None;
+        self.token_holdings = TabData::NotLoaded;
+        self.inflation_rewards = TabData::NotLoaded;
+        self.network_search = TabData::NotLoaded;
+        self.largest_holders = TabData::NotLoaded;
+        self.holders_view = false;
+        self.leader_lookup = false;
+        self.selected_history = None;
+        self.scroll_offset = 0;
+        self.view_history.clear();
+        self.state = QueryState::Idle;
+        self.raw_view = None;
     }
 
-    fn format_longnumber(&self, number: i64) -> String {
-        use std::fmt::Write;
+    // Whether the query on screen was looked up and doesn't exist
+    fn shows_not_found(&self) -> bool {
+        match &self.state {
+            QueryState::Failed { query, error: AppError::NotFound(_) } => *query == self.query,
+            QueryState::Loaded => self.shows_missing_account(),
+            _ => false,
+        }
+    }
 
-        let mut formatted = String::new();
-        let number_str = number.abs().to_string();
-        let len = number_str.len();
+    // Whether the address on screen has no account
+    fn shows_missing_account(&self) -> bool {
+        self.json_response.as_ref().is_some_and(|response| response.get("missingAccount").is_some())
+    }
 
-        for (i, c) in number_str.chars().enumerate() {
-            if i > 0 && (len - i) % 3 == 0 {
-                write!(&mut formatted, ",").unwrap();
+    // Returns false when nothing was being watched
+    fn stop_watch(&mut self) -> bool {
+        self.arrivals.clear();
+        match self.watch.take() {
+            Some(watch) => {
+                watch.handle.abort();
+                true
             }
-            write!(&mut formatted, "{}", c).unwrap();
+            None => false,
         }
+    }
 
-        if number < 0 {
-            format!("-{}", formatted)
-        } else {
-            formatted
+    // Put transactions found by the watch on top of the history, skipping known
+    // ones; returns how many were added
+    fn prepend_history(&mut self, entries: Vec<HistoryEntry>) -> usize {
+        let Some(signatures) = self.address_sign.as_mut() else {
+            return 0;
+        };
+        let mut new = Vec::new();
+        for entry in entries {
+            if !signatures.iter().chain(&new).any(|known: &HistoryEntry| known.signature == entry.signature) {
+                new.push(entry);
+            }
         }
+        let now = Instant::now();
+        self.arrivals.retain(|_, at| at.elapsed() < NEW_ROW_HIGHLIGHT);
+        self.arrivals.extend(new.iter().map(|entry| (entry.signature.clone(), now)));
+        let added = new.len();
+        signatures.splice(0..0, new);
+        self.refresh_raw_view();
+        added
     }
-}
 
-impl Widget for &App {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let instruction = Line::from(vec![
-            " Quit ".into(),
-            "<Q> ".blue().bold(),
-            " | ".into(),
-            " Help ".into(),
-            " ? ".blue().bold(),
-        ]);
-        let block = Block::bordered()
-            .title_bottom(instruction.centered())
-            .border_set(border::THICK);
+    // Add a page of older history; returns false once the history hit its cap
+    fn append_history(&mut self, page: Vec<HistoryEntry>) -> bool {
+        let Some(signatures) = self.address_sign.as_mut() else {
+            return true;
+        };
+        let page_len = page.len();
+        let room = MAX_HISTORY_ROWS.saturating_sub(signatures.len());
+        signatures.extend(page.into_iter().take(room));
+        let capped = signatures.len() >= MAX_HISTORY_ROWS;
 
-        let mut rows = vec![];
+        self.refresh_raw_view();
+        if capped {
+            self.history_paging = HistoryPaging::Capped;
+            return false;
+        }
+        self.history_paging = HistoryPaging::after_page(page_len);
+        true
+    }
 
-        // Show blockchain data when no query is done!
-        if self.query.is_empty() {
-            if let Some(slot_info) = self.slot_info {
-                rows.push(Row::new(vec![
-                    Cell::from("Network").bold(),
-                    Cell::from("SoonScan Devnet").bold(),
-                ]));
+    // The fetched result and history as one JSON document, for the raw view
+    fn raw_document(&self) -> Value {
+        let mut document = serde_json::Map::new();
+        if let Some(response) = &self.json_response {
+            document.insert("result".to_string(), response.clone());
+        }
+        if let Some(entries) = &self.address_sign {
+            document.insert("signatures".to_string(), entries.iter().map(HistoryEntry::to_value).collect());
+        }
+        Value::Object(document)
+    }
 
-                rows.push(Row::new(vec![
-                    Cell::from("Slot:").bold(),
-                    Cell::from(self.format_longnumber(slot_info).yellow()),
-                ]));
+    // Show the current data in the raw view, if it is open
+    fn refresh_raw_view(&mut self) {
+        if self.raw_view.is_some() {
+            let document = self.raw_document();
+            if let Some(tree) = self.raw_view.as_mut() {
+                tree.set_root(document);
             }
+        }
+    }
 
-            if let Some(supply_info) = &self.supply_info {
-                if let Some(value) = supply_info.get("value") {
-                    let total_supply = value.get("total").and_then(|t| t.as_i64()).unwrap_or(0);
-                    let circulating_supply = value
-                        .get("circulating")
-                        .and_then(|c| c.as_i64())
-                        .unwrap_or(0);
-
-                    // Calculate the percentage of circulating supply
-                    let circulating_percentage = if total_supply > 0 {
-                        (circulating_supply as f64 / total_supply as f64) * 100.0
-                    } else {
-                        0.0
-                    };
-
-                    rows.extend(vec![
-                        Row::new(vec![
-                            Cell::from("Circulating Supply:").bold(),
-                            Cell::from(
-                                format!(
-                                    "{} / {}",
-                                    self.format_longnumber(circulating_supply),
-                                    self.format_longnumber(total_supply)
-                                )
-                                .green(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Circulating Percentage:").bold(),
-                            Cell::from(
-                                format!("{:.1}% is circulating", circulating_percentage).green(),
-                            ),
-                        ]),
-                    ]);
+    // Path of views leading to the current one, e.g. "account ▸ tx ▸ account"
+    fn breadcrumbs(&self) -> String {
+        // Mints are named by their label when they have one, e.g. "USDC mint"
+        fn view_kind(query: &str, json_response: &Option<Value>) -> String {
+            match json_response.as_ref().and_then(|r| r.as_object()) {
+                Some(obj) if obj.contains_key("mint") => {
+                    labels::lookup(query).map_or("mint".to_string(), |label| format!("{} mint", label))
                 }
+                Some(obj) if obj.contains_key("lamports") || obj.contains_key("missingAccount") => {
+                    "account".to_string()
+                }
+                Some(obj) if obj.contains_key("block") => "block".to_string(),
+                Some(obj) if obj.contains_key("slot") || obj.contains_key("pending") => "tx".to_string(),
+                _ => "query".to_string(),
             }
+        }
 
-            if let Some(transaction_info) = self.transaction_info {
-                rows.push(Row::new(vec![
-                    Cell::from("Transaction count:").bold(),
-                    Cell::from(self.format_longnumber(transaction_info).yellow()),
-                ]));
-            }
-        } else if let Some(json_response) = &self.json_response {
-        // println!("Address Signatures: {:?}", self.address_sign);
-            if let Some(response_obj) = json_response.as_object() {
-                if response_obj.contains_key("lamports") {
-                    // This is an account response
-                    rows.extend(vec![
-                        Row::new(vec![
-                            Cell::from("Type:").bold(),
-                            Cell::from("Account Info".blue()),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Balance (SOL):").bold(),
-                            Cell::from(
-                                format!(
-                                    "◎ {:.9}",
-                                    response_obj
-                                        .get("lamports")
-                                        .and_then(|l| l.as_u64())
-                                        .unwrap_or(0) as f64
-                                        / 1_000_000_000.0
-                                )
-                                .yellow(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Allocated Data Size:").bold(),
-                            Cell::from(
-                                format!(
-                                    "{} byte(s)",
-                                    response_obj
-                                        .get("space")
-                                        .and_then(|s| s.as_u64())
-                                        .unwrap_or(0)
-                                )
-                                .yellow(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Assigned Program Id:").bold(),
-                            Cell::from(
-                                response_obj
-                                    .get("owner")
-                                    .and_then(|o| o.as_str())
-                                    .map(|owner| {
-                                        if owner == "11111111111111111111111111111111" {
-                                            "System Program".to_string()
-                                        } else {
-                                            owner.to_string()
-                                        }
-                                    })
-                                    .unwrap_or("N/A".to_string())
-                                    .green(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Executable:").bold(),
-                            Cell::from(
-                                if response_obj
-                                    .get("executable")
-                                    .and_then(|e| e.as_bool())
-                                    .unwrap_or(false)
-                                {
-                                    "Yes".green()
-                                } else {
-                                    "No".red()
-                                },
-                            ),
-                        ]),
-                    ]);
-
-
-if let Some(address_sign) = &self.address_sign {
-    // Check if the value inside `address_sign` is an array
-    if let Some(address_signatures) = address_sign.as_array() {
-        // Iterate over the array of signatures
-
-            rows.push(Row::new(vec![
-                Cell::from(" "),
-            ]));
-
+        self.view_history
+            .iter()
+            .map(|view| view_kind(&view.query, &view.json_response))
+            .chain(std::iter::once(view_kind(&self.query, &self.json_response)))
+            .collect::<Vec<_>>()
+            .join(" ▸ ")
+    }
 
-            rows.push(Row::new(vec![
-                Cell::from("Transaction History").bold(),
-            ]));
+    // Log messages of the transaction on screen, if there is one
+    fn transaction_logs(&self) -> Option<Vec<&str>> {
+        let logs = self.json_response.as_ref()?.get("meta")?.get("logMessages")?;
+        Some(
+            logs.as_array()
+                .map(|logs| logs.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default(),
+        )
+    }
 
+    // Instructions and account keys of the transaction on screen, with the inner
+    // instructions under their parents unless `collapse_inner`
+    fn transaction_message(&self, collapse_inner: bool) -> Option<(Vec<ListedInstruction>, AccountKeys<'_>)> {
+        let meta = self.json_response.as_ref()?.get("meta")?;
+        let instructions = instruction::list_instructions(meta, collapse_inner)?;
+        Some((instructions, AccountKeys::from_meta(meta)))
+    }
+}
 
-            rows.push(Row::new(vec![
-                Cell::from(" "),
-            ]));
+// Cluster data shown on the dashboard when no query is done
+#[derive(Debug, Default, Clone)]
+pub struct DashboardData {
+    pub slot_info: Option<u64>,
+    pub transaction_info: Option<u64>,
+    pub supply_info: Option<Value>,
+    pub performance_samples: Option<Vec<PerformanceSample>>, // Newest first
+    pub epoch_info: Option<EpochInfo>,
+    pub inflation: Option<Inflation>,
+    pub fees: Option<FeeEstimate>,
+    pub first_available_block: Option<u64>, // How far back the node's ledger goes
+    pub block_height: Option<u64>,
+    pub snapshot: Option<SnapshotSlots>,
+    pub max_retransmit_slot: Option<u64>, // Highest slot the node has seen shreds for
+}
 
-            rows.push(Row::new(vec![
-                Cell::from("Transaction").bold(),
-                Cell::from("Block").bold(),
-                Cell::from("Timestamp").bold(),
-                Cell::from("Result").bold(),
+// Slots of the newest snapshots the node can serve, from getHighestSnapshotSlot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotSlots {
+    pub full: u64,
+    pub incremental: Option<u64>,
+}
 
-            ]));
+impl SnapshotSlots {
+    pub fn newest(&self) -> u64 {
+        self.incremental.unwrap_or(self.full).max(self.full)
+    }
+}
 
+impl DashboardData {
+    // Average seconds per slot over the recent performance samples
+    pub fn average_slot_time(&self) -> Option<f64> {
+        let samples = self.performance_samples.as_ref()?;
+        let slots: u64 = samples.iter().map(|sample| sample.num_slots).sum();
+        let secs: u64 = samples.iter().map(|sample| sample.sample_period_secs).sum();
+        (slots > 0).then(|| secs as f64 / slots as f64)
+    }
+}
 
-        for signature_info in address_signatures {
-            // Extract relevant fields from each signature info object
-            let signature = signature_info
-                .get("signature")
-                .and_then(|s| s.as_str())
-                .unwrap_or("N/A");
-            
-            let slot = signature_info
-                .get("slot")
-                .and_then(|s| s.as_u64())
-                .unwrap_or(0);
+// Result of getInflationRate as fractions, with the schedule from getInflationGovernor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inflation {
+    pub epoch: u64,
+    pub total: f64,
+    pub validator: f64,
+    pub foundation: f64,
+    pub schedule: Option<InflationSchedule>,
+}
 
-            let block_time = signature_info
-                .get("blockTime")
-                                    .and_then(|bt| bt.as_u64())
-                                    .map_or("N/A".to_string(), |time| {
-                                        self.format_timestamp(time as i64)
+// How the yearly inflation rate starts, shrinks and settles
+#[derive(Debug, Clone, PartialEq)]
+pub struct InflationSchedule {
+    pub initial: f64,
+    pub terminal: f64,
+    pub taper: f64, // Yearly rate of decrease
+}
 
-                                    });
+// Result of getEpochInfo
+#[derive(Debug, Clone)]
+pub struct EpochInfo {
+    pub epoch: u64,
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+}
 
-            let confirmation_status = signature_info
-                .get("confirmationStatus")
-                .and_then(|s| s.as_str())
-                .unwrap_or("Unknown");
+impl EpochInfo {
+    // Fraction of the epoch completed, clamped to 100%
+    pub fn progress(&self) -> f64 {
+        if self.slots_in_epoch == 0 {
+            return 1.0;
+        }
+        (self.slot_index as f64 / self.slots_in_epoch as f64).min(1.0)
+    }
 
+    pub fn slots_remaining(&self) -> u64 {
+        self.slots_in_epoch.saturating_sub(self.slot_index)
+    }
+}
 
+// How the dashboard slot is kept current
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SlotFeed {
+    #[default]
+    Polling, // Only the dashboard refresh
+    Live, // slotSubscribe notifications over WebSocket
+}
 
-            // Create rows for each signature's details
-            rows.push(Row::new(vec![
-                Cell::from(format!("{}...", &signature[0..23]).yellow()),
+// Node health reported by getHealth
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ClusterHealth {
+    #[default]
+    Unknown,
+    Ok,
+    Behind(Option<u64>), // Number of slots behind, when the node reports it
+    Unreachable,
+}
 
-                Cell::from(format!("{}", self.format_longnumber(slot as i64)).to_string().blue()),
+// One entry of getRecentPerformanceSamples
+#[derive(Debug, Clone)]
+pub struct PerformanceSample {
+    pub num_transactions: u64,
+    pub num_slots: u64,
+    pub sample_period_secs: u64,
+}
 
-                Cell::from(block_time.yellow()),
-                
-                Cell::from(confirmation_status.green()),
-            ]));
+impl PerformanceSample {
+    // Transactions per second over the sample period
+    pub fn tps(&self) -> f64 {
+        if self.sample_period_secs == 0 {
+            return 0.0;
         }
+        self.num_transactions as f64 / self.sample_period_secs as f64
     }
 }
 
+// Whether more account history can be fetched with 'L'
+#[derive(Debug, Default)]
+pub enum HistoryPaging {
+    #[default]
+    More,
+    Loading(JoinHandle<()>),
+    End,
+    Capped, // Stopped at MAX_HISTORY_ROWS
+}
 
-                } else if response_obj.contains_key("slot") {
-                    // This is a transaction response
-                    // println!("Transaction Data: {:?}", self.json_response);
-                    rows.extend(vec![
-                        Row::new(vec![
-                            Cell::from("Type:").bold(),
-                            Cell::from("Transaction Info".blue()),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Slot:").bold(),
-                            Cell::from(
-                                response_obj
-                                    .get("slot")
-                                    .and_then(|s| s.as_u64())
-                                    .map_or("N/A".to_string(), |slot| {
-                                        self.format_longnumber(slot as i64)
-                                    })
-                                    .yellow(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Block Time:").bold(),
-                            Cell::from(
-                                response_obj
-                                    .get("blockTime")
-                                    .and_then(|bt| bt.as_u64())
-                                    .map_or("N/A".to_string(), |time| {
-                                        self.format_timestamp(time as i64)
-                                    })
-                                    .yellow(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Fee (SOL):").bold(),
-                            Cell::from(
-                                response_obj
-                                    .get("meta")
-                                    .and_then(|meta| meta.get("fee"))
-                                    .and_then(|f| f.as_u64())
-                                    .map_or("N/A".to_string(), |fee| {
-                                        format!("◎ {:.9}", fee as f64 / 1_000_000_000.0)
-                                    })
-                                    .yellow(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Status:").bold(),
-                            Cell::from(
-                                response_obj
-                                    .get("meta")
-                                    .and_then(|meta| meta.get("status"))
-                                    .and_then(|status| {
-                                        if let Some(status_str) = status.as_str() {
-                                            // Handle the 'Ok(())' status
-                                            if status_str == "Ok(())" {
-                                                Some("SUCCESS".to_string())
-                                            } else {
-                                                Some("Err".to_string())
-                                            }
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .unwrap_or("Unknown".to_string())
-                                    .green(),
-                            ),
-                        ]),
-                        Row::new(vec![
-                            Cell::from("Signatures:").bold(),
-                            Cell::from(format!("{}...", &self.query[0..24])).red(),
-                        ]),
-                    ]);
-                } else {
-                    // Handle unknown or unsupported response type
-                    rows.push(Row::new(vec![
-                        Cell::from("Error:").bold(),
-                        Cell::from("Unsupported response type.".red()),
-                    ]));
+impl HistoryPaging {
+    // State after receiving a page of `len` signatures
+    fn after_page(len: usize) -> Self {
+        if len < HISTORY_PAGE_SIZE {
+            HistoryPaging::End
+        } else {
+            HistoryPaging::More
+        }
+    }
+}
+
+// Order of the account history table, cycled with 's'
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HistorySort {
+    #[default]
+    Newest,
+    Oldest,
+    Failed, // Failed transactions first, then newest
+}
+
+impl HistorySort {
+    pub fn next(self) -> Self {
+        match self {
+            HistorySort::Newest => HistorySort::Oldest,
+            HistorySort::Oldest => HistorySort::Failed,
+            HistorySort::Failed => HistorySort::Newest,
+        }
+    }
+
+    // Stable, so entries in the same slot keep the order the RPC returned them in
+    pub fn sort(self, entries: &mut [&HistoryEntry]) {
+        match self {
+            HistorySort::Newest => entries.sort_by_key(|entry| std::cmp::Reverse(entry.slot)),
+            HistorySort::Oldest => entries.sort_by_key(|entry| entry.slot),
+            HistorySort::Failed => {
+                entries.sort_by_key(|entry| (entry.err.is_none(), std::cmp::Reverse(entry.slot)))
+            }
+        }
+    }
+}
+
+// Which history entries are shown, cycled with 'f'
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Failed,
+    Succeeded,
+}
+
+impl StatusFilter {
+    pub fn next(self) -> Self {
+        match self {
+            StatusFilter::All => StatusFilter::Failed,
+            StatusFilter::Failed => StatusFilter::Succeeded,
+            StatusFilter::Succeeded => StatusFilter::All,
+        }
+    }
+}
+
+// Order of the Validators tab, cycled with 's'
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ValidatorSort {
+    #[default]
+    Stake, // Largest stake first
+    Commission, // Lowest commission first, then largest stake
+    LastVote, // Most recent vote first
+    SkipRate, // Most leader slots skipped first, validators without leader slots last
+}
+
+impl ValidatorSort {
+    pub fn next(self) -> Self {
+        match self {
+            ValidatorSort::Stake => ValidatorSort::Commission,
+            ValidatorSort::Commission => ValidatorSort::LastVote,
+            ValidatorSort::LastVote => ValidatorSort::SkipRate,
+            ValidatorSort::SkipRate => ValidatorSort::Stake,
+        }
+    }
+
+    // Skip rates come from the block production of the current epoch
+    pub fn sort(self, validators: &mut [&VoteAccountSummary], production: Option<&BlockProduction>) {
+        match self {
+            ValidatorSort::Stake => validators.sort_by_key(|validator| std::cmp::Reverse(validator.activated_stake)),
+            ValidatorSort::Commission => validators
+                .sort_by_key(|validator| (validator.commission, std::cmp::Reverse(validator.activated_stake))),
+            ValidatorSort::LastVote => validators.sort_by_key(|validator| std::cmp::Reverse(validator.last_vote)),
+            ValidatorSort::SkipRate => validators.sort_by_key(|validator| {
+                let rate = production.and_then(|production| production.skip_rate(&validator.node_pubkey));
+                (std::cmp::Reverse(rate), std::cmp::Reverse(validator.activated_stake))
+            }),
+        }
+    }
+}
+
+// Which validators the Validators tab shows, cycled with 'f'
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ValidatorFilter {
+    #[default]
+    All,
+    Current,
+    Delinquent,
+}
+
+impl ValidatorFilter {
+    pub fn next(self) -> Self {
+        match self {
+            ValidatorFilter::All => ValidatorFilter::Current,
+            ValidatorFilter::Current => ValidatorFilter::Delinquent,
+            ValidatorFilter::Delinquent => ValidatorFilter::All,
+        }
+    }
+
+    pub fn matches(self, validator: &VoteAccountSummary) -> bool {
+        match self {
+            ValidatorFilter::All => true,
+            ValidatorFilter::Current => !validator.delinquent,
+            ValidatorFilter::Delinquent => validator.delinquent,
+        }
+    }
+}
+
+// Leader slots and blocks produced per validator identity over a range of slots,
+// from getBlockProduction
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockProduction {
+    pub by_identity: HashMap<String, (u64, u64)>, // Leader slots, blocks produced
+    pub first_slot: u64,
+    pub last_slot: u64,
+}
+
+impl BlockProduction {
+    // Leader slots and skipped slots of the whole cluster
+    pub fn total(&self) -> (u64, u64) {
+        self.by_identity
+            .values()
+            .fold((0, 0), |(slots, skipped), (leader_slots, produced)| {
+                (slots + leader_slots, skipped + leader_slots.saturating_sub(*produced))
+            })
+    }
+
+    // Skipped leader slots of an identity in hundredths of a percent, None without leader slots
+    pub fn skip_rate(&self, identity: &str) -> Option<u64> {
+        let (leader_slots, produced) = self.by_identity.get(identity)?;
+        ratio(leader_slots.saturating_sub(*produced).into(), (*leader_slots).into())
+    }
+
+    // Whether an identity skips badly enough to stand out: at least twice the
+    // cluster's rate, and more than one slot in a hundred
+    pub fn skips_badly(&self, identity: &str) -> bool {
+        let (slots, skipped) = self.total();
+        let cluster = ratio(skipped.into(), slots.into()).unwrap_or(0);
+        self.skip_rate(identity).is_some_and(|rate| rate > 100 && rate >= cluster * 2)
+    }
+}
+
+// Filters applied to the account history table
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub status: StatusFilter,
+    pub since: Option<(i64, String)>, // Earliest block time and the text it was entered as
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        let status_matches = match self.status {
+            StatusFilter::All => true,
+            StatusFilter::Failed => entry.err.is_some(),
+            StatusFilter::Succeeded => entry.err.is_none(),
+        };
+        let time_matches = self
+            .since
+            .as_ref()
+            .is_none_or(|(since, _)| entry.block_time.is_some_and(|time| time >= *since));
+        status_matches && time_matches
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status != StatusFilter::All || self.since.is_some()
+    }
+
+    // e.g. "failed, since 2024-05-01"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        match self.status {
+            StatusFilter::All => {}
+            StatusFilter::Failed => parts.push("failed".to_string()),
+            StatusFilter::Succeeded => parts.push("succeeded".to_string()),
+        }
+        if let Some((_, text)) = &self.since {
+            parts.push(format!("since {}", text));
+        }
+        parts.join(", ")
+    }
+}
+
+// Parse a date filter: "YYYY-MM-DD" (UTC midnight) or a span back from `now` like "24h" or "7d"
+pub fn parse_since(input: &str, now: i64) -> Option<i64> {
+    let input = input.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp());
+    }
+    let (amount, unit_secs) = if let Some(hours) = input.strip_suffix('h') {
+        (hours, 3_600)
+    } else if let Some(days) = input.strip_suffix('d') {
+        (days, 86_400)
+    } else {
+        return None;
+    };
+    let amount: i64 = amount.trim().parse().ok()?;
+    Some(now.saturating_sub(amount.checked_mul(unit_secs)?))
+}
+
+// One entry of an account's transaction history
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub confirmation_status: Option<String>,
+    pub err: Option<Value>,
+    pub memo: Option<String>,
+}
+
+impl HistoryEntry {
+    // Parse an entry of the getSignaturesForAddress result
+    pub fn from_value(value: &Value) -> Option<Self> {
+        Some(Self {
+            signature: value.get("signature")?.as_str()?.to_string(),
+            slot: value.get("slot").and_then(|s| s.as_u64()).unwrap_or(0),
+            block_time: value.get("blockTime").and_then(|t| t.as_i64()),
+            confirmation_status: value
+                .get("confirmationStatus")
+                .and_then(|s| s.as_str())
+                .map(str::to_string),
+            err: value.get("err").filter(|err| !err.is_null()).cloned(),
+            memo: value.get("memo").and_then(|m| m.as_str()).map(str::to_string),
+        })
+    }
+
+    // Commitment the transaction reached, shortened for the history table
+    fn status_badge(&self) -> &'static str {
+        match self.confirmation_status.as_deref() {
+            Some("finalized") => "fin",
+            Some("confirmed") => "conf",
+            Some("processed") => "proc",
+            _ => "?",
+        }
+    }
+
+    // The entry in the shape getSignaturesForAddress returns it
+    pub fn to_value(&self) -> Value {
+        serde_json::json!({
+            "signature": self.signature,
+            "slot": self.slot,
+            "blockTime": self.block_time,
+            "confirmationStatus": self.confirmation_status,
+            "err": self.err,
+            "memo": self.memo,
+        })
+    }
+}
+
+// Account keys of a fetched transaction whose owners haven't been looked up yet
+fn unresolved_account_keys(response: &Value) -> Option<Vec<String>> {
+    let meta = response
+        .get("meta")
+        .filter(|meta| meta.get("accountKeys").is_some() && meta.get("keyAccounts").is_none())?;
+    let keys = AccountKeys::from_meta(meta).keys;
+    (!keys.is_empty()).then(|| keys.into_iter().map(str::to_string).collect())
+}
+
+// Every account a transaction references with its flags, as jsonParsed lists them:
+// the static keys of the message, then for v0 messages the addresses loaded from
+// lookup tables
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountKeys<'a> {
+    pub keys: Vec<&'a str>,
+    flags: Vec<KeyFlags>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct KeyFlags {
+    signer: bool,
+    writable: bool,
+    loaded: bool, // From an address lookup table
+}
+
+impl<'a> AccountKeys<'a> {
+    // From the meta of a fetched transaction. Keys may be bare addresses; when none
+    // came from a lookup table the loaded addresses are appended, writable ones first,
+    // so instruction indexes past the static keys still resolve.
+    pub fn from_meta(meta: &'a Value) -> Self {
+        let flag = |key: &Value, name: &str| key.get(name).and_then(Value::as_bool).unwrap_or(false);
+        let (mut keys, mut flags): (Vec<&str>, Vec<KeyFlags>) = meta
+            .get("accountKeys")
+            .and_then(|keys| keys.as_array())
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|key| {
+                        let flags = KeyFlags {
+                            signer: flag(key, "signer"),
+                            writable: flag(key, "writable"),
+                            loaded: key.get("source").and_then(Value::as_str) == Some("lookupTable"),
+                        };
+                        Some((key.as_str().or_else(|| key.get("pubkey")?.as_str())?, flags))
+                    })
+                    .unzip()
+            })
+            .unwrap_or_default();
+        if !flags.iter().any(|flags| flags.loaded) {
+            for (list, writable) in [("writable", true), ("readonly", false)] {
+                let loaded = meta
+                    .get("loadedAddresses")
+                    .and_then(|loaded| loaded.get(list))
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for key in loaded.iter().filter_map(Value::as_str) {
+                    keys.push(key);
+                    flags.push(KeyFlags {
+                        signer: false,
+                        writable,
+                        loaded: true,
+                    });
                 }
             }
-        } else if !self.query.is_empty() {
-            rows.push(Row::new(vec![
-                Cell::from("Status:").bold(),
-                Cell::from("Loading...".yellow()),
-            ]));
         }
+        Self { keys, flags }
+    }
+
+    // Where each lookup table's addresses sit among the keys: after the static keys
+    // come the writable addresses of every table in turn, then the readonly ones
+    pub fn resolve_lookups<'v>(&self, lookups: &'v [Value]) -> Vec<ResolvedLookup<'v>> {
+        let indexes = |lookup: &Value, name: &str| -> Vec<u64> {
+            lookup
+                .get(name)
+                .and_then(Value::as_array)
+                .map(|indexes| indexes.iter().filter_map(Value::as_u64).collect())
+                .unwrap_or_default()
+        };
+        let static_count = self.flags.iter().filter(|flags| !flags.loaded).count();
+        let writable_count: usize = lookups.iter().map(|lookup| indexes(lookup, "writableIndexes").len()).sum();
+        let (mut next_writable, mut next_readonly) = (static_count, static_count + writable_count);
+        lookups
+            .iter()
+            .map(|lookup| {
+                let resolve = |name: &str, next: &mut usize| {
+                    let indexes = indexes(lookup, name);
+                    let first = *next;
+                    *next += indexes.len();
+                    indexes.into_iter().zip(first..).collect()
+                };
+                ResolvedLookup {
+                    table: lookup.get("accountKey").and_then(Value::as_str).unwrap_or_default(),
+                    writable: resolve("writableIndexes", &mut next_writable),
+                    readonly: resolve("readonlyIndexes", &mut next_readonly),
+                }
+            })
+            .collect()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a str> {
+        self.keys.get(index).copied()
+    }
 
-        let widths = [Constraint::Length(40), Constraint::Percentage(20), Constraint::Percentage(15), Constraint::Percentage(15)];
+    pub fn is_signer(&self, index: usize) -> bool {
+        self.flags.get(index).is_some_and(|flags| flags.signer)
+    }
 
-        let table = Table::new(rows, &widths).block(block).column_spacing(2);
+    pub fn is_writable(&self, index: usize) -> bool {
+        self.flags.get(index).is_some_and(|flags| flags.writable)
+    }
 
-        table.render(area, buf);
+    // Which lookup list an address was loaded from, None for static keys
+    pub fn loaded_as(&self, index: usize) -> Option<&'static str> {
+        match self.flags.get(index)? {
+            KeyFlags { loaded: false, .. } => None,
+            KeyFlags { writable: true, .. } => Some("loaded writable"),
+            KeyFlags { writable: false, .. } => Some("loaded readonly"),
+        }
     }
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
-    let popup_width = area.width * percent_x / 100;
-    let popup_height = area.height * percent_y / 100;
-    let popup_x = (area.width - popup_width) / 2;
-    let popup_y = (area.height - popup_height) / 2;
+// An address lookup table of a v0 transaction with the addresses it supplied, as
+// pairs of their index in the table and in the transaction's account keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLookup<'a> {
+    pub table: &'a str,
+    pub writable: Vec<(u64, usize)>,
+    pub readonly: Vec<(u64, usize)>,
+}
 
-    Rect::new(popup_x, popup_y, popup_width, popup_height)
+// An SPL token account owned by the queried address
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenHolding {
+    pub mint: String,
+    pub amount: u128,
+    pub decimals: u8,
+    pub frozen: bool,
+}
+
+impl TokenHolding {
+    // Parse an entry of getTokenAccountsByOwner with jsonParsed encoding
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let info = value.get("account")?.get("data")?.get("parsed")?.get("info")?;
+        let amount = info.get("tokenAmount")?;
+        Some(Self {
+            mint: info.get("mint")?.as_str()?.to_string(),
+            amount: amount.get("amount")?.as_str()?.parse().ok()?,
+            decimals: amount.get("decimals")?.as_u64()? as u8,
+            frozen: info.get("state").and_then(|state| state.as_str()) == Some("frozen"),
+        })
+    }
+
+    // Balance with decimals applied, for ordering holdings of different mints
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.decimals.into())
+    }
+}
+
+// What getInflationReward paid an account for an epoch
+#[derive(Debug, Clone, PartialEq)]
+pub struct InflationReward {
+    pub amount: u64,
+    pub post_balance: u64,
+    pub commission: Option<u8>, // Of the vote account when the reward was paid
+}
+
+impl InflationReward {
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(Self {
+            amount: value.get("amount")?.as_u64()?,
+            post_balance: value.get("postBalance")?.as_u64()?,
+            commission: value.get("commission").and_then(Value::as_u64).map(|commission| commission as u8),
+        })
+    }
+}
+
+// An epoch and its reward, None when the account was paid nothing for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochReward {
+    pub epoch: u64,
+    pub reward: Option<InflationReward>,
+}
+
+// Whether another network has a query the current one couldn't find
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkPresence {
+    pub network: RpcNetwork,
+    pub found: Result<bool, String>, // The error when the network couldn't be asked
+}
+
+// One of the largest token accounts of a mint
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenHolder {
+    pub account: String,
+    pub owner: Option<String>, // The wallet owning the token account, once resolved
+    pub amount: u128,
+    pub decimals: u8,
+}
+
+impl TokenHolder {
+    // Parse an entry of getTokenLargestAccounts
+    pub fn from_value(value: &Value) -> Option<Self> {
+        Some(Self {
+            account: value.get("address")?.as_str()?.to_string(),
+            owner: None,
+            amount: value.get("amount")?.as_str()?.parse().ok()?,
+            decimals: value.get("decimals")?.as_u64()? as u8,
+        })
+    }
+}
+
+// Token balance of one account before or after a transaction, in raw units
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalance {
+    pub account_index: usize,
+    pub mint: String,
+    pub owner: Option<String>,
+    pub amount: u128,
+    pub decimals: u8,
+}
+
+impl TokenBalance {
+    // Parse an entry of meta.preTokenBalances / postTokenBalances
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let amount = value.get("uiTokenAmount")?;
+        Some(Self {
+            account_index: value.get("accountIndex")?.as_u64()? as usize,
+            mint: value.get("mint")?.as_str()?.to_string(),
+            owner: value.get("owner").and_then(|owner| owner.as_str()).map(str::to_string),
+            amount: amount.get("amount")?.as_str()?.parse().ok()?,
+            decimals: amount.get("decimals")?.as_u64()? as u8,
+        })
+    }
+}
+
+// A token account's balance across a transaction; accounts created or closed by it count from or to zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalanceChange {
+    pub account_index: usize,
+    pub mint: String,
+    pub owner: Option<String>,
+    pub decimals: u8,
+    pub pre: u128,
+    pub post: u128,
+}
+
+// Join pre and post token balances by account index, in account order
+fn token_balance_changes(pre: &[TokenBalance], post: &[TokenBalance]) -> Vec<TokenBalanceChange> {
+    let mut changes: std::collections::BTreeMap<usize, TokenBalanceChange> = std::collections::BTreeMap::new();
+    for (balance, is_post) in pre.iter().map(|b| (b, false)).chain(post.iter().map(|b| (b, true))) {
+        let change = changes.entry(balance.account_index).or_insert_with(|| TokenBalanceChange {
+            account_index: balance.account_index,
+            mint: balance.mint.clone(),
+            owner: balance.owner.clone(),
+            decimals: balance.decimals,
+            pre: 0,
+            post: 0,
+        });
+        if is_post {
+            change.post = balance.amount;
+        } else {
+            change.pre = balance.amount;
+        }
+    }
+    changes.into_values().collect()
+}
+
+// A fetched result kept around so it can be restored without refetching
+#[derive(Debug, Clone)]
+pub struct ViewSnapshot {
+    pub query: String,
+    pub json_response: Option<Value>,
+    pub address_sign: Option<Vec<HistoryEntry>>,
+    pub selected_history: Option<usize>,
+    pub scroll_offset: usize,
+    pub largest_holders: Option<Vec<TokenHolder>>, // Of a mint, once loaded
+    pub holders_view: bool,
+}
+
+// Result of fetching a query
+#[derive(Debug, Default)]
+pub struct FetchResult {
+    pub json_response: Option<Value>,
+    pub address_sign: Option<Vec<HistoryEntry>>,
+    pub error: Option<AppError>,
+}
+
+// What background tasks report back. They send these rather than lock the app,
+// and the coordinator hands each one to `App::apply`.
+#[derive(Debug)]
+pub enum AppUpdate {
+    // A query missed the cache and its request went out
+    FetchStarted {
+        pane_id: usize,
+        generation: u64,
+    },
+    FetchCompleted {
+        pane_id: usize,
+        generation: u64,
+        query: String,
+        result: FetchResult,
+        cached: bool,
+    },
+    // Whatever was fetched before the error is in `result`
+    FetchFailed {
+        pane_id: usize,
+        generation: u64,
+        query: String,
+        error: AppError,
+        result: FetchResult,
+    },
+    // Owners of the accounts of the transaction fetched for `query`
+    KeyAccounts {
+        pane_id: usize,
+        query: String,
+        accounts: RpcResult<Vec<Value>>,
+    },
+    SlotTick(SlotEvent),
+    // A page of older history of the account `query` names
+    HistoryPage {
+        pane_id: usize,
+        query: String,
+        page: RpcResult<Vec<HistoryEntry>>,
+    },
+    // The data of the account `query` names, for the hex viewer
+    AccountData {
+        pane_id: usize,
+        query: String,
+        data: RpcResult<Vec<u8>>,
+    },
+    TokenHoldings {
+        pane_id: usize,
+        query: String,
+        holdings: RpcResult<Vec<TokenHolding>>,
+    },
+    LargestHolders {
+        pane_id: usize,
+        query: String,
+        holders: RpcResult<Vec<TokenHolder>>,
+    },
+    InflationRewards {
+        pane_id: usize,
+        query: String,
+        rewards: RpcResult<Vec<EpochReward>>,
+    },
+    // Whether each other network has what `query` names
+    NetworkSearch {
+        pane_id: usize,
+        query: String,
+        presence: Vec<NetworkPresence>,
+    },
+    // Who was scheduled to produce the block `query` names
+    SlotLeader {
+        pane_id: usize,
+        query: String,
+        leader: RpcResult<Value>,
+    },
+    // A pane's account watch went live, or fell back to polling after losing its
+    // connection for `lost`
+    WatchMode {
+        pane_id: usize,
+        live: bool,
+        lost: Option<String>,
+    },
+    // New transactions of the address a pane watches
+    WatchedTransactions {
+        pane_id: usize,
+        address: Pubkey,
+        entries: Vec<HistoryEntry>,
+    },
+    SignatureStatus {
+        signature: String,
+        progress: SignatureProgress,
+        slot: Option<u64>,
+    },
+    // What the tabs show, fetched from `rpc_url`
+    RecentBlocks {
+        rpc_url: String,
+        blocks: RpcResult<Vec<BlockSummary>>,
+    },
+    VoteAccounts {
+        rpc_url: String,
+        validators: RpcResult<Vec<VoteAccountSummary>>,
+    },
+    BlockProduction {
+        rpc_url: String,
+        production: RpcResult<BlockProduction>,
+    },
+    // The refresh interval is up
+    RefreshDashboard,
+    // The dashboard data and cluster health fetched from `rpc_url`; boxed, as the
+    // dashboard is much larger than any other update
+    Dashboard {
+        rpc_url: String,
+        health: ClusterHealth,
+        genesis_hash: Option<String>,
+        result: Box<RpcResult<(DashboardData, Vec<AppError>)>>,
+    },
+    // A message for the status bar
+    Notify(StatusLevel, String),
+}
+
+// A successful fetch as the query cache keeps it
+#[derive(Debug, Clone)]
+struct CachedQuery {
+    json_response: Option<Value>,
+    address_sign: Option<Vec<HistoryEntry>>,
+}
+
+// An error shown in the banner above the results
+#[derive(Debug, Clone)]
+pub struct ErrorBanner {
+    pub message: String,
+    pub at: Instant,
+}
+
+impl ErrorBanner {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            at: Instant::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+// Status bar text and level for a failed load of `label`: nothing to find is a
+// warning, the RPC failing an error. The banner has the details.
+fn failure_status(err: &AppError, label: &str) -> (StatusLevel, String) {
+    match err {
+        AppError::NotFound(_) => (StatusLevel::Warning, format!("No such {}", label)),
+        AppError::Pruned { first_available } => {
+            (StatusLevel::Warning, format!("Pruned here, this RPC only serves blocks ≥ {}", first_available))
+        }
+        AppError::InvalidQuery(_) | AppError::SignatureParse(_) | AppError::PubkeyParse(_) => {
+            (StatusLevel::Warning, "Not a slot, address or signature".to_string())
+        }
+        AppError::Timeout => (StatusLevel::Error, "The RPC timed out".to_string()),
+        AppError::RpcTransport(_) => (StatusLevel::Error, "The RPC is unreachable".to_string()),
+        _ => (StatusLevel::Error, format!("Failed to load {}", label)),
+    }
+}
+
+// A short-lived message shown in the status bar, set with App::notify
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub level: StatusLevel,
+    pub at: Instant,
+}
+
+impl StatusMessage {
+    // Warnings and errors stay up longer so they can be read
+    fn is_visible(&self) -> bool {
+        let duration = match self.level {
+            StatusLevel::Info => STATUS_DURATION,
+            StatusLevel::Warning | StatusLevel::Error => STATUS_ERROR_DURATION,
+        };
+        self.at.elapsed() < duration
+    }
+}
+
+// Where the event loop gets terminal events from; tests feed their own
+pub trait EventSource {
+    // The next event, or None when there was none within `timeout`
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>;
+}
+
+// Events of the real terminal
+pub struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+        event::read().map(Some)
+    }
+}
+
+// A query fetch running in the background
+#[derive(Debug)]
+pub struct FetchTask {
+    handle: JoinHandle<()>,
+    pub label: &'static str, // What is being fetched, e.g. "account"
+    previous: ViewSnapshot, // View to restore when the fetch is cancelled
+    pushed_view: bool, // Whether `previous` was pushed onto the view history
+    generation: u64, // Results of any other generation are stale
+}
+
+// Signal shared between the event loop and background tasks; once triggered,
+// requests still waiting on the network are dropped instead of holding up exit
+#[derive(Debug, Clone)]
+pub struct Shutdown(Arc<watch::Sender<bool>>);
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Shutdown(Arc::new(watch::channel(false).0))
+    }
+}
+
+impl Shutdown {
+    pub fn trigger(&self) {
+        self.0.send_replace(true);
+    }
+
+    // Run `future` to completion, or give up with None once shutdown is triggered
+    pub async fn or_cancel<F: std::future::Future>(&self, future: F) -> Option<F::Output> {
+        let mut triggered = self.0.subscribe();
+        tokio::select! {
+            biased;
+            _ = triggered.wait_for(|triggered| *triggered) => None,
+            output = future => Some(output),
+        }
+    }
+}
+
+// Rows of the results table along with the selectable history entries
+struct ResultTable<'a> {
+    rows: Vec<ResultRow<'a>>,
+    history_start: Option<usize>, // Index of the first history row
+    history_signatures: Vec<&'a str>, // Full signatures, one per history row
+    instruction_start: Option<usize>, // Index of the first instruction row
+    instruction_count: usize,
+    address_start: Option<usize>, // Index of the first row that opens an address, such as a holder or validator
+    addresses: Vec<&'a str>, // The address Enter opens for each of those rows
+    column_widths: Option<Vec<Constraint>>, // For tables other than labels and values
+    inline_widgets: Vec<(usize, InlineWidget)>, // Widgets drawn over the last columns of a row
+}
+
+// One row of the results table; cells stay as Lines so they can be searched before rendering
+struct ResultRow<'a> {
+    cells: Vec<Line<'a>>,
+}
+
+impl<'a> ResultRow<'a> {
+    fn new<T: Into<Line<'a>>>(cells: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            cells: cells.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+// Incremental search over the cells of the result view
+#[derive(Debug, Clone, Default)]
+pub struct ViewSearch {
+    pub term: String,
+    pub editing: bool, // Whether the term is still being typed
+    current: usize,    // Index of the focused match
+}
+
+// A small widget drawn inside a row of the results table
+enum InlineWidget {
+    Sparkline(Vec<u64>),
+    Gauge { ratio: f64, label: String },
+}
+
+impl<'a> ResultTable<'a> {
+    fn from_rows(rows: Vec<ResultRow<'a>>) -> Self {
+        Self {
+            rows,
+            history_start: None,
+            history_signatures: vec![],
+            instruction_start: None,
+            instruction_count: 0,
+            address_start: None,
+            addresses: vec![],
+            column_widths: None,
+            inline_widgets: vec![],
+        }
+    }
+
+    // First row and number of rows the selection moves over: history rows, instructions
+    // or addresses
+    fn selectable_rows(&self) -> Option<(usize, usize)> {
+        self.history_start
+            .map(|start| (start, self.history_signatures.len()))
+            .or(self.instruction_start.map(|start| (start, self.instruction_count)))
+            .or(self.address_start.map(|start| (start, self.addresses.len())))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tab {
+    Dashboard,
+    Search,
+    Blocks,
+    Validators,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 4] = [Tab::Dashboard, Tab::Search, Tab::Blocks, Tab::Validators];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Dashboard => "Dashboard",
+            Tab::Search => "Search",
+            Tab::Blocks => "Blocks",
+            Tab::Validators => "Validators",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Tab::ALL.iter().position(|tab| tab == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> Tab {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    pub fn previous(&self) -> Tab {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+}
+
+// A tab with the state it is drawn from and handles keys for. The app keeps the
+// screen of every tab, so switching back finds a tab as it was left.
+#[derive(Debug)]
+pub enum Screen {
+    Dashboard(Box<DashboardScreen>),
+    Search(SearchScreen),
+    Blocks(BlocksScreen),
+    Validators(ValidatorsScreen),
+}
+
+impl Screen {
+    fn new(tab: Tab) -> Screen {
+        match tab {
+            Tab::Dashboard => Screen::Dashboard(Box::default()),
+            Tab::Search => Screen::Search(SearchScreen::default()),
+            Tab::Blocks => Screen::Blocks(BlocksScreen::default()),
+            Tab::Validators => Screen::Validators(ValidatorsScreen::default()),
+        }
+    }
+
+    // Drop what was fetched from the network, keeping how the screen shows it
+    fn clear_fetched(&mut self) {
+        match self {
+            Screen::Dashboard(dashboard) => {
+                dashboard.data = DashboardData::default();
+                dashboard.last_refresh = None;
+            }
+            // Query results are in the panes, which the network switch clears
+            Screen::Search(_) => {}
+            Screen::Blocks(blocks) => blocks.blocks = TabData::NotLoaded,
+            Screen::Validators(validators) => {
+                validators.validators = TabData::NotLoaded;
+                validators.block_production = TabData::NotLoaded;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DashboardScreen {
+    pub data: DashboardData,
+    pub last_refresh: Option<Instant>, // When the data was last fetched
+    pub show_non_circulating: bool, // List the non-circulating accounts, toggled with 'c'
+}
+
+impl DashboardScreen {
+    // Show a refetch; values whose request failed this time stay as they were. The
+    // slot doesn't step back while `live` slots are ahead of getSlot.
+    fn update(&mut self, mut data: DashboardData, live: bool) {
+        let old = std::mem::take(&mut self.data);
+        if live {
+            data.slot_info = data.slot_info.max(old.slot_info);
+        }
+        data.slot_info = data.slot_info.or(old.slot_info);
+        data.supply_info = data.supply_info.or(old.supply_info);
+        data.transaction_info = data.transaction_info.or(old.transaction_info);
+        data.inflation = data.inflation.or(old.inflation);
+        data.fees = data.fees.or(old.fees);
+        data.first_available_block = data.first_available_block.or(old.first_available_block);
+        data.block_height = data.block_height.or(old.block_height);
+        data.snapshot = data.snapshot.or(old.snapshot);
+        data.max_retransmit_slot = data.max_retransmit_slot.or(old.max_retransmit_slot);
+        self.data = data;
+        self.last_refresh = Some(Instant::now());
+    }
+}
+
+// How the Search tab shows results; the results themselves are in the panes
+#[derive(Debug)]
+pub struct SearchScreen {
+    pub history_filter: HistoryFilter,
+    pub history_sort: HistorySort,
+    pub show_unchanged_balances: bool, // List accounts whose balance did not change, toggled with 'a'
+    pub show_logs: bool, // Log pane under a transaction, toggled with 'l'
+    pub show_inner_instructions: bool, // Inner instructions under their parents, toggled with 'i'
+    logs_scroll: u16, // First visible line of the log pane
+}
+
+impl Default for SearchScreen {
+    fn default() -> Self {
+        Self {
+            history_filter: HistoryFilter::default(),
+            history_sort: HistorySort::default(),
+            show_unchanged_balances: false,
+            show_logs: false,
+            show_inner_instructions: true,
+            logs_scroll: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BlocksScreen {
+    pub blocks: TabData<Vec<BlockSummary>>,
+}
+
+#[derive(Debug, Default)]
+pub struct ValidatorsScreen {
+    pub validators: TabData<Vec<VoteAccountSummary>>,
+    pub block_production: TabData<BlockProduction>, // Of the current epoch
+    pub sort: ValidatorSort,
+    pub filter: ValidatorFilter,
+}
+
+// What the Search tab shows of its query, decided by what the query loaded
+#[derive(Debug, Clone, Copy)]
+pub enum SearchView<'a> {
+    // Nothing loaded yet: the prompt, the query in flight or why it failed
+    Query(&'a QueryState),
+    Account(&'a Map<String, Value>),
+    MissingAccount(&'a str),
+    // The largest holders of a mint, toggled from its account view
+    TokenHolders(&'a Value),
+    Transaction(&'a Map<String, Value>),
+    // Known by its signature status only, until the transaction can be read
+    PendingTransaction(&'a Value),
+    Block(&'a Value),
+    Unsupported,
+}
+
+// Data of a tab that is fetched the first time it is opened
+#[derive(Debug, Default)]
+pub enum TabData<T> {
+    #[default]
+    NotLoaded,
+    Loading,
+    Loaded(T),
+}
+
+// A recently produced block shown on the Blocks tab
+#[derive(Debug, Clone)]
+pub struct BlockSummary {
+    pub slot: u64,
+    pub blockhash: String,
+    pub block_time: Option<i64>,
+    pub transaction_count: usize,
+}
+
+// A vote account shown on the Validators tab
+#[derive(Debug, Clone)]
+pub struct VoteAccountSummary {
+    pub vote_pubkey: String,
+    pub node_pubkey: String, // Identity of the validator voting with the account
+    pub activated_stake: u64,
+    pub commission: u64,
+    pub last_vote: u64,
+    pub delinquent: bool,
+}
+
+
+#[derive(Debug, Default)]
+pub enum InputMode {
+    #[default]
+    Normal,
+    Editing,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        let (updates, update_queue) = mpsc::unbounded_channel();
+        Self {
+            pane: Pane::default(),
+            split: None,
+            input: QueryInput::default(),
+            query_history: QueryHistory::default(),
+            bookmarks: Bookmarks::default(),
+            signature_watches: Vec::new(),
+            finished_watch: None,
+            bell: false,
+            dirty: true,
+            popup: None,
+            refresh_interval: Duration::from_secs(DEFAULT_REFRESH_SECS),
+            health: ClusterHealth::Unknown,
+            last_contact: None,
+            live_slots: true,
+            transport: Transport::Network,
+            slot_feed: SlotFeed::default(),
+            updates,
+            update_queue: Some(update_queue),
+            slot_task: None,
+            dashboard_task: None,
+            latencies: Arc::default(),
+            rate_limiter: Arc::new(RateLimiter::new(rpc::DEFAULT_RATE_LIMIT)),
+            genesis_hash: None,
+            query_cache: Arc::new(std::sync::Mutex::new(ResponseCache::new(QUERY_CACHE_SIZE))),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            search: None,
+            exit: false,
+            shutdown: Shutdown::default(),
+            log: LogBuffer::default(),
+            log_file: None,
+            current_rpc_network: RpcNetwork::Devnet,
+            error: None,
+            status: None,
+            theme: Theme::default(),
+            keymap: Keymap::default(),
+            fetch_generation: 0,
+            commitment: CommitmentConfig::default(),
+            currency_unit: CurrencyUnit::default(),
+            relative_times: false,
+            tab: Tab::Dashboard,
+            screens: Tab::ALL.map(Screen::new),
+            layout: FrameLayout::default(),
+            last_click: None,
+            client: rpc::http_client(),
+            chain: None,
+        }
+    }
+}
+
+impl App {
+    //toggle RPCs
+     pub fn toggle_rpc_network(&mut self) {
+        // Cycle Devnet -> Testnet -> Mainnet -> Solana Mainnet
+        let network = self.current_rpc_network.next();
+        self.set_rpc_network(network);
+    }
+
+    // Switch networks, dropping everything fetched from the previous one
+    pub fn set_rpc_network(&mut self, network: RpcNetwork) {
+        self.current_rpc_network = network;
+        self.health = ClusterHealth::Unknown;
+        self.last_contact = None;
+        self.latencies = Arc::default();
+        self.rate_limiter = Arc::new(RateLimiter::new(self.rate_limiter.rate()));
+        self.genesis_hash = None;
+        // Cached data belongs to the previous network; refetch the dashboard right away
+        for screen in self.screens.iter_mut() {
+            screen.clear_fetched();
+        }
+        if let Some(task) = self.dashboard_task.take() {
+            task.abort();
+        }
+        self.refresh_dashboard();
+        self.start_slot_feed();
+        // Results came from the previous network too
+        let had_results = self.pane.json_response.is_some()
+            || self.split.as_ref().is_some_and(|other| other.json_response.is_some());
+        self.pane.clear_results();
+        if let Some(other) = self.split.as_mut() {
+            other.clear_results();
+        }
+        self.error = None;
+        self.popup.take_if(|popup| matches!(popup, Popup::HexViewer(_)));
+        if had_results {
+            self.notify(
+                StatusLevel::Info,
+                format!("Cleared results from the previous network, Enter searches {}", self.current_rpc_network.name()),
+            );
+        }
+        self.ensure_tab_loaded();
+    }
+
+    // (Re)connect the WebSocket slot subscription for the current network
+    fn start_slot_feed(&mut self) {
+        if let Some(task) = self.slot_task.take() {
+            task.abort();
+        }
+        self.slot_feed = SlotFeed::Polling;
+        let Some(url) = live::ws_url(self.get_current_rpc_url()).filter(|_| self.live_slots) else {
+            return;
+        };
+        let (sender, mut events) = mpsc::unbounded_channel();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        self.slot_task = Some(tokio::spawn(async move {
+            let forward = async move {
+                while let Some(event) = events.recv().await {
+                    let _ = updates.send(AppUpdate::SlotTick(event));
+                }
+            };
+            shutdown.or_cancel(async { tokio::join!(live::subscribe_slots(url, sender), forward) }).await;
+        }));
+    }
+
+    // Act on an update from a background task. Results of a superseded fetch, or
+    // for a pane that was closed meanwhile, are dropped.
+    pub fn apply(&mut self, update: AppUpdate) {
+        self.dirty = true;
+        match update {
+            // The time shown from here on is the request's, not the debounce's
+            AppUpdate::FetchStarted { pane_id, generation } => {
+                if let Some(pane) = self.fetching_pane(pane_id, generation) {
+                    if let QueryState::Loading { started_at, .. } = &mut pane.state {
+                        *started_at = Instant::now();
+                    }
+                }
+            }
+            AppUpdate::FetchCompleted {
+                pane_id,
+                generation,
+                query,
+                result,
+                cached,
+            } => self.finish_fetch(pane_id, generation, query, result, cached),
+            AppUpdate::FetchFailed {
+                pane_id,
+                generation,
+                query,
+                error,
+                result,
+            } => {
+                let result = FetchResult {
+                    error: Some(error),
+                    ..result
+                };
+                self.finish_fetch(pane_id, generation, query, result, false);
+            }
+            AppUpdate::KeyAccounts { pane_id, query, accounts } => {
+                let Some(pane) = self.pane_mut(pane_id).filter(|pane| pane.query == query) else {
+                    return;
+                };
+                match accounts {
+                    Ok(accounts) => {
+                        let meta = pane
+                            .json_response
+                            .as_mut()
+                            .and_then(|response| response.get_mut("meta"))
+                            .filter(|meta| meta.get("keyAccounts").is_none());
+                        if let Some(meta) = meta {
+                            meta["keyAccounts"] = Value::from(accounts);
+                            pane.refresh_raw_view();
+                        }
+                    }
+                    Err(e) => self.notify(StatusLevel::Warning, format!("Failed to look up account owners: {}", e)),
+                }
+            }
+            AppUpdate::SlotTick(SlotEvent::Connected) => self.slot_feed = SlotFeed::Live,
+            AppUpdate::SlotTick(SlotEvent::Slot(slot)) => {
+                self.dashboard_screen_mut().data.slot_info = Some(slot);
+                self.last_contact = Some(Instant::now());
+            }
+            // Failed reconnects stay quiet; only losing a live feed is worth a message
+            AppUpdate::SlotTick(SlotEvent::Disconnected(reason)) => {
+                if self.slot_feed == SlotFeed::Live {
+                    self.notify(
+                        StatusLevel::Warning,
+                        format!("Live slot updates lost ({}), polling until reconnected", reason),
+                    );
+                }
+                self.slot_feed = SlotFeed::Polling;
+            }
+            AppUpdate::HistoryPage { pane_id, query, page } => {
+                // A different view replaced this one meanwhile
+                let Some(pane) = self.pane_mut(pane_id).filter(|pane| pane.query == query) else {
+                    return;
+                };
+                match page {
+                    Ok(page) => {
+                        let len = page.len();
+                        let more = pane.append_history(page);
+                        self.notify(StatusLevel::Info, format!("Loaded {} more transactions", len));
+                        if !more {
+                            self.notify(
+                                StatusLevel::Warning,
+                                format!("History capped at {} transactions", MAX_HISTORY_ROWS),
+                            );
+                        }
+                    }
+                    Err(err) => {
+                        pane.history_paging = HistoryPaging::More;
+                        self.notify(StatusLevel::Error, format!("Failed to fetch more history: {}", err));
+                    }
+                }
+            }
+            AppUpdate::AccountData { pane_id, query, data } => {
+                // A different view replaced this one meanwhile, or the network changed
+                if self.pane.id != pane_id || self.pane.query != query || self.pane.json_response.is_none() {
+                    return;
+                }
+                match data {
+                    Ok(data) => {
+                        self.notify(StatusLevel::Info, format!("Loaded {} bytes", data.len()));
+                        self.popup = Some(Popup::HexViewer(HexViewer {
+                            data,
+                            ..HexViewer::default()
+                        }));
+                    }
+                    Err(err) => self.notify(StatusLevel::Error, format!("Failed to fetch account data: {}", err)),
+                }
+            }
+            AppUpdate::TokenHoldings { pane_id, query, holdings } => {
+                let Some(pane) = self
+                    .pane_mut(pane_id)
+                    .filter(|pane| pane.query == query && matches!(pane.token_holdings, TabData::Loading))
+                else {
+                    return;
+                };
+                match holdings {
+                    Ok(holdings) => {
+                        let count = holdings.len();
+                        pane.token_holdings = TabData::Loaded(holdings);
+                        self.notify(StatusLevel::Info, format!("Loaded {} token accounts", count));
+                    }
+                    Err(e) => {
+                        pane.token_holdings = TabData::NotLoaded;
+                        self.notify(StatusLevel::Error, format!("Failed to fetch token accounts: {}", e));
+                    }
+                }
+            }
+            AppUpdate::LargestHolders { pane_id, query, holders } => {
+                let Some(pane) = self
+                    .pane_mut(pane_id)
+                    .filter(|pane| pane.query == query && matches!(pane.largest_holders, TabData::Loading))
+                else {
+                    return;
+                };
+                match holders {
+                    Ok(holders) => {
+                        let count = holders.len();
+                        pane.largest_holders = TabData::Loaded(holders);
+                        self.notify(StatusLevel::Info, format!("Loaded the {} largest holders", count));
+                    }
+                    Err(e) => {
+                        pane.largest_holders = TabData::NotLoaded;
+                        self.notify(StatusLevel::Error, format!("Failed to fetch the largest holders: {}", e));
+                    }
+                }
+            }
+            AppUpdate::InflationRewards { pane_id, query, rewards } => {
+                let Some(pane) = self
+                    .pane_mut(pane_id)
+                    .filter(|pane| pane.query == query && matches!(pane.inflation_rewards, TabData::Loading))
+                else {
+                    return;
+                };
+                match rewards {
+                    Ok(rewards) => pane.inflation_rewards = TabData::Loaded(rewards),
+                    Err(e) => {
+                        pane.inflation_rewards = TabData::NotLoaded;
+                        self.notify(StatusLevel::Error, format!("Failed to fetch inflation rewards: {}", e));
+                    }
+                }
+            }
+            AppUpdate::NetworkSearch { pane_id, query, presence } => {
+                let Some(pane) = self
+                    .pane_mut(pane_id)
+                    .filter(|pane| pane.query == query && matches!(pane.network_search, TabData::Loading))
+                else {
+                    return;
+                };
+                let found: Vec<&str> = presence
+                    .iter()
+                    .filter(|presence| presence.found == Ok(true))
+                    .map(|presence| presence.network.name())
+                    .collect();
+                let summary = match found.is_empty() {
+                    true => "Not found on any other network".to_string(),
+                    false => format!("Found on {}", found.join(", ")),
+                };
+                pane.network_search = TabData::Loaded(presence);
+                self.notify(StatusLevel::Info, summary);
+            }
+            AppUpdate::SlotLeader { pane_id, query, leader } => {
+                let Some(pane) = self.pane_mut(pane_id).filter(|pane| pane.query == query && pane.leader_lookup) else {
+                    return;
+                };
+                pane.leader_lookup = false;
+                match leader {
+                    Ok(leader) => {
+                        let block = pane.json_response.as_mut().and_then(|response| response.get_mut("block"));
+                        if let Some(block) = block {
+                            block["leader"] = leader;
+                            pane.refresh_raw_view();
+                        }
+                    }
+                    Err(e) => self.notify(StatusLevel::Error, format!("Failed to look up the slot leader: {}", e)),
+                }
+            }
+            AppUpdate::WatchMode { pane_id, live, lost } => self.set_watch_mode(pane_id, live, lost),
+            AppUpdate::WatchedTransactions { pane_id, address, entries } => {
+                self.add_watched_transactions(pane_id, &address, entries)
+            }
+            AppUpdate::SignatureStatus { signature, progress, slot } => {
+                self.update_signature_watch(&signature, progress, slot)
+            }
+            AppUpdate::RecentBlocks { rpc_url, blocks } => {
+                if self.chain_url() != rpc_url {
+                    return;
+                }
+                match blocks {
+                    Ok(blocks) => self.blocks_screen_mut().blocks = TabData::Loaded(blocks),
+                    Err(e) => {
+                        self.blocks_screen_mut().blocks = TabData::NotLoaded;
+                        self.notify(StatusLevel::Error, format!("Failed to fetch recent blocks: {}", e));
+                    }
+                }
+            }
+            AppUpdate::VoteAccounts { rpc_url, validators } => {
+                if self.chain_url() != rpc_url {
+                    return;
+                }
+                match validators {
+                    Ok(validators) => self.validators_screen_mut().validators = TabData::Loaded(validators),
+                    Err(e) => {
+                        self.validators_screen_mut().validators = TabData::NotLoaded;
+                        self.notify(StatusLevel::Error, format!("Failed to fetch vote accounts: {}", e));
+                    }
+                }
+            }
+            AppUpdate::BlockProduction { rpc_url, production } => {
+                if self.chain_url() != rpc_url {
+                    return;
+                }
+                match production {
+                    Ok(production) => self.validators_screen_mut().block_production = TabData::Loaded(production),
+                    Err(e) => {
+                        self.validators_screen_mut().block_production = TabData::NotLoaded;
+                        self.notify(StatusLevel::Warning, format!("Failed to fetch block production: {}", e));
+                    }
+                }
+            }
+            AppUpdate::RefreshDashboard => self.refresh_dashboard(),
+            AppUpdate::Dashboard {
+                rpc_url,
+                health,
+                genesis_hash,
+                result,
+            } => self.finish_dashboard_refresh(rpc_url, health, genesis_hash, *result),
+            AppUpdate::Notify(level, text) => self.notify(level, text),
+        }
+    }
+
+    // The pane with `pane_id`, if its current fetch is of `generation`
+    fn fetching_pane(&mut self, pane_id: usize, generation: u64) -> Option<&mut Pane> {
+        self.pane_mut(pane_id)
+            .filter(|pane| pane.fetch_task.as_ref().is_some_and(|task| task.generation == generation))
+    }
+
+    // Show the result of the pane's fetch, and say how it went
+    fn finish_fetch(&mut self, pane_id: usize, generation: u64, query: String, result: FetchResult, cached: bool) {
+        // The pane may have lost focus while this was loading, or started another query
+        let Some(pane) = self.fetching_pane(pane_id, generation) else {
+            return;
+        };
+        let label = pane.fetch_task.take().map_or("query", |task| task.label);
+        let started_at = match pane.state {
+            QueryState::Loading { started_at, .. } => started_at,
+            _ => Instant::now(),
+        };
+        pane.json_response = result.json_response;
+        pane.history_paging = HistoryPaging::after_page(result.address_sign.as_ref().map_or(0, Vec::len));
+        pane.address_sign = result.address_sign;
+        pane.refresh_raw_view();
+        let missing_account = pane.shows_missing_account();
+        self.error = result.error.as_ref().map(|err| ErrorBanner::new(err.to_string()));
+        match &result.error {
+            // Mistaking the network is the usual reason, another one may have it
+            Some(AppError::NotFound(_)) if label != "block" => {
+                let network = self.current_rpc_network.name().to_string();
+                self.notify(
+                    StatusLevel::Warning,
+                    format!("Not found on {} — press 'a' to search all configured networks", network),
+                );
+            }
+            None if missing_account => {
+                let network = self.current_rpc_network.name().to_string();
+                self.notify(
+                    StatusLevel::Warning,
+                    format!("No account on {} — press 'a' to search all configured networks", network),
+                );
+            }
+            Some(err) => {
+                let (level, text) = failure_status(err, label);
+                self.notify(level, text);
+            }
+            None if cached => self.notify(StatusLevel::Info, format!("Loaded {} from the cache", label)),
+            None => {
+                let elapsed = started_at.elapsed().as_secs_f64();
+                self.notify(StatusLevel::Info, format!("Loaded {} in {:.1}s", label, elapsed));
+            }
+        }
+        if let Some(pane) = self.pane_mut(pane_id) {
+            pane.state = match result.error {
+                Some(error) => QueryState::Failed { query, error },
+                None if pane.json_response.is_some() => QueryState::Loaded,
+                None => QueryState::Idle,
+            };
+        }
+    }
+
+    pub fn get_current_rpc_url(&self) -> &str {
+        self.current_rpc_network.get_url()
+    }        
+
+    // The pane with `id`, whether or not it has focus
+    fn pane_mut(&mut self, id: usize) -> Option<&mut Pane> {
+        if self.pane.id == id {
+            return Some(&mut self.pane);
+        }
+        self.split.as_mut().filter(|pane| pane.id == id)
+    }
+
+    // Split the Search results into two panes, or close the one without focus
+    pub fn toggle_split(&mut self) {
+        if let Some(mut other) = self.split.take() {
+            other.stop_tasks();
+            return;
+        }
+        self.set_tab(Tab::Search);
+        // The new pane opens on the right and takes focus, ready for a query
+        let right = Pane {
+            id: 1 - self.pane.id,
+            ..Pane::default()
+        };
+        self.split = Some(std::mem::replace(&mut self.pane, right));
+        self.notify(StatusLevel::Info, "Split view: Tab switches panes, | closes the other one");
+    }
+
+    // Move focus to the other pane of a split
+    fn switch_pane(&mut self) {
+        if let Some(other) = self.split.as_mut() {
+            std::mem::swap(&mut self.pane, other);
+            self.search = None;
+            self.search_screen_mut().logs_scroll = 0;
+        }
+    }
+
+    // Show a message in the status bar until it times out
+    pub fn notify(&mut self, level: StatusLevel, text: impl Into<String>) {
+        self.status = Some(StatusMessage {
+            text: text.into(),
+            level,
+            at: Instant::now(),
+        });
+        self.dirty = true;
+    }
+
+    // Client for the current network, sharing the latency statistics
+    pub fn rpc_endpoint(&self) -> RpcApi {
+        RpcApi::shared(
+            self.client.clone(),
+            self.get_current_rpc_url(),
+            Arc::clone(&self.latencies),
+        )
+        .with_rate_limiter(Arc::clone(&self.rate_limiter))
+        .with_transport(self.transport.clone())
+    }
+
+    fn snapshot_view(&self) -> ViewSnapshot {
+        ViewSnapshot {
+            query: self.pane.query.clone(),
+            json_response: self.pane.json_response.clone(),
+            address_sign: self.pane.address_sign.clone(),
+            selected_history: self.pane.selected_history,
+            scroll_offset: self.pane.scroll_offset,
+            largest_holders: match &self.pane.largest_holders {
+                TabData::Loaded(holders) => Some(holders.clone()),
+                _ => None,
+            },
+            holders_view: self.pane.holders_view,
+        }
+    }
+
+    fn restore_view(&mut self, view: ViewSnapshot) {
+        self.pane.reset_history_paging();
+        self.pane.stop_watch();
+        self.pane.token_holdings = TabData::NotLoaded;
+        self.pane.inflation_rewards = TabData::NotLoaded;
+        self.pane.network_search = TabData::NotLoaded;
+        self.pane.largest_holders = view.largest_holders.map_or(TabData::NotLoaded, TabData::Loaded);
+        self.pane.holders_view = view.holders_view;
+        self.pane.leader_lookup = false;
+        self.pane.query = view.query;
+        self.pane.json_response = view.json_response;
+        self.pane.address_sign = view.address_sign;
+        self.pane.selected_history = view.selected_history;
+        self.pane.scroll_offset = view.scroll_offset;
+        self.pane.settle_state();
+        self.pane.refresh_raw_view();
+    }
+
+    // Remember a view for the Back key, dropping the oldest beyond the cap
+    fn push_view(&mut self, view: ViewSnapshot) {
+        if view.query.is_empty() {
+            return;
+        }
+        self.pane.view_history.push(view);
+        if self.pane.view_history.len() > MAX_VIEW_HISTORY {
+            self.pane.view_history.remove(0);
+        }
+    }
+
+    // Start editing the query, remembering the current view
+    pub fn start_editing(&mut self) {
+        self.input.editing_from = Some(self.snapshot_view());
+        self.input.mode = InputMode::Editing;
+        self.input.cursor = grapheme_count(&self.pane.query);
+        self.input.history_index = None;
+    }
+
+    // Replace the input with an older (Up) or newer (Down) submitted query
+    fn browse_history(&mut self, older: bool) {
+        let index = match (self.input.history_index, older) {
+            (None, true) => 0,
+            (Some(index), true) => index + 1,
+            (None, false) => return,
+            (Some(0), false) => {
+                // Back past the newest entry: restore the typed text
+                self.input.history_index = None;
+                self.pane.query = std::mem::take(&mut self.input.history_draft);
+                self.input.cursor = grapheme_count(&self.pane.query);
+                return;
+            }
+            (Some(index), false) => index - 1,
+        };
+        let Some(entry) = self.query_history.get(index) else {
+            return;
+        };
+        if self.input.history_index.is_none() {
+            self.input.history_draft = self.pane.query.clone();
+        }
+        self.pane.query = entry.to_string();
+        self.input.cursor = grapheme_count(&self.pane.query);
+        self.input.history_index = Some(index);
+    }
+
+    // Byte offset of the cursor, so edits always land between whole characters
+    fn cursor_byte_index(&self) -> usize {
+        self.pane.query
+            .grapheme_indices(true)
+            .nth(self.input.cursor)
+            .map_or(self.pane.query.len(), |(index, _)| index)
+    }
+
+    fn insert_at_cursor(&mut self, text: &str) {
+        let index = self.cursor_byte_index();
+        self.pane.query.insert_str(index, text);
+        // Counted again, as a combining mark joins the character before it
+        self.input.cursor = grapheme_count(&self.pane.query[..index + text.len()]);
+    }
+
+    // Remove the chars between `start` and the cursor, leaving the cursor at `start`
+    fn delete_back_to(&mut self, start: usize) {
+        let end = self.cursor_byte_index();
+        self.input.cursor = start;
+        let start = self.cursor_byte_index();
+        self.pane.query.replace_range(start..end, "");
+    }
+
+    // Start of the word before the cursor, skipping whitespace first like a shell
+    fn previous_word_start(&self) -> usize {
+        let is_space = |grapheme: &str| grapheme.chars().all(char::is_whitespace);
+        let before: Vec<&str> = self.pane.query.graphemes(true).take(self.input.cursor).collect();
+        let mut start = before.len();
+        while start > 0 && is_space(before[start - 1]) {
+            start -= 1;
+        }
+        while start > 0 && !is_space(before[start - 1]) {
+            start -= 1;
+        }
+        start
+    }
+
+    // Text pasted in the terminal (bracketed paste) arrives in one event: it goes to
+    // whichever input is open, or starts editing the query
+    fn handle_paste(&mut self, text: &str) {
+        let text = paste_text(text);
+        if matches!(self.input.mode, InputMode::Editing) {
+            self.insert_at_cursor(&text);
+        } else if let Some(Prompt::Command(input)) = self.input.prompt.as_mut() {
+            input.push_str(&text);
+        } else if let Some(Prompt::Rpc(prompt)) = self.input.prompt.as_mut() {
+            prompt.input.push_str(&text);
+        } else if let Some(Prompt::Date(input)) = self.input.prompt.as_mut() {
+            input.push_str(&text);
+        } else if let Some(Popup::Bookmarks(BookmarkPopup::Label(label))) = self.popup.as_mut() {
+            label.push_str(&text);
+        } else if let Some(Popup::HexViewer(HexViewer { goto: Some(input), .. })) = self.popup.as_mut() {
+            input.push_str(&text);
+        } else if let Some(search) = self.search.as_mut().filter(|search| search.editing) {
+            search.term.push_str(&text);
+        } else if !self.popup_open() {
+            self.start_editing();
+            self.insert_at_cursor(&text);
+        }
+    }
+
+    // Whether a popup or question takes the keys instead of the main view
+    fn popup_open(&self) -> bool {
+        self.popup.is_some()
+            || self.finished_watch.is_some()
+            || matches!(self.input.prompt, Some(Prompt::ConfirmExport(_)))
+    }
+
+    // Line editing keys for the query input; returns false for keys handled elsewhere
+    fn edit_query(&mut self, key_event: KeyEvent) -> bool {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let len = grapheme_count(&self.pane.query);
+        self.input.cursor = self.input.cursor.min(len);
+        match key_event.code {
+            KeyCode::Char('a') if ctrl => self.input.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.input.cursor = len,
+            KeyCode::Char('w') if ctrl => self.delete_back_to(self.previous_word_start()),
+            KeyCode::Char('u') if ctrl => {
+                self.pane.query.clear();
+                self.input.cursor = 0;
+            }
+            // Paste from the clipboard (Ctrl+V)
+            KeyCode::Char('v') if ctrl => match clipboard_contents() {
+                Ok(clipboard_content) => self.insert_at_cursor(&paste_text(&clipboard_content)),
+                Err(err) => self.notify(StatusLevel::Warning, err.to_string()),
+            },
+            KeyCode::Char(c) if !ctrl => self.insert_at_cursor(c.encode_utf8(&mut [0; 4])),
+            KeyCode::Backspace => {
+                if self.input.cursor > 0 {
+                    self.delete_back_to(self.input.cursor - 1);
+                }
+            }
+            KeyCode::Delete => {
+                if self.input.cursor < len {
+                    self.input.cursor += 1;
+                    self.delete_back_to(self.input.cursor - 1);
+                }
+            }
+            KeyCode::Left => self.input.cursor = self.input.cursor.saturating_sub(1),
+            KeyCode::Right => self.input.cursor = (self.input.cursor + 1).min(len),
+            KeyCode::Home => self.input.cursor = 0,
+            KeyCode::End => self.input.cursor = len,
+            KeyCode::Up => self.browse_history(true),
+            KeyCode::Down => self.browse_history(false),
+            _ => return false,
+        }
+        true
+    }
+
+    // Submit the typed query, keeping the replaced view for going back
+    pub fn submit_query(&mut self) {
+        self.input.mode = InputMode::Normal;
+        self.input.history_index = None;
+        // Say what a pasted link or quoted text was read as
+        let query = normalize_query(&self.pane.query);
+        if query != self.pane.query {
+            self.pane.query = query;
+            self.input.cursor = grapheme_count(&self.pane.query);
+            if let Ok(kind) = QueryKind::parse(&self.pane.query) {
+                let text = format!("Interpreted as {} {}", kind.label(), truncate(&self.pane.query, 12));
+                self.notify(StatusLevel::Info, text);
+            }
+        }
+        self.query_history.add(&self.pane.query);
+        let previous = self.input.editing_from.take().unwrap_or_else(|| self.snapshot_view());
+        let pushed_view = !previous.query.is_empty() && previous.query != self.pane.query;
+        if pushed_view {
+            self.push_view(previous.clone());
+        }
+        self.pane.scroll_offset = 0;
+        self.pane.selected_history = None;
+        if self.pane.query.is_empty() {
+            return;
+        }
+        self.tab = Tab::Search;
+        self.spawn_fetch(previous, pushed_view);
+    }
+
+    // Fetch the selected history transaction, keeping the account view for going back
+    // Enter on a selection: instructions expand into a popup, history rows open their transaction
+    pub fn open_selected_row(&mut self) {
+        if self.result_rows(&self.pane).instruction_start.is_some() {
+            if let Some(selected) = self.pane.selected_history {
+                self.popup = Some(Popup::InstructionDetail { index: selected, scroll: 0 });
+            }
+            return;
+        }
+        if let Some(address) = self.selected_address() {
+            self.tab = Tab::Search;
+            self.open_query(address);
+            return;
+        }
+        self.open_selected_transaction();
+    }
+
+    pub fn open_selected_transaction(&mut self) {
+        let Some(signature) = self.selected_signature() else {
+            return;
+        };
+        self.open_query(signature);
+    }
+
+    // Look up `query` from the current view, which Back returns to
+    fn open_query(&mut self, query: String) {
+        let previous = self.snapshot_view();
+        self.push_view(previous.clone());
+        self.pane.query = query;
+        self.pane.selected_history = None;
+        self.pane.scroll_offset = 0;
+        self.spawn_fetch(previous, true);
+    }
+
+    // Fetch the current query in a background task so the UI keeps responding
+    fn spawn_fetch(&mut self, previous: ViewSnapshot, pushed_view: bool) {
+        let rpc = self.query_chain();
+        self.spawn_fetch_from(rpc, previous, pushed_view, false);
+    }
+
+    // Fetch the current query again, skipping the cache
+    fn refresh_query(&mut self) {
+        if self.pane.query.is_empty() || self.pane.fetch_task.is_some() {
+            return;
+        }
+        let rpc = self.query_chain();
+        let previous = self.snapshot_view();
+        self.spawn_fetch_from(rpc, previous, false, true);
+    }
+
+    // Fetch the current query from `rpc`, or from the cache unless `refresh` is set.
+    // A newer query aborts this one, and its result is dropped if it still arrives,
+    // so the pane never shows stale data.
+    fn spawn_fetch_from(
+        &mut self,
+        rpc: Arc<dyn ChainApi>,
+        previous: ViewSnapshot,
+        pushed_view: bool,
+        refresh: bool,
+    ) {
+        if let Some(task) = self.pane.fetch_task.take() {
+            task.handle.abort();
+        }
+        self.error = None;
+        self.pane.reset_history_paging();
+        self.pane.stop_watch();
+        self.pane.token_holdings = TabData::NotLoaded;
+        self.pane.inflation_rewards = TabData::NotLoaded;
+        self.pane.network_search = TabData::NotLoaded;
+        self.pane.largest_holders = TabData::NotLoaded;
+        self.pane.holders_view = false;
+        self.pane.leader_lookup = false;
+        self.search_screen_mut().logs_scroll = 0;
+
+        let query = self.pane.query.clone();
+        let label = QueryKind::parse(&query).map_or("query", |kind| kind.label());
+
+        self.pane.state = QueryState::Loading {
+            query: query.clone(),
+            started_at: Instant::now(),
+        };
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        let cache = Arc::clone(&self.query_cache);
+        let cache_ttl = self.cache_ttl;
+        self.fetch_generation += 1;
+        let generation = self.fetch_generation;
+        // Everything logged while the query is answered, RPC requests included, is under its span
+        let span = tracing::info_span!("query", %query, kind = label);
+        let fetch_task = async move {
+            let request_sent = || {
+                tracing::debug!("Not cached, asking the RPC");
+                let _ = updates.send(AppUpdate::FetchStarted { pane_id, generation });
+            };
+            let fetch = App::fetch_cached(rpc.as_ref(), &cache, &query, cache_ttl, refresh, request_sent);
+            let Some((mut result, cached)) = shutdown.or_cancel(fetch).await else {
+                return;
+            };
+
+            // The owners of a transaction's accounts are looked up once it is on screen
+            let keys = result.json_response.as_ref().and_then(unresolved_account_keys);
+            match &result.error {
+                Some(error) => tracing::warn!(%error, "Query failed"),
+                None => tracing::info!(cached, "Query answered"),
+            }
+            let update = match result.error.take() {
+                Some(error) => AppUpdate::FetchFailed {
+                    pane_id,
+                    generation,
+                    query: query.clone(),
+                    error,
+                    result,
+                },
+                None => AppUpdate::FetchCompleted {
+                    pane_id,
+                    generation,
+                    query: query.clone(),
+                    result,
+                    cached,
+                },
+            };
+            if updates.send(update).is_err() {
+                return;
+            }
+            let Some(keys) = keys else {
+                return;
+            };
+            if let Some(accounts) = shutdown.or_cancel(App::fetch_key_accounts(rpc.as_ref(), &keys)).await {
+                let _ = updates.send(AppUpdate::KeyAccounts { pane_id, query, accounts });
+            }
+        };
+        let handle = tokio::spawn(fetch_task.instrument(span));
+
+        self.pane.fetch_task = Some(FetchTask {
+            handle,
+            label,
+            previous,
+            pushed_view,
+            generation,
+        });
+    }
+
+    // Client for search queries and account history, on the current network
+    fn query_endpoint(&self) -> RpcApi {
+        self.rpc_endpoint().with_commitment(self.commitment)
+    }
+
+    // Where search queries and what hangs off their results are answered: the
+    // injected chain, or the current network
+    fn query_chain(&self) -> Arc<dyn ChainApi> {
+        match &self.chain {
+            Some(chain) => Arc::clone(chain),
+            None => Arc::new(self.query_endpoint()),
+        }
+    }
+
+    // Where the dashboard and tabs are answered, at the RPC's own commitment
+    fn chain(&self) -> Arc<dyn ChainApi> {
+        match &self.chain {
+            Some(chain) => Arc::clone(chain),
+            None => Arc::new(self.rpc_endpoint()),
+        }
+    }
+
+    // What answers for the current network; results from anything else are stale
+    fn chain_url(&self) -> &str {
+        self.chain.as_ref().map_or(self.get_current_rpc_url(), |chain| chain.url())
+    }
+
+    // Fetch the page of account history before the last loaded signature
+    pub fn load_more_history(&mut self) {
+        if !matches!(self.pane.history_paging, HistoryPaging::More) || self.pane.fetch_task.is_some() {
+            return;
+        }
+        let Ok(address) = Pubkey::from_str(&self.pane.query) else {
+            return;
+        };
+        // Pages are requested in RPC order, whatever the table is sorted by
+        let Some(before) = self
+            .pane
+            .address_sign
+            .as_ref()
+            .and_then(|entries| entries.last())
+            .and_then(|entry| Signature::from_str(&entry.signature).ok())
+        else {
+            return;
+        };
+
+        let rpc = self.query_chain();
+        let query = self.pane.query.clone();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        let handle = tokio::spawn(async move {
+            let fetch = App::fetch_history(rpc.as_ref(), &address, Some(before), None);
+            let Some(page) = shutdown.or_cancel(fetch).await else {
+                return;
+            };
+            let _ = updates.send(AppUpdate::HistoryPage { pane_id, query, page });
+        });
+        self.pane.history_paging = HistoryPaging::Loading(handle);
+    }
+
+    // Fetch the queried account's data and show it in the hex viewer
+    pub fn open_hex_viewer(&mut self) {
+        if self.pane.fetch_task.is_some() || self.pane.json_response.as_ref().is_none_or(|response| response.get("lamports").is_none()) {
+            return;
+        }
+        let Ok(address) = Pubkey::from_str(&self.pane.query) else {
+            return;
+        };
+
+        let rpc = self.query_chain();
+        let query = self.pane.query.clone();
+        self.notify(StatusLevel::Info, "Loading account data…");
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        tokio::spawn(async move {
+            let Some(result) = shutdown.or_cancel(rpc.get_account(&address)).await else {
+                return;
+            };
+            let data = result.map(|account| account.data);
+            let _ = updates.send(AppUpdate::AccountData { pane_id, query, data });
+        });
+    }
+
+    fn handle_hex_viewer_key(&mut self, key_event: KeyEvent) {
+        let Some(Popup::HexViewer(viewer)) = self.popup.as_mut() else {
+            return;
+        };
+        // Typing an offset to jump to
+        if let Some(input) = viewer.goto.as_mut() {
+            let len = viewer.data.len();
+            match key_event.code {
+                KeyCode::Enter => match parse_offset(input) {
+                    Some(offset) if offset < len.max(1) => {
+                        viewer.goto = None;
+                        viewer.scroll_to(offset / 16);
+                    }
+                    _ => self.notify(StatusLevel::Error, format!("Offset must be below {:#x}", len)),
+                },
+                KeyCode::Esc => viewer.goto = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        let page = viewer.page_lines.max(1);
+        match key_event.code {
+            KeyCode::Up => viewer.scroll_to(viewer.top_line.saturating_sub(1)),
+            KeyCode::Down => viewer.scroll_to(viewer.top_line + 1),
+            KeyCode::PageUp => viewer.scroll_to(viewer.top_line.saturating_sub(page)),
+            KeyCode::PageDown => viewer.scroll_to(viewer.top_line + page),
+            KeyCode::Home => viewer.scroll_to(0),
+            KeyCode::End => viewer.scroll_to(usize::MAX),
+            KeyCode::Char('g') => viewer.goto = Some(String::new()),
+            KeyCode::Esc | KeyCode::Char('x') | KeyCode::Char('q') => self.popup = None,
+            _ => {}
+        }
+    }
+
+    // Only the visible lines are formatted, so large accounts stay cheap to draw
+    fn draw_hex_viewer(&self, frame: &mut Frame, area: Rect, viewer: &HexViewer) {
+        let page_lines = area.height.saturating_sub(2) as usize;
+        let top_line = viewer.top_line.min(viewer.line_count().saturating_sub(page_lines.max(1)));
+
+        let start = (top_line * 16).min(viewer.data.len());
+        let end = (start + page_lines * 16).min(viewer.data.len());
+        let lines: Vec<Line> = hex_dump(&viewer.data[start..end], start)
+            .into_iter()
+            .map(|line| Line::from(line.set_style(self.theme.value)))
+            .collect();
+
+        let footer = match &viewer.goto {
+            Some(input) => format!(" Go to offset (0x… or decimal): {}_ ", input),
+            None => " ↑/↓ PgUp/PgDn Home/End | g: Go to offset | Esc: Close ".to_string(),
+        };
+        let block = Block::bordered()
+            .title(format!(
+                " Account Data: {} bytes, offset {:#x} ",
+                viewer.data.len(),
+                start
+            ))
+            .title_bottom(Line::from(footer).centered())
+            .border_style(self.theme.border);
+        let viewer = if viewer.data.is_empty() {
+            Paragraph::new("No data".set_style(self.theme.title)).block(block)
+        } else {
+            Paragraph::new(lines).block(block)
+        };
+        frame.render_widget(Clear, area);
+        frame.render_widget(viewer, area);
+    }
+
+    // Fetch the SPL token accounts of the queried address, or the largest holders of
+    // a mint; skipped until asked for since wallets can hold hundreds of them
+    pub fn load_token_holdings(&mut self) {
+        if self.pane.json_response.as_ref().is_some_and(|response| response.get("mint").is_some()) {
+            self.show_largest_holders();
+            return;
+        }
+        if !matches!(self.pane.token_holdings, TabData::NotLoaded)
+            || self.pane.fetch_task.is_some()
+            || self.pane.json_response.as_ref().is_none_or(|response| response.get("lamports").is_none())
+        {
+            return;
+        }
+
+        let rpc = self.query_chain();
+        let query = self.pane.query.clone();
+        self.pane.token_holdings = TabData::Loading;
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        tokio::spawn(async move {
+            let Some(holdings) = shutdown.or_cancel(App::fetch_token_holdings(rpc.as_ref(), &query)).await else {
+                return;
+            };
+            let _ = updates.send(AppUpdate::TokenHoldings { pane_id, query, holdings });
+        });
+    }
+
+    // Show the largest holders of the queried mint ranked, loading them the first time
+    fn show_largest_holders(&mut self) {
+        if self.pane.json_response.as_ref().is_none_or(|response| response.get("mint").is_none()) {
+            self.notify(StatusLevel::Error, "Not a token mint");
+            return;
+        }
+        self.tab = Tab::Search;
+        self.pane.holders_view = true;
+        self.pane.selected_history = None;
+        self.pane.scroll_offset = 0;
+        self.load_largest_holders();
+    }
+
+    fn close_holders_view(&mut self) {
+        self.pane.holders_view = false;
+        self.pane.selected_history = None;
+        self.pane.scroll_offset = 0;
+    }
+
+    fn load_largest_holders(&mut self) {
+        if !matches!(self.pane.largest_holders, TabData::NotLoaded) || self.pane.fetch_task.is_some() {
+            return;
+        }
+
+        let rpc = self.query_chain();
+        let query = self.pane.query.clone();
+        self.pane.largest_holders = TabData::Loading;
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        tokio::spawn(async move {
+            let Some(holders) = shutdown.or_cancel(App::fetch_largest_holders(rpc.as_ref(), &query)).await else {
+                return;
+            };
+            let _ = updates.send(AppUpdate::LargestHolders { pane_id, query, holders });
+        });
+    }
+
+    pub fn load_inflation_rewards(&mut self) {
+        if !self.pane.json_response.as_ref().is_some_and(earns_inflation_rewards) {
+            self.notify(StatusLevel::Error, "Not a stake or vote account");
+            return;
+        }
+        if !matches!(self.pane.inflation_rewards, TabData::NotLoaded) || self.pane.fetch_task.is_some() {
+            return;
+        }
+
+        let rpc = self.query_chain();
+        let query = self.pane.query.clone();
+        self.pane.inflation_rewards = TabData::Loading;
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        tokio::spawn(async move {
+            let Some(rewards) = shutdown.or_cancel(App::fetch_inflation_rewards(rpc.as_ref(), &query)).await else {
+                return;
+            };
+            let _ = updates.send(AppUpdate::InflationRewards { pane_id, query, rewards });
+        });
+    }
+
+    // Networks worth asking for a query the current one doesn't have: the presets
+    // and the custom RPC in use
+    fn other_networks(&self) -> Vec<RpcNetwork> {
+        let mut networks = RpcNetwork::ALL.to_vec();
+        if matches!(self.current_rpc_network, RpcNetwork::Custom(_)) {
+            networks.push(self.current_rpc_network.clone());
+        }
+        networks.retain(|network| network.get_url() != self.get_current_rpc_url());
+        networks
+    }
+
+    // Ask every other network at once whether it has the query that wasn't found
+    fn search_all_networks(&mut self) {
+        if !matches!(self.pane.network_search, TabData::NotLoaded) || self.pane.fetch_task.is_some() {
+            return;
+        }
+        let endpoints: Vec<(RpcNetwork, RpcApi)> = self
+            .other_networks()
+            .into_iter()
+            .map(|network| {
+                // Other networks' latencies don't belong in the status bar
+                let rpc = RpcApi::shared(self.client.clone(), network.get_url(), Arc::default())
+                    .with_rate_limiter(Arc::new(RateLimiter::new(self.rate_limiter.rate())))
+                    .with_transport(self.transport.clone());
+                (network, rpc.with_commitment(self.commitment))
+            })
+            .collect();
+        let query = self.pane.query.clone();
+        self.pane.network_search = TabData::Loading;
+        self.notify(StatusLevel::Info, format!("Searching {} other networks", endpoints.len()));
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        tokio::spawn(async move {
+            let search = futures_util::future::join_all(endpoints.iter().map(|(network, rpc)| async {
+                NetworkPresence {
+                    network: network.clone(),
+                    found: App::locate(rpc, &query).await.map_err(|err| err.to_string()),
+                }
+            }));
+            if let Some(presence) = shutdown.or_cancel(search).await {
+                let _ = updates.send(AppUpdate::NetworkSearch { pane_id, query, presence });
+            }
+        });
+    }
+
+    // Whether the account or transaction `query` names exists on the network of `api`
+    async fn locate(api: &dyn ChainApi, query: &str) -> RpcResult<bool> {
+        let found = match QueryKind::parse(query)? {
+            QueryKind::Address(address) => api.get_account(&address).await.map(|_| ()),
+            // The status is cheaper, but not every node keeps it for old transactions
+            QueryKind::Signature(signature) => match api.get_signature_status(&signature).await {
+                Ok(Some(_)) => Ok(()),
+                _ => api.get_transaction_details(&signature).await.map(|_| ()),
+            },
+            QueryKind::Slot(_) => return Err(AppError::InvalidQuery(query.to_string())),
+        };
+        match found {
+            Ok(()) => Ok(true),
+            Err(AppError::NotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Look up who was scheduled to produce the block on screen and keep it in the
+    // response as block.leader, also for a skipped slot
+    fn look_up_slot_leader(&mut self) {
+        let block = self.pane.json_response.as_ref().and_then(|response| response.get("block"));
+        let Some(slot) = block.and_then(|block| block.get("slot")).and_then(Value::as_u64) else {
+            self.notify(StatusLevel::Error, "Not a block");
+            return;
+        };
+        if block.is_some_and(|block| block.get("leader").is_some()) || self.pane.leader_lookup {
+            return;
+        }
+
+        let rpc = self.query_chain();
+        let query = self.pane.query.clone();
+        self.pane.leader_lookup = true;
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        tokio::spawn(async move {
+            let Some(leader) = shutdown.or_cancel(App::fetch_slot_leader(rpc.as_ref(), slot)).await else {
+                return;
+            };
+            let _ = updates.send(AppUpdate::SlotLeader { pane_id, query, leader });
+        });
+    }
+
+    // Watch the account on screen for new transactions, or stop watching it
+    pub fn toggle_watch(&mut self) {
+        if let Ok(signature) = Signature::from_str(&self.pane.query) {
+            self.toggle_signature_watch(signature);
+            return;
+        }
+        if self.pane.stop_watch() {
+            self.notify(StatusLevel::Info, "Stopped watching");
+            return;
+        }
+        let Ok(address) = Pubkey::from_str(&self.pane.query) else {
+            return;
+        };
+        if self.pane.fetch_task.is_some() || self.pane.address_sign.is_none() {
+            return;
+        }
+
+        let rpc = self.query_chain();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let pane_id = self.pane.id;
+        // Polls only ask for what is newer than the top of the history
+        let newest = self
+            .pane
+            .address_sign
+            .as_ref()
+            .and_then(|entries| entries.first())
+            .and_then(|entry| Signature::from_str(&entry.signature).ok());
+        let handle = tokio::spawn(async move {
+            shutdown.or_cancel(App::watch_address(updates, pane_id, rpc, address, newest)).await;
+        });
+        self.pane.watch = Some(AddressWatch { handle, live: false });
+        self.notify(StatusLevel::Info, "Watching for new transactions, 'w' stops");
+    }
+
+    // Report new transactions of `address` for a pane's history as they confirm: from
+    // logsSubscribe while the WebSocket is up, by polling the signatures newer than
+    // `newest` otherwise. Runs until the pane stops the watch.
+    async fn watch_address(
+        updates: mpsc::UnboundedSender<AppUpdate>,
+        pane_id: usize,
+        rpc: Arc<dyn ChainApi>,
+        address: Pubkey,
+        mut newest: Option<Signature>,
+    ) {
+        let (sender, mut events) = mpsc::unbounded_channel();
+        // Without a ws endpoint the feed never reports and polling does all the work
+        let mut feed: std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> = match live::ws_url(rpc.url()) {
+            Some(url) => Box::pin(live::subscribe_logs(url, address.to_string(), sender)),
+            None => Box::pin(std::future::pending()),
+        };
+        let mut poll = tokio::time::interval(WATCH_POLL_INTERVAL);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut live = false;
+
+        loop {
+            let found = tokio::select! {
+                _ = &mut feed => return,
+                Some(event) = events.recv() => match event {
+                    WatchEvent::Connected => {
+                        live = true;
+                        let _ = updates.send(AppUpdate::WatchMode { pane_id, live, lost: None });
+                        continue;
+                    }
+                    WatchEvent::Disconnected(reason) => {
+                        let lost = live.then_some(reason);
+                        live = false;
+                        let _ = updates.send(AppUpdate::WatchMode { pane_id, live, lost });
+                        continue;
+                    }
+                    WatchEvent::Transaction { signature, slot, err } => vec![HistoryEntry {
+                        signature,
+                        slot,
+                        block_time: Some(chrono::Utc::now().timestamp()),
+                        confirmation_status: Some("confirmed".to_string()),
+                        err,
+                        memo: None,
+                    }],
+                },
+                _ = poll.tick(), if !live => {
+                    match App::fetch_history(rpc.as_ref(), &address, None, newest).await {
+                        Ok(entries) => entries,
+                        Err(err) => {
+                            let _ = updates.send(AppUpdate::Notify(
+                                StatusLevel::Warning,
+                                format!("Watch could not poll: {}", err),
+                            ));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if let Some(signature) = found.first().and_then(|entry| Signature::from_str(&entry.signature).ok()) {
+                newest = Some(signature);
+            }
+            let entries = found;
+            if updates.send(AppUpdate::WatchedTransactions { pane_id, address, entries }).is_err() {
+                return;
+            }
+        }
+    }
+
+    // Follow a transaction until it confirms, or stop following it. The watch keeps
+    // polling the network it was started on when the network is switched.
+    fn toggle_signature_watch(&mut self, signature: Signature) {
+        let key = signature.to_string();
+        if let Some(index) = self.signature_watches.iter().position(|watch| watch.signature == key) {
+            let watch = self.signature_watches.remove(index);
+            watch.handle.abort();
+            if self.finished_watch.as_ref() == Some(&key) {
+                self.finished_watch = None;
+            }
+            self.notify(StatusLevel::Info, "Stopped watching the transaction");
+            return;
+        }
+
+        let rpc = self.query_chain();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        let handle = tokio::spawn(async move {
+            shutdown.or_cancel(App::watch_signature(updates, rpc, signature)).await;
+        });
+        self.signature_watches.push(SignatureWatch {
+            signature: key,
+            network: self.current_rpc_network.clone(),
+            progress: SignatureProgress::NotFound,
+            slot: None,
+            handle,
+        });
+        self.notify(
+            StatusLevel::Info,
+            format!("Watching the transaction on {}, 'w' stops", self.current_rpc_network.name()),
+        );
+    }
+
+    // Poll the status of a transaction until its outcome is known. Failed polls are
+    // retried on the next tick.
+    async fn watch_signature(updates: mpsc::UnboundedSender<AppUpdate>, rpc: Arc<dyn ChainApi>, signature: Signature) {
+        let mut poll = tokio::time::interval(SIGNATURE_POLL_INTERVAL);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            poll.tick().await;
+            let Ok(status) = rpc.get_signature_status(&signature).await else {
+                continue;
+            };
+            let progress = SignatureProgress::from_status(status.as_ref());
+            let done = progress.is_done();
+            let slot = status.map(|status| status.slot);
+            let update = AppUpdate::SignatureStatus {
+                signature: signature.to_string(),
+                progress,
+                slot,
+            };
+            if updates.send(update).is_err() || done {
+                return;
+            }
+        }
+    }
+
+    // Record a watched transaction's progress, with the bell and banner once it is
+    // done. Dropped when the watch was stopped.
+    fn update_signature_watch(&mut self, signature: &str, progress: SignatureProgress, slot: Option<u64>) {
+        let Some(watch) = self.signature_watches.iter_mut().find(|watch| watch.signature == signature) else {
+            return;
+        };
+        if progress.is_done() && !watch.progress.is_done() {
+            self.finished_watch = Some(signature.to_string());
+            self.bell = true;
+        }
+        watch.progress = progress;
+        watch.slot = slot.or(watch.slot);
+    }
+
+    fn handle_watch_banner_key(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter => {
+                let Some((signature, network)) = self.close_watch_banner() else {
+                    return;
+                };
+                if network != self.current_rpc_network {
+                    self.set_rpc_network(network);
+                }
+                self.pane.query = signature;
+                self.submit_query();
+            }
+            KeyCode::Esc | KeyCode::Char(' ') | KeyCode::Char('q') => {
+                self.close_watch_banner();
+            }
+            _ => {}
+        }
+    }
+
+    // Dismiss the banner, dropping finished watches from the panel; returns the
+    // transaction it showed and the network it was watched on
+    fn close_watch_banner(&mut self) -> Option<(String, RpcNetwork)> {
+        let shown = self.finished_watch.take().and_then(|signature| {
+            let watch = self.signature_watches.iter().find(|watch| watch.signature == signature)?;
+            Some((signature, watch.network.clone()))
+        });
+        self.signature_watches.retain(|watch| !watch.progress.is_done());
+        shown
+    }
+
+    fn set_watch_mode(&mut self, pane_id: usize, live: bool, lost: Option<String>) {
+        if let Some(watch) = self.pane_mut(pane_id).and_then(|pane| pane.watch.as_mut()) {
+            watch.live = live;
+        }
+        if let Some(reason) = lost {
+            self.notify(
+                StatusLevel::Warning,
+                format!("Watch connection lost ({}), polling while it reconnects", reason),
+            );
+        }
+    }
+
+    // Add transactions found by a pane's watch, keeping the selection on the same
+    // transaction. Dropped when the pane no longer watches `address`.
+    fn add_watched_transactions(&mut self, pane_id: usize, address: &Pubkey, entries: Vec<HistoryEntry>) {
+        let focused = self.pane.id == pane_id;
+        let Some(pane) = (if focused { Some(&self.pane) } else { self.split.as_ref() }) else {
+            return;
+        };
+        if pane.watch.is_none() || pane.query != address.to_string() {
+            return;
+        }
+        let selected = pane
+            .selected_history
+            .and_then(|selected| self.visible_history(pane).get(selected).map(|entry| entry.signature.clone()));
+
+        let Some(pane) = self.pane_mut(pane_id) else {
+            return;
+        };
+        let added = pane.prepend_history(entries);
+        if added == 0 {
+            return;
+        }
+        if let Some(selected) = selected {
+            let pane = if focused { &self.pane } else { self.split.as_ref().unwrap_or(&self.pane) };
+            let index = self.visible_history(pane).iter().position(|entry| entry.signature == selected);
+            if let Some(pane) = self.pane_mut(pane_id) {
+                pane.selected_history = index;
+            }
+        }
+        self.notify(StatusLevel::Info, format!("{} new transaction{}", added, if added == 1 { "" } else { "s" }));
+    }
+
+    // True when the last loaded history row is selected
+    fn at_history_end(&self) -> bool {
+        let len = self.visible_history(&self.pane).len();
+        self.tab == Tab::Search && len > 0 && self.pane.selected_history == Some(len - 1)
+    }
+
+    // Abort the in-flight fetch and restore the view shown before it started
+    pub fn cancel_fetch(&mut self) -> bool {
+        match self.pane.fetch_task.take() {
+            Some(task) => {
+                task.handle.abort();
+                if task.pushed_view {
+                    self.pane.view_history.pop();
+                }
+                self.restore_view(task.previous);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Return to the previous view, if any
+    pub fn go_back(&mut self) -> bool {
+        if self.cancel_fetch() {
+            return true;
+        }
+        if self.pane.holders_view {
+            self.close_holders_view();
+            return true;
+        }
+        match self.pane.view_history.pop() {
+            Some(view) => {
+                self.restore_view(view);
+                true
+            }
+            None => false,
+        }
+    }
+
+
+    // Fetch the dashboard data, all requests at once. A failed request leaves its
+    // field empty and its error is returned with the data; the refresh only fails
+    // when the slot, supply and transaction count all do.
+    pub async fn fetch_initial_blockchain_data(rpc: &dyn ChainApi) -> RpcResult<(DashboardData, Vec<AppError>)> {
+        let (
+            slot,
+            supply,
+            transaction_count,
+            samples,
+            epoch_info,
+            inflation_rate,
+            inflation_governor,
+            fees,
+            first_available_block,
+            block_height,
+            snapshot,
+            max_retransmit_slot,
+        ) = tokio::join!(
+            rpc.get_slot(),
+            rpc.get_supply(),
+            rpc.get_transaction_count(),
+            rpc.call("getRecentPerformanceSamples", serde_json::json!([PERFORMANCE_SAMPLES])),
+            rpc.call("getEpochInfo", serde_json::json!([])),
+            rpc.call("getInflationRate", serde_json::json!([])),
+            rpc.call("getInflationGovernor", serde_json::json!([])),
+            App::fetch_fee_estimate(rpc),
+            rpc.get_first_available_block(),
+            rpc.call("getBlockHeight", serde_json::json!([])),
+            rpc.call("getHighestSnapshotSlot", serde_json::json!([])),
+            rpc.call("getMaxRetransmitSlot", serde_json::json!([])),
+        );
+
+        // The value of a request that succeeded; failures are collected
+        fn keep<T>(result: RpcResult<T>, errors: &mut Vec<AppError>) -> Option<T> {
+            result.map_err(|err| errors.push(err)).ok()
+        }
+        let mut errors = vec![];
+        let slot_info = keep(slot, &mut errors);
+        let supply_info = keep(supply, &mut errors);
+        let transaction_info = keep(transaction_count, &mut errors);
+        if errors.len() == 3 {
+            return Err(errors.swap_remove(0));
+        }
+
+        let data = DashboardData {
+            slot_info,
+            supply_info,
+            transaction_info,
+            // Recent throughput; endpoints without this method just hide the TPS row
+            performance_samples: samples
+                .ok()
+                .and_then(|samples| {
+                    samples.as_array().map(|samples| {
+                        samples
+                            .iter()
+                            .map(|sample| PerformanceSample {
+                                num_transactions: sample
+                                    .get("numTransactions")
+                                    .and_then(|n| n.as_u64())
+                                    .unwrap_or(0),
+                                num_slots: sample.get("numSlots").and_then(|n| n.as_u64()).unwrap_or(0),
+                                sample_period_secs: sample
+                                    .get("samplePeriodSecs")
+                                    .and_then(|p| p.as_u64())
+                                    .unwrap_or(0),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .filter(|samples| !samples.is_empty()),
+            // Epoch progress
+            epoch_info: epoch_info.ok().and_then(|epoch_info| {
+                Some(EpochInfo {
+                    epoch: epoch_info.get("epoch")?.as_u64()?,
+                    slot_index: epoch_info.get("slotIndex")?.as_u64()?,
+                    slots_in_epoch: epoch_info.get("slotsInEpoch")?.as_u64()?,
+                })
+            }),
+            // Endpoints without inflation, such as rollups, hide the inflation rows
+            inflation: inflation_rate.ok().and_then(|rate| {
+                Some(Inflation {
+                    epoch: rate.get("epoch")?.as_u64()?,
+                    total: rate.get("total")?.as_f64()?,
+                    validator: rate.get("validator")?.as_f64()?,
+                    foundation: rate.get("foundation")?.as_f64()?,
+                    schedule: inflation_governor.ok().and_then(|governor| {
+                        Some(InflationSchedule {
+                            initial: governor.get("initial")?.as_f64()?,
+                            terminal: governor.get("terminal")?.as_f64()?,
+                            taper: governor.get("taper")?.as_f64()?,
+                        })
+                    }),
+                })
+            }),
+            fees: fees.ok(),
+            // Endpoints that don't say how far back they go hide the row
+            first_available_block: first_available_block.ok(),
+            // Ledger rows are left out by nodes without the method, like the rows above
+            block_height: block_height.ok().and_then(|height| height.as_u64()),
+            snapshot: snapshot.ok().and_then(|snapshot| {
+                Some(SnapshotSlots {
+                    full: snapshot.get("full")?.as_u64()?,
+                    incremental: snapshot.get("incremental").and_then(Value::as_u64),
+                })
+            }),
+            max_retransmit_slot: max_retransmit_slot.ok().and_then(|slot| slot.as_u64()),
+        };
+
+        Ok((data, errors))
+    }
+
+    // The base fee, and the prioritization fees paid over recent slots when the node
+    // reports them
+    pub async fn fetch_fee_estimate(rpc: &dyn ChainApi) -> RpcResult<FeeEstimate> {
+        let (base_fee, priority) = tokio::join!(
+            rpc.get_base_fee(),
+            rpc.call("getRecentPrioritizationFees", serde_json::json!([])),
+        );
+        Ok(FeeEstimate {
+            lamports_per_signature: base_fee?,
+            priority: priority.ok().and_then(|response| fees::percentiles(&fees::prioritization_fees(&response))),
+        })
+    }
+
+    // Fetch the latest blocks with their hash, time and transaction count
+    pub async fn fetch_recent_blocks(
+        rpc: &dyn ChainApi,
+    ) -> RpcResult<Vec<BlockSummary>> {
+        let slot = rpc.get_slot().await?;
+
+        // Look back far enough to find RECENT_BLOCKS blocks even with skipped slots
+        let start_slot = slot.saturating_sub(RECENT_BLOCKS as u64 * 4);
+        let slots = rpc
+            .call("getBlocks", serde_json::json!([start_slot, slot]))
+            .await?;
+        let slots: Vec<u64> = slots
+            .as_array()
+            .map(|slots| slots.iter().filter_map(|s| s.as_u64()).collect())
+            .unwrap_or_default();
+
+        let requests = slots.into_iter().rev().take(RECENT_BLOCKS).map(|slot| async move {
+            let params = serde_json::json!([slot, {
+                "encoding": "json",
+                "transactionDetails": "signatures",
+                "rewards": false,
+                "maxSupportedTransactionVersion": 0,
+            }]);
+            rpc.call("getBlock", params)
+                .await
+                .map(|block| BlockSummary {
+                    slot,
+                    blockhash: block
+                        .get("blockhash")
+                        .and_then(|h| h.as_str())
+                        .unwrap_or("N/A")
+                        .to_string(),
+                    block_time: block.get("blockTime").and_then(|t| t.as_i64()),
+                    transaction_count: block
+                        .get("signatures")
+                        .and_then(|s| s.as_array())
+                        .map_or(0, |s| s.len()),
+                })
+        });
+
+        let mut blocks = futures_util::future::join_all(requests).await.into_iter().collect::<RpcResult<Vec<_>>>()?;
+        blocks.sort_by_key(|block| std::cmp::Reverse(block.slot));
+
+        Ok(blocks)
+    }
+
+    // Fetch current and delinquent vote accounts
+    pub async fn fetch_vote_accounts(
+        rpc: &dyn ChainApi,
+    ) -> RpcResult<Vec<VoteAccountSummary>> {
+        let vote_accounts = rpc.call("getVoteAccounts", serde_json::json!([])).await?;
+
+        let mut validators = vec![];
+        for (key, delinquent) in [("current", false), ("delinquent", true)] {
+            let accounts = vote_accounts.get(key).and_then(|a| a.as_array());
+            for account in accounts.into_iter().flatten() {
+                validators.push(VoteAccountSummary {
+                    vote_pubkey: account
+                        .get("votePubkey")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("N/A")
+                        .to_string(),
+                    node_pubkey: account
+                        .get("nodePubkey")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("N/A")
+                        .to_string(),
+                    activated_stake: account
+                        .get("activatedStake")
+                        .and_then(|s| s.as_u64())
+                        .unwrap_or(0),
+                    commission: account.get("commission").and_then(|c| c.as_u64()).unwrap_or(0),
+                    last_vote: account.get("lastVote").and_then(|l| l.as_u64()).unwrap_or(0),
+                    delinquent,
+                });
+            }
+        }
+        validators.sort_by_key(|validator| std::cmp::Reverse(validator.activated_stake));
+
+        Ok(validators)
+    }
+
+    // Leader slots and blocks produced by each identity so far in the current epoch
+    pub async fn fetch_block_production(rpc: &dyn ChainApi) -> RpcResult<BlockProduction> {
+        let response = rpc.call("getBlockProduction", serde_json::json!([])).await?;
+        let value = response.get("value").unwrap_or(&response);
+        let range = value
+            .get("range")
+            .ok_or_else(|| AppError::UnexpectedResponse(format!("getBlockProduction returned {}", response)))?;
+        let mut by_identity = HashMap::new();
+        for (identity, counts) in value.get("byIdentity").and_then(Value::as_object).into_iter().flatten() {
+            let count = |index: usize| counts.get(index).and_then(Value::as_u64).unwrap_or(0);
+            by_identity.insert(identity.clone(), (count(0), count(1)));
+        }
+        Ok(BlockProduction {
+            by_identity,
+            first_slot: range.get("firstSlot").and_then(Value::as_u64).unwrap_or(0),
+            last_slot: range.get("lastSlot").and_then(Value::as_u64).unwrap_or(0),
+        })
+    }
+
+    // SPL token accounts of an owner, largest balance first
+    pub async fn fetch_token_holdings(
+        rpc: &dyn ChainApi,
+        owner: &str,
+    ) -> RpcResult<Vec<TokenHolding>> {
+        Pubkey::from_str(owner).map_err(|_| AppError::PubkeyParse(owner.to_string()))?;
+        let accounts = rpc
+            .call(
+                "getTokenAccountsByOwner",
+                serde_json::json!([
+                    owner,
+                    { "programId": token::TOKEN_PROGRAM_ID },
+                    { "encoding": "jsonParsed" }
+                ]),
+            )
+            .await?;
+
+        let mut holdings: Vec<TokenHolding> = accounts
+            .get("value")
+            .and_then(|value| value.as_array())
+            .map(|accounts| accounts.iter().filter_map(TokenHolding::from_value).collect())
+            .unwrap_or_default();
+        holdings.sort_by(|a, b| b.ui_amount().total_cmp(&a.ui_amount()).then_with(|| a.mint.cmp(&b.mint)));
+
+        Ok(holdings)
+    }
+
+    // Rewards of an account for the last REWARD_EPOCHS completed epochs, newest first.
+    // Stops at the first epoch the node no longer has rewards for.
+    pub async fn fetch_inflation_rewards(rpc: &dyn ChainApi, address: &str) -> RpcResult<Vec<EpochReward>> {
+        Pubkey::from_str(address).map_err(|_| AppError::PubkeyParse(address.to_string()))?;
+        let epoch_info = rpc.call("getEpochInfo", serde_json::json!([])).await?;
+        let epoch = epoch_info
+            .get("epoch")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| AppError::UnexpectedResponse(format!("getEpochInfo returned {}", epoch_info)))?;
+
+        // An epoch's rewards are paid at the start of the next, so the current one has none yet
+        let epochs: Vec<u64> = (1..=REWARD_EPOCHS).filter_map(|back| epoch.checked_sub(back)).collect();
+        let results = futures_util::future::join_all(epochs.iter().map(|epoch| {
+            rpc.call("getInflationReward", serde_json::json!([[address], { "epoch": epoch }]))
+        }))
+        .await;
+
+        let mut rewards = vec![];
+        for (epoch, result) in epochs.into_iter().zip(results) {
+            match result {
+                Ok(value) => rewards.push(EpochReward {
+                    epoch,
+                    reward: value.get(0).and_then(InflationReward::from_value),
+                }),
+                Err(_) if !rewards.is_empty() => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(rewards)
+    }
+
+    // The leader scheduled for `slot` and where the slot falls in its epoch; the
+    // identity is null when the node has no schedule for that epoch
+    pub async fn fetch_slot_leader(rpc: &dyn ChainApi, slot: u64) -> RpcResult<Value> {
+        let (epoch, slot_index) = rpc.get_epoch_schedule().await?.get_epoch_and_slot_index(slot);
+        let leader = rpc.get_slot_leader(slot).await?;
+        Ok(serde_json::json!({
+            "identity": leader.map(|leader| leader.to_string()),
+            "epoch": epoch,
+            "slotIndex": slot_index,
+        }))
+    }
+
+    // The largest token accounts of a mint (the node returns up to 20), with the
+    // wallets that own them
+    pub async fn fetch_largest_holders(rpc: &dyn ChainApi, mint: &str) -> RpcResult<Vec<TokenHolder>> {
+        Pubkey::from_str(mint).map_err(|_| AppError::PubkeyParse(mint.to_string()))?;
+        let largest = rpc.call("getTokenLargestAccounts", serde_json::json!([mint])).await?;
+        let mut holders: Vec<TokenHolder> = largest
+            .get("value")
+            .and_then(Value::as_array)
+            .map(|accounts| accounts.iter().filter_map(TokenHolder::from_value).collect())
+            .unwrap_or_default();
+        if holders.is_empty() {
+            return Ok(holders);
+        }
+
+        let addresses = holders
+            .iter()
+            .map(|holder| {
+                Pubkey::from_str(&holder.account)
+                    .map_err(|_| AppError::UnexpectedResponse(format!("token account {}", holder.account)))
+            })
+            .collect::<RpcResult<Vec<_>>>()?;
+        // Token accounts name the wallet that owns them
+        let owners = rpc.get_multiple_accounts(&addresses).await?.into_iter().map(|account| {
+            account
+                .and_then(|account| token::unpack_account(&account.data))
+                .map(|token_account| token_account.owner.to_string())
+        });
+        for (holder, owner) in holders.iter_mut().zip(owners) {
+            holder.owner = owner;
+        }
+        Ok(holders)
+    }
+
+    // The owner and executable flag of each account, in order; null where there is
+    // no account. Fetched in batches however many keys a transaction has.
+    pub async fn fetch_key_accounts(api: &dyn ChainApi, keys: &[String]) -> RpcResult<Vec<Value>> {
+        let addresses = keys
+            .iter()
+            .map(|key| Pubkey::from_str(key).map_err(|_| AppError::PubkeyParse(key.clone())))
+            .collect::<RpcResult<Vec<_>>>()?;
+        let accounts = api.get_multiple_accounts(&addresses).await?;
+        Ok(accounts
+            .into_iter()
+            .map(|account| {
+                account.map_or(Value::Null, |account| {
+                    serde_json::json!({ "owner": account.owner.to_string(), "executable": account.executable })
+                })
+            })
+            .collect())
+    }
+
+    // Switch tabs, fetching the new tab's data the first time it is opened
+    pub fn set_tab(&mut self, tab: Tab) {
+        if self.tab != tab {
+            self.tab = tab;
+            self.pane.scroll_offset = 0;
+            self.pane.selected_history = None;
+        }
+        self.ensure_tab_loaded();
+    }
+
+    fn ensure_tab_loaded(&mut self) {
+        if self.tab == Tab::Validators {
+            self.ensure_block_production_loaded();
+        }
+        let rpc = self.chain();
+        let rpc_url = rpc.url().to_string();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+
+        match self.tab {
+            Tab::Blocks if matches!(self.blocks_screen().blocks, TabData::NotLoaded) => {
+                self.blocks_screen_mut().blocks = TabData::Loading;
+                tokio::spawn(async move {
+                    if let Some(blocks) = shutdown.or_cancel(App::fetch_recent_blocks(rpc.as_ref())).await {
+                        let _ = updates.send(AppUpdate::RecentBlocks { rpc_url, blocks });
+                    }
+                });
+            }
+            Tab::Validators if matches!(self.validators_screen().validators, TabData::NotLoaded) => {
+                self.validators_screen_mut().validators = TabData::Loading;
+                tokio::spawn(async move {
+                    if let Some(validators) = shutdown.or_cancel(App::fetch_vote_accounts(rpc.as_ref())).await {
+                        let _ = updates.send(AppUpdate::VoteAccounts { rpc_url, validators });
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // A heavier call than the vote accounts, so it is only made when the Validators
+    // tab is opened or refreshed
+    fn ensure_block_production_loaded(&mut self) {
+        if !matches!(self.validators_screen().block_production, TabData::NotLoaded) {
+            return;
+        }
+        let rpc = self.chain();
+        let rpc_url = rpc.url().to_string();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        self.validators_screen_mut().block_production = TabData::Loading;
+        tokio::spawn(async move {
+            if let Some(production) = shutdown.or_cancel(App::fetch_block_production(rpc.as_ref())).await {
+                let _ = updates.send(AppUpdate::BlockProduction { rpc_url, production });
+            }
+        });
+    }
+
+    // Ask the node whether it is keeping up with the cluster
+    pub async fn fetch_health(rpc: &dyn ChainApi) -> ClusterHealth {
+        let Ok(response) = rpc.request("getHealth", serde_json::json!([])).await else {
+            return ClusterHealth::Unreachable;
+        };
+
+        match response.get("error") {
+            None => ClusterHealth::Ok,
+            Some(error) => ClusterHealth::Behind(
+                error
+                    .get("data")
+                    .and_then(|data| data.get("numSlotsBehind"))
+                    .and_then(|behind| behind.as_u64()),
+            ),
+        }
+    }
+
+    // Refetch the dashboard data and cluster health in the background, unless the
+    // last refetch is still running
+    fn refresh_dashboard(&mut self) {
+        if self.dashboard_task.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+        let rpc = self.chain();
+        let needs_genesis = self.genesis_hash.is_none();
+        let updates = self.updates.clone();
+        let shutdown = self.shutdown.clone();
+        self.dashboard_task = Some(tokio::spawn(async move {
+            if let Some(update) = shutdown.or_cancel(App::fetch_dashboard(rpc.as_ref(), needs_genesis)).await {
+                let _ = updates.send(update);
+            }
+        }));
+    }
+
+    // Fetch the dashboard data and cluster health once
+    async fn fetch_dashboard(rpc: &dyn ChainApi, needs_genesis: bool) -> AppUpdate {
+        // The genesis hash can't change, it is asked for once per network
+        let genesis = async {
+            match needs_genesis {
+                true => rpc.get_genesis_hash().await.ok(),
+                false => None,
+            }
+        };
+        let (health, result, genesis_hash) =
+            tokio::join!(App::fetch_health(rpc), App::fetch_initial_blockchain_data(rpc), genesis);
+        AppUpdate::Dashboard {
+            rpc_url: rpc.url().to_string(),
+            health,
+            genesis_hash,
+            result: Box::new(result),
+        }
+    }
+
+    // Show a dashboard refetch; values whose request failed this time stay as they were
+    fn finish_dashboard_refresh(
+        &mut self,
+        rpc_url: String,
+        health: ClusterHealth,
+        genesis_hash: Option<String>,
+        result: RpcResult<(DashboardData, Vec<AppError>)>,
+    ) {
+        // Drop results for a network that was toggled away from meanwhile
+        if self.chain_url() != rpc_url {
+            return;
+        }
+        if health != ClusterHealth::Unreachable {
+            self.last_contact = Some(Instant::now());
+        }
+        self.health = health;
+        if genesis_hash.is_some() {
+            self.genesis_hash = genesis_hash;
+        }
+
+        match result {
+            Ok((data, errors)) => {
+                // getSlot lags the processed slots the subscription reports
+                let live = self.slot_feed == SlotFeed::Live;
+                self.dashboard_screen_mut().update(data, live);
+                self.last_contact = Some(Instant::now());
+                if let Some(err) = errors.first() {
+                    self.notify(StatusLevel::Warning, format!("Dashboard partly refreshed: {}", err));
+                }
+            }
+            // An unreachable endpoint is already shown in the title bar
+            Err(_) if health == ClusterHealth::Unreachable => {}
+            Err(e) => self.notify(StatusLevel::Warning, format!("Error fetching dashboard data: {}", e)),
+        }
+    }
+
+    // Run the app until it quits, then hand it back for what outlives it, such as
+    // the query history
+    pub async fn run(
+        mut app: App,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    ) -> (App, io::Result<()>) {
+        // Refetch the dashboard now and on every refresh interval
+        let updates = app.updates.clone();
+        let refresh_interval = app.refresh_interval;
+        let refresh_task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(refresh_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if updates.send(AppUpdate::RefreshDashboard).is_err() {
+                    break;
+                }
+            }
+        });
+        app.start_slot_feed();
+
+        let result = app.event_loop(terminal, TerminalEvents).await;
+
+        // Also reached when drawing fails, so tasks never outlive the event loop
+        app.quit();
+        refresh_task.abort();
+        for task in [app.slot_task.take(), app.dashboard_task.take()].into_iter().flatten() {
+            task.abort();
+        }
+        app.pane.stop_tasks();
+        if let Some(other) = app.split.as_mut() {
+            other.stop_tasks();
+        }
+        for watch in app.signature_watches.drain(..) {
+            watch.handle.abort();
+        }
+        (app, result)
+    }
+
+    // The coordinator: owns the app and applies terminal events and what background
+    // tasks report as they come in, drawing whenever something changed. Input is read
+    // on a thread of its own, so waiting on it never holds up redraws of updates.
+    async fn event_loop<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        events: impl EventSource + Send + 'static,
+    ) -> io::Result<()> {
+        let Some(mut updates) = self.update_queue.take() else {
+            return Ok(());
+        };
+        let mut input = App::read_input(events);
+        let mut drawn_at: Option<Instant> = None;
+        while !self.exit {
+            if self.needs_redraw(drawn_at) {
+                self.render_frame(terminal)?;
+                self.dirty = false;
+                drawn_at = Some(Instant::now());
+            }
+            if std::mem::take(&mut self.bell) {
+                let mut stdout = io::stdout();
+                stdout.write_all(b"\x07")?;
+                stdout.flush()?;
+            }
+
+            // Updates go first, so what a task reported before a key press is applied before it
+            tokio::select! {
+                biased;
+                Some(update) = updates.recv() => self.apply(update),
+                event = input.recv() => match event {
+                    Some(event) => self.handle_event(event),
+                    None => break,
+                },
+                // Spinners, fading highlights and clocks move without any event
+                _ = tokio::time::sleep(EVENT_POLL_INTERVAL) => {}
+            }
+        }
+        self.update_queue = Some(updates);
+        Ok(())
+    }
+
+    // Read terminal events on a thread of their own until the receiver is gone. A
+    // failed read is retried on the next poll.
+    fn read_input(mut events: impl EventSource + Send + 'static) -> mpsc::UnboundedReceiver<Event> {
+        let (sender, input) = mpsc::unbounded_channel();
+        std::thread::spawn(move || {
+            while !sender.is_closed() {
+                if let Ok(Some(event)) = events.next_event(EVENT_POLL_INTERVAL) {
+                    let _ = sender.send(event);
+                }
+            }
+        });
+        input
+    }
+
+    // Whether the screen is out of date: something changed, something on it moves
+    // (spinners, fading highlights, status messages), or the idle interval is up
+    fn needs_redraw(&self, drawn_at: Option<Instant>) -> bool {
+        let Some(drawn_at) = drawn_at else {
+            return true;
+        };
+        self.dirty
+            || self.activity().is_some()
+            || self.status.as_ref().is_some_and(StatusMessage::is_visible)
+            || !self.pane.arrivals.is_empty()
+            || drawn_at.elapsed() >= IDLE_REDRAW_INTERVAL
+    }
+
+    // Leave the event loop and drop any request still in flight
+    pub fn quit(&mut self) {
+        self.exit = true;
+        self.shutdown.trigger();
+    }
+
+    // Draw a frame of the app as it is, then take in what the frame measured
+    fn render_frame<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        let mut layout = FrameLayout::default();
+        terminal.draw(|frame| layout = self.draw(frame))?;
+        self.apply_layout(layout);
+        Ok(())
+    }
+
+    // Clicks go by where the last frame put things, and scrolling stops where its
+    // content ended
+    fn apply_layout(&mut self, layout: FrameLayout) {
+        // A terminal too small shows only a warning; the last sizes stand
+        if !layout.results_area.is_empty() {
+            let rows = layout.results_area.height.saturating_sub(2) as usize;
+            self.pane.results_height = rows;
+            self.pane.scroll_offset = self.pane.scroll_offset.min(self.max_scroll_offset());
+            if let Some(Popup::HexViewer(viewer)) = self.popup.as_mut() {
+                viewer.page_lines = rows;
+                viewer.scroll_to(viewer.top_line);
+            }
+        }
+        if let (Some(Popup::Help { scroll } | Popup::InstructionDetail { scroll, .. }), Some(max)) =
+            (self.popup.as_mut(), layout.max_popup_scroll)
+        {
+            *scroll = (*scroll).min(max);
+        }
+        if let Some(max) = layout.max_logs_scroll {
+            let search = self.search_screen_mut();
+            search.logs_scroll = search.logs_scroll.min(max);
+        }
+        self.layout = layout;
+    }
+
+    fn draw(&self, frame: &mut Frame) -> FrameLayout {
+        let area = frame.area();
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            // Nothing is clickable while the warning is shown
+            let warning = Paragraph::new(vec![
+                Line::from("Terminal too small"),
+                Line::from(format!("need {}x{}, have {}x{}", MIN_WIDTH, MIN_HEIGHT, area.width, area.height)),
+            ])
+            .style(self.theme.error)
+            .alignment(Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true });
+            frame.render_widget(warning, area);
+            return FrameLayout::default();
+        }
+
+        let error_height = if self.error.is_some() { 3 } else { 0 };
+        let bottom_line = self.search.is_some() || matches!(self.input.prompt, Some(Prompt::Command(_)));
+        let search_height = if bottom_line { 1 } else { 0 };
+        let chunks = Layout::vertical([
+            Constraint::Length(3),             // Input field
+            Constraint::Length(1),             // Tab bar
+            Constraint::Min(1),                // Results area
+            Constraint::Length(search_height), // Search or command line
+            Constraint::Length(error_height),  // Error banner
+            Constraint::Length(1),             // Status bar
+        ])
+        .split(frame.area());
+
+        // A split puts the left pane first, whichever of the two has focus
+        let (pane_area, other_area) = match self.split {
+            Some(_) if self.tab == Tab::Search => {
+                let [left, right] =
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(chunks[2]);
+                if self.pane.id == 0 {
+                    (left, right)
+                } else {
+                    (right, left)
+                }
+            }
+            _ => (chunks[2], Rect::default()),
+        };
+
+        // The log pane takes the lower half of the results area while it is open
+        let (results_area, logs_area) = if self.logs_pane().is_some() {
+            let [results_area, logs_area] =
+                Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(pane_area);
+            (results_area, logs_area)
+        } else {
+            (pane_area, Rect::default())
+        };
+
+        // How many rows fit, so scrolling can clamp to the content
+        let mut layout = FrameLayout {
+            input_area: chunks[0],
+            results_area,
+            other_pane_area: other_area,
+            logs_area,
+            ..FrameLayout::default()
+        };
+
+        // Toggle with the N button
+        let mut input_title = Line::from(vec![
+            " SOONSCAN ".into(),
+            format!(" 🌐 {} ", self.current_rpc_network.name()).set_style(self.network_style()),
+        ]);
+
+        // Health dot next to the network name
+        match self.health {
+            ClusterHealth::Unknown => {}
+            ClusterHealth::Ok => input_title.push_span("● ".set_style(self.theme.success)),
+            ClusterHealth::Behind(slots) => input_title.push_span(
+                slots
+                    .map_or("● behind ".to_string(), |slots| format!("● behind {} slots ", slots))
+                    .set_style(self.theme.value),
+            ),
+            ClusterHealth::Unreachable => input_title.push_span(
+                self.last_contact
+                    .map_or("● unreachable ".to_string(), |last_contact| {
+                        format!("● offline for {}s ", last_contact.elapsed().as_secs())
+                    })
+                    .set_style(self.theme.error),
+            ),
+        }
+
+        // Scroll the input horizontally so the cursor stays visible, one whole
+        // character at a time
+        let (visible_query, cursor_column) =
+            input_window(&self.pane.query, self.input.cursor, chunks[0].width.saturating_sub(3) as usize);
+
+        let input = Paragraph::new(visible_query)
+            .style(match self.input.mode {
+                InputMode::Normal => Style::default(),
+                InputMode::Editing => self.theme.value,
+            })
+            .block(Block::bordered().title(input_title));
+
+        frame.render_widget(input, chunks[0]);
+
+        if matches!(self.input.mode, InputMode::Editing) {
+            frame.set_cursor_position(Position::new(chunks[0].x + 1 + cursor_column, chunks[0].y + 1));
+        }
+
+        // Render the tab bar under the input
+        let tabs = Tabs::new(
+            Tab::ALL
+                .iter()
+                .enumerate()
+                .map(|(i, tab)| format!("{} {}", i + 1, tab.title())),
+        )
+        .select(self.tab.index())
+        .highlight_style(self.theme.value.bold())
+        .divider("|");
+        frame.render_widget(tabs, chunks[1]);
+
+        // Render results area
+        frame.render_widget(
+            PaneView {
+                app: self,
+                pane: &self.pane,
+                focused: true,
+            },
+            results_area,
+        );
+        if let Some(other) = self.split.as_ref().filter(|_| !other_area.is_empty()) {
+            frame.render_widget(
+                PaneView {
+                    app: self,
+                    pane: other,
+                    focused: false,
+                },
+                other_area,
+            );
+        }
+        layout.max_logs_scroll = self.draw_logs(frame, logs_area);
+        if matches!(self.input.prompt, Some(Prompt::Command(_))) {
+            self.draw_command_line(frame, chunks[3]);
+        } else {
+            self.draw_search(frame, chunks[3]);
+        }
+
+        // Render the error banner below the results
+        if let Some(error) = &self.error {
+            let banner = Paragraph::new(error.message.as_str().set_style(self.theme.error))
+                .block(
+                    Block::bordered()
+                        .title(format!(" Error ({}s ago) ", error.at.elapsed().as_secs()))
+                        .border_style(self.theme.error),
+                )
+                .wrap(ratatui::widgets::Wrap { trim: true });
+            frame.render_widget(banner, chunks[4]);
+        }
+        self.draw_status_bar(frame, chunks[5]);
+        self.draw_watch_panel(frame, chunks[2]);
+        match &self.popup {
+            Some(Popup::Help { scroll }) => layout.max_popup_scroll = Some(self.draw_help(frame, *scroll)),
+            Some(Popup::DebugLog) => self.draw_debug_log(frame),
+            Some(Popup::Bookmarks(popup)) => self.draw_bookmark_popup(frame, popup),
+            Some(Popup::HistoryDetail(entry)) => self.draw_history_detail(frame, entry),
+            Some(Popup::InstructionDetail { index, scroll }) => {
+                layout.max_popup_scroll = self.draw_instruction_detail(frame, *index, *scroll)
+            }
+            Some(Popup::HexViewer(viewer)) => self.draw_hex_viewer(frame, results_area, viewer),
+            None => {}
+        }
+        self.draw_date_prompt(frame);
+        self.draw_rpc_prompt(frame);
+        self.draw_watch_banner(frame);
+        layout
+    }
+
+    // The help popup, scrolled within what fits; returns how far it can scroll
+    fn draw_help(&self, frame: &mut Frame, scroll: u16) -> u16 {
+        let popup_area = centered_rect(70, 60, frame.area());
+        let mut popup_block = Block::bordered()
+            .title("SoonScan - Help & Guide")
+            .title_bottom(Line::from(" Esc / ? close ").left_aligned())
+            .border_style(self.theme.border);
+
+        let help_text = self.help_lines();
+
+        let popup_text = Paragraph::new(help_text).wrap(ratatui::widgets::Wrap { trim: true });
+
+        // Scroll with Up/Down when the wrapped help doesn't fit
+        let wrapped_lines = popup_text.line_count(popup_area.width.saturating_sub(2)) as u16;
+        let max_scroll = wrapped_lines.saturating_sub(popup_area.height.saturating_sub(2));
+        if max_scroll > 0 {
+            popup_block = popup_block.title_bottom(Line::from(" ↑/↓ scroll ").right_aligned());
+        }
+        let popup_text = popup_text.block(popup_block).scroll((scroll.min(max_scroll), 0));
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(popup_text, popup_area);
+        max_scroll
+    }
+
+    // The latest log lines, newest at the bottom
+    fn draw_debug_log(&self, frame: &mut Frame) {
+        let popup_area = centered_rect(90, 70, frame.area());
+        let mut block = Block::bordered()
+            .title("Debug Log")
+            .title_bottom(Line::from(" Esc / D close ").left_aligned())
+            .border_style(self.theme.border);
+        if let Some(path) = &self.log_file {
+            block = block.title_bottom(Line::from(format!(" also in {} ", path.display())).right_aligned());
+        }
+        let visible = popup_area.height.saturating_sub(2) as usize;
+        let lines = self.log.lines();
+        let text: Vec<Line> = if lines.is_empty() {
+            vec![Line::from("Nothing logged yet".set_style(self.theme.title))]
+        } else {
+            lines[lines.len().saturating_sub(visible)..]
+                .iter()
+                .map(|line| {
+                    let style = match line {
+                        _ if line.contains(" ERROR ") => self.theme.error,
+                        _ if line.contains(" WARN ") => self.theme.value,
+                        _ => self.theme.title,
+                    };
+                    Line::from(line.clone().set_style(style))
+                })
+                .collect()
+        };
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(Paragraph::new(text).block(block), popup_area);
+    }
+
+    // Watched transactions and their progress, in the corner of the results
+    fn draw_watch_panel(&self, frame: &mut Frame, area: Rect) {
+        if self.signature_watches.is_empty() {
+            return;
+        }
+        let lines: Vec<Line> = self
+            .signature_watches
+            .iter()
+            .map(|watch| {
+                let style = match watch.progress {
+                    SignatureProgress::NotFound | SignatureProgress::Processed => self.theme.title,
+                    SignatureProgress::Confirmed | SignatureProgress::Finalized => self.theme.success,
+                    SignatureProgress::Failed(_) => self.theme.error,
+                };
+                Line::from(vec![
+                    format!(" {} ", truncate(&watch.signature, 9)).set_style(self.theme.value),
+                    format!("{:<14} ", watch.network.name()).into(),
+                    watch.progress.label().set_style(style),
+                ])
+            })
+            .collect();
+        let width = 44.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let panel_area = Rect::new(area.right() - width, area.bottom() - height, width, height);
+        let panel = Paragraph::new(lines).block(
+            Block::bordered()
+                .title(" Watching ")
+                .border_style(self.theme.border),
+        );
+        frame.render_widget(Clear, panel_area);
+        frame.render_widget(panel, panel_area);
+    }
+
+    // Outcome of a watched transaction, across the top until dismissed
+    fn draw_watch_banner(&self, frame: &mut Frame) {
+        let Some(watch) = self
+            .finished_watch
+            .as_ref()
+            .and_then(|signature| self.signature_watches.iter().find(|watch| &watch.signature == signature))
+        else {
+            return;
+        };
+        let (title, style) = match &watch.progress {
+            SignatureProgress::Failed(_) => (" ✘ Transaction failed ", self.theme.error),
+            _ => (" ✔ Transaction confirmed ", self.theme.success),
+        };
+        let mut lines = vec![
+            Line::from(format!(" {}", watch.signature).set_style(self.theme.value)),
+            Line::from(format!(
+                " {} on {}{}",
+                watch.progress.label(),
+                watch.network.name(),
+                watch.slot.map_or(String::new(), |slot| format!(" in slot {}", slot))
+            )),
+        ];
+        if let SignatureProgress::Failed(err) = &watch.progress {
+            lines.push(Line::from(format!(" {}", err).set_style(self.theme.error)));
+        }
+
+        let area = frame.area();
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let banner_area = Rect::new(area.x, area.y, area.width, height);
+        let banner = Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Thick)
+                    .title(title.set_style(style.bold()))
+                    .title_bottom(Line::from(" Enter: Open | Esc: Close ").centered())
+                    .border_style(style),
+            )
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        frame.render_widget(Clear, banner_area);
+        frame.render_widget(banner, banner_area);
+    }
+
+    // Apply one terminal event
+    pub fn handle_event(&mut self, event: Event) {
+        self.dirty = true;
+        match event {
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => self.handle_key(key_event),
+            Event::Mouse(mouse_event) => self.handle_mouse(mouse_event),
+            Event::Paste(text) => self.handle_paste(&text),
+            _ => {}
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        // Any key press dismisses the error banner
+        self.error = None;
+        if matches!(self.input.mode, InputMode::Editing) {
+            if !self.edit_query(key_event) {
+                match key_event.code {
+                    KeyCode::Esc => self.input.mode = InputMode::Normal,
+                    KeyCode::Enter => self.submit_query(),
+                    _ => {}
+                }
+            }
+            return;
+        }
+        match &self.input.prompt {
+            Some(Prompt::ConfirmExport(_)) => return self.handle_confirm_export_key(key_event),
+            Some(Prompt::Date(_)) => return self.handle_date_prompt_key(key_event),
+            Some(Prompt::Rpc(_)) => return self.handle_rpc_prompt_key(key_event),
+            Some(Prompt::Command(_)) => return self.handle_command_line_key(key_event),
+            None => {}
+        }
+        if self.finished_watch.is_some() {
+            self.handle_watch_banner_key(key_event);
+            return;
+        }
+        match &self.popup {
+            Some(Popup::Help { .. }) => return self.handle_help_key(key_event),
+            Some(Popup::DebugLog) => return self.handle_debug_log_key(key_event),
+            Some(Popup::Bookmarks(_)) => return self.handle_bookmark_key(key_event),
+            Some(Popup::HistoryDetail(_)) => return self.handle_history_detail_key(key_event),
+            Some(Popup::InstructionDetail { .. }) => return self.handle_instruction_detail_key(key_event),
+            Some(Popup::HexViewer(_)) => return self.handle_hex_viewer_key(key_event),
+            None => {}
+        }
+        // An active search takes n/N/Esc; while typing it takes every key
+        if self.handle_search_key(key_event) {
+            return;
+        }
+
+        let action = self.keymap.actions(&key_event).iter().find(|action| self.applies(action)).cloned();
+        if let Some(action) = action {
+            self.perform(action);
+        }
+    }
+
+    // Whether an action does anything right now, so a key bound to more than one
+    // can skip it: 'a' asks the other networks only for a query that wasn't found
+    fn applies(&self, action: &AppAction) -> bool {
+        match action {
+            AppAction::SearchAllNetworks => self.tab == Tab::Search && self.pane.shows_not_found(),
+            _ => true,
+        }
+    }
+
+    // The help popup takes every key while open: it scrolls, and Esc or its own key closes it
+    fn handle_help_key(&mut self, key_event: KeyEvent) {
+        let page = self.pane.results_height.max(1) as u16;
+        let Some(Popup::Help { scroll }) = self.popup.as_mut() else {
+            return;
+        };
+        // The next frame clamps the scroll to the content
+        match (key_event.code, self.keymap.action(&key_event)) {
+            (KeyCode::Esc, _) | (_, Some(AppAction::ToggleHelp)) => self.popup = None,
+            (_, Some(AppAction::Up)) => *scroll = scroll.saturating_sub(1),
+            (_, Some(AppAction::Down)) => *scroll = scroll.saturating_add(1),
+            (_, Some(AppAction::PageUp | AppAction::HalfPageUp)) => *scroll = scroll.saturating_sub(page),
+            (_, Some(AppAction::PageDown | AppAction::HalfPageDown)) => *scroll = scroll.saturating_add(page),
+            (_, Some(AppAction::Top)) => *scroll = 0,
+            (_, Some(AppAction::Bottom)) => *scroll = u16::MAX,
+            _ => {}
+        }
+    }
+
+    // The debug log takes every key while open; Esc or its own key closes it
+    fn handle_debug_log_key(&mut self, key_event: KeyEvent) {
+        if key_event.code == KeyCode::Esc || self.keymap.action(&key_event) == Some(AppAction::ToggleDebugLog) {
+            self.popup = None;
+        }
+    }
+
+    // 'y' overwrites the file an export waits on; any other key keeps it
+    fn handle_confirm_export_key(&mut self, key_event: KeyEvent) {
+        let Some(Prompt::ConfirmExport(path)) = self.input.prompt.take() else {
+            return;
+        };
+        if key_event.code == KeyCode::Char('y') {
+            self.write_export(&path);
+        } else {
+            self.notify(StatusLevel::Info, "Export cancelled");
+        }
+    }
+
+    // Carry out a normal-mode action: the screen on show gets the first go, then
+    // the actions every tab shares
+    fn perform(&mut self, action: AppAction) {
+        let taken = match self.screen() {
+            Screen::Dashboard(_) => self.perform_on_dashboard(&action),
+            Screen::Search(_) => self.perform_on_search(&action),
+            Screen::Blocks(_) => self.perform_on_blocks(&action),
+            Screen::Validators(_) => self.perform_on_validators(&action),
+        };
+        if taken {
+            return;
+        }
+        match action {
+            AppAction::Quit => self.quit(),
+            AppAction::EditQuery => self.start_editing(),
+            AppAction::NextNetwork => self.toggle_rpc_network(),
+            // Prompt for a custom RPC URL
+            AppAction::CustomRpc => {
+                let input = match &self.current_rpc_network {
+                    RpcNetwork::Custom(url) => url.clone(),
+                    _ => String::new(),
+                };
+                self.input.prompt = Some(Prompt::Rpc(RpcPrompt { input, error: None }));
+            }
+            AppAction::NextTab => self.set_tab(self.tab.next()),
+            AppAction::PreviousTab => self.set_tab(self.tab.previous()),
+            AppAction::SelectTab(index) => {
+                if let Some(tab) = Tab::ALL.get(index) {
+                    self.set_tab(*tab);
+                }
+            }
+            AppAction::Back => {
+                self.go_back();
+            }
+            AppAction::Open => self.open_selected_row(),
+            AppAction::ToggleHelp => self.popup = Some(Popup::Help { scroll: 0 }),
+            AppAction::ToggleDebugLog => self.popup = Some(Popup::DebugLog),
+            // Cycle through the built-in themes
+            AppAction::NextTheme => {
+                self.theme = self.theme.next();
+                self.notify(StatusLevel::Info, format!("Theme: {}", self.theme.name));
+            }
+            // Switch amounts between SOL and lamports
+            AppAction::ToggleUnit => self.currency_unit = self.currency_unit.toggle(),
+            // Switch the history time column between dates and "time ago"
+            AppAction::ToggleRelativeTimes => self.relative_times = !self.relative_times,
+            AppAction::Copy => self.copy_to_clipboard(),
+            // Fetch the next page of account history
+            AppAction::LoadMoreHistory => self.load_more_history(),
+            // Details of the selected history row
+            AppAction::HistoryDetail => self.popup = self.selected_history_entry().map(Popup::HistoryDetail),
+            // Open the raw data of the queried account
+            AppAction::HexViewer => self.open_hex_viewer(),
+            // Load the token accounts of the queried address
+            AppAction::TokenHoldings => self.load_token_holdings(),
+            // Rank the holders of the queried mint, or go back to its details
+            AppAction::LargestHolders if self.pane.holders_view => self.close_holders_view(),
+            AppAction::LargestHolders => self.show_largest_holders(),
+            // Find the validator scheduled for the slot of the block on screen
+            AppAction::SlotLeader => self.look_up_slot_leader(),
+            // Rewards of the stake or vote account on screen over the last epochs
+            AppAction::InflationRewards => self.load_inflation_rewards(),
+            AppAction::JumpToFailedInstruction => self.jump_to_failed_instruction(),
+            // Search the current result view
+            AppAction::Search => {
+                self.search = Some(ViewSearch {
+                    editing: true,
+                    ..ViewSearch::default()
+                });
+            }
+            // Bookmark the current query
+            AppAction::Bookmark => {
+                if !self.pane.query.is_empty() {
+                    self.popup = Some(Popup::Bookmarks(BookmarkPopup::Label(String::new())));
+                }
+            }
+            AppAction::OpenBookmarks => self.popup = Some(Popup::Bookmarks(BookmarkPopup::List(0))),
+            AppAction::ToggleSplit => self.toggle_split(),
+            AppAction::Watch => self.toggle_watch(),
+            AppAction::ToggleRawView => self.toggle_raw_view(),
+            AppAction::CommandLine => self.input.prompt = Some(Prompt::Command(String::new())),
+            // Prompt for the export file on the command line, with a name filled in
+            AppAction::SaveExport => {
+                self.input.prompt = Some(Prompt::Command(format!("export {}", self.default_export_name())))
+            }
+            AppAction::SetNetwork(network) => {
+                self.notify(StatusLevel::Info, format!("Network: {}", network.name()));
+                self.set_rpc_network(network);
+            }
+            // Used by queries from now on
+            AppAction::SetCommitment(commitment) => {
+                self.commitment = CommitmentConfig { commitment };
+                self.notify(StatusLevel::Info, format!("Commitment: {}", commitment));
+            }
+            AppAction::Export(path) => self.export_result(&path),
+            AppAction::SaveBookmark(_) if self.pane.query.is_empty() => {
+                self.notify(StatusLevel::Error, "Nothing to bookmark");
+            }
+            AppAction::SaveBookmark(label) => {
+                self.add_bookmark(label);
+                self.notify(StatusLevel::Info, "Bookmarked");
+            }
+            // Scroll the results area
+            AppAction::Up => {
+                if !self.move_selection(false) {
+                    self.scroll_up(1);
+                }
+            }
+            AppAction::Down => {
+                if !self.move_selection(true) {
+                    self.scroll_down(1);
+                } else if self.at_history_end() {
+                    // Reaching the last row loads the next page
+                    self.load_more_history();
+                }
+            }
+            AppAction::PageUp => self.scroll_up(self.pane.results_height.max(1)),
+            AppAction::PageDown => self.scroll_down(self.pane.results_height.max(1)),
+            AppAction::HalfPageUp => self.scroll_up((self.pane.results_height / 2).max(1)),
+            AppAction::HalfPageDown => self.scroll_down((self.pane.results_height / 2).max(1)),
+            AppAction::Top => self.pane.scroll_offset = 0,
+            AppAction::Bottom => self.pane.scroll_offset = self.max_scroll_offset(),
+            // The rest belong to screens other than the one on show
+            AppAction::CycleHistoryFilter
+            | AppAction::CycleHistorySort
+            | AppAction::DateFilter
+            | AppAction::ToggleNonCirculating
+            | AppAction::ToggleUnchangedBalances
+            | AppAction::SearchAllNetworks
+            | AppAction::ToggleLogs
+            | AppAction::ToggleInnerInstructions
+            | AppAction::ScrollLogsUp
+            | AppAction::ScrollLogsDown
+            | AppAction::Refresh => {}
+        }
+    }
+
+    // Dashboard actions; false for the ones it leaves to `perform`
+    fn perform_on_dashboard(&mut self, action: &AppAction) -> bool {
+        match action {
+            // List or hide the accounts of the non-circulating supply
+            AppAction::ToggleNonCirculating => {
+                let dashboard = self.dashboard_screen_mut();
+                dashboard.show_non_circulating = !dashboard.show_non_circulating;
+                self.pane.selected_history = None;
+            }
+            AppAction::Refresh => self.refresh_dashboard(),
+            _ => return false,
+        }
+        true
+    }
+
+    // Search actions, which work on the results of the focused pane
+    fn perform_on_search(&mut self, action: &AppAction) -> bool {
+        if self.perform_in_raw_view(action) {
+            return true;
+        }
+        match action {
+            // Tab moves between the panes of a split instead
+            AppAction::NextTab | AppAction::PreviousTab if self.split.is_some() => self.switch_pane(),
+            // Cycle the history status filter: all, failed, succeeded
+            AppAction::CycleHistoryFilter => {
+                let current = &self.search_screen().history_filter;
+                let filter = HistoryFilter {
+                    status: current.status.next(),
+                    ..current.clone()
+                };
+                self.set_history_filter(filter);
+            }
+            // Cycle the history order: newest, oldest, failed first
+            AppAction::CycleHistorySort => self.cycle_history_sort(),
+            // Prompt for the history date filter
+            AppAction::DateFilter => {
+                let current = self.search_screen().history_filter.since.as_ref().map(|(_, text)| text.clone());
+                self.input.prompt = Some(Prompt::Date(current.unwrap_or_default()));
+            }
+            // Look for a query that wasn't found on the other networks
+            AppAction::SearchAllNetworks if self.pane.shows_not_found() => self.search_all_networks(),
+            // Show or hide accounts whose balance did not change
+            AppAction::ToggleUnchangedBalances => {
+                let search = self.search_screen_mut();
+                search.show_unchanged_balances = !search.show_unchanged_balances;
+            }
+            // Show or hide the transaction log pane
+            AppAction::ToggleLogs => {
+                let search = self.search_screen_mut();
+                search.show_logs = !search.show_logs;
+                search.logs_scroll = 0;
+            }
+            // Collapse or expand all inner instructions; the rows under the selection move
+            AppAction::ToggleInnerInstructions => {
+                let search = self.search_screen_mut();
+                search.show_inner_instructions = !search.show_inner_instructions;
+                if self.result_rows(&self.pane).instruction_start.is_some() {
+                    self.pane.selected_history = None;
+                }
+            }
+            // Scroll the log pane; the next frame clamps it to the content
+            AppAction::ScrollLogsUp => {
+                let search = self.search_screen_mut();
+                search.logs_scroll = search.logs_scroll.saturating_sub(1);
+            }
+            AppAction::ScrollLogsDown => {
+                let search = self.search_screen_mut();
+                search.logs_scroll = search.logs_scroll.saturating_add(1);
+            }
+            // Rerun the query, bypassing the query cache
+            AppAction::Refresh => self.refresh_query(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn perform_on_blocks(&mut self, action: &AppAction) -> bool {
+        match action {
+            // Refetch the recent blocks unless a fetch is on its way
+            AppAction::Refresh => {
+                let blocks = self.blocks_screen_mut();
+                if !matches!(blocks.blocks, TabData::Loading) {
+                    blocks.blocks = TabData::NotLoaded;
+                }
+                self.ensure_tab_loaded();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    // The validator list reuses the history filter and sort keys
+    fn perform_on_validators(&mut self, action: &AppAction) -> bool {
+        match action {
+            AppAction::CycleHistoryFilter => {
+                let validators = self.validators_screen_mut();
+                validators.filter = validators.filter.next();
+                self.pane.selected_history = None;
+                self.pane.scroll_offset = 0;
+            }
+            AppAction::CycleHistorySort => {
+                let validators = self.validators_screen_mut();
+                validators.sort = validators.sort.next();
+                self.pane.selected_history = None;
+                self.pane.scroll_offset = 0;
+            }
+            // Refetch the vote accounts and block production unless a fetch is on its way
+            AppAction::Refresh => {
+                let validators = self.validators_screen_mut();
+                if !matches!(validators.validators, TabData::Loading) {
+                    validators.validators = TabData::NotLoaded;
+                }
+                if !matches!(validators.block_production, TabData::Loading) {
+                    validators.block_production = TabData::NotLoaded;
+                }
+                self.ensure_tab_loaded();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    // Show the raw JSON of the result as a tree instead of the table, or go back
+    fn toggle_raw_view(&mut self) {
+        if self.pane.raw_view.take().is_some() {
+            return;
+        }
+        if self.pane.json_response.is_none() && self.pane.address_sign.is_none() {
+            self.notify(StatusLevel::Error, "Nothing to show");
+            return;
+        }
+        self.tab = Tab::Search;
+        self.pane.raw_view = Some(JsonTree::new(self.pane.raw_document()));
+    }
+
+    // Navigation moves the tree cursor while the raw view is shown, and Enter folds
+    // the node under it. Returns false for actions the tree doesn't take.
+    fn perform_in_raw_view(&mut self, action: &AppAction) -> bool {
+        let height = self.pane.results_height;
+        let Some(tree) = self.pane.raw_view.as_mut() else {
+            return false;
+        };
+        let page = height.max(1) as isize;
+        match action {
+            AppAction::Open => tree.toggle(),
+            AppAction::Up => tree.move_cursor(-1, height),
+            AppAction::Down => tree.move_cursor(1, height),
+            AppAction::PageUp => tree.move_cursor(-page, height),
+            AppAction::PageDown => tree.move_cursor(page, height),
+            AppAction::HalfPageUp => tree.move_cursor(-(page / 2).max(1), height),
+            AppAction::HalfPageDown => tree.move_cursor((page / 2).max(1), height),
+            AppAction::Top => tree.move_cursor(isize::MIN, height),
+            AppAction::Bottom => tree.move_cursor(isize::MAX, height),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, mouse_event: MouseEvent) {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                if self.layout.logs_area.contains(Position::new(mouse_event.column, mouse_event.row)) =>
+            {
+                // The next frame clamps the log scroll to the content
+                let search = self.search_screen_mut();
+                search.logs_scroll = if mouse_event.kind == MouseEventKind::ScrollUp {
+                    search.logs_scroll.saturating_sub(MOUSE_SCROLL_ROWS as u16)
+                } else {
+                    search.logs_scroll.saturating_add(MOUSE_SCROLL_ROWS as u16)
+                };
+            }
+            MouseEventKind::ScrollUp => self.scroll_up(MOUSE_SCROLL_ROWS),
+            MouseEventKind::ScrollDown => self.scroll_down(MOUSE_SCROLL_ROWS),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                if matches!(self.popup, Some(Popup::Help { .. } | Popup::DebugLog)) {
+                    self.popup = None;
+                } else if self.layout.input_area.contains(position) {
+                    if matches!(self.input.mode, InputMode::Normal) {
+                        self.start_editing();
+                    }
+                } else if self.layout.results_area.contains(position) {
+                    self.input.mode = InputMode::Normal;
+                    self.click_results_row(mouse_event.row);
+                } else if self.layout.other_pane_area.contains(position) {
+                    // Clicking the other pane of a split focuses it
+                    self.input.mode = InputMode::Normal;
+                    self.switch_pane();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_bookmark_key(&mut self, key_event: KeyEvent) {
+        let Some(Popup::Bookmarks(popup)) = self.popup.as_mut() else {
+            return;
+        };
+        match popup {
+            BookmarkPopup::Label(label) => match key_event.code {
+                KeyCode::Enter => {
+                    let label = label.trim().to_string();
+                    self.popup = None;
+                    self.add_bookmark(label);
+                }
+                KeyCode::Esc => self.popup = None,
+                KeyCode::Backspace => {
+                    pop_grapheme(label);
+                }
+                KeyCode::Char(c) => label.push(c),
+                _ => {}
+            },
+            BookmarkPopup::List(selected) => match key_event.code {
+                KeyCode::Up => *selected = selected.saturating_sub(1),
+                KeyCode::Down => *selected = (*selected + 1).min(self.bookmarks.len().saturating_sub(1)),
+                KeyCode::Enter => {
+                    let selected = *selected;
+                    self.open_bookmark(selected);
+                }
+                KeyCode::Char('d') => {
+                    let index = *selected;
+                    *selected = index.min(self.bookmarks.len().saturating_sub(2));
+                    self.bookmarks.remove(index);
+                    self.save_bookmarks();
+                }
+                KeyCode::Esc | KeyCode::Char('M') | KeyCode::Char('q') => self.popup = None,
+                _ => {}
+            },
+        }
+    }
+
+    // Bookmark the current query on the current network
+    fn add_bookmark(&mut self, label: String) {
+        let bookmark = Bookmark {
+            label,
+            query: self.pane.query.clone(),
+            network: self.current_rpc_network.clone(),
+        };
+        self.bookmarks.add(bookmark);
+        self.save_bookmarks();
+    }
+
+    fn handle_command_line_key(&mut self, key_event: KeyEvent) {
+        let Some(Prompt::Command(input)) = self.input.prompt.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Enter => {
+                let input = std::mem::take(input);
+                self.input.prompt = None;
+                match command::parse(&input) {
+                    Ok(action) => self.perform(action),
+                    Err(err) => self.notify(StatusLevel::Error, err),
+                }
+            }
+            KeyCode::Esc => self.input.prompt = None,
+            KeyCode::Tab => {
+                if let Some(completed) = command::complete(input) {
+                    *input = completed;
+                }
+            }
+            // Deleting past the ':' closes the command line, as in vim
+            KeyCode::Backspace if input.is_empty() => self.input.prompt = None,
+            KeyCode::Backspace => {
+                pop_grapheme(input);
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    }
+
+    fn draw_command_line(&self, frame: &mut Frame, area: Rect) {
+        let Some(Prompt::Command(input)) = &self.input.prompt else {
+            return;
+        };
+        let [input_area, hint_area] = Layout::horizontal([Constraint::Min(1), Constraint::Length(40)]).areas(area);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![":".set_style(self.theme.title).bold(), input.as_str().set_style(self.theme.value)])),
+            input_area,
+        );
+        frame.render_widget(
+            Paragraph::new(" Tab: Complete, Enter: Run, Esc: Cancel ".set_style(self.theme.title)).alignment(Alignment::Right),
+            hint_area,
+        );
+        let column = Line::from(input.as_str()).width() as u16 + 1;
+        frame.set_cursor_position(Position::new(input_area.x + column.min(input_area.width), input_area.y));
+    }
+
+    // Write the current result to a file, asking before replacing one
+    fn export_result(&mut self, path: &str) {
+        if self.pane.json_response.is_none() && self.pane.address_sign.is_none() {
+            self.notify(StatusLevel::Error, "Nothing to export");
+            return;
+        }
+        if storage::expand_home(path).exists() {
+            self.input.prompt = Some(Prompt::ConfirmExport(path.to_string()));
+            return;
+        }
+        self.write_export(path);
+    }
+
+    // A .csv path gets the history table as shown; anything else gets the result
+    // and history as pretty-printed JSON, with the query, network and time
+    fn write_export(&mut self, path: &str) {
+        let contents = if path.to_ascii_lowercase().ends_with(".csv") {
+            if self.pane.address_sign.is_none() {
+                self.notify(StatusLevel::Error, "Only a transaction history can be exported as CSV");
+                return;
+            }
+            Ok(history_csv(&self.visible_history(&self.pane)))
+        } else {
+            let mut document = self.pane.raw_document();
+            if let Value::Object(fields) = &mut document {
+                fields.insert(
+                    "export".to_string(),
+                    serde_json::json!({
+                        "query": self.pane.query,
+                        "network": self.current_rpc_network.name(),
+                        "rpcUrl": self.get_current_rpc_url(),
+                        "exportedAt": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                        "soonscanVersion": env!("CARGO_PKG_VERSION"),
+                    }),
+                );
+            }
+            serde_json::to_string_pretty(&document)
+        };
+        let written = contents
+            .map_err(io::Error::from)
+            .and_then(|contents| std::fs::write(storage::expand_home(path), contents));
+        match written {
+            Ok(()) => self.notify(StatusLevel::Info, format!("Exported to {}", path)),
+            Err(err) => self.notify(StatusLevel::Error, format!("Export to {} failed: {}", path, err)),
+        }
+    }
+
+    // File name offered by Ctrl+S: CSV while a history row is selected, JSON otherwise
+    fn default_export_name(&self) -> String {
+        let extension = if self.pane.selected_history.is_some() { "csv" } else { "json" };
+        let query: String = self.pane.query.chars().filter(char::is_ascii_alphanumeric).take(12).collect();
+        format!(
+            "soonscan-{}-{}.{}",
+            if query.is_empty() { "result" } else { &query },
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            extension
+        )
+    }
+
+    fn selected_history_entry(&self) -> Option<HistoryEntry> {
+        let selected = self.pane.selected_history?;
+        self.visible_history(&self.pane).get(selected).map(|entry| (*entry).clone())
+    }
+
+    // History entries passing the active filter, in table order
+    fn visible_history<'a>(&self, pane: &'a Pane) -> Vec<&'a HistoryEntry> {
+        let Some(entries) = &pane.address_sign else {
+            return Vec::new();
+        };
+        let mut visible: Vec<&HistoryEntry> = entries
+            .iter()
+            .filter(|entry| self.search_screen().history_filter.matches(entry))
+            .collect();
+        self.search_screen().history_sort.sort(&mut visible);
+        visible
+    }
+
+    // Filters change which row an index points at, so drop the selection
+    fn set_history_filter(&mut self, filter: HistoryFilter) {
+        self.search_screen_mut().history_filter = filter;
+        self.pane.selected_history = None;
+        self.popup.take_if(|popup| matches!(popup, Popup::HistoryDetail(_)));
+    }
+
+    // Reorder the history table; the selection stays on the same transaction
+    fn cycle_history_sort(&mut self) {
+        let selected = self.selected_history_entry();
+        let search = self.search_screen_mut();
+        search.history_sort = search.history_sort.next();
+        self.pane.selected_history = selected.and_then(|selected| {
+            self.visible_history(&self.pane)
+                .iter()
+                .position(|entry| entry.signature == selected.signature)
+        });
+    }
+
+    fn handle_date_prompt_key(&mut self, key_event: KeyEvent) {
+        let Some(Prompt::Date(input)) = self.input.prompt.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Enter => {
+                let input = input.trim().to_string();
+                let since = if input.is_empty() {
+                    None
+                } else {
+                    match parse_since(&input, chrono::Utc::now().timestamp()) {
+                        Some(since) => Some((since, input)),
+                        None => {
+                            self.notify(StatusLevel::Error, "Use YYYY-MM-DD, or e.g. 24h or 7d");
+                            return;
+                        }
+                    }
+                };
+                self.input.prompt = None;
+                let filter = HistoryFilter {
+                    since,
+                    ..self.search_screen().history_filter.clone()
+                };
+                self.set_history_filter(filter);
+            }
+            KeyCode::Esc => self.input.prompt = None,
+            KeyCode::Backspace => {
+                pop_grapheme(input);
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    }
+
+    fn draw_date_prompt(&self, frame: &mut Frame) {
+        let Some(Prompt::Date(input)) = &self.input.prompt else {
+            return;
+        };
+        let popup_area = centered_rect(60, 20, frame.area());
+        let prompt = Paragraph::new(vec![
+            Line::from(" Show transactions since YYYY-MM-DD, or e.g. 24h or 7d (empty clears)".set_style(self.theme.title)),
+            Line::from(vec![" Since: ".set_style(self.theme.title).bold(), input.as_str().set_style(self.theme.value)]),
+        ])
+        .block(
+            Block::bordered()
+                .title(" Date Filter (Enter: Apply, Esc: Cancel) ")
+                .border_style(self.theme.border),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false });
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(prompt, popup_area);
+    }
+
+    fn handle_rpc_prompt_key(&mut self, key_event: KeyEvent) {
+        let Some(Prompt::Rpc(prompt)) = self.input.prompt.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Enter => match parse_rpc_url(&prompt.input) {
+                Ok(url) => {
+                    self.input.prompt = None;
+                    self.set_rpc_network(RpcNetwork::Custom(url));
+                }
+                // Keep the popup open so the URL can be corrected
+                Err(err) => prompt.error = Some(err),
+            },
+            KeyCode::Esc => self.input.prompt = None,
+            KeyCode::Char('v') if key_event.modifiers.contains(KeyModifiers::CONTROL) => match clipboard_contents() {
+                Ok(clipboard_content) => {
+                    prompt.input.push_str(clipboard_content.trim());
+                    prompt.error = None;
+                }
+                Err(err) => prompt.error = Some(err.to_string()),
+            },
+            KeyCode::Backspace => {
+                pop_grapheme(&mut prompt.input);
+                prompt.error = None;
+            }
+            KeyCode::Char(c) => {
+                prompt.input.push(c);
+                prompt.error = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_rpc_prompt(&self, frame: &mut Frame) {
+        let Some(Prompt::Rpc(prompt)) = &self.input.prompt else {
+            return;
+        };
+        let popup_area = centered_rect(60, 25, frame.area());
+        let mut lines = vec![
+            Line::from(" Enter an http(s) RPC URL, e.g. https://rpc.example.com".set_style(self.theme.title)),
+            Line::from(vec![" URL: ".set_style(self.theme.title).bold(), prompt.input.as_str().set_style(self.theme.value)]),
+        ];
+        if let Some(error) = &prompt.error {
+            lines.push(Line::from(format!(" {}", error).set_style(self.theme.error)));
+        }
+        let popup = Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title(" Custom RPC (Enter: Connect, Esc: Cancel, Ctrl+V: Paste) ")
+                    .border_style(self.theme.border),
+            )
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(popup, popup_area);
+    }
+
+    // Logs to show in the log pane, when it is open over a transaction
+    fn logs_pane(&self) -> Option<Vec<&str>> {
+        if !self.search_screen().show_logs || self.tab != Tab::Search || self.pane.fetch_task.is_some() {
+            return None;
+        }
+        self.pane.transaction_logs()
+    }
+
+    fn draw_logs(&self, frame: &mut Frame, area: Rect) -> Option<u16> {
+        let logs = self.logs_pane()?;
+        let lines: Vec<Line> = if logs.is_empty() {
+            vec![Line::from("No log messages".set_style(self.theme.title))]
+        } else {
+            logs.iter()
+                .zip(log_line_styles(&logs, &self.theme))
+                .map(|(line, style)| Line::from(line.to_string().set_style(style)))
+                .collect()
+        };
+        let title = format!(" Logs ({} lines) ", logs.len());
+
+        let logs = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
+        let wrapped_lines = logs.line_count(area.width.saturating_sub(2)) as u16;
+        let max_scroll = wrapped_lines.saturating_sub(area.height.saturating_sub(2));
+
+        let mut block = Block::bordered().title(title).border_style(self.theme.border);
+        if max_scroll > 0 {
+            block = block.title_bottom(Line::from(" [ / ] scroll, l: hide ").right_aligned());
+        }
+        frame.render_widget(logs.block(block).scroll((self.search_screen().logs_scroll.min(max_scroll), 0)), area);
+        Some(max_scroll)
+    }
+
+    // Search keys; returns false for keys the search leaves to the normal bindings
+    fn handle_search_key(&mut self, key_event: KeyEvent) -> bool {
+        let Some(search) = self.search.as_mut() else {
+            return false;
+        };
+        if search.editing {
+            match key_event.code {
+                KeyCode::Enter if search.term.is_empty() => self.search = None,
+                KeyCode::Enter => search.editing = false,
+                KeyCode::Esc => self.search = None,
+                KeyCode::Backspace => {
+                    pop_grapheme(&mut search.term);
+                    search.current = 0;
+                    self.focus_search_match();
+                }
+                KeyCode::Char(c) => {
+                    search.term.push(c);
+                    search.current = 0;
+                    self.focus_search_match();
+                }
+                _ => {}
+            }
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                let count = self.search_matches(&self.result_rows(&self.pane).rows).len();
+                if let Some(search) = self.search.as_mut().filter(|_| count > 0) {
+                    search.current = if key_event.code == KeyCode::Char('n') {
+                        (search.current + 1) % count
+                    } else {
+                        (search.current + count - 1) % count
+                    };
+                    self.focus_search_match();
+                }
+                true
+            }
+            KeyCode::Esc => {
+                self.search = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Cells of the result view matching the search term, as (row, column) in reading order
+    fn search_matches(&self, rows: &[ResultRow]) -> Vec<(usize, usize)> {
+        let Some(search) = self.search.as_ref().filter(|search| !search.term.is_empty()) else {
+            return vec![];
+        };
+        rows.iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells.cells.iter().enumerate().filter_map(move |(column, cell)| {
+                    (!find_matches(&cell.to_string(), &search.term).is_empty()).then_some((row, column))
+                })
+            })
+            .collect()
+    }
+
+    // The focused match, wrapping around when the view changed under the search
+    fn current_search_match(&self, matches: &[(usize, usize)]) -> Option<(usize, usize)> {
+        let search = self.search.as_ref()?;
+        (!matches.is_empty()).then(|| matches[search.current % matches.len()])
+    }
+
+    // Scroll so the focused match is visible
+    fn focus_search_match(&mut self) {
+        let matches = self.search_matches(&self.result_rows(&self.pane).rows);
+        if let Some((row, _)) = self.current_search_match(&matches) {
+            let height = self.pane.results_height.max(1);
+            if row < self.pane.scroll_offset {
+                self.pane.scroll_offset = row;
+            } else if row >= self.pane.scroll_offset + height {
+                self.pane.scroll_offset = row + 1 - height;
+            }
+        }
+    }
+
+    // Search line under the results: the term, and the match count once there is one
+    // Help popup content per mode; keys come from the keymap so custom bindings show up
+    fn help_lines(&self) -> Vec<Line<'static>> {
+        let keys = |action: AppAction| self.keymap.keys_for(&action).join(" / ");
+        let normal: Vec<(String, &'static str)> = ACTION_NAMES
+            .iter()
+            .map(|(_, action)| (keys(action.clone()), action.description()))
+            .filter(|(keys, _)| !keys.is_empty())
+            .collect();
+        let sections = [
+            ("Normal", normal),
+            (
+                "Editing",
+                vec![
+                    ("Enter".to_string(), "Submit the query: a slot, account or transaction"),
+                    ("Esc".to_string(), "Stop editing"),
+                    ("←/→, Home/End, Ctrl+A/E".to_string(), "Move the cursor"),
+                    ("Del, Ctrl+W, Ctrl+U".to_string(), "Delete forward, word, line"),
+                    ("Ctrl+V".to_string(), "Paste from the clipboard"),
+                    ("↑/↓".to_string(), "Recall previous queries"),
+                ],
+            ),
+            (
+                "Search",
+                vec![
+                    (keys(AppAction::Search), "Search the results"),
+                    ("Enter".to_string(), "Finish typing and keep the search"),
+                    ("n / N".to_string(), "Next / previous match"),
+                    ("Esc".to_string(), "Clear the search"),
+                ],
+            ),
+            (
+                "Block view",
+                vec![
+                    (format!("{} / {}", keys(AppAction::Up), keys(AppAction::Down)), "Select a transaction"),
+                    (keys(AppAction::Open), "Open the selected transaction"),
+                    (keys(AppAction::Copy), "Copy its signature"),
+                    (keys(AppAction::Back), "Back to the block from the transaction"),
+                ],
+            ),
+        ];
+
+        let mut lines = vec![
+            Line::from(" Look up slots, accounts and transactions".set_style(self.theme.title)),
+            Line::from(" Mouse: click the input to edit, double-click a transaction to open it".set_style(self.theme.title)),
+        ];
+        for (title, entries) in sections {
+            let width = entries.iter().map(|(keys, _)| keys.chars().count()).max().unwrap_or(0);
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!(" {}", title).set_style(self.theme.title).bold()));
+            for (keys, description) in entries {
+                lines.push(Line::from(vec![
+                    format!(" {:<width$} : ", keys, width = width).set_style(self.theme.value),
+                    description.set_style(self.theme.title),
+                ]));
+            }
+        }
+        lines
+    }
+
+    // Color of the network name, so the active network is recognizable at a glance
+    fn network_style(&self) -> Style {
+        match self.current_rpc_network {
+            RpcNetwork::Devnet => self.theme.success,
+            RpcNetwork::Testnet => self.theme.title,
+            RpcNetwork::Mainnet => self.theme.value,
+            RpcNetwork::SolanaMainnet => self.theme.border,
+            RpcNetwork::Custom(_) => self.theme.value.italic(),
+        }
+    }
+
+    // Background work the status bar reports, the query fetch first
+    fn activity(&self) -> Option<String> {
+        if let (Some(task), QueryState::Loading { started_at, .. }) = (&self.pane.fetch_task, &self.pane.state) {
+            return Some(format!("fetching {} {}s", task.label, started_at.elapsed().as_secs()));
+        }
+        [
+            (matches!(self.pane.history_paging, HistoryPaging::Loading(_)), "loading history"),
+            (matches!(self.pane.token_holdings, TabData::Loading), "loading token accounts"),
+            (matches!(self.pane.inflation_rewards, TabData::Loading), "loading inflation rewards"),
+            (matches!(self.pane.largest_holders, TabData::Loading), "loading largest holders"),
+            (self.pane.leader_lookup, "looking up the slot leader"),
+            (matches!(self.blocks_screen().blocks, TabData::Loading), "loading blocks"),
+            (matches!(self.validators_screen().validators, TabData::Loading), "loading validators"),
+            (matches!(self.validators_screen().block_production, TabData::Loading), "loading block production"),
+            (self.pane.watch.as_ref().is_some_and(|watch| watch.live), "watching live"),
+            (self.pane.watch.is_some(), "watching, polling"),
+        ]
+        .into_iter()
+        .find(|(loading, _)| *loading)
+        .map(|(_, activity)| activity.to_string())
+    }
+
+    // Bottom line: network and host, last refresh, work in flight, throttling, the latest
+    // message, and key hints
+    fn draw_status_bar(&self, frame: &mut Frame, area: Rect) {
+        let separator = || " │ ".set_style(self.theme.border);
+        let mut status = Line::from(vec![
+            format!(" {} ", self.current_rpc_network.name()).set_style(self.network_style()),
+            format!("({})", rpc_host(self.get_current_rpc_url())).set_style(self.theme.title),
+        ]);
+        if let Some(hash) = &self.genesis_hash {
+            status.push_span(separator());
+            let prefix: String = hash.chars().take(8).collect();
+            status.push_span(format!("genesis {}", prefix).set_style(self.theme.title));
+        }
+        if let Some(last_refresh) = self.dashboard_screen().last_refresh {
+            status.push_span(separator());
+            status.push_span(format!("updated {}s ago", last_refresh.elapsed().as_secs()).set_style(self.theme.title));
+        }
+        if let Some(activity) = self.activity() {
+            status.push_span(separator());
+            status.push_span(format!("⟳ {}", activity).set_style(self.theme.value));
+        }
+        if self.rate_limiter.is_throttled() {
+            status.push_span(separator());
+            status.push_span("throttled".set_style(self.theme.value));
+        }
+        // An export waiting for its overwrite answer keeps asking until a key is pressed
+        if let Some(Prompt::ConfirmExport(path)) = &self.input.prompt {
+            status.push_span(separator());
+            let name = std::path::Path::new(path).file_name().map_or(path.into(), |name| name.to_string_lossy());
+            status.push_span(format!("Overwrite {}? (y/n)", name).set_style(self.theme.value).bold());
+        } else if let Some(message) = self.status.as_ref().filter(|message| message.is_visible()) {
+            status.push_span(separator());
+            status.push_span(message.text.as_str().set_style(match message.level {
+                StatusLevel::Info => self.theme.success,
+                StatusLevel::Warning => self.theme.value,
+                StatusLevel::Error => self.theme.error,
+            }));
+        }
+
+        let hints = match self.input.mode {
+            InputMode::Editing => Line::from(" Enter: Submit, Esc: Cancel ".set_style(self.theme.title).bold()),
+            InputMode::Normal => {
+                let mut hints = Line::from(vec![
+                    " Press 'e' to edit".set_style(self.theme.title).bold(),
+                    " | ".into(),
+                    "?".set_style(self.theme.title).bold(),
+                    " Help | ".into(),
+                    "q".set_style(self.theme.title).bold(),
+                    " Quit ".into(),
+                ]);
+                if !self.pane.view_history.is_empty() {
+                    hints.push_span("| ");
+                    hints.push_span("Esc".set_style(self.theme.title).bold());
+                    hints.push_span(" Back ");
+                }
+                hints
+            }
+        };
+
+        let [status_area, hints_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(hints.width() as u16)]).areas(area);
+        frame.render_widget(Paragraph::new(status), status_area);
+        frame.render_widget(Paragraph::new(hints), hints_area);
+    }
+
+    fn draw_search(&self, frame: &mut Frame, area: Rect) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        let matches = self.search_matches(&self.result_rows(&self.pane).rows);
+        let status = match self.current_search_match(&matches) {
+            _ if search.term.is_empty() => String::new(),
+            Some(current) => format!(
+                " {}/{} ",
+                matches.iter().position(|found| *found == current).unwrap_or(0) + 1,
+                matches.len()
+            ),
+            None => " no matches ".to_string(),
+        };
+        let hint = if search.editing { " Enter: Done, Esc: Clear " } else { " n/N: Next/Previous, Esc: Clear " };
+
+        let [input_area, status_area] =
+            Layout::horizontal([Constraint::Min(1), Constraint::Length((status.len() + hint.len()) as u16)]).areas(area);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec!["/".set_style(self.theme.title).bold(), search.term.as_str().set_style(self.theme.value)])),
+            input_area,
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                status.set_style(if matches.is_empty() { self.theme.error } else { self.theme.success }),
+                hint.set_style(self.theme.title),
+            ]))
+            .alignment(Alignment::Right),
+            status_area,
+        );
+        if search.editing {
+            let column = Line::from(search.term.as_str()).width() as u16 + 1;
+            frame.set_cursor_position(Position::new(input_area.x + column.min(input_area.width), input_area.y));
+        }
+    }
+
+    fn handle_history_detail_key(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') => self.copy_to_clipboard(),
+            KeyCode::Enter => {
+                self.popup = None;
+                self.open_selected_transaction();
+            }
+            KeyCode::Esc | KeyCode::Char(' ') | KeyCode::Char('q') => self.popup = None,
+            _ => {}
+        }
+    }
+
+    fn draw_history_detail(&self, frame: &mut Frame, entry: &HistoryEntry) {
+        let field = |name: &'static str, value: String| {
+            Line::from(vec![format!(" {:<14}", name).bold(), value.set_style(self.theme.value)])
+        };
+        let lines = vec![
+            Line::from(" Signature:".bold()),
+            Line::from(format!(" {}", entry.signature).set_style(self.theme.value)),
+            Line::from(""),
+            field("Slot:", self.format_longnumber(entry.slot)),
+            field(
+                "Block Time:",
+                match entry.block_time {
+                    Some(time) => format!("{} ({})", self.format_timestamp(Some(time)), self.format_time_ago(time)),
+                    None => self.format_timestamp(None),
+                },
+            ),
+            field(
+                "Status:",
+                entry.confirmation_status.clone().unwrap_or("Unknown".to_string()),
+            ),
+            Line::from(vec![
+                format!(" {:<14}", "Error:").bold(),
+                match &entry.err {
+                    Some(err) => instruction::describe_transaction_error(err).set_style(self.theme.error),
+                    None => "None".set_style(self.theme.success),
+                },
+            ]),
+            field("Memo:", entry.memo.clone().unwrap_or("None".to_string())),
+        ];
+
+        let popup_area = centered_rect(70, 50, frame.area());
+        let popup = Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title(" Transaction ")
+                    .title_bottom(Line::from(" Enter: Open | y: Copy | Esc: Close ").centered())
+                    .border_style(self.theme.border),
+            )
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(popup, popup_area);
+    }
+
+
+    fn handle_instruction_detail_key(&mut self, key_event: KeyEvent) {
+        let Some(Popup::InstructionDetail { scroll, .. }) = self.popup.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            // The next frame clamps the scroll to the content
+            KeyCode::Up => *scroll = scroll.saturating_sub(1),
+            KeyCode::Down => *scroll = scroll.saturating_add(1),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.popup = None,
+            _ => {}
+        }
+    }
+
+    fn draw_instruction_detail(&self, frame: &mut Frame, index: usize, scroll: u16) -> Option<u16> {
+        let collapse_inner = !self.search_screen().show_inner_instructions;
+        let (instructions, account_keys) = self.pane.transaction_message(collapse_inner)?;
+        let listed = instructions.get(index)?;
+        let instruction = &listed.instruction;
+        let key_name = |key: &str| labels::lookup(key).map_or(key.to_string(), |label| format!("{} ({})", key, label));
+        let index_name = |key_index: usize| account_keys.get(key_index).map_or(format!("unknown[{}]", key_index), key_name);
+
+        let mut lines = vec![Line::from(vec![
+            " Program: ".bold(),
+            index_name(instruction.program_index).set_style(self.theme.success),
+        ])];
+        let description = instruction.describe(account_keys.get(instruction.program_index), self.currency_unit);
+        if let Some(description) = description {
+            lines.push(Line::from(vec![" Action: ".bold(), description.set_style(self.theme.value)]));
+        }
+        lines.push(Line::from(""));
+
+        // Decoded instructions list their fields, then any accounts and raw data (the
+        // node leaves those out of the ones it decodes)
+        if let Some(parsed) = &instruction.parsed {
+            lines.push(Line::from(format!(" {} {}", parsed.program, parsed.kind).bold()));
+            for (name, value) in parsed.info.as_object().into_iter().flatten() {
+                let value = match value {
+                    Value::String(text) => key_name(text),
+                    Value::Object(_) => value
+                        .get("uiAmountString")
+                        .and_then(Value::as_str)
+                        .map_or_else(|| value.to_string(), str::to_string),
+                    _ => value.to_string(),
+                };
+                lines.push(Line::from(vec![format!(" {:>16}  ", name).set_style(self.theme.title), value.into()]));
+            }
+            lines.push(Line::from(""));
+        }
+        if instruction.parsed.is_none() || !instruction.accounts.is_empty() || !instruction.data.is_empty() {
+            lines.push(Line::from(format!(" Accounts ({})", instruction.accounts.len()).bold()));
+            for (position, account) in instruction.accounts.iter().enumerate() {
+                let key_index = account.index().map_or("-".to_string(), |index| index.to_string());
+                let mut line = Line::from(vec![
+                    format!(" {:>3}  #{:<3} ", position, key_index).set_style(self.theme.title),
+                    account.address(&account_keys).map_or_else(|| account.name(&account_keys), key_name).into(),
+                ]);
+                if account.index().is_some_and(|index| account_keys.is_signer(index)) {
+                    line.push_span(" signer".set_style(self.theme.value));
+                }
+                if account.index().is_some_and(|index| account_keys.is_writable(index)) {
+                    line.push_span(" writable".set_style(self.theme.error));
+                }
+                lines.push(line);
+            }
+
+            let data = instruction.data_bytes();
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!(" Data ({} bytes)", data.len()).bold()));
+            lines.push(Line::from(format!(" {}", instruction.data).set_style(self.theme.value)));
+            lines.extend(
+                hex_dump(&data, 0)
+                    .into_iter()
+                    .map(|row| Line::from(format!(" {}", row).set_style(self.theme.title))),
+            );
+        }
+
+        let popup_area = centered_rect(80, 70, frame.area());
+        let popup = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
+        let max_scroll = (popup.line_count(popup_area.width.saturating_sub(2)) as u16)
+            .saturating_sub(popup_area.height.saturating_sub(2));
+
+        let popup = popup
+            .block(
+                Block::bordered()
+                    .title(match listed.depth {
+                        1 => format!(" Instruction #{} ", listed.number),
+                        depth => format!(" Instruction #{} (depth {}) ", listed.number, depth),
+                    })
+                    .title_bottom(Line::from(" ↑/↓: Scroll | Esc: Close ").centered())
+                    .border_style(self.theme.border),
+            )
+            .scroll((scroll.min(max_scroll), 0));
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(popup, popup_area);        Some(max_scroll)
+    }
+
+    // Copy the selected history signature, or the full query, to the clipboard
+    fn copy_to_clipboard(&mut self) {
+        let Some(text) = self
+            .selected_signature()
+            .or_else(|| self.selected_address())
+            .or_else(|| (!self.pane.query.is_empty()).then(|| self.pane.query.clone()))
+        else {
+            return;
+        };
+        match cli_clipboard::set_contents(text) {
+            Ok(()) => self.notify(StatusLevel::Info, "Copied!"),
+            Err(err) => self.notify(StatusLevel::Warning, AppError::ClipboardUnavailable(err.to_string()).to_string()),
+        }
+    }
+
+    fn save_bookmarks(&mut self) {
+        if let Err(err) = self.bookmarks.save() {
+            self.error = Some(ErrorBanner::new(format!("Could not save bookmarks: {}", err)));
+        }
+    }
+
+    // Load a bookmarked query, switching to the network it was saved on
+    fn open_bookmark(&mut self, index: usize) {
+        let Some(bookmark) = self.bookmarks.get(index).cloned() else {
+            return;
+        };
+        self.popup = None;
+        if bookmark.network != self.current_rpc_network {
+            self.set_rpc_network(bookmark.network);
+        }
+        self.pane.query = bookmark.query;
+        self.submit_query();
+    }
+
+    fn draw_bookmark_popup(&self, frame: &mut Frame, popup: &BookmarkPopup) {
+        match popup {
+            BookmarkPopup::Label(label) => {
+                let popup_area = centered_rect(60, 20, frame.area());
+                let prompt = Paragraph::new(vec![
+                    Line::from(format!(" Bookmark {}", truncate(&self.pane.query, 44)).set_style(self.theme.title)),
+                    Line::from(vec![" Label: ".set_style(self.theme.title).bold(), label.as_str().set_style(self.theme.value)]),
+                ])
+                .block(
+                    Block::bordered()
+                        .title(" Add Bookmark (Enter: Save, Esc: Cancel) ")
+                        .border_style(self.theme.border),
+                );
+                frame.render_widget(Clear, popup_area);
+                frame.render_widget(prompt, popup_area);
+            }
+            BookmarkPopup::List(selected) => {
+                let popup_area = centered_rect(70, 50, frame.area());
+                let block = Block::bordered()
+                    .title(" Bookmarks ")
+                    .title_bottom(Line::from(" Enter: Open | d: Delete | Esc: Close ").centered())
+                    .border_style(self.theme.border);
+                frame.render_widget(Clear, popup_area);
+
+                if self.bookmarks.is_empty() {
+                    let empty = Paragraph::new(" No bookmarks yet, press 'm' on a result to add one".set_style(self.theme.title))
+                        .block(block);
+                    frame.render_widget(empty, popup_area);
+                    return;
+                }
+
+                let rows = self.bookmarks.iter().map(|bookmark| {
+                    Row::new(vec![
+                        Cell::from(if bookmark.label.is_empty() { "-".to_string() } else { bookmark.label.clone() }),
+                        Cell::from(truncate(&bookmark.query, 24)),
+                        Cell::from(bookmark.network.name()),
+                    ])
+                });
+                let widths = [Constraint::Percentage(40), Constraint::Length(25), Constraint::Min(7)];
+                let table = Table::new(rows, widths)
+                    .block(block)
+                    .column_spacing(2)
+                    .row_highlight_style(self.theme.highlight);
+                let mut table_state = TableState::default().with_selected(Some(*selected));
+                frame.render_stateful_widget(table, popup_area, &mut table_state);
+            }
+        }
+    }
+
+    // Select the clicked history row, or open it on a double-click
+    fn click_results_row(&mut self, screen_row: u16) {
+        // Skip the top border of the results block
+        let Some(visible_row) = screen_row.checked_sub(self.layout.results_area.y + 1) else {
+            return;
+        };
+        let row = self.pane.scroll_offset + visible_row as usize;
+        let clicked = self
+            .result_rows(&self.pane)
+            .selectable_rows()
+            .and_then(|(start, len)| row.checked_sub(start).filter(|index| *index < len));
+        let Some(index) = clicked else {
+            return;
+        };
+
+        let double_click = self.last_click.is_some_and(|(at, last_index)| {
+            last_index == index && at.elapsed() < DOUBLE_CLICK_INTERVAL
+        });
+        self.pane.selected_history = Some(index);
+        if double_click {
+            self.last_click = None;
+            self.open_selected_row();
+        } else {
+            self.last_click = Some((Instant::now(), index));
+        }
+    }
+
+    // One page of an address's signatures, newest first, starting before `before`
+    async fn fetch_history(
+        api: &dyn ChainApi,
+        address: &Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+    ) -> RpcResult<Vec<HistoryEntry>> {
+        let signatures = api.get_signatures(address, before, until, HISTORY_PAGE_SIZE).await?;
+        Ok(signatures
+            .iter()
+            .filter_map(|signature| HistoryEntry::from_value(&serde_json::json!(signature)))
+            .collect())
+    }
+
+    // Answer the query from the cache when it holds a fresh result, otherwise fetch
+    // and cache it. `refresh` skips the lookup. Also returns whether the cache answered.
+    async fn fetch_cached(
+        rpc: &dyn ChainApi,
+        cache: &std::sync::Mutex<ResponseCache<CachedQuery>>,
+        query: &str,
+        ttl: Duration,
+        refresh: bool,
+        request_sent: impl FnOnce(),
+    ) -> (FetchResult, bool) {
+        if !refresh {
+            let hit = cache.lock().ok().and_then(|mut cache| cache.get(rpc.url(), query));
+            if let Some(hit) = hit {
+                let result = FetchResult {
+                    json_response: hit.json_response,
+                    address_sign: hit.address_sign,
+                    error: None,
+                };
+                return (result, true);
+            }
+        }
+
+        tokio::time::sleep(FETCH_DEBOUNCE).await;
+        request_sent();
+        let result = App::fetch_data(rpc, query).await;
+        // A transaction only known by its status is fetched again next time
+        let pending = result.json_response.as_ref().is_some_and(|response| response.get("pending").is_some());
+        if result.error.is_none() && !pending {
+            // Blocks and transactions are read at finalized commitment and can't change,
+            // unlike accounts and slots that may still be produced
+            let skipped = result
+                .json_response
+                .as_ref()
+                .is_some_and(|response| response.pointer("/block/skipped").is_some());
+            let address = matches!(QueryKind::parse(query), Ok(QueryKind::Address(_)));
+            let ttl = (address || skipped).then_some(ttl);
+            let entry = CachedQuery {
+                json_response: result.json_response.clone(),
+                address_sign: result.address_sign.clone(),
+            };
+            if let Ok(mut cache) = cache.lock() {
+                cache.insert(rpc.url(), query, entry, ttl);
+            }
+        }
+        (result, false)
+    }
+
+    // Fetch the block, account or transaction the query names
+    async fn fetch_data(api: &dyn ChainApi, query: &str) -> FetchResult {
+        let mut result = FetchResult::default();
+
+        let kind = match QueryKind::parse(query) {
+            Ok(kind) => kind,
+            Err(err) => {
+                result.error = Some(err);
+                return result;
+            }
+        };
+        match kind {
+            QueryKind::Slot(slot) => match api.get_block(slot).await {
+                Ok(Some(block)) => result.json_response = Some(block_info(slot, &block)),
+                Ok(None) => {
+                    result.json_response = Some(serde_json::json!({
+                        "block": { "slot": slot, "skipped": true },
+                    }));
+                }
+                Err(err) => result.error = Some(err),
+            },
+            QueryKind::Address(pubkey) => match api.get_account_overview(&pubkey).await {
+                Ok(account_info) => {
+                    result.json_response = Some(account_info);
+                    match App::fetch_history(api, &pubkey, None, None).await {
+                        Ok(signatures) => result.address_sign = Some(signatures),
+                        Err(err) => result.error = Some(err),
+                    }
+                }
+                // Never funded or closed: there is nothing to show but any history it has
+                Err(AppError::NotFound(_)) => {
+                    result.json_response = Some(serde_json::json!({ "missingAccount": pubkey.to_string() }));
+                    result.address_sign = App::fetch_history(api, &pubkey, None, None).await.ok();
+                }
+                Err(err) => result.error = Some(err),
+            },
+            QueryKind::Signature(signature) => {
+                let mut transaction = api.get_transaction_details(&signature).await;
+                for delay in TRANSACTION_RETRY_DELAYS {
+                    if !matches!(transaction, Err(AppError::NotFound(_))) {
+                        break;
+                    }
+                    tokio::time::sleep(delay).await;
+                    transaction = api.get_transaction_details(&signature).await;
+                }
+                match transaction {
+                    Ok(transaction_info) => result.json_response = Some(transaction_info),
+                    // Show what the signature status says until the transaction can be read
+                    Err(AppError::NotFound(what)) => match api.get_signature_status(&signature).await {
+                        Ok(Some(status)) => match api.get_first_available_block().await {
+                            Ok(first_available) if status.slot < first_available => {
+                                result.error = Some(AppError::Pruned { first_available })
+                            }
+                            _ => {
+                                tracing::warn!(%signature, "Transaction not readable yet, showing its status");
+                                result.json_response = Some(pending_transaction(&signature, &status));
+                            }
+                        },
+                        _ => result.error = Some(AppError::NotFound(what)),
+                    },
+                    Err(err) => result.error = Some(err),
+                }
+            }
+        }
+
+        result
+    }
+
+    // A block time as a UTC date, before 1970 too; "N/A" when the node has none and
+    // "invalid time" when it is beyond what a date can hold
+    fn format_timestamp(&self, timestamp: Option<i64>) -> String {
+        use chrono::{TimeZone, Utc};
+        let Some(timestamp) = timestamp else {
+            return "N/A".to_string();
+        };
+        Utc.timestamp_opt(timestamp, 0)
+            .single()
+            .map_or("invalid time".to_string(), |dt| {
+                dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+            })
+    }
+
+    // "42s ago", "15 min ago", ... relative to the current time
+    fn format_time_ago(&self, timestamp: i64) -> String {
+        format_relative(timestamp, chrono::Utc::now().timestamp())
+    }
+
+    // Any integer with thousands separators; i128 holds both u64 and i64 without wrapping
+    fn format_longnumber(&self, number: impl Into<i128>) -> String {
+        let number = number.into();
+        let grouped = group_digits_u128(number.unsigned_abs());
+        if number < 0 {
+            format!("-{}", grouped)
+        } else {
+            grouped
+        }
+    }
+}
+
+impl App {
+    // Build every row of the results table for the active tab; rendering windows them by scroll_offset
+    fn result_rows<'a>(&'a self, pane: &'a Pane) -> ResultTable<'a> {
+        match self.screen() {
+            Screen::Dashboard(dashboard) => self.dashboard_rows(dashboard),
+            Screen::Search(_) => self.search_rows(pane),
+            Screen::Blocks(blocks) => ResultTable::from_rows(self.blocks_rows(blocks)),
+            Screen::Validators(validators) => self.validators_rows(validators),
+        }
+    }
+
+    // Show blockchain data on the dashboard
+    fn dashboard_rows<'a>(&'a self, dashboard: &'a DashboardScreen) -> ResultTable<'a> {
+        let mut rows = vec![];
+        let mut inline_widgets = vec![];
+
+        if let Some(slot_info) = dashboard.data.slot_info {
+            rows.push(ResultRow::new(vec![
+                Line::from("Network").bold(),
+                Line::from(format!("SoonScan {}", self.current_rpc_network.name())).bold(),
+            ]));
+
+            rows.push(ResultRow::new(vec![
+                Line::from("Slot:").bold(),
+                Line::from(self.format_longnumber(slot_info).set_style(self.theme.value)),
+                match self.slot_feed {
+                    SlotFeed::Live => Line::from("● live".set_style(self.theme.success)),
+                    SlotFeed::Polling => Line::from("polling".set_style(self.theme.title)),
+                },
+            ]));
+        }
+
+        if let Some(first_available) = dashboard.data.first_available_block {
+            // How much history that is, in slots and, at the recent pace, in time
+            let depth = dashboard.data.slot_info.map(|slot| slot.saturating_sub(first_available));
+            let span = match (depth, dashboard.data.average_slot_time()) {
+                (Some(depth), Some(slot_time)) => format!(
+                    "{} slots of history, about {}",
+                    group_digits(depth),
+                    format_duration((depth as f64 * slot_time) as u64)
+                ),
+                (Some(depth), None) => format!("{} slots of history", group_digits(depth)),
+                _ => String::new(),
+            };
+            rows.push(ResultRow::new(vec![
+                Line::from("First Available Block:").bold(),
+                Line::from(self.format_longnumber(first_available).set_style(self.theme.value)),
+                Line::from(span.set_style(self.theme.title)),
+            ]));
+        }
+        rows.extend(self.ledger_rows(dashboard));
+
+        if let Some(supply_info) = &dashboard.data.supply_info {
+            if let Some(value) = supply_info.get("value") {
+                let total_supply = value.get("total").and_then(|t| t.as_u64()).unwrap_or(0);
+                let circulating_supply = value
+                    .get("circulating")
+                    .and_then(|c| c.as_u64())
+                    .unwrap_or(0);
+                let non_circulating_supply = value.get("nonCirculating").and_then(Value::as_u64).unwrap_or(0);
+
+                // Calculate the percentage of circulating supply
+                let circulating_percentage = if total_supply > 0 {
+                    (circulating_supply as f64 / total_supply as f64) * 100.0
+                } else {
+                    0.0
+                };
+
+                rows.extend(vec![
+                    ResultRow::new(vec![
+                        Line::from("Circulating Supply:").bold(),
+                        Line::from(
+                            format!(
+                                "{} / {}",
+                                format_stake(circulating_supply, self.currency_unit),
+                                format_stake(total_supply, self.currency_unit)
+                            )
+                            .set_style(self.theme.success),
+                        ),
+                    ]),
+                    ResultRow::new(vec![
+                        Line::from("Non-Circulating Supply:").bold(),
+                        Line::from(format_stake(non_circulating_supply, self.currency_unit).set_style(self.theme.value)),
+                        Line::from(
+                            match (non_circulating_accounts(supply_info).len(), dashboard.show_non_circulating) {
+                                (0, _) => String::new(),
+                                (count, false) => format!("in {} accounts, 'c' lists them", group_digits(count as u64)),
+                                (count, true) => format!("in {} accounts, listed below", group_digits(count as u64)),
+                            }
+                            .set_style(self.theme.title),
+                        ),
+                    ]),
+                    ResultRow::new(vec![
+                        Line::from("Circulating Percentage:").bold(),
+                        Line::from(
+                            format!("{:.1}% is circulating", circulating_percentage).set_style(self.theme.success),
+                        ),
+                    ]),
+                ]);
+            }
+        }
+
+        if let Some(transaction_info) = dashboard.data.transaction_info {
+            rows.push(ResultRow::new(vec![
+                Line::from("Transaction count:").bold(),
+                Line::from(self.format_longnumber(transaction_info).set_style(self.theme.value)),
+            ]));
+        }
+
+        if let Some(samples) = &dashboard.data.performance_samples {
+            // Latest TPS with a sparkline of the recent samples, oldest to newest
+            let tps = samples.first().map_or(0.0, |sample| sample.tps());
+            inline_widgets.push((
+                rows.len(),
+                InlineWidget::Sparkline(
+                    samples.iter().rev().map(|sample| sample.tps().round() as u64).collect(),
+                ),
+            ));
+            rows.push(ResultRow::new(vec![
+                Line::from("TPS:").bold(),
+                Line::from(format!("{} tx/s", self.format_longnumber(tps.round() as u64)).set_style(self.theme.value)),
+            ]));
+        }
+
+        if let Some(epoch_info) = &dashboard.data.epoch_info {
+            rows.push(ResultRow::new(vec![
+                Line::from("Epoch:").bold(),
+                Line::from(self.format_longnumber(epoch_info.epoch).set_style(self.theme.value)),
+            ]));
+
+            inline_widgets.push((
+                rows.len(),
+                InlineWidget::Gauge {
+                    ratio: epoch_info.progress(),
+                    label: format!("{:.1}%", epoch_info.progress() * 100.0),
+                },
+            ));
+            rows.push(ResultRow::new(vec![
+                Line::from("Epoch Progress (slots):").bold(),
+                Line::from(
+                    format!(
+                        "{} / {}",
+                        self.format_longnumber(epoch_info.slot_index),
+                        self.format_longnumber(epoch_info.slots_in_epoch)
+                    )
+                    .set_style(self.theme.value),
+                ),
+            ]));
+
+            // Estimate from the measured slot time rather than assuming 400ms
+            let time_remaining = self
+                .dashboard_screen()
+                .data
+                .average_slot_time()
+                .map_or("unknown".to_string(), |slot_time| {
+                    format!(
+                        "~{}",
+                        format_duration((epoch_info.slots_remaining() as f64 * slot_time) as u64)
+                    )
+                });
+            rows.push(ResultRow::new(vec![
+                Line::from("Epoch Ends In:").bold(),
+                Line::from(time_remaining.set_style(self.theme.value)),
+            ]));
+        }
+
+        if let Some(fees) = &dashboard.data.fees {
+            rows.push(ResultRow::new(vec![
+                Line::from("Base Fee:").bold(),
+                Line::from(
+                    format!("{} per signature", format_lamports(fees.lamports_per_signature, self.currency_unit))
+                        .set_style(self.theme.value),
+                ),
+            ]));
+            if let Some(priority) = &fees.priority {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Priority Fees:").bold(),
+                    Line::from(
+                        format!(
+                            "min {}, median {}, p75 {}, max {}",
+                            group_digits(priority.min),
+                            group_digits(priority.median),
+                            group_digits(priority.p75),
+                            group_digits(priority.max)
+                        )
+                        .set_style(self.theme.value),
+                    ),
+                    Line::from(
+                        format!("micro-lamports per CU over {} slots", priority.slots).set_style(self.theme.title),
+                    ),
+                ]));
+            }
+        }
+
+        if let Some(inflation) = &dashboard.data.inflation {
+            rows.push(ResultRow::new(vec![
+                Line::from("Inflation:").bold(),
+                Line::from(format!("{:.2}% total", inflation.total * 100.0).set_style(self.theme.value)),
+                Line::from(
+                    format!(
+                        "{:.2}% validators, {:.2}% foundation in epoch {}",
+                        inflation.validator * 100.0,
+                        inflation.foundation * 100.0,
+                        inflation.epoch
+                    )
+                    .set_style(self.theme.title),
+                ),
+            ]));
+            if let Some(schedule) = &inflation.schedule {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Inflation Schedule:").bold(),
+                    Line::from(
+                        format!(
+                            "{:.2}% initial, {:.2}% terminal, tapering {:.0}% a year",
+                            schedule.initial * 100.0,
+                            schedule.terminal * 100.0,
+                            schedule.taper * 100.0
+                        )
+                        .set_style(self.theme.value),
+                    ),
+                ]));
+            }
+        }
+
+        let mut table = ResultTable {
+            inline_widgets,
+            ..ResultTable::from_rows(vec![])
+        };
+        // The accounts of the non-circulating supply, each opened with Enter
+        let accounts = dashboard.data.supply_info.as_ref().map_or(vec![], non_circulating_accounts);
+        if dashboard.show_non_circulating && !accounts.is_empty() {
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from("Non-Circulating Accounts").bold()]));
+            table.address_start = Some(rows.len());
+            for (index, address) in accounts.iter().enumerate() {
+                rows.push(ResultRow::new(vec![
+                    Line::from(format!("{}.", index + 1).set_style(self.theme.title)),
+                    Line::from(address.to_string().set_style(self.theme.value)),
+                    Line::from(labels::lookup(address).unwrap_or_default().to_string().set_style(self.theme.title)),
+                ]));
+            }
+            table.addresses = accounts;
+        }
+        table.rows = rows;
+        table
+    }
+
+    // Recent blocks, newest first
+    fn blocks_rows<'a>(&'a self, screen: &'a BlocksScreen) -> Vec<ResultRow<'a>> {
+        let blocks = match &screen.blocks {
+            TabData::Loaded(blocks) => blocks,
+            TabData::Loading => return vec![self.tab_loading_row("blocks")],
+            TabData::NotLoaded => return vec![],
+        };
+
+        let mut rows = vec![ResultRow::new(vec![
+            Line::from("Blockhash").bold(),
+            Line::from("Slot").bold(),
+            Line::from("Timestamp").bold(),
+            Line::from("Transactions").bold(),
+        ])];
+
+        for block in blocks {
+            rows.push(ResultRow::new(vec![
+                Line::from(block.blockhash.clone().set_style(self.theme.value)),
+                Line::from(self.format_longnumber(block.slot).set_style(self.theme.title)),
+                Line::from(
+                    self.format_timestamp(block.block_time).set_style(self.theme.value),
+                ),
+                Line::from(block.transaction_count.to_string().set_style(self.theme.success)),
+            ]));
+        }
+
+        rows
+    }
+
+    // Vote accounts in the chosen order, each with its share of the total stake and
+    // the leader slots its identity skipped this epoch
+    fn validators_rows<'a>(&'a self, screen: &'a ValidatorsScreen) -> ResultTable<'a> {
+        let validators = match &screen.validators {
+            TabData::Loaded(validators) => validators,
+            TabData::Loading => return ResultTable::from_rows(vec![self.tab_loading_row("validators")]),
+            TabData::NotLoaded => return ResultTable::from_rows(vec![]),
+        };
+
+        let mut table = ResultTable::from_rows(vec![]);
+        table.column_widths = Some(vec![
+            Constraint::Min(16),
+            Constraint::Min(16),
+            Constraint::Length(22),
+            Constraint::Length(8),
+            Constraint::Length(11),
+            Constraint::Length(14),
+            Constraint::Length(13),
+            Constraint::Length(11),
+            Constraint::Length(11),
+        ]);
+        let rows = &mut table.rows;
+
+        let total_stake = validators.iter().map(|validator| validator.activated_stake as u128).sum::<u128>();
+        let delinquent = validators.iter().filter(|validator| validator.delinquent).count();
+        rows.push(ResultRow::new(vec![
+            Line::from("Vote Accounts:").bold(),
+            Line::from(format!(
+                "{} current, {} delinquent",
+                group_digits((validators.len() - delinquent) as u64),
+                group_digits(delinquent as u64)
+            ))
+            .set_style(self.theme.value),
+            Line::from(format_stake(total_stake.min(u64::MAX as u128) as u64, self.currency_unit))
+                .set_style(self.theme.success),
+        ]));
+        let production = match &screen.block_production {
+            TabData::Loaded(production) => Some(production),
+            _ => None,
+        };
+        rows.push(match production {
+            Some(production) => {
+                let (slots, skipped) = production.total();
+                ResultRow::new(vec![
+                    Line::from("Skip Rate:").bold(),
+                    Line::from(format_ratio(skipped.into(), slots.into()).set_style(self.theme.value)),
+                    Line::from(
+                        format!(
+                            "{} of {} leader slots skipped, slots {} to {}",
+                            group_digits(skipped),
+                            group_digits(slots),
+                            group_digits(production.first_slot),
+                            group_digits(production.last_slot)
+                        )
+                        .set_style(self.theme.title),
+                    ),
+                ])
+            }
+            None => ResultRow::new(vec![
+                Line::from("Skip Rate:").bold(),
+                Line::from(
+                    if matches!(screen.block_production, TabData::Loading) { "Fetching…" } else { "N/A" }
+                        .set_style(self.theme.title),
+                ),
+            ]),
+        });
+        let filter = match screen.filter {
+            ValidatorFilter::All => "all",
+            ValidatorFilter::Current => "current only",
+            ValidatorFilter::Delinquent => "delinquent only",
+        };
+        rows.push(ResultRow::new(vec![
+            Line::from("Showing:").bold(),
+            Line::from(filter.set_style(self.theme.value)),
+            Line::from("s: sort | f: filter | Enter: open".set_style(self.theme.title)),
+        ]));
+
+        let (stake, commission, last_vote, skip_rate) = match screen.sort {
+            ValidatorSort::Stake => ("Stake ↓", "Commission", "Last Vote", "Skip Rate"),
+            ValidatorSort::Commission => ("Stake", "Commission ↑", "Last Vote", "Skip Rate"),
+            ValidatorSort::LastVote => ("Stake", "Commission", "Last Vote ↓", "Skip Rate"),
+            ValidatorSort::SkipRate => ("Stake", "Commission", "Last Vote", "Skip Rate ↓"),
+        };
+        rows.push(ResultRow::new(vec![
+            Line::from("Vote Account").bold(),
+            Line::from("Identity").bold(),
+            Line::from(stake).bold(),
+            Line::from("Share").bold(),
+            Line::from(commission).bold(),
+            Line::from(last_vote).bold(),
+            Line::from("Leader Slots").bold(),
+            Line::from(skip_rate).bold(),
+            Line::from("Status").bold(),
+        ]));
+
+        let mut shown: Vec<&VoteAccountSummary> =
+            validators.iter().filter(|validator| screen.filter.matches(validator)).collect();
+        screen.sort.sort(&mut shown, production);
+
+        table.address_start = Some(rows.len());
+        for validator in shown {
+            let style = if validator.delinquent { self.theme.error } else { self.theme.value };
+            // Of the stake of all vote accounts, current and delinquent
+            let share = format_ratio(validator.activated_stake.into(), total_stake);
+            let counts = production.and_then(|production| production.by_identity.get(&validator.node_pubkey));
+            let skip_style = if production.is_some_and(|production| production.skips_badly(&validator.node_pubkey)) {
+                self.theme.error
+            } else {
+                self.theme.title
+            };
+            rows.push(ResultRow::new(vec![
+                Line::from(truncate(&validator.vote_pubkey, 44).set_style(style)),
+                Line::from(labels::lookup(&validator.node_pubkey).map_or_else(
+                    || truncate(&validator.node_pubkey, 44),
+                    str::to_string,
+                ))
+                .set_style(style),
+                Line::from(format_stake(validator.activated_stake, self.currency_unit).set_style(self.theme.success)),
+                Line::from(share.set_style(self.theme.title)),
+                Line::from(format!("{}%", validator.commission).set_style(self.theme.title)),
+                Line::from(self.format_longnumber(validator.last_vote).set_style(self.theme.title)),
+                Line::from(
+                    counts
+                        .map_or("–".to_string(), |(slots, produced)| format!("{}/{}", produced, slots))
+                        .set_style(self.theme.title),
+                ),
+                Line::from(
+                    counts
+                        .map_or("–".to_string(), |(slots, produced)| {
+                            format_ratio(slots.saturating_sub(*produced).into(), (*slots).into())
+                        })
+                        .set_style(skip_style),
+                ),
+                if validator.delinquent {
+                    Line::from("Delinquent".set_style(self.theme.error)).bold()
+                } else {
+                    Line::from("Current".set_style(self.theme.success))
+                },
+            ]));
+            table.addresses.push(&validator.vote_pubkey);
+        }
+
+        table
+    }
+
+    // A pane with nothing searched: how to search, or that the query typed hasn't been yet
+    fn idle_rows(&self, pane: &Pane) -> ResultTable<'_> {
+        let row = if pane.query.is_empty() {
+            ResultRow::new(vec![
+                Line::from("Press 'e' to search for a slot, account or transaction".set_style(self.theme.title)),
+            ])
+        } else {
+            let hint = match self.input.mode {
+                InputMode::Editing => "Not searched yet (press Enter to search)",
+                InputMode::Normal => "Not searched yet (press 'r' to search)",
+            };
+            ResultRow::new(vec![Line::from("Status:").bold(), Line::from(hint.set_style(self.theme.value))])
+        };
+        ResultTable::from_rows(vec![row])
+    }
+
+    // Why the last search of a pane failed and how to go on
+    fn failed_rows(&self, pane: &Pane, query: &str, error: &AppError) -> ResultTable<'_> {
+        let mut rows = vec![];
+        if matches!(error, AppError::NotFound(_)) && parse_slot(query).is_none() {
+            rows.push(ResultRow::new(vec![
+                Line::from("Status:").bold(),
+                Line::from(
+                    format!(
+                        "Not found on {} (press 'a' to search all configured networks)",
+                        self.current_rpc_network.name()
+                    )
+                    .set_style(self.theme.error),
+                ),
+            ]));
+        } else {
+            rows.push(ResultRow::new(vec![
+                Line::from("Status:").bold(),
+                Line::from(format!("Failed: {}", error).set_style(self.theme.error)),
+            ]));
+            rows.push(ResultRow::new(vec![
+                Line::from(""),
+                Line::from("Press 'r' to retry or 'e' to edit the query"),
+            ]));
+        }
+        // The query may have been edited since without being searched
+        if query != pane.query {
+            rows.push(ResultRow::new(vec![Line::from("Query:").bold(), Line::from(truncate(query, 44))]));
+        }
+        rows.extend(self.network_search_rows(pane));
+        ResultTable::from_rows(rows)
+    }
+
+    // Where the other networks stand on a query the current one didn't find
+    fn network_search_rows(&self, pane: &Pane) -> Vec<ResultRow<'_>> {
+        let presence = match &pane.network_search {
+            TabData::NotLoaded => return Vec::new(),
+            TabData::Loading => {
+                return vec![ResultRow::new(vec![
+                    Line::from("Other Networks:").bold(),
+                    Line::from("Searching...".set_style(self.theme.value)),
+                ])]
+            }
+            TabData::Loaded(presence) => presence,
+        };
+        let mut rows = vec![ResultRow::new(vec![Line::from(" ")])];
+        rows.extend(presence.iter().map(|presence| {
+            let found = match &presence.found {
+                Ok(true) => "Found (press 'n' to switch networks)".set_style(self.theme.success),
+                Ok(false) => "Not found".set_style(self.theme.value),
+                Err(err) => format!("Failed: {}", err).set_style(self.theme.error),
+            };
+            ResultRow::new(vec![Line::from(format!("{}:", presence.network.name())).bold(), Line::from(found)])
+        }));
+        rows
+    }
+
+    // How the node keeps up: block height against the slot, its newest snapshots and
+    // the highest slot it has received
+    fn ledger_rows(&self, dashboard: &DashboardScreen) -> Vec<ResultRow<'_>> {
+        let slot = dashboard.data.slot_info;
+        let mut rows = vec![];
+        if let Some(height) = dashboard.data.block_height {
+            // Every skipped slot leaves the height one block behind the slot
+            let skipped = slot.map_or(String::new(), |slot| {
+                format!("{} slots skipped", group_digits(slot.saturating_sub(height)))
+            });
+            rows.push(ResultRow::new(vec![
+                Line::from("Block Height:").bold(),
+                Line::from(self.format_longnumber(height).set_style(self.theme.value)),
+                Line::from(skipped.set_style(self.theme.title)),
+            ]));
+        }
+        if let Some(snapshot) = dashboard.data.snapshot {
+            let slots = match snapshot.incremental {
+                Some(incremental) => {
+                    format!("full {}, incremental {}", group_digits(snapshot.full), group_digits(incremental))
+                }
+                None => format!("full {}", group_digits(snapshot.full)),
+            };
+            let lag = slot.map(|slot| slot.saturating_sub(snapshot.newest()));
+            let behind = match lag {
+                Some(lag) => format!("{} slots behind", group_digits(lag)),
+                None => String::new(),
+            };
+            let style = match lag {
+                Some(lag) if lag > SNAPSHOT_LAG_ALERT => self.theme.error,
+                _ => self.theme.title,
+            };
+            rows.push(ResultRow::new(vec![
+                Line::from("Snapshots:").bold(),
+                Line::from(slots.set_style(self.theme.value)),
+                Line::from(behind.set_style(style)),
+            ]));
+        }
+        if let Some(retransmit) = dashboard.data.max_retransmit_slot {
+            // Shreds arrive ahead of the slot the node has processed
+            let ahead = slot.map_or(String::new(), |slot| match retransmit.cmp(&slot) {
+                std::cmp::Ordering::Greater => format!("{} ahead of the slot", group_digits(retransmit - slot)),
+                std::cmp::Ordering::Less => format!("{} behind the slot", group_digits(slot - retransmit)),
+                std::cmp::Ordering::Equal => "at the slot".to_string(),
+            });
+            rows.push(ResultRow::new(vec![
+                Line::from("Max Retransmit Slot:").bold(),
+                Line::from(self.format_longnumber(retransmit).set_style(self.theme.value)),
+                Line::from(ahead.set_style(self.theme.title)),
+            ]));
+        }
+        rows
+    }
+
+    // SUCCESS only when the node says the transaction succeeded, FAILED when it recorded an error
+    fn transaction_status(&self, transaction: &Map<String, Value>) -> Line<'static> {
+        let meta = transaction.get("meta");
+        let status = if meta.and_then(|meta| meta.get("err")).is_some_and(|err| !err.is_null()) {
+            "FAILED".set_style(self.theme.error)
+        } else if meta.and_then(|meta| meta.get("status")).and_then(Value::as_str) == Some("Ok(())") {
+            "SUCCESS".set_style(self.theme.success)
+        } else {
+            "Unknown".set_style(self.theme.value)
+        };
+        Line::from(status)
+    }
+
+    // The transaction history of an address below its details; returns the row of the
+    // first transaction
+    fn push_history_rows<'a>(
+        &'a self,
+        pane: &'a Pane,
+        entries: &[HistoryEntry],
+        rows: &mut Vec<ResultRow<'a>>,
+        history_signatures: &mut Vec<&'a str>,
+    ) -> usize {
+        let visible = self.visible_history(pane);
+        rows.push(ResultRow::new(vec![Line::from(" ")]));
+
+        if self.search_screen().history_filter.is_active() {
+            rows.push(ResultRow::new(vec![
+                Line::from(vec![
+                    "Transaction History ".bold(),
+                    format!("(showing {} of {})", visible.len(), entries.len()).set_style(self.theme.value),
+                ]),
+                Line::from(self.search_screen().history_filter.describe().set_style(self.theme.value)),
+            ]));
+        } else {
+            rows.push(ResultRow::new(vec![Line::from("Transaction History").bold()]));
+        }
+        rows.push(ResultRow::new(vec![Line::from(" ")]));
+
+        // The sorted column carries an arrow
+        let (block_header, result_header) = match self.search_screen().history_sort {
+            HistorySort::Newest => ("Block ↓", "Result"),
+            HistorySort::Oldest => ("Block ↑", "Result"),
+            HistorySort::Failed => ("Block", "Result ↓ failed first"),
+        };
+        rows.push(ResultRow::new(vec![
+            Line::from("Transaction").bold(),
+            Line::from(block_header).bold(),
+            Line::from("Timestamp").bold(),
+            Line::from(result_header).bold(),
+        ]));
+
+        let start = rows.len();
+        for entry in visible {
+            let signature = entry.signature.as_str();
+            let block_time = match entry.block_time {
+                Some(time) if self.relative_times => self.format_time_ago(time),
+                time => self.format_timestamp(time),
+            };
+
+            // Transactions the watch just added stand out, then fade back
+            let signature_style = match pane.arrivals.get(signature).map(Instant::elapsed) {
+                Some(age) if age < NEW_ROW_HIGHLIGHT / 2 => self.theme.success.reversed(),
+                Some(age) if age < NEW_ROW_HIGHLIGHT => self.theme.success.bold(),
+                _ => self.theme.value,
+            };
+
+            history_signatures.push(signature);
+            rows.push(ResultRow::new(vec![
+                Line::from(
+                    labels::display(signature)
+                        .unwrap_or_else(|| truncate(signature, 24))
+                        .set_style(signature_style),
+                ),
+                Line::from(self.format_longnumber(entry.slot).set_style(self.theme.title)),
+                Line::from(block_time.set_style(self.theme.value)),
+                Line::from(vec![
+                    match entry.err {
+                        Some(_) => "Failed".set_style(self.theme.error),
+                        None => "Success".set_style(self.theme.success),
+                    },
+                    format!(" {}", entry.status_badge()).set_style(self.theme.title),
+                ]),
+            ]));
+        }
+
+        rows.push(ResultRow::new(vec![Line::from(match &pane.history_paging {
+            HistoryPaging::More => "Press 'L' to load more".set_style(self.theme.title),
+            HistoryPaging::Loading(_) => "Loading more…".set_style(self.theme.value),
+            HistoryPaging::End => "End of history".set_style(self.theme.title),
+            HistoryPaging::Capped => format!("Stopped at {} transactions", MAX_HISTORY_ROWS)
+                .set_style(self.theme.error),
+        })]));
+        start
+    }
+
+    // An address without an account: it holds no SOL and no data, but can still be sent
+    // funds, and a closed account keeps its history
+    fn missing_account_rows<'a>(&'a self, pane: &'a Pane, address: &str) -> Vec<ResultRow<'a>> {
+        let mut rows = vec![
+            ResultRow::new(vec![
+                Line::from("Type:").bold(),
+                Line::from("Account (not found)".set_style(self.theme.title)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Address:").bold(),
+                Line::from(address.to_string().set_style(self.theme.value)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Status:").bold(),
+                Line::from(
+                    format!(
+                        "Account not found on {} — it has no SOL and no data (it may still receive funds at this address)",
+                        self.current_rpc_network.name()
+                    )
+                    .set_style(self.theme.error),
+                ),
+            ]),
+        ];
+        match pane.address_sign.as_ref().map(Vec::len) {
+            Some(0) => rows.push(ResultRow::new(vec![
+                Line::from("History:").bold(),
+                Line::from("No transactions (press 'a' to search all configured networks)".set_style(self.theme.value)),
+            ])),
+            Some(_) => rows.push(ResultRow::new(vec![
+                Line::from("History:").bold(),
+                Line::from("The account was closed; its transactions are listed below".set_style(self.theme.value)),
+            ])),
+            None => rows.push(ResultRow::new(vec![
+                Line::from("History:").bold(),
+                Line::from("Could not be loaded".set_style(self.theme.error)),
+            ])),
+        }
+        rows.extend(self.network_search_rows(pane));
+        rows
+    }
+
+    // A transaction the node has a status for but can't return yet
+    fn pending_transaction_rows(&self, pane: &Pane, pending: &Value) -> Vec<ResultRow<'_>> {
+        let confirmations = match pending.get("confirmations").and_then(Value::as_u64) {
+            Some(confirmations) => confirmations.to_string(),
+            None => "Rooted".to_string(),
+        };
+        let result = match pending.get("err").filter(|err| !err.is_null()) {
+            Some(err) => {
+                format!("Failed: {}", instruction::describe_transaction_error(err)).set_style(self.theme.error)
+            }
+            None => "Success".set_style(self.theme.success),
+        };
+        vec![
+            ResultRow::new(vec![
+                Line::from("Type:").bold(),
+                Line::from("Transaction (status only)".set_style(self.theme.title)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Signature:").bold(),
+                Line::from(pane.query.clone().set_style(self.theme.value)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Slot:").bold(),
+                Line::from(
+                    self.format_longnumber(pending.get("slot").and_then(Value::as_u64).unwrap_or(0))
+                        .set_style(self.theme.value),
+                ),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Confirmations:").bold(),
+                Line::from(confirmations.set_style(self.theme.value)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Confirmation Status:").bold(),
+                Line::from(
+                    pending
+                        .get("confirmationStatus")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown")
+                        .to_string()
+                        .set_style(self.theme.value),
+                ),
+            ]),
+            ResultRow::new(vec![Line::from("Result:").bold(), Line::from(result)]),
+            ResultRow::new(vec![Line::from(" ")]),
+            ResultRow::new(vec![Line::from(
+                "Full details not yet available — press r to retry".set_style(self.theme.title),
+            )]),
+        ]
+    }
+
+    // Whether the account holds enough lamports for its size to be exempt from rent
+    fn rent_row(&self, account: &Map<String, Value>) -> ResultRow<'_> {
+        let lamports = account.get("lamports").and_then(Value::as_u64).unwrap_or(0);
+        let Some(minimum) = account.get("rentExemptMinimum").and_then(Value::as_u64) else {
+            return ResultRow::new(vec![Line::from("Rent:").bold(), Line::from("Unknown".set_style(self.theme.title))]);
+        };
+        let status = if lamports >= minimum {
+            format!(
+                "exempt (needs {}, has {})",
+                format_lamports_short(minimum, self.currency_unit),
+                format_lamports_short(lamports, self.currency_unit)
+            )
+            .set_style(self.theme.success)
+        } else {
+            format!("NOT rent-exempt, short by {}", format_lamports_short(minimum - lamports, self.currency_unit))
+                .set_style(self.theme.error)
+        };
+        ResultRow::new(vec![Line::from("Rent:").bold(), Line::from(status)])
+    }
+
+    // Inflation rewards of a stake or vote account per epoch, once loaded with 'R'
+    fn inflation_reward_rows(&self, pane: &Pane) -> Vec<ResultRow<'_>> {
+        let mut rows = vec![ResultRow::new(vec![Line::from(" ")])];
+        let rewards = match &pane.inflation_rewards {
+            TabData::Loaded(rewards) => rewards,
+            TabData::Loading => {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Inflation Rewards").bold(),
+                    Line::from("Fetching…".set_style(self.theme.title)),
+                ]));
+                return rows;
+            }
+            TabData::NotLoaded => {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Inflation Rewards").bold(),
+                    Line::from(format!("Press 'R' for the last {} epochs", REWARD_EPOCHS).set_style(self.theme.title)),
+                ]));
+                return rows;
+            }
+        };
+        rows.push(ResultRow::new(vec![Line::from("Inflation Rewards").bold()]));
+        rows.push(ResultRow::new(vec![
+            Line::from("Epoch").bold(),
+            Line::from("Reward").bold(),
+            Line::from("Post Balance").bold(),
+            Line::from("Commission").bold(),
+        ]));
+        for EpochReward { epoch, reward } in rewards {
+            let epoch = Line::from(epoch.to_string().set_style(self.theme.value));
+            rows.push(match reward {
+                Some(reward) => ResultRow::new(vec![
+                    epoch,
+                    Line::from(format_lamports(reward.amount, self.currency_unit).set_style(self.theme.success)),
+                    Line::from(format_lamports(reward.post_balance, self.currency_unit).set_style(self.theme.value)),
+                    Line::from(
+                        reward
+                            .commission
+                            .map_or("N/A".to_string(), |commission| format!("{}%", commission))
+                            .set_style(self.theme.title),
+                    ),
+                ]),
+                None => ResultRow::new(vec![epoch, Line::from("no reward".set_style(self.theme.title))]),
+            });
+        }
+        if rewards.is_empty() {
+            rows.push(ResultRow::new(vec![Line::from("No completed epochs yet".set_style(self.theme.title))]));
+        }
+        rows
+    }
+
+    // Who was scheduled to produce a block, once looked up with 'P'
+    fn leader_row(&self, leader: Option<&Value>, looking_up: bool, skipped: bool) -> ResultRow<'_> {
+        let label = Line::from(if skipped { "Scheduled Leader:" } else { "Leader:" }).bold();
+        let Some(leader) = leader else {
+            let hint = if looking_up { "Looking up…".to_string() } else { "Press 'P' to look it up".to_string() };
+            return ResultRow::new(vec![label, Line::from(hint.set_style(self.theme.title))]);
+        };
+        let identity = match leader.get("identity").and_then(Value::as_str) {
+            Some(identity) => Line::from(identity.to_string().set_style(self.theme.value)),
+            None => Line::from("Unknown, the node has no schedule for this epoch".set_style(self.theme.error)),
+        };
+        let position = format!(
+            "{}epoch {}, slot {} of the epoch",
+            leader
+                .get("identity")
+                .and_then(Value::as_str)
+                .and_then(labels::lookup)
+                .map_or(String::new(), |name| format!("{}, ", name)),
+            leader.get("epoch").and_then(Value::as_u64).unwrap_or(0),
+            self.format_longnumber(leader.get("slotIndex").and_then(Value::as_u64).unwrap_or(0)),
+        );
+        ResultRow::new(vec![label, identity, Line::from(position.set_style(self.theme.title))])
+    }
+
+    fn tab_loading_row(&self, label: &str) -> ResultRow<'_> {
+        ResultRow::new(vec![Line::from(format!("Fetching {}…", label).set_style(self.theme.value))])
+    }
+
+    // The largest holders of the mint in a pane, ranked, each with its share of the supply
+    fn holders_rows<'a>(&'a self, pane: &'a Pane, mint: &Value) -> ResultTable<'a> {
+        let mut table = ResultTable::from_rows(vec![]);
+        table.column_widths = Some(vec![
+            Constraint::Length(24),
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Percentage(20),
+            Constraint::Length(8),
+        ]);
+        let rows = &mut table.rows;
+        let name = labels::lookup(&pane.query).map_or_else(|| truncate(&pane.query, 40), str::to_string);
+        rows.push(ResultRow::new(vec![
+            Line::from(format!("Largest Holders of {}", name)).bold(),
+            Line::from("Enter: open the owner | h: back to the mint".set_style(self.theme.title)),
+        ]));
+        let supply = mint.get("supply").and_then(Value::as_u64).unwrap_or(0);
+        let decimals = mint.get("decimals").and_then(Value::as_u64).unwrap_or(0) as u8;
+        rows.push(ResultRow::new(vec![
+            Line::from("Supply:").bold(),
+            Line::from(format_token_amount(u128::from(supply), decimals).set_style(self.theme.value)),
+        ]));
+        rows.push(ResultRow::new(vec![Line::from(" ")]));
+
+        let holders = match &pane.largest_holders {
+            TabData::Loaded(holders) if !holders.is_empty() => holders,
+            state => {
+                let status = match state {
+                    TabData::Loaded(_) => "None".set_style(self.theme.title),
+                    _ => "Loading…".set_style(self.theme.value),
+                };
+                rows.push(ResultRow::new(vec![Line::from(status)]));
+                return table;
+            }
+        };
+        rows.push(ResultRow::new(vec![
+            Line::from("#").bold(),
+            Line::from("Owner").bold(),
+            Line::from("Token Account").bold(),
+            Line::from("Amount").bold(),
+            Line::from("Share").bold(),
+        ]));
+        let amounts: Vec<u128> = holders.iter().map(|holder| holder.amount).collect();
+        let shares = supply_shares(&amounts, u128::from(supply));
+        let share = |index: usize| {
+            shares
+                .as_ref()
+                .map_or("N/A".to_string(), |shares| format!("{}.{:02}%", shares[index] / 100, shares[index] % 100))
+        };
+        table.address_start = Some(rows.len());
+        for (rank, holder) in holders.iter().enumerate() {
+            table.addresses.push(holder.owner.as_deref().unwrap_or(&holder.account));
+            rows.push(ResultRow::new(vec![
+                Line::from(format!("{}", rank + 1).set_style(self.theme.title)),
+                Line::from(
+                    holder
+                        .owner
+                        .as_deref()
+                        .map_or("Unknown".to_string(), |owner| labels::display(owner).unwrap_or_else(|| owner.to_string()))
+                        .set_style(self.theme.success),
+                ),
+                Line::from(truncate(&holder.account, 12)),
+                Line::from(format_token_amount(holder.amount, holder.decimals).set_style(self.theme.value)),
+                Line::from(share(rank)),
+            ]));
+        }
+        // The supply outside the largest accounts
+        let held: u128 = amounts.iter().sum();
+        if u128::from(supply) > held {
+            rows.push(ResultRow::new(vec![
+                Line::from(""),
+                Line::from("Everyone else".set_style(self.theme.title)),
+                Line::from(""),
+                Line::from(format_token_amount(u128::from(supply) - held, decimals).set_style(self.theme.value)),
+                Line::from(share(holders.len())),
+            ]));
+        }
+        table
+    }
+
+    // The screen of the tab on show
+    pub fn screen(&self) -> &Screen {
+        &self.screens[self.tab.index()]
+    }
+
+    // The screens of each tab, whether or not it is on show
+    pub fn dashboard_screen(&self) -> &DashboardScreen {
+        let Screen::Dashboard(screen) = &self.screens[Tab::Dashboard.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    fn dashboard_screen_mut(&mut self) -> &mut DashboardScreen {
+        let Screen::Dashboard(screen) = &mut self.screens[Tab::Dashboard.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    pub fn search_screen(&self) -> &SearchScreen {
+        let Screen::Search(screen) = &self.screens[Tab::Search.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    fn search_screen_mut(&mut self) -> &mut SearchScreen {
+        let Screen::Search(screen) = &mut self.screens[Tab::Search.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    pub fn blocks_screen(&self) -> &BlocksScreen {
+        let Screen::Blocks(screen) = &self.screens[Tab::Blocks.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    fn blocks_screen_mut(&mut self) -> &mut BlocksScreen {
+        let Screen::Blocks(screen) = &mut self.screens[Tab::Blocks.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    pub fn validators_screen(&self) -> &ValidatorsScreen {
+        let Screen::Validators(screen) = &self.screens[Tab::Validators.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    fn validators_screen_mut(&mut self) -> &mut ValidatorsScreen {
+        let Screen::Validators(screen) = &mut self.screens[Tab::Validators.index()] else {
+            unreachable!("screens are kept in tab order");
+        };
+        screen
+    }
+
+    // How a pane shows what its query loaded
+    fn search_view<'a>(&self, pane: &'a Pane) -> SearchView<'a> {
+        let response = match (&pane.state, &pane.json_response) {
+            (QueryState::Loaded, Some(response)) if !pane.query.is_empty() => response,
+            (state, _) => return SearchView::Query(state),
+        };
+        let Some(response_obj) = response.as_object() else {
+            return SearchView::Unsupported;
+        };
+        if let Some(mint) = response_obj.get("mint").filter(|_| pane.holders_view) {
+            SearchView::TokenHolders(mint)
+        } else if response_obj.contains_key("lamports") {
+            SearchView::Account(response_obj)
+        } else if let Some(block) = response_obj.get("block") {
+            SearchView::Block(block)
+        } else if let Some(address) = response_obj.get("missingAccount").and_then(Value::as_str) {
+            SearchView::MissingAccount(address)
+        } else if let Some(pending) = response_obj.get("pending") {
+            SearchView::PendingTransaction(pending)
+        } else if response_obj.contains_key("slot") {
+            SearchView::Transaction(response_obj)
+        } else {
+            SearchView::Unsupported
+        }
+    }
+
+    // Query results shown on the Search tab, drawn by the view of what was loaded
+    fn search_rows<'a>(&'a self, pane: &'a Pane) -> ResultTable<'a> {
+        match self.search_view(pane) {
+            SearchView::Query(state) => self.query_rows(pane, state),
+            SearchView::Account(account) => self.account_view(pane, account),
+            SearchView::MissingAccount(address) => self.missing_account_view(pane, address),
+            SearchView::TokenHolders(mint) => self.holders_rows(pane, mint),
+            SearchView::Transaction(transaction) => self.transaction_view(pane, transaction),
+            SearchView::PendingTransaction(pending) => {
+                ResultTable::from_rows(self.pending_transaction_rows(pane, pending))
+            }
+            SearchView::Block(block) => self.block_view(pane, block),
+            SearchView::Unsupported => ResultTable::from_rows(vec![ResultRow::new(vec![
+                Line::from("Error:").bold(),
+                Line::from("Unsupported response type.".set_style(self.theme.error)),
+            ])]),
+        }
+    }
+
+    // The prompt, the query in flight or why it failed, before anything is loaded
+    fn query_rows<'a>(&'a self, pane: &'a Pane, state: &QueryState) -> ResultTable<'a> {
+        match state {
+            QueryState::Idle | QueryState::Loaded => self.idle_rows(pane),
+            // Show a spinner while the query is in flight
+            QueryState::Loading { query, started_at } => {
+                let elapsed = started_at.elapsed();
+                let frame = SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()];
+                let label = QueryKind::parse(query).map_or("query", |kind| kind.label());
+                ResultTable::from_rows(vec![ResultRow::new(vec![
+                    Line::from(format!("{} Fetching {}…", frame, label).set_style(self.theme.value)),
+                    Line::from(format!("{}s", elapsed.as_secs())),
+                ])])
+            }
+            QueryState::Failed { query, error } => self.failed_rows(pane, query, error),
+        }
+    }
+
+    // An account: balance, rent, owner, what its data decodes to and its history
+    fn account_view<'a>(&'a self, pane: &'a Pane, response_obj: &'a Map<String, Value>) -> ResultTable<'a> {
+        let mut rows = vec![];
+        let mut history_start = None;
+        let mut history_signatures = vec![];
+
+        rows.extend(vec![
+            ResultRow::new(vec![
+                Line::from("Type:").bold(),
+                Line::from("Account Info".set_style(self.theme.title)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Balance:").bold(),
+                Line::from(
+                    format_lamports(
+                        response_obj
+                            .get("lamports")
+                            .and_then(|l| l.as_u64())
+                            .unwrap_or(0),
+                        self.currency_unit,
+                    )
+                    .set_style(self.theme.value),
+                ),
+            ]),
+            self.rent_row(response_obj),
+            ResultRow::new(vec![
+                Line::from("Allocated Data Size:").bold(),
+                Line::from(
+                    format!(
+                        "{} byte(s)",
+                        response_obj
+                            .get("space")
+                            .and_then(|s| s.as_u64())
+                            .unwrap_or(0)
+                    )
+                    .set_style(self.theme.value),
+                ),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Assigned Program Id:").bold(),
+                Line::from(
+                    response_obj
+                        .get("owner")
+                        .and_then(|o| o.as_str())
+                        .map(|owner| {
+                            labels::display(owner).unwrap_or_else(|| owner.to_string())
+                        })
+                        .unwrap_or("N/A".to_string())
+                        .set_style(self.theme.success),
+                ),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Executable:").bold(),
+                Line::from(
+                    if response_obj
+                        .get("executable")
+                        .and_then(|e| e.as_bool())
+                        .unwrap_or(false)
+                    {
+                        "Yes".set_style(self.theme.success)
+                    } else {
+                        "No".set_style(self.theme.error)
+                    },
+                ),
+            ]),
+        ]);
+
+        // Decoded SPL Token mint or token account
+        let pubkey_field = |value: Option<&Value>, none: &str| -> Line<'_> {
+            match value.and_then(|key| key.as_str()) {
+                Some(key) => Line::from(
+                    labels::display(key).unwrap_or_else(|| key.to_string()).set_style(self.theme.success),
+                ),
+                None => Line::from(none.to_string().set_style(self.theme.title)),
+            }
+        };
+        if let Some(mint) = response_obj.get("mint") {
+            let decimals = mint.get("decimals").and_then(|d| d.as_u64()).unwrap_or(0) as u8;
+            let supply = mint.get("supply").and_then(|s| s.as_u64()).unwrap_or(0);
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            // A single indivisible token is an NFT
+            rows.push(ResultRow::new(vec![Line::from(if supply == 1 && decimals == 0 {
+                "Token Mint (NFT)"
+            } else {
+                "Token Mint"
+            })
+            .bold()]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Supply:").bold(),
+                Line::from(format_token_amount(supply.into(), decimals).set_style(self.theme.value)),
+            ]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Decimals:").bold(),
+                Line::from(decimals.to_string().set_style(self.theme.value)),
+            ]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Mint Authority:").bold(),
+                pubkey_field(mint.get("mintAuthority"), "None (fixed supply)"),
+            ]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Freeze Authority:").bold(),
+                pubkey_field(mint.get("freezeAuthority"), "None (freezing disabled)"),
+            ]));
+
+            // Metaplex metadata, and the off-chain JSON its URI points to
+            if let Some(metadata) = response_obj.get("metadata") {
+                let text = |name: &str| metadata.get(name).and_then(Value::as_str).unwrap_or_default().to_string();
+                rows.push(ResultRow::new(vec![Line::from(" ")]));
+                rows.push(ResultRow::new(vec![Line::from("Token Metadata").bold()]));
+                if let Some(err) = metadata.get("error").and_then(Value::as_str) {
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Error:").bold(),
+                        Line::from(format!("Undecodable ({})", err).set_style(self.theme.error)),
+                    ]));
+                } else {
+                    let seller_fee = metadata.get("sellerFeeBasisPoints").and_then(Value::as_u64).unwrap_or(0);
+                    for (label, value) in [
+                        ("Name:", text("name")),
+                        ("Symbol:", text("symbol")),
+                        ("URI:", text("uri")),
+                        ("Royalties:", format!("{:.2}% ({} bps)", seller_fee as f64 / 100.0, seller_fee)),
+                        (
+                            "Mutable:",
+                            if metadata.get("isMutable").and_then(Value::as_bool).unwrap_or(false) {
+                                "Yes".to_string()
+                            } else {
+                                "No".to_string()
+                            },
+                        ),
+                    ] {
+                        rows.push(ResultRow::new(vec![
+                            Line::from(label).bold(),
+                            Line::from(value.set_style(self.theme.value)),
+                        ]));
+                    }
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Update Authority:").bold(),
+                        pubkey_field(metadata.get("updateAuthority"), "N/A"),
+                    ]));
+                    let creators = metadata.get("creators").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+                    if !creators.is_empty() {
+                        rows.push(ResultRow::new(vec![
+                            Line::from("Creators").bold(),
+                            Line::from("Share").bold(),
+                            Line::from("Verified").bold(),
+                        ]));
+                    }
+                    for creator in creators {
+                        let verified = creator.get("verified").and_then(Value::as_bool).unwrap_or(false);
+                        rows.push(ResultRow::new(vec![
+                            Line::from(
+                                truncate(creator.get("address").and_then(Value::as_str).unwrap_or_default(), 40)
+                                    .set_style(self.theme.success),
+                            ),
+                            Line::from(format!("{}%", creator.get("share").and_then(Value::as_u64).unwrap_or(0))),
+                            if verified {
+                                Line::from("Yes".set_style(self.theme.success))
+                            } else {
+                                Line::from("No".set_style(self.theme.error))
+                            },
+                        ]));
+                    }
+                    match metadata.get("offChain").filter(|off_chain| !off_chain.is_null()) {
+                        Some(off_chain) => {
+                            rows.push(ResultRow::new(vec![
+                                Line::from("Image:").bold(),
+                                Line::from(
+                                    off_chain
+                                        .get("image")
+                                        .and_then(Value::as_str)
+                                        .unwrap_or("None")
+                                        .set_style(self.theme.value),
+                                ),
+                            ]));
+                            let attributes = off_chain
+                                .get("attributes")
+                                .and_then(Value::as_array)
+                                .map(Vec::as_slice)
+                                .unwrap_or_default();
+                            if !attributes.is_empty() {
+                                rows.push(ResultRow::new(vec![
+                                    Line::from("Attributes").bold(),
+                                    Line::from("Value").bold(),
+                                ]));
+                            }
+                            for attribute in attributes {
+                                let field = |name: &str| {
+                                    attribute.get(name).and_then(Value::as_str).unwrap_or_default().to_string()
+                                };
+                                rows.push(ResultRow::new(vec![
+                                    Line::from(field("trait_type").set_style(self.theme.title)),
+                                    Line::from(field("value").set_style(self.theme.value)),
+                                ]));
+                            }
+                        }
+                        None => rows.push(ResultRow::new(vec![
+                            Line::from("Off-chain JSON:").bold(),
+                            Line::from("Unavailable".set_style(self.theme.title)),
+                        ])),
+                    }
+                }
+            }
+        } else if let Some(token_account) = response_obj.get("tokenAccount") {
+            let decimals = token_account.get("decimals").and_then(|d| d.as_u64()).map(|d| d as u8);
+            let amount = |name: &str| {
+                let raw = token_account.get(name).and_then(|a| a.as_u64()).unwrap_or(0);
+                match decimals {
+                    Some(decimals) => format_token_amount(raw.into(), decimals),
+                    None => format!("{} (raw units)", group_digits(raw)),
+                }
+            };
+            let state = token_account.get("state").and_then(|s| s.as_str()).unwrap_or("Unknown");
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from("Token Account").bold()]));
+            rows.push(ResultRow::new(vec![Line::from("Mint:").bold(), pubkey_field(token_account.get("mint"), "N/A")]));
+            rows.push(ResultRow::new(vec![Line::from("Owner:").bold(), pubkey_field(token_account.get("owner"), "N/A")]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Amount:").bold(),
+                Line::from(amount("amount").set_style(self.theme.value)),
+            ]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Delegate:").bold(),
+                pubkey_field(token_account.get("delegate"), "None"),
+            ]));
+            if token_account.get("delegate").is_some_and(|d| !d.is_null()) {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Delegated Amount:").bold(),
+                    Line::from(amount("delegatedAmount").set_style(self.theme.value)),
+                ]));
+            }
+            rows.push(ResultRow::new(vec![
+                Line::from("State:").bold(),
+                Line::from(state.set_style(if state == "Frozen" { self.theme.error } else { self.theme.success })),
+            ]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Close Authority:").bold(),
+                pubkey_field(token_account.get("closeAuthority"), "None (owner)"),
+            ]));
+        }
+
+        // Stake account: its authorities, lockup and delegation
+        if let Some(stake) = response_obj.get("stake") {
+            let lamports = |value: Option<&Value>| {
+                Line::from(
+                    format_lamports(value.and_then(Value::as_u64).unwrap_or(0), self.currency_unit)
+                        .set_style(self.theme.value),
+                )
+            };
+            let epoch = |value: Option<&Value>, none: &str| {
+                Line::from(
+                    value
+                        .and_then(Value::as_u64)
+                        .map_or(none.to_string(), |epoch| epoch.to_string())
+                        .set_style(self.theme.value),
+                )
+            };
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from("Stake Account").bold()]));
+            rows.push(ResultRow::new(vec![
+                Line::from("State:").bold(),
+                Line::from(
+                    stake.get("state").and_then(Value::as_str).unwrap_or_default().set_style(self.theme.success),
+                ),
+            ]));
+            if stake.get("staker").is_some() {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Rent-Exempt Reserve:").bold(),
+                    lamports(stake.get("rentExemptReserve")),
+                ]));
+                rows.push(ResultRow::new(vec![
+                    Line::from("Staker:").bold(),
+                    pubkey_field(stake.get("staker"), "N/A"),
+                ]));
+                rows.push(ResultRow::new(vec![
+                    Line::from("Withdrawer:").bold(),
+                    pubkey_field(stake.get("withdrawer"), "N/A"),
+                ]));
+            }
+            if let Some(lockup) = stake.get("lockup") {
+                let timestamp = lockup.get("unixTimestamp").and_then(Value::as_i64).unwrap_or(0);
+                let lockup_epoch = lockup.get("epoch").and_then(Value::as_u64).unwrap_or(0);
+                let lockup_text = if timestamp == 0 && lockup_epoch == 0 {
+                    "None".to_string()
+                } else {
+                    let state = match lockup.get("inForce").and_then(Value::as_bool) {
+                        Some(true) => " (in force)",
+                        Some(false) => " (expired)",
+                        None => "",
+                    };
+                    format!("Until {} and epoch {}{}", self.format_timestamp(Some(timestamp)), lockup_epoch, state)
+                };
+                rows.push(ResultRow::new(vec![
+                    Line::from("Lockup:").bold(),
+                    Line::from(lockup_text.set_style(self.theme.value)),
+                ]));
+                if lockup.get("custodian").and_then(Value::as_str) != Some(&Pubkey::default().to_string()) {
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Lockup Custodian:").bold(),
+                        pubkey_field(lockup.get("custodian"), "None"),
+                    ]));
+                }
+            }
+            if let Some(delegation) = stake.get("delegation") {
+                rows.push(ResultRow::new(vec![
+                    Line::from("Delegated Vote Account:").bold(),
+                    pubkey_field(delegation.get("voter"), "N/A"),
+                ]));
+                rows.push(ResultRow::new(vec![Line::from("Stake:").bold(), lamports(delegation.get("stake"))]));
+                rows.push(ResultRow::new(vec![
+                    Line::from("Activation Epoch:").bold(),
+                    epoch(delegation.get("activationEpoch"), "Genesis"),
+                ]));
+                rows.push(ResultRow::new(vec![
+                    Line::from("Deactivation Epoch:").bold(),
+                    epoch(delegation.get("deactivationEpoch"), "Not deactivated"),
+                ]));
+                if let Some(status) = delegation.get("status").and_then(Value::as_str) {
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Status:").bold(),
+                        Line::from(
+                            format!(
+                                "{} in epoch {}",
+                                status,
+                                stake.get("epoch").and_then(Value::as_u64).unwrap_or(0)
+                            )
+                            .set_style(if status == "inactive" { self.theme.error } else { self.theme.success }),
+                        ),
+                    ]));
+                    for (label, field) in [
+                        ("Active Stake:", "active"),
+                        ("Activating Stake:", "activating"),
+                        ("Deactivating Stake:", "deactivating"),
+                    ] {
+                        rows.push(ResultRow::new(vec![Line::from(label).bold(), lamports(delegation.get(field))]));
+                    }
+                }
+            }
+        }
+
+        if pane.json_response.as_ref().is_some_and(earns_inflation_rewards) {
+            rows.extend(self.inflation_reward_rows(pane));
+        }
+
+        // Upgradeable program, or a ProgramData account queried directly
+        if let Some(program_data) = response_obj.get("programData") {
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from("Upgradeable Program").bold()]));
+            if program_data.get("address").is_some_and(|address| !address.is_null()) {
+                rows.push(ResultRow::new(vec![
+                    Line::from("ProgramData Address:").bold(),
+                    pubkey_field(program_data.get("address"), "N/A"),
+                ]));
+                // Matches the address above unless the account is unusual
+                rows.push(ResultRow::new(vec![
+                    Line::from("Derived ProgramData:").bold(),
+                    pubkey_field(program_data.get("derivedAddress"), "N/A"),
+                ]));
+            }
+            match program_data.get("lastDeploySlot").and_then(|slot| slot.as_u64()) {
+                Some(slot) => {
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Upgrade Authority:").bold(),
+                        match program_data.get("upgradeAuthority").and_then(|key| key.as_str()) {
+                            Some(_) => pubkey_field(program_data.get("upgradeAuthority"), ""),
+                            None => Line::from("immutable".set_style(self.theme.value)),
+                        },
+                    ]));
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Last Deployed Slot:").bold(),
+                        Line::from(self.format_longnumber(slot).set_style(self.theme.value)),
+                    ]));
+                    rows.push(ResultRow::new(vec![
+                        Line::from("Executable Data:").bold(),
+                        Line::from(
+                            format!(
+                                "{} bytes",
+                                group_digits(program_data.get("executableLen").and_then(|len| len.as_u64()).unwrap_or(0))
+                            )
+                            .set_style(self.theme.value),
+                        ),
+                    ]));
+                }
+                None => rows.push(ResultRow::new(vec![
+                    Line::from("ProgramData:").bold(),
+                    Line::from("Unavailable".set_style(self.theme.error)),
+                ])),
+            }
+        }
+
+        rows.push(ResultRow::new(vec![Line::from(" ")]));
+        let supply = response_obj.get("mint").and_then(|mint| mint.get("supply")).and_then(Value::as_u64);
+        if supply.is_some() {
+            rows.push(ResultRow::new(vec![
+                Line::from("Largest Holders").bold(),
+                Line::from(match &pane.largest_holders {
+                    TabData::Loaded(holders) => format!("{} loaded, press 'h' to rank them", holders.len()),
+                    _ => "Press 'h' to rank them".to_string(),
+                }
+                .set_style(self.theme.title)),
+            ]));
+        }
+        match &pane.token_holdings {
+            // A mint holds no tokens itself
+            _ if supply.is_some() => {}
+            TabData::NotLoaded => rows.push(ResultRow::new(vec![
+                Line::from("Token Holdings").bold(),
+                Line::from("Press 'T' to load".set_style(self.theme.title)),
+            ])),
+            TabData::Loading => rows.push(ResultRow::new(vec![
+                Line::from("Token Holdings").bold(),
+                Line::from("Loading…".set_style(self.theme.value)),
+            ])),
+            TabData::Loaded(holdings) if holdings.is_empty() => rows.push(ResultRow::new(vec![
+                Line::from("Token Holdings").bold(),
+                Line::from("None".set_style(self.theme.title)),
+            ])),
+            TabData::Loaded(holdings) => {
+                rows.push(ResultRow::new(vec![
+                    Line::from(format!("Token Holdings ({})", holdings.len())).bold(),
+                    Line::from("Amount").bold(),
+                    Line::from("Decimals").bold(),
+                    Line::from("State").bold(),
+                ]));
+                for holding in holdings.iter().take(MAX_TOKEN_HOLDING_ROWS) {
+                    rows.push(ResultRow::new(vec![
+                        Line::from(
+                            labels::display(&holding.mint)
+                                .unwrap_or_else(|| truncate(&holding.mint, 40))
+                                .set_style(self.theme.success),
+                        ),
+                        Line::from(format_token_amount(holding.amount, holding.decimals).set_style(self.theme.value)),
+                        Line::from(holding.decimals.to_string()),
+                        if holding.frozen {
+                            Line::from("Frozen".set_style(self.theme.error))
+                        } else {
+                            Line::from("Active".set_style(self.theme.success))
+                        },
+                    ]));
+                }
+                if holdings.len() > MAX_TOKEN_HOLDING_ROWS {
+                    rows.push(ResultRow::new(vec![Line::from(
+                        format!("+{} more", holdings.len() - MAX_TOKEN_HOLDING_ROWS).set_style(self.theme.title),
+                    )]));
+                }
+            }
+        }
+
+        if let Some(entries) = &pane.address_sign {
+            history_start = Some(self.push_history_rows(pane, entries, &mut rows, &mut history_signatures));
+        }
+
+        ResultTable {
+            history_start,
+            history_signatures,
+            ..ResultTable::from_rows(rows)
+        }
+    }
+
+    // An address without an account, and any transactions it still has
+    fn missing_account_view<'a>(&'a self, pane: &'a Pane, address: &str) -> ResultTable<'a> {
+        let mut rows = self.missing_account_rows(pane, address);
+        let mut history_start = None;
+        let mut history_signatures = vec![];
+        if let Some(entries) = pane.address_sign.as_ref().filter(|entries| !entries.is_empty()) {
+            history_start = Some(self.push_history_rows(pane, entries, &mut rows, &mut history_signatures));
+        }
+
+        ResultTable {
+            history_start,
+            history_signatures,
+            ..ResultTable::from_rows(rows)
+        }
+    }
+
+    // A block, or a skipped slot, with its transactions
+    fn block_view<'a>(&'a self, pane: &'a Pane, block: &'a Value) -> ResultTable<'a> {
+        let mut rows = vec![];
+        let mut history_start = None;
+        let mut history_signatures = vec![];
+
+        let slot = block.get("slot").and_then(|s| s.as_u64()).unwrap_or(0);
+        rows.push(ResultRow::new(vec![
+            Line::from("Type:").bold(),
+            Line::from("Block".set_style(self.theme.title)),
+        ]));
+        rows.push(ResultRow::new(vec![
+            Line::from("Slot:").bold(),
+            Line::from(self.format_longnumber(slot).set_style(self.theme.value)),
+        ]));
+        let skipped = block.get("skipped").and_then(Value::as_bool).unwrap_or(false);
+        rows.push(self.leader_row(block.get("leader"), pane.leader_lookup, skipped));
+        if block.get("skipped").and_then(|s| s.as_bool()).unwrap_or(false) {
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from(
+                format!("Slot {} was skipped or pruned", slot).set_style(self.theme.error),
+            )]));
+        } else {
+            let number = |key: &str| {
+                block
+                    .get(key)
+                    .and_then(|n| n.as_u64())
+                    .map_or("N/A".to_string(), |n| self.format_longnumber(n))
+            };
+            let block_time = block.get("blockTime").and_then(|t| t.as_i64());
+            let transactions = block
+                .get("transactions")
+                .and_then(|txs| txs.as_array())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let failed = transactions.iter().filter(|tx| !tx["err"].is_null()).count();
+            rows.extend(vec![
+                ResultRow::new(vec![
+                    Line::from("Blockhash:").bold(),
+                    Line::from(
+                        block
+                            .get("blockhash")
+                            .and_then(|h| h.as_str())
+                            .unwrap_or("N/A")
+                            .set_style(self.theme.value),
+                    ),
+                ]),
+                ResultRow::new(vec![
+                    Line::from("Parent Slot:").bold(),
+                    Line::from(number("parentSlot").set_style(self.theme.value)),
+                ]),
+                ResultRow::new(vec![
+                    Line::from("Block Height:").bold(),
+                    Line::from(number("blockHeight").set_style(self.theme.value)),
+                ]),
+                ResultRow::new(vec![
+                    Line::from("Block Time:").bold(),
+                    Line::from(
+                        self.format_timestamp(block_time).set_style(self.theme.value),
+                    ),
+                    Line::from(
+                        block_time
+                            .map_or(String::new(), |time| self.format_time_ago(time))
+                            .set_style(self.theme.title),
+                    ),
+                ]),
+                ResultRow::new(vec![
+                    Line::from("Total Fees:").bold(),
+                    Line::from(
+                        format_lamports(
+                            block.get("totalFees").and_then(|f| f.as_u64()).unwrap_or(0),
+                            self.currency_unit,
+                        )
+                        .set_style(self.theme.value),
+                    ),
+                ]),
+                ResultRow::new(vec![
+                    Line::from("Transactions:").bold(),
+                    Line::from(transactions.len().to_string().set_style(self.theme.value)),
+                    Line::from(format!("{} failed", failed).set_style(if failed > 0 {
+                        self.theme.error
+                    } else {
+                        self.theme.title
+                    })),
+                ]),
+            ]);
+
+            if let Some(rewards) = block.get("rewards").and_then(|r| r.as_array()).filter(|r| !r.is_empty()) {
+                rows.push(ResultRow::new(vec![Line::from(" ")]));
+                rows.push(ResultRow::new(vec![
+                    Line::from("Reward Recipient").bold(),
+                    Line::from("Type").bold(),
+                    Line::from("Amount").bold(),
+                ]));
+                for reward in rewards {
+                    let pubkey = reward.get("pubkey").and_then(|p| p.as_str()).unwrap_or("N/A");
+                    let lamports = reward.get("lamports").and_then(|l| l.as_i64()).unwrap_or(0);
+                    // Rewards are signed; a negative one is a rent collection
+                    let (pre, post) = if lamports < 0 {
+                        (lamports.unsigned_abs(), 0)
+                    } else {
+                        (0, lamports as u64)
+                    };
+                    rows.push(ResultRow::new(vec![
+                        Line::from(
+                            labels::display(pubkey)
+                                .unwrap_or_else(|| truncate(pubkey, 40))
+                                .set_style(self.theme.success),
+                        ),
+                        Line::from(reward.get("rewardType").and_then(|t| t.as_str()).unwrap_or("N/A")),
+                        Line::from(
+                            format_lamport_change(pre, post, self.currency_unit).set_style(if lamports < 0 {
+                                self.theme.error
+                            } else {
+                                self.theme.value
+                            }),
+                        ),
+                    ]));
+                }
+            }
+
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Transaction").bold(),
+                Line::from("Fee").bold(),
+                Line::from("Result").bold(),
+            ]));
+            history_start = Some(rows.len());
+            for tx in transactions {
+                let Some(signature) = tx.get("signature").and_then(|s| s.as_str()) else {
+                    continue;
+                };
+                history_signatures.push(signature);
+                rows.push(ResultRow::new(vec![
+                    Line::from(truncate(signature, 40).set_style(self.theme.value)),
+                    Line::from(format_lamports(
+                        tx.get("fee").and_then(|f| f.as_u64()).unwrap_or(0),
+                        self.currency_unit,
+                    )),
+                    if tx["err"].is_null() {
+                        Line::from("Success".set_style(self.theme.success))
+                    } else {
+                        Line::from("Failed".set_style(self.theme.error))
+                    },
+                ]));
+            }
+        }
+
+        ResultTable {
+            history_start,
+            history_signatures,
+            ..ResultTable::from_rows(rows)
+        }
+    }
+
+    // A transaction: its status, balance changes, instructions and logs
+    fn transaction_view<'a>(&'a self, pane: &'a Pane, response_obj: &'a Map<String, Value>) -> ResultTable<'a> {
+        let mut rows = vec![];
+        let mut instruction_start = None;
+        let mut instruction_count = 0;
+
+        rows.extend(vec![
+            ResultRow::new(vec![
+                Line::from("Type:").bold(),
+                Line::from("Transaction Info".set_style(self.theme.title)),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Slot:").bold(),
+                Line::from(
+                    response_obj
+                        .get("slot")
+                        .and_then(|s| s.as_u64())
+                        .map_or("N/A".to_string(), |slot| {
+                            self.format_longnumber(slot)
+                        })
+                        .set_style(self.theme.value),
+                ),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Block Time:").bold(),
+                Line::from(
+                    self.format_timestamp(response_obj.get("blockTime").and_then(|bt| bt.as_i64()))
+                        .set_style(self.theme.value),
+                ),
+                Line::from(
+                    response_obj
+                        .get("blockTime")
+                        .and_then(|bt| bt.as_i64())
+                        .map_or(String::new(), |time| self.format_time_ago(time))
+                        .set_style(self.theme.title),
+                ),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Fee:").bold(),
+                Line::from(
+                    response_obj
+                        .get("meta")
+                        .and_then(|meta| meta.get("fee"))
+                        .and_then(|f| f.as_u64())
+                        .map_or("N/A".to_string(), |fee| {
+                            format_lamports(fee, self.currency_unit)
+                        })
+                        .set_style(self.theme.value),
+                ),
+            ]),
+            ResultRow::new(vec![Line::from("Status:").bold(), self.transaction_status(response_obj)]),
+            ResultRow::new(vec![
+                Line::from("Version:").bold(),
+                Line::from(
+                    match response_obj.get("version") {
+                        Some(Value::Number(version)) => version.to_string(),
+                        _ => "legacy".to_string(),
+                    }
+                    .set_style(self.theme.value),
+                ),
+            ]),
+            ResultRow::new(vec![
+                Line::from("Signatures:").bold(),
+                Line::from(truncate(&pane.query, 25)).set_style(self.theme.error),
+            ]),
+        ]);
+
+        // What failed, and which instruction to look at
+        let err = response_obj.get("meta").and_then(|meta| meta.get("err")).filter(|err| !err.is_null());
+        let failed_instruction = err.and_then(instruction::failed_instruction);
+        if let Some(err) = err {
+            rows.push(ResultRow::new(vec![
+                Line::from("Error:").bold(),
+                Line::from(instruction::describe_transaction_error(err).set_style(self.theme.error)),
+            ]));
+        }
+        if let Some(index) = failed_instruction {
+            rows.push(ResultRow::new(vec![
+                Line::from("Failed Instruction:").bold(),
+                Line::from(format!("#{} (press 'F' to jump to it)", index).set_style(self.theme.error)),
+            ]));
+        }
+
+        if let Some(logs) = pane.transaction_logs() {
+            rows.push(ResultRow::new(vec![
+                Line::from("Logs:").bold(),
+                Line::from(
+                    format!("{} lines ('l')", logs.len()).set_style(self.theme.title),
+                ),
+            ]));
+        }
+
+        // Numbered instructions; Enter on one opens its details
+        let collapse_inner = !self.search_screen().show_inner_instructions;
+        if let Some((instructions, account_keys)) = pane.transaction_message(collapse_inner) {
+            let account_name = |account: &instruction::InstructionAccount| {
+                account.address(&account_keys).map_or_else(
+                    || account.name(&account_keys),
+                    |key| labels::display(key).unwrap_or_else(|| truncate(key, 8)),
+                )
+            };
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Instructions").bold(),
+                Line::from("Accounts").bold(),
+                Line::from("Data").bold(),
+                Line::from("Size").bold(),
+            ]));
+            instruction_start = Some(rows.len());
+            instruction_count = instructions.len();
+            for listed in &instructions {
+                let instruction = &listed.instruction;
+                // Inner instructions are indented by their invoking depth
+                let indent = match listed.depth {
+                    1 => String::new(),
+                    depth => format!("{}↳ ", "  ".repeat(depth - 2)),
+                };
+                let failed = failed_instruction.is_some_and(|index| listed.number == index.to_string());
+                let mut program = Line::from(vec![
+                    format!("{}#{} ", indent, listed.number)
+                        .set_style(if failed { self.theme.error } else { self.theme.title }),
+                    account_name(&instruction::InstructionAccount::Index(instruction.program_index))
+                        .set_style(self.theme.success),
+                ]);
+                if listed.inner_count > 0 && !self.search_screen().show_inner_instructions {
+                    program.push_span(format!(" +{} inner", listed.inner_count).set_style(self.theme.title));
+                }
+                // Decoded instructions read as a sentence across the other columns
+                let program_id = account_keys.get(instruction.program_index);
+                if let Some(phrases) = instruction.phrases(program_id, self.currency_unit) {
+                    rows.push(ResultRow::new(
+                        std::iter::once(program)
+                            .chain(phrases.into_iter().map(|phrase| Line::from(phrase.set_style(self.theme.value)))),
+                    ));
+                    continue;
+                }
+                rows.push(ResultRow::new(vec![
+                    program,
+                    Line::from(
+                        instruction
+                            .accounts
+                            .iter()
+                            .map(account_name)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                    Line::from(truncate(&instruction.data, 16).set_style(self.theme.value)),
+                    Line::from(format!("{} bytes", instruction.data_bytes().len())),
+                ]));
+            }
+            if response_obj.get("meta").is_some_and(instruction::inner_instructions_unrecorded) {
+                rows.push(ResultRow::new(vec![Line::from(
+                    "Inner instructions not recorded by this RPC node".set_style(self.theme.title),
+                )]));
+            }
+
+            // What the transaction asked of the compute budget and paid for it
+            let budget = ComputeBudget::from_instructions(
+                instructions
+                    .iter()
+                    .filter(|listed| listed.depth == 1)
+                    .map(|listed| (account_keys.get(listed.instruction.program_index), &listed.instruction)),
+            );
+            let meta = response_obj.get("meta");
+            let consumed = meta.and_then(|meta| meta.get("computeUnitsConsumed")).and_then(Value::as_u64);
+            let signatures = meta
+                .and_then(|meta| meta.get("signatures"))
+                .and_then(Value::as_array)
+                .map_or(1, Vec::len);
+            let units = |units: u64| self.format_longnumber(units);
+            let fee = |fee: Option<u64>| {
+                fee.map_or("overflow".to_string(), |fee| format_lamports(fee, self.currency_unit))
+            };
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from("Compute").bold()]));
+            for (label, value) in [
+                ("Units consumed", consumed.map_or("N/A".to_string(), units)),
+                (
+                    "Unit limit",
+                    format!(
+                        "{} ({})",
+                        units(budget.unit_limit),
+                        if budget.limit_requested { "requested" } else { "default" }
+                    ),
+                ),
+                (
+                    "Utilization",
+                    consumed
+                        .and_then(|consumed| budget.utilization(consumed))
+                        .map_or("N/A".to_string(), |percent| format!("{:.1}%", percent)),
+                ),
+                ("Unit price", format!("{} micro-lamports", units(budget.unit_price))),
+                (
+                    "Base fee",
+                    format!(
+                        "{} ({} × {})",
+                        fee(instruction::base_fee(signatures)),
+                        signatures,
+                        instruction::LAMPORTS_PER_SIGNATURE
+                    ),
+                ),
+                ("Priority fee", fee(budget.priority_fee())),
+            ] {
+                rows.push(ResultRow::new(vec![
+                    Line::from(format!("  {}", label).set_style(self.theme.title)),
+                    Line::from(value.set_style(self.theme.value)),
+                ]));
+            }
+        }
+
+        // Lamport balance changes; the fee payer (account 0) also paid the fee
+        let balances = |name: &str| -> Vec<u64> {
+            response_obj
+                .get("meta")
+                .and_then(|meta| meta.get(name))
+                .and_then(|balances| balances.as_array())
+                .map(|balances| balances.iter().filter_map(Value::as_u64).collect())
+                .unwrap_or_default()
+        };
+        let (pre_balances, post_balances) = (balances("preBalances"), balances("postBalances"));
+        if !pre_balances.is_empty() {
+            let account_keys = response_obj.get("meta").map(AccountKeys::from_meta).unwrap_or_default();
+            let fee = response_obj
+                .get("meta")
+                .and_then(|meta| meta.get("fee"))
+                .and_then(|fee| fee.as_u64())
+                .unwrap_or(0);
+
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Balance Changes").bold(),
+                Line::from("Before").bold(),
+                Line::from("After").bold(),
+                Line::from("Change").bold(),
+            ]));
+            let mut unchanged = 0;
+            for (index, key, pre, post) in balance_changes(&account_keys.keys, &pre_balances, &post_balances) {
+                if pre == post && !self.search_screen().show_unchanged_balances {
+                    unchanged += 1;
+                    continue;
+                }
+                let change_style = match post.cmp(&pre) {
+                    std::cmp::Ordering::Greater => self.theme.success,
+                    std::cmp::Ordering::Less => self.theme.error,
+                    std::cmp::Ordering::Equal => self.theme.title,
+                };
+                let name = key.map_or(format!("unknown[{}]", index), |key| {
+                    labels::display(key).unwrap_or_else(|| truncate(key, 40))
+                });
+                rows.push(ResultRow::new(vec![
+                    Line::from(name.set_style(self.theme.value)),
+                    Line::from(format_lamports(pre, self.currency_unit)),
+                    Line::from(format_lamports(post, self.currency_unit)),
+                    Line::from(format_lamport_change(pre, post, self.currency_unit).set_style(change_style)),
+                ]));
+                // Split the fee payer's change into the fee and what it actually moved
+                if index == 0 && fee > 0 {
+                    rows.push(ResultRow::new(vec![
+                        Line::from("  ↳ fee".set_style(self.theme.title)),
+                        Line::from(""),
+                        Line::from(""),
+                        Line::from(format_lamport_change(fee, 0, self.currency_unit).set_style(self.theme.error)),
+                    ]));
+                    rows.push(ResultRow::new(vec![
+                        Line::from("  ↳ transfers".set_style(self.theme.title)),
+                        Line::from(""),
+                        Line::from(""),
+                        Line::from(
+                            format_lamport_change(pre, post.saturating_add(fee), self.currency_unit)
+                                .set_style(self.theme.value),
+                        ),
+                    ]));
+                }
+            }
+            if unchanged > 0 {
+                rows.push(ResultRow::new(vec![Line::from(
+                    format!("{} unchanged hidden ('a' shows)", unchanged).set_style(self.theme.title),
+                )]));
+            }
+        }
+
+        // SPL token balance changes, joined by account index
+        let token_balances = |name: &str| -> Vec<TokenBalance> {
+            response_obj
+                .get("meta")
+                .and_then(|meta| meta.get(name))
+                .and_then(|balances| balances.as_array())
+                .map(|balances| balances.iter().filter_map(TokenBalance::from_value).collect())
+                .unwrap_or_default()
+        };
+        let token_changes =
+            token_balance_changes(&token_balances("preTokenBalances"), &token_balances("postTokenBalances"));
+        if !token_changes.is_empty() {
+            let account_keys = response_obj.get("meta").map(AccountKeys::from_meta).unwrap_or_default();
+
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![
+                Line::from("Token Changes (account / owner)").bold(),
+                Line::from("Mint").bold(),
+                Line::from("Before → After").bold(),
+                Line::from("Change").bold(),
+            ]));
+            for change in token_changes {
+                let account = account_keys
+                    .get(change.account_index)
+                    .map_or(format!("unknown[{}]", change.account_index), |key| truncate(key, 12));
+                let owner = change.owner.as_deref().map_or(String::new(), |owner| {
+                    format!(" / {}", labels::display(owner).unwrap_or_else(|| truncate(owner, 12)))
+                });
+                let change_style = match change.post.cmp(&change.pre) {
+                    std::cmp::Ordering::Greater => self.theme.success,
+                    std::cmp::Ordering::Less => self.theme.error,
+                    std::cmp::Ordering::Equal => self.theme.title,
+                };
+                rows.push(ResultRow::new(vec![
+                    Line::from(vec![account.set_style(self.theme.value), owner.set_style(self.theme.title)]),
+                    Line::from(labels::display(&change.mint).unwrap_or_else(|| truncate(&change.mint, 12))),
+                    Line::from(format!(
+                        "{} → {}",
+                        format_token_amount(change.pre, change.decimals),
+                        format_token_amount(change.post, change.decimals)
+                    )),
+                    Line::from(
+                        format_token_change(change.pre, change.post, change.decimals).set_style(change_style),
+                    ),
+                ]));
+            }
+        }
+
+        // Accounts referenced by the transaction, labelled where known; v0
+        // transactions follow the static keys with those loaded from lookup tables
+        if let Some(meta) = response_obj.get("meta").filter(|meta| meta.get("accountKeys").is_some()) {
+            let account_keys = AccountKeys::from_meta(meta);
+            // Owners are looked up after the transaction is shown
+            let key_accounts = meta.get("keyAccounts").and_then(Value::as_array);
+            rows.push(ResultRow::new(vec![Line::from(" ")]));
+            rows.push(ResultRow::new(vec![Line::from("Account Keys").bold()]));
+            for (index, key) in account_keys.keys.iter().enumerate() {
+                let owner = match key_accounts.and_then(|accounts| accounts.get(index)) {
+                    None => Line::from(""),
+                    Some(Value::Null) => Line::from("No account".set_style(self.theme.title)),
+                    Some(account) => {
+                        let owner = account.get("owner").and_then(Value::as_str).unwrap_or_default();
+                        let owner = labels::lookup(owner).map_or_else(|| truncate(owner, 20), str::to_string);
+                        let executable = account.get("executable").and_then(Value::as_bool).unwrap_or(false);
+                        Line::from(
+                            if executable { format!("Program of {}", owner) } else { format!("Owned by {}", owner) }
+                                .set_style(self.theme.value),
+                        )
+                    }
+                };
+                rows.push(ResultRow::new(vec![
+                    Line::from(
+                        labels::display(key)
+                            .unwrap_or_else(|| truncate(key, 40))
+                            .set_style(self.theme.success),
+                    ),
+                    Line::from(format!("#{}", index).set_style(self.theme.title)),
+                    Line::from(account_keys.loaded_as(index).unwrap_or_default().set_style(self.theme.value)),
+                    owner,
+                ]));
+            }
+            let lookups = meta
+                .get("addressTableLookups")
+                .and_then(|lookups| lookups.as_array())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            if !lookups.is_empty() {
+                rows.push(ResultRow::new(vec![Line::from(" ")]));
+                rows.push(ResultRow::new(vec![
+                    Line::from("Address Lookup Tables").bold(),
+                    Line::from("Writable").bold(),
+                    Line::from("Readonly").bold(),
+                ]));
+            }
+            // Each table index with the address it supplied
+            for lookup in account_keys.resolve_lookups(lookups) {
+                let addresses = |addresses: &[(u64, usize)]| {
+                    addresses
+                        .iter()
+                        .map(|(table_index, key_index)| {
+                            let key = account_keys
+                                .get(*key_index)
+                                .map_or(format!("unknown[{}]", key_index), |key| truncate(key, 8));
+                            format!("{} → {}", table_index, key)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                rows.push(ResultRow::new(vec![
+                    Line::from(truncate(lookup.table, 40).set_style(self.theme.success)),
+                    Line::from(addresses(&lookup.writable)),
+                    Line::from(addresses(&lookup.readonly)),
+                ]));
+            }
+        }
+
+        ResultTable {
+            instruction_start,
+            instruction_count,
+            ..ResultTable::from_rows(rows)
+        }
+    }
+
+    // Largest scroll offset that still fills the results area
+    fn max_scroll_offset(&self) -> usize {
+        self.result_rows(&self.pane).rows.len().saturating_sub(self.pane.results_height)
+    }
+
+    // The address of the selected holder or validator
+    fn selected_address(&self) -> Option<String> {
+        let selected = self.pane.selected_history?;
+        self.result_rows(&self.pane)
+            .addresses
+            .get(selected)
+            .map(|address| address.to_string())
+    }
+
+    fn selected_signature(&self) -> Option<String> {
+        let selected = self.pane.selected_history?;
+        self.result_rows(&self.pane)
+            .history_signatures
+            .get(selected)
+            .map(|signature| signature.to_string())
+    }
+
+    // Move the history selection and keep the selected row visible.
+    // Returns false when there was nothing to select so the caller can scroll instead.
+    fn move_selection(&mut self, down: bool) -> bool {
+        let Some((history_start, history_len)) = self.result_rows(&self.pane).selectable_rows() else {
+            return false;
+        };
+        if history_len == 0 {
+            return false;
+        }
+
+        self.pane.selected_history = match (self.pane.selected_history, down) {
+            (None, true) => Some(0),
+            (Some(selected), true) => Some((selected + 1).min(history_len - 1)),
+            (Some(selected), false) if selected > 0 => Some(selected - 1),
+            _ => {
+                self.pane.selected_history = None;
+                return false;
+            }
+        };
+
+        if let Some(selected) = self.pane.selected_history {
+            self.reveal_row(history_start + selected);
+        }
+        true
+    }
+
+    // Scroll just enough for a row of the results to be visible
+    fn reveal_row(&mut self, row: usize) {
+        let height = self.pane.results_height.max(1);
+        if row < self.pane.scroll_offset {
+            self.pane.scroll_offset = row;
+        } else if row >= self.pane.scroll_offset + height {
+            self.pane.scroll_offset = row + 1 - height;
+        }
+    }
+
+    // Select the instruction a failed transaction failed at in the instruction list
+    fn jump_to_failed_instruction(&mut self) {
+        // Only a transaction on screen has instructions to jump to
+        let failed = match (self.screen(), self.search_view(&self.pane)) {
+            (Screen::Search(_), SearchView::Transaction(transaction)) => {
+                transaction.get("meta").and_then(|meta| meta.get("err")).and_then(instruction::failed_instruction)
+            }
+            _ => None,
+        };
+        let Some(index) = failed else {
+            self.notify(StatusLevel::Info, "No failed instruction to jump to");
+            return;
+        };
+        let collapse_inner = !self.search_screen().show_inner_instructions;
+        let position = self.pane.transaction_message(collapse_inner).and_then(|(instructions, _)| {
+            instructions.iter().position(|listed| listed.number == index.to_string())
+        });
+        let (Some(position), Some(start)) = (position, self.result_rows(&self.pane).instruction_start) else {
+            self.notify(StatusLevel::Warning, format!("Instruction #{} is not in the instruction list", index));
+            return;
+        };
+        self.pane.selected_history = Some(position);
+        self.reveal_row(start + position);
+    }
+
+    fn scroll_up(&mut self, amount: usize) {
+        if let Some(tree) = self.pane.raw_view.as_mut().filter(|_| self.tab == Tab::Search) {
+            tree.move_cursor(-(amount as isize), self.pane.results_height);
+            return;
+        }
+        self.pane.scroll_offset = self.pane.scroll_offset.saturating_sub(amount);
+    }
+
+    fn scroll_down(&mut self, amount: usize) {
+        if let Some(tree) = self.pane.raw_view.as_mut().filter(|_| self.tab == Tab::Search) {
+            tree.move_cursor(amount as isize, self.pane.results_height);
+            return;
+        }
+        self.pane.scroll_offset = (self.pane.scroll_offset + amount).min(self.max_scroll_offset());
+    }
+}
+
+// A results pane drawn with the shared app settings (theme, units, search)
+struct PaneView<'a> {
+    app: &'a App,
+    pane: &'a Pane,
+    focused: bool,
+}
+
+impl Widget for PaneView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let PaneView { app, pane, focused } = self;
+        let mut block = Block::bordered().border_set(border::THICK);
+        // Only the focused pane takes keys, so dim the other one of a split
+        if !focused {
+            block = block.border_set(border::PLAIN).border_style(app.theme.border);
+        }
+
+        // Latency of the last request and the rolling average, and how many
+        // queries the cache answered
+        let mut rpc_stats = Vec::new();
+        if let Ok(latencies) = app.latencies.lock() {
+            if let (Some(last), Some(average)) = (latencies.last(), latencies.average()) {
+                rpc_stats.push(format!(
+                    "RPC: {} {}ms avg {}ms",
+                    rpc_host(app.get_current_rpc_url()),
+                    last.as_millis(),
+                    average.as_millis()
+                ));
+            }
+        }
+        if let Ok(cache) = app.query_cache.lock() {
+            if cache.hits + cache.misses > 0 {
+                rpc_stats.push(format!("cache {} hits {} misses", cache.hits, cache.misses));
+            }
+        }
+        if !rpc_stats.is_empty() {
+            block = block.title_top(Line::from(format!(" {} ", rpc_stats.join(" · "))).right_aligned());
+        }
+        match app.screen() {
+            Screen::Search(_) if !pane.query.is_empty() => {
+                block = block.title(format!(" {} ", pane.breadcrumbs()));
+            }
+            Screen::Dashboard(dashboard) => {
+                if let Some(last_refresh) = dashboard.last_refresh {
+                    block = block.title(format!(" updated {}s ago ", last_refresh.elapsed().as_secs()));
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(tree) = pane.raw_view.as_ref().filter(|_| app.tab == Tab::Search) {
+            let block = block.title_bottom(Line::from(" raw JSON · Enter: fold · J: table ").right_aligned());
+            let inner = block.inner(area);
+            block.render(area, buf);
+            JsonTreeView { tree, theme: &app.theme }.render(inner, buf);
+            return;
+        }
+
+        // Only render the rows that fit inside the block borders
+        let table = app.result_rows(pane);
+        let selectable_start = table.selectable_rows().map(|(start, _)| start);
+        let ResultTable {
+            rows,
+            inline_widgets,
+            column_widths,
+            ..
+        } = table;
+        let matches = if focused { app.search_matches(&rows) } else { Vec::new() };
+        let current_match = app.current_search_match(&matches);
+        let total_rows = rows.len();
+        let visible_rows = area.height.saturating_sub(2) as usize;
+        let max_offset = total_rows.saturating_sub(visible_rows);
+        let offset = pane.scroll_offset.min(max_offset);
+
+        // The label column gives up room in the narrower panes of a split
+        let label_width = (area.width / 3).min(40);
+        let widths = column_widths.unwrap_or_else(|| {
+            vec![
+                Constraint::Length(label_width),
+                Constraint::Percentage(20),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+            ]
+        });
+
+        // Highlight the selected history row or instruction when it is inside the window
+        let selected_row = selectable_start
+            .zip(pane.selected_history)
+            .and_then(|(start, selected)| (start + selected).checked_sub(offset))
+            .filter(|row| *row < visible_rows);
+        let mut table_state = TableState::default().with_selected(selected_row);
+
+        // Highlight search matches, the focused one more strongly
+        let term = app.search.as_ref().map(|search| search.term.as_str()).unwrap_or_default();
+        let rows = rows.into_iter().enumerate().skip(offset).take(visible_rows).map(|(row, cells)| {
+            Row::new(cells.cells.into_iter().enumerate().map(|(column, cell)| {
+                if !matches.contains(&(row, column)) {
+                    return Cell::from(cell);
+                }
+                let style = if current_match == Some((row, column)) {
+                    app.theme.success.reversed().bold()
+                } else {
+                    app.theme.value.reversed()
+                };
+                Cell::from(highlight_matches(cell, term, style))
+            }))
+        });
+
+        let table = Table::new(rows, &widths)
+            .block(block)
+            .column_spacing(2)
+            .row_highlight_style(app.theme.highlight);
+
+        StatefulWidget::render(table, area, buf, &mut table_state);
+
+        // Draw inline widgets over the last two columns of their rows
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        let columns = Layout::horizontal(widths).spacing(2).split(inner);
+        for (row, widget) in inline_widgets {
+            if row < offset || row >= offset + visible_rows {
+                continue;
+            }
+            let widget_area = Rect::new(
+                columns[2].x,
+                inner.y + (row - offset) as u16,
+                (columns[3].x + columns[3].width).saturating_sub(columns[2].x),
+                1,
+            );
+            match widget {
+                InlineWidget::Sparkline(data) => Sparkline::default()
+                    .data(&data)
+                    .style(app.theme.success)
+                    .render(widget_area, buf),
+                InlineWidget::Gauge { ratio, label } => Gauge::default()
+                    .ratio(ratio.clamp(0.0, 1.0))
+                    .label(label)
+                    .gauge_style(app.theme.gauge)
+                    .render(widget_area, buf),
+            }
+        }
+
+        // Scrollbar on the right edge when the content overflows
+        if max_offset > 0 {
+            let mut scrollbar_state = ScrollbarState::new(max_offset).position(offset);
+            StatefulWidget::render(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                buf,
+                &mut scrollbar_state,
+            );
+        }
+    }
+}
+
+// Pair account keys with their pre and post balances. v0 transactions list loaded addresses
+// only in the balances, so keys can run out before the balances do.
+fn balance_changes<'a>(keys: &[&'a str], pre: &[u64], post: &[u64]) -> Vec<(usize, Option<&'a str>, u64, u64)> {
+    pre.iter()
+        .zip(post)
+        .enumerate()
+        .map(|(index, (pre, post))| (index, keys.get(index).copied(), *pre, *post))
+        .collect()
+}
+
+// Stake and vote accounts are paid inflation rewards
+fn earns_inflation_rewards(response: &Value) -> bool {
+    response
+        .get("owner")
+        .and_then(Value::as_str)
+        .is_some_and(|owner| [stake::STAKE_PROGRAM_ID, stake::VOTE_PROGRAM_ID].contains(&owner))
+}
+
+// Addresses holding the non-circulating supply, as getSupply lists them
+fn non_circulating_accounts(supply: &Value) -> Vec<&str> {
+    supply
+        .pointer("/value/nonCirculatingAccounts")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+// The status of a transaction getTransaction doesn't return yet, shown until it does
+fn pending_transaction(signature: &Signature, status: &TransactionStatus) -> Value {
+    serde_json::json!({
+        "signature": signature.to_string(),
+        "pending": {
+            "slot": status.slot,
+            "confirmations": status.confirmations,
+            "confirmationStatus": status.confirmation_status.as_ref().map(|status| match status {
+                TransactionConfirmationStatus::Processed => "processed",
+                TransactionConfirmationStatus::Confirmed => "confirmed",
+                TransactionConfirmationStatus::Finalized => "finalized",
+            }),
+            "err": status.err,
+        },
+    })
+}
+
+// Keep the parts of a getBlock response the block view shows: one row per transaction
+// (first signature, fee, error) and the reward recipients
+fn block_info(slot: u64, block: &Value) -> Value {
+    let transactions: Vec<Value> = block
+        .get("transactions")
+        .and_then(|txs| txs.as_array())
+        .map(|txs| {
+            txs.iter()
+                .map(|tx| {
+                    serde_json::json!({
+                        "signature": tx.pointer("/transaction/signatures/0").cloned().unwrap_or(Value::Null),
+                        "fee": tx.pointer("/meta/fee").and_then(|fee| fee.as_u64()).unwrap_or(0),
+                        "err": tx.pointer("/meta/err").cloned().unwrap_or(Value::Null),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let total_fees: u64 = transactions
+        .iter()
+        .filter_map(|tx| tx.get("fee").and_then(|fee| fee.as_u64()))
+        .sum();
+
+    serde_json::json!({
+        "block": {
+            "slot": slot,
+            "blockhash": block.get("blockhash"),
+            "parentSlot": block.get("parentSlot"),
+            "blockHeight": block.get("blockHeight"),
+            "blockTime": block.get("blockTime"),
+            "totalFees": total_fees,
+            "rewards": block.get("rewards").cloned().unwrap_or(serde_json::json!([])),
+            "transactions": transactions,
+        }
+    })
+}
+
+// Shares of `supply` in hundredths of a percent, one per amount and a last one for
+// the rest, rounded so they add up to exactly 100.00%: each is rounded down and the
+// hundredths left over go to the largest remainders. None for a zero supply.
+fn supply_shares(amounts: &[u128], supply: u128) -> Option<Vec<u64>> {
+    let held = amounts.iter().fold(0u128, |held, amount| held.saturating_add(*amount));
+    // The accounts can't hold more than the supply, unless the two were read at different slots
+    let total = supply.max(held);
+    if total == 0 {
+        return None;
+    }
+    let parts: Vec<u128> = amounts.iter().copied().chain([total - held]).collect();
+    let mut shares = Vec::with_capacity(parts.len());
+    let mut remainders = Vec::with_capacity(parts.len());
+    for (index, part) in parts.iter().enumerate() {
+        let scaled = part.saturating_mul(10_000);
+        shares.push((scaled / total) as u64);
+        remainders.push((scaled % total, index));
+    }
+    let left_over = 10_000 - shares.iter().sum::<u64>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, index) in remainders.into_iter().take(left_over as usize) {
+        shares[index] += 1;
+    }
+    Some(shares)
+}
+
+// The end of `text` shown in an input `width` columns wide: it starts as far back
+// from the cursor (in graphemes) as fits. Also returns the cursor's column in it.
+fn input_window(text: &str, cursor: usize, width: usize) -> (&str, u16) {
+    let graphemes: Vec<(usize, &str)> = text.grapheme_indices(true).collect();
+    let cursor = cursor.min(graphemes.len());
+    let mut start = cursor;
+    let mut column = 0;
+    while start > 0 {
+        let grapheme_width = Line::from(graphemes[start - 1].1).width();
+        if column + grapheme_width > width {
+            break;
+        }
+        column += grapheme_width;
+        start -= 1;
+    }
+    let start_index = graphemes.get(start).map_or(text.len(), |(index, _)| *index);
+    (&text[start_index..], column as u16)
+}
+
+// Style of each program log line: invokes, successes and "Program log:" output each get a color,
+// while errors, failures and the invoke of the failing program are red
+fn log_line_styles(logs: &[&str], theme: &Theme) -> Vec<Style> {
+    let mut styles: Vec<Style> = logs
+        .iter()
+        .map(|line| {
+            if line.contains("Error") || line.contains("error") || line.contains(" failed") {
+                theme.error
+            } else if line.starts_with("Program log:") {
+                theme.value
+            } else if line.starts_with("Program ") && line.contains(" invoke [") {
+                theme.title
+            } else if line.starts_with("Program ") && line.ends_with(" success") {
+                theme.success
+            } else {
+                Style::default()
+            }
+        })
+        .collect();
+
+    // Mark the instruction that failed: the last invoke of the program before its failure
+    for (index, line) in logs.iter().enumerate() {
+        let Some(program) = line
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(" failed"))
+            .map(|(program, _)| program)
+        else {
+            continue;
+        };
+        let invoke = format!("Program {} invoke [", program);
+        if let Some(invoke_index) = logs[..index].iter().rposition(|line| line.starts_with(&invoke)) {
+            styles[invoke_index] = theme.error;
+        }
+    }
+    styles
+}
+
+// Classic hex dump, 16 bytes per line: offset, hex bytes and printable ASCII.
+// `base_offset` is the offset of `data` in a larger buffer, so windows of it can be dumped.
+fn hex_dump(data: &[u8], base_offset: usize) -> Vec<String> {
+    data.chunks(16)
+        .enumerate()
+        .map(|(line, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| if byte.is_ascii_graphic() || *byte == b' ' { *byte as char } else { '.' })
+                .collect();
+            format!("{:04x}  {:<47}  |{}|", base_offset + line * 16, hex.join(" "), ascii)
+        })
+        .collect()
+}
+
+// Char ranges of every case-insensitive, non-overlapping occurrence of `term` in `text`
+fn find_matches(text: &str, term: &str) -> Vec<std::ops::Range<usize>> {
+    let fold = |c: char| c.to_lowercase().collect::<String>();
+    let text: Vec<String> = text.chars().map(fold).collect();
+    let term: Vec<String> = term.chars().map(fold).collect();
+    if term.is_empty() {
+        return vec![];
+    }
+
+    let mut matches = vec![];
+    let mut start = 0;
+    while start + term.len() <= text.len() {
+        if text[start..start + term.len()] == term[..] {
+            matches.push(start..start + term.len());
+            start += term.len();
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+// Restyle the parts of a line that match the search term, keeping the rest of its styling
+fn highlight_matches<'a>(line: Line<'a>, term: &str, style: Style) -> Line<'a> {
+    let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+    let matches = find_matches(&text, term);
+    if matches.is_empty() {
+        return line;
+    }
+
+    let mut spans = vec![];
+    let mut position = 0;
+    for span in &line.spans {
+        // Split the span wherever it enters or leaves a match
+        let mut segment = String::new();
+        let mut segment_matched = false;
+        for c in span.content.chars() {
+            let matched = matches.iter().any(|range| range.contains(&position));
+            if matched != segment_matched && !segment.is_empty() {
+                let segment_style = if segment_matched { span.style.patch(style) } else { span.style };
+                spans.push(std::mem::take(&mut segment).set_style(segment_style));
+            }
+            segment_matched = matched;
+            segment.push(c);
+            position += 1;
+        }
+        if !segment.is_empty() {
+            let segment_style = if segment_matched { span.style.patch(style) } else { span.style };
+            spans.push(segment.set_style(segment_style));
+        }
+    }
+    Line {
+        spans,
+        ..line
+    }
+}
+
+// Check a custom RPC URL typed by the user, returning it trimmed
+pub fn parse_rpc_url(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    let url = reqwest::Url::parse(input).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported scheme '{}', use http or https", url.scheme()));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err("The URL has no host".to_string());
+    }
+    Ok(input.to_string())
+}
+
+// Host part of an RPC URL, e.g. "rpc.devnet.soo.network"
+fn rpc_host(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    without_scheme.split('/').next().unwrap_or(without_scheme)
+}
+
+// A rect covering the given percentages of `area`, centered inside it.
+// Percentages above 100 are clamped, so the result always fits in `area`.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_width = (u32::from(area.width) * u32::from(percent_x.min(100)) / 100) as u16;
+    let popup_height = (u32::from(area.height) * u32::from(percent_y.min(100)) / 100) as u16;
+    let popup_x = area.x + area.width.saturating_sub(popup_width) / 2;
+    let popup_y = area.y + area.height.saturating_sub(popup_height) / 2;
+
+    Rect::new(popup_x, popup_y, popup_width, popup_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::tests::{mock_rpc, recording_rpc, MockChainClient};
+    use crate::format::LAMPORTS_PER_SOL;
+    use ratatui::backend::TestBackend;
+    use ratatui::style::Modifier;
+
+    fn render(app: &mut App) -> Buffer {
+        render_sized(app, 100, 20)
+    }
+
+    fn render_sized(app: &mut App, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        app.render_frame(&mut terminal).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    // Terminal events scripted for the event loop; Idle stands for a poll without
+    // input, and a status message is posted as a background task would
+    struct ScriptedEvents {
+        updates: mpsc::UnboundedSender<AppUpdate>,
+        steps: std::collections::VecDeque<ScriptedStep>,
+    }
+
+    enum ScriptedStep {
+        Idle,
+        Key(char),
+        Notify(&'static str),
+    }
+
+    impl EventSource for ScriptedEvents {
+        fn next_event(&mut self, _timeout: Duration) -> io::Result<Option<Event>> {
+            Ok(match self.steps.pop_front().unwrap_or(ScriptedStep::Key('q')) {
+                ScriptedStep::Idle => None,
+                ScriptedStep::Key(c) => Some(Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))),
+                ScriptedStep::Notify(text) => {
+                    self.updates.send(AppUpdate::Notify(StatusLevel::Info, text.to_string())).unwrap();
+                    None
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn the_event_loop_only_draws_when_something_changed() {
+        let mut app = App::default();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let events = ScriptedEvents {
+            updates: app.updates.clone(),
+            steps: [
+                ScriptedStep::Idle,
+                ScriptedStep::Idle,
+                ScriptedStep::Key('2'),
+                ScriptedStep::Idle,
+                ScriptedStep::Notify("Loaded in the background"),
+            ]
+            .into(),
+        };
+        app.event_loop(&mut terminal, events).await.unwrap();
+
+        // The first frame, the one after the key and the one after the background
+        // update; idle ticks draw nothing, and quitting leaves the last frame up
+        assert_eq!(terminal.get_frame().count(), 3);
+        assert!(app.exit);
+        assert_eq!(app.tab, Tab::Search);
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Loaded in the background"));
+    }
+
+    // Style of the first cell where `text` starts
+    fn style_of(buffer: &Buffer, text: &str) -> Style {
+        let area = buffer.area;
+        for y in 0..area.height {
+            let line: String = (0..area.width).map(|x| buffer[(x, y)].symbol()).collect();
+            if let Some(byte_index) = line.find(text) {
+                let x = line[..byte_index].chars().count() as u16;
+                return buffer[(x, y)].style();
+            }
+        }
+        panic!("{:?} not rendered", text);
+    }
+
+    fn dashboard_app(theme: Theme) -> App {
+        let mut app = App {
+            theme,
+            ..App::default()
+        };
+        app.dashboard_screen_mut().data.slot_info = Some(123_456_789);
+        app
+    }
+
+    #[test]
+    fn dashboard_values_use_theme_colors() {
+        for theme in Theme::ALL {
+            let buffer = render(&mut dashboard_app(theme));
+            assert_eq!(style_of(&buffer, "123,456,789").fg, theme.value.fg);
+            assert_eq!(style_of(&buffer, "Press 'e' to edit").fg, theme.title.fg);
+        }
+    }
+
+    #[test]
+    fn each_screen_draws_its_own_view() {
+        let mut app = dashboard_app(Theme::DARK);
+        assert!(matches!(app.screen(), Screen::Dashboard(_)));
+        style_of(&render_sized(&mut app, 120, 30), "123,456,789");
+
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let address = Pubkey::new_unique().to_string();
+        let account = serde_json::json!({ "lamports": 5, "owner": address, "space": 0, "executable": false });
+        let views = [
+            ("Account Info", address.clone(), account),
+            ("Scheduled Leader:", "42".to_string(), serde_json::json!({ "block": { "slot": 42, "skipped": true } })),
+            ("Transaction Info", Signature::default().to_string(), rpc::transaction_details(&transaction)),
+        ];
+        app.tab = Tab::Search;
+        for (marker, query, response) in views {
+            app.pane.query = query;
+            app.pane.state = QueryState::Loaded;
+            app.pane.json_response = Some(response);
+            style_of(&render_sized(&mut app, 120, 30), marker);
+            assert!(matches!(app.screen(), Screen::Search(_)));
+            match marker {
+                "Account Info" => assert!(matches!(app.search_view(&app.pane), SearchView::Account(_))),
+                "Scheduled Leader:" => assert!(matches!(app.search_view(&app.pane), SearchView::Block(_))),
+                _ => assert!(matches!(app.search_view(&app.pane), SearchView::Transaction(_))),
+            }
+        }
+
+        app.pane.state = QueryState::Idle;
+        assert!(matches!(app.search_view(&app.pane), SearchView::Query(QueryState::Idle)));
+        style_of(&render_sized(&mut app, 120, 30), "Not searched yet");
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE));
+        assert!(matches!(app.popup, Some(Popup::Help { scroll: 0 })));
+        style_of(&render_sized(&mut app, 120, 30), "SoonScan - Help & Guide");
+        // The help takes the keys while it is on screen
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(app.popup.is_none());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE));
+        assert!(matches!(app.popup, Some(Popup::DebugLog)));
+        style_of(&render_sized(&mut app, 120, 30), "Nothing logged yet");
+        app.log.push("12:00:00.000  WARN query{query=42}: RPC request failed");
+        let buffer = render_sized(&mut app, 120, 30);
+        assert_eq!(style_of(&buffer, "12:00:00.000  WARN").fg, app.theme.value.fg);
+        // Other keys are left to the popup, which its own key closes
+        app.handle_key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE));
+        assert!(app.popup.is_none());
+        assert!(matches!(app.screen(), Screen::Search(_)));
+    }
+
+    #[test]
+    fn screen_actions_reach_only_the_screen_on_show() {
+        let mut app = App::default();
+        app.perform(AppAction::ToggleLogs);
+        assert!(!app.search_screen().show_logs);
+        app.perform(AppAction::ToggleNonCirculating);
+        assert!(app.dashboard_screen().show_non_circulating);
+
+        app.set_tab(Tab::Search);
+        app.perform(AppAction::ToggleLogs);
+        app.perform(AppAction::CycleHistoryFilter);
+        assert!(app.search_screen().show_logs);
+        assert_eq!(app.validators_screen().filter, ValidatorFilter::default());
+
+        // Each tab is found as it was left
+        app.set_tab(Tab::Dashboard);
+        assert!(app.dashboard_screen().show_non_circulating);
+        app.set_tab(Tab::Search);
+        assert!(app.search_screen().show_logs);
+    }
+
+    #[test]
+    fn light_theme_changes_rendered_colors() {
+        let dark = render(&mut dashboard_app(Theme::DARK));
+        let light = render(&mut dashboard_app(Theme::LIGHT));
+        assert_ne!(style_of(&dark, "123,456,789"), style_of(&light, "123,456,789"));
+        assert_eq!(Theme::DARK.next(), Theme::LIGHT);
+        assert_eq!(Theme::LIGHT.next(), Theme::DARK);
+    }
+
+    #[test]
+    fn centered_rect_fits_small_areas() {
+        assert_eq!(centered_rect(60, 40, Rect::new(0, 0, 10, 5)), Rect::new(2, 1, 6, 2));
+        assert_eq!(centered_rect(60, 40, Rect::new(0, 0, 0, 0)), Rect::new(0, 0, 0, 0));
+        assert_eq!(centered_rect(100, 100, Rect::new(0, 0, 1, 1)), Rect::new(0, 0, 1, 1));
+        // Oversized percentages are clamped to the area
+        assert_eq!(centered_rect(250, 150, Rect::new(0, 0, 10, 5)), Rect::new(0, 0, 10, 5));
+    }
+
+    #[test]
+    fn centered_rect_stays_inside_offset_areas() {
+        let area = Rect::new(5, 3, 20, 10);
+        let popup = centered_rect(50, 50, area);
+        assert_eq!(popup, Rect::new(10, 5, 10, 5));
+        assert_eq!(area.intersection(popup), popup);
+        // No overflow on the largest possible area
+        let popup = centered_rect(100, 100, Rect::new(0, 0, u16::MAX, u16::MAX));
+        assert_eq!(popup.width, u16::MAX);
+    }
+
+    #[test]
+    fn tiny_terminal_shows_size_warning() {
+        for (width, height) in [(0, 0), (10, 5), (39, 30), (80, 9)] {
+            let mut app = App {
+                popup: Some(Popup::Help { scroll: 0 }),
+                ..App::default()
+            };
+            let buffer = render_sized(&mut app, width, height);
+            // Areas narrower than the message itself only have to not panic
+            if width >= 18 && height > 0 {
+                style_of(&buffer, "Terminal too small");
+            }
+        }
+    }
+
+    #[test]
+    fn help_popup_scrolls_on_short_terminals() {
+        let mut app = App {
+            popup: Some(Popup::Help { scroll: 1_000 }),
+            ..App::default()
+        };
+        let buffer = render_sized(&mut app, 60, 12);
+        // Scrolled to the end so the last entry is visible
+        style_of(&buffer, "Back to the block");
+        assert!(matches!(app.popup, Some(Popup::Help { scroll: 1..1_000 })));
+    }
+
+    #[test]
+    fn parse_history_date_filters() {
+        let now = 1_700_000_000;
+        assert_eq!(parse_since("2023-11-14", now), Some(1_699_920_000));
+        assert_eq!(parse_since(" 24h ", now), Some(now - 86_400));
+        assert_eq!(parse_since("7d", now), Some(now - 7 * 86_400));
+        assert_eq!(parse_since("yesterday", now), None);
+        assert_eq!(parse_since("2023-13-01", now), None);
+        assert_eq!(parse_since("99999999999999999d", now), None);
+    }
+
+    #[test]
+    fn history_filter_combines_status_and_date() {
+        let entry = |block_time, err: Option<Value>| HistoryEntry {
+            signature: "sig".to_string(),
+            slot: 1,
+            block_time,
+            confirmation_status: None,
+            err,
+            memo: None,
+        };
+        let failed_recent = entry(Some(200), Some(serde_json::json!("error")));
+        let ok_recent = entry(Some(200), None);
+        let failed_old = entry(Some(50), Some(serde_json::json!("error")));
+        let failed_unknown_time = entry(None, Some(serde_json::json!("error")));
+
+        let filter = HistoryFilter {
+            status: StatusFilter::Failed,
+            since: Some((100, "1d".to_string())),
+        };
+        assert!(filter.matches(&failed_recent));
+        assert!(!filter.matches(&ok_recent));
+        assert!(!filter.matches(&failed_old));
+        assert!(!filter.matches(&failed_unknown_time));
+        assert_eq!(filter.describe(), "failed, since 1d");
+
+        let all = HistoryFilter::default();
+        assert!(!all.is_active());
+        assert!([failed_recent, ok_recent, failed_old, failed_unknown_time]
+            .iter()
+            .all(|entry| all.matches(entry)));
+    }
+
+    #[test]
+    fn history_sort_orders_entries() {
+        let entry = |slot, failed: bool| HistoryEntry {
+            signature: format!("sig{}", slot),
+            slot,
+            block_time: None,
+            confirmation_status: None,
+            err: failed.then(|| serde_json::json!("error")),
+            memo: None,
+        };
+        let entries = [entry(3, false), entry(2, true), entry(5, false), entry(1, true)];
+        let slots = |sort: HistorySort| {
+            let mut sorted: Vec<&HistoryEntry> = entries.iter().collect();
+            sort.sort(&mut sorted);
+            sorted.iter().map(|entry| entry.slot).collect::<Vec<_>>()
+        };
+        assert_eq!(slots(HistorySort::Newest), [5, 3, 2, 1]);
+        assert_eq!(slots(HistorySort::Oldest), [1, 2, 3, 5]);
+        assert_eq!(slots(HistorySort::Failed), [2, 1, 5, 3]);
+    }
+
+    #[test]
+    fn history_rows_show_the_result_and_commitment() {
+        let entry = |signature: &str, value: Value| {
+            let mut value = value;
+            value["signature"] = Value::from(signature);
+            HistoryEntry::from_value(&value).unwrap()
+        };
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        app.pane.query = Pubkey::new_unique().to_string();
+        app.pane.state = QueryState::Loaded;
+        app.pane.json_response =
+            Some(serde_json::json!({ "lamports": 1, "owner": "o", "space": 0, "executable": false }));
+        app.pane.address_sign = Some(vec![
+            entry("ok", serde_json::json!({ "slot": 2, "err": null, "confirmationStatus": "finalized" })),
+            entry("bad", serde_json::json!({
+                "slot": 1,
+                "err": { "InstructionError": [0, { "Custom": 1 }] },
+                "confirmationStatus": "confirmed",
+            })),
+        ]);
+        let table = app.search_rows(&app.pane);
+        let start = table.history_start.unwrap();
+        let result = |row: usize| {
+            let cell = &table.rows[start + row].cells[3];
+            (cell.to_string(), cell.spans[0].style.fg)
+        };
+        assert_eq!(result(0), ("Success fin".to_string(), app.theme.success.fg));
+        assert_eq!(result(1), ("Failed conf".to_string(), app.theme.error.fg));
+    }
+
+
+
+
+    #[test]
+    fn search_matches_ignore_case() {
+        assert_eq!(find_matches("Slot slot SLOT", "slot"), vec![0..4, 5..9, 10..14]);
+        assert_eq!(find_matches("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(find_matches("Epoch", "").is_empty());
+        assert!(find_matches("Epoch", "slot").is_empty());
+    }
+
+    #[test]
+    fn search_highlights_matching_cells() {
+        let mut app = dashboard_app(Theme::DARK);
+        app.search = Some(ViewSearch {
+            term: "soonscan".to_string(),
+            ..ViewSearch::default()
+        });
+        let buffer = render(&mut app);
+        let focused = style_of(&buffer, "SoonScan");
+        assert_eq!(focused.fg, Theme::DARK.success.fg);
+        assert!(focused.add_modifier.contains(Modifier::REVERSED));
+        assert!(!style_of(&buffer, "Network").add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn timestamps_outside_the_date_range_are_invalid() {
+        let app = App::default();
+        assert_eq!(app.format_timestamp(Some(1_700_000_000)), "2023-11-14 22:13:20 UTC");
+        assert_eq!(app.format_timestamp(Some(0)), "1970-01-01 00:00:00 UTC");
+        // Before 1970 is still a date
+        assert_eq!(app.format_timestamp(Some(-86_400)), "1969-12-31 00:00:00 UTC");
+        assert_eq!(app.format_timestamp(Some(i64::MAX)), "invalid time");
+        assert_eq!(app.format_timestamp(Some(i64::MIN)), "invalid time");
+        assert_eq!(app.format_timestamp(None), "N/A");
+    }
+
+    #[test]
+    fn long_numbers_are_grouped_without_wrapping() {
+        let app = App::default();
+        for (number, grouped) in [
+            (1u64, "1"),
+            (12, "12"),
+            (123, "123"),
+            (1234, "1,234"),
+            (1_000_000, "1,000,000"),
+            (u64::MAX, "18,446,744,073,709,551,615"),
+        ] {
+            assert_eq!(app.format_longnumber(number), grouped);
+        }
+        assert_eq!(app.format_longnumber(-1234i64), "-1,234");
+        assert_eq!(app.format_longnumber(i64::MIN), "-9,223,372,036,854,775,808");
+
+        // Every digit survives, with a separator before each group of three from the right
+        for number in [0u64, 7, 999, 1000, 65_536, 4_294_967_296, u64::MAX / 3, u64::MAX] {
+            let grouped = app.format_longnumber(number);
+            assert_eq!(grouped.replace(',', ""), number.to_string());
+            let groups: Vec<&str> = grouped.split(',').collect();
+            assert!((1..=3).contains(&groups[0].len()));
+            assert!(groups[1..].iter().all(|group| group.len() == 3));
+        }
+    }
+
+
+    #[test]
+    fn query_editing_handles_wide_and_combined_characters() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let mut app = App::default();
+        app.start_editing();
+        app.handle_paste("日本 e\u{301}👍🏽");
+        assert_eq!(app.input.cursor, 5);
+        app.edit_query(key(KeyCode::Backspace, KeyModifiers::NONE));
+        app.edit_query(key(KeyCode::Left, KeyModifiers::NONE));
+        app.edit_query(key(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(app.pane.query, "日本 xe\u{301}");
+        app.edit_query(key(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!((app.pane.query.as_str(), app.input.cursor), ("日本 e\u{301}", 3));
+
+        // The cursor sits after the two-column characters, and a long query
+        // scrolls the input so the cursor stays in view
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        app.render_frame(&mut terminal).unwrap();
+        assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(6, 1));
+        app.edit_query(key(KeyCode::End, KeyModifiers::NONE));
+        app.handle_paste(&"語".repeat(40));
+        app.render_frame(&mut terminal).unwrap();
+        let cursor = terminal.get_cursor_position().unwrap();
+        assert!(cursor.x < 59, "cursor at {:?}", cursor);
+        assert_eq!(terminal.backend().buffer()[(cursor.x - 2, 1)].symbol(), "語");
+    }
+
+    #[test]
+    fn custom_rpc_urls_are_validated() {
+        assert_eq!(parse_rpc_url(" https://rpc.example.com/rpc "), Ok("https://rpc.example.com/rpc".to_string()));
+        assert!(parse_rpc_url("http://localhost:8899").is_ok());
+        assert!(parse_rpc_url("ftp://rpc.example.com").is_err());
+        assert!(parse_rpc_url("rpc.example.com").is_err());
+        assert!(parse_rpc_url("").is_err());
+    }
+
+    #[test]
+    fn networks_round_trip_through_saved_names() {
+        let custom = RpcNetwork::Custom("https://rpc.example.com/rpc".to_string());
+        assert_eq!(custom.name(), "rpc.example.com");
+        for network in RpcNetwork::ALL.into_iter().chain([custom]) {
+            assert_eq!(RpcNetwork::from_name(network.saved_name()), Some(network));
+        }
+        assert_eq!(RpcNetwork::SolanaMainnet.next(), RpcNetwork::Devnet);
+    }
+
+    #[test]
+    fn log_lines_are_colored_by_kind() {
+        let theme = Theme::DARK;
+        let logs = [
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program Token111 invoke [1]",
+            "Program log: Instruction: Transfer",
+            "Program log: Error: insufficient funds",
+            "Program Token111 consumed 4645 of 200000 compute units",
+            "Program Token111 failed: custom program error: 0x1",
+        ];
+        assert_eq!(
+            log_line_styles(&logs, &theme),
+            vec![
+                theme.title,
+                theme.success,
+                theme.error, // Invoke of the failing instruction
+                theme.value,
+                theme.error,
+                Style::default(),
+                theme.error,
+            ]
+        );
+    }
+
+    #[test]
+    fn instruction_data_hex_dump() {
+        let instruction = instruction::InstructionInfo::from_value(&serde_json::json!({
+            "programIdIndex": 2,
+            "accounts": [0, 1],
+            "data": "3Bxs4h24hBtQy9rw",
+        }))
+        .unwrap();
+        assert_eq!(
+            instruction.accounts,
+            vec![instruction::InstructionAccount::Index(0), instruction::InstructionAccount::Index(1)]
+        );
+        // System transfer of 2,039,280 lamports
+        assert_eq!(instruction.data_bytes(), vec![2, 0, 0, 0, 240, 29, 31, 0, 0, 0, 0, 0]);
+
+        let dump = hex_dump(b"Hello, world!\n\x00\x01\xffXYZ", 0);
+        assert_eq!(
+            dump,
+            vec![
+                "0000  48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a 00 01  |Hello, world!...|",
+                "0010  ff 58 59 5a                                      |.XYZ|",
+            ]
+        );
+        assert_eq!(hex_dump(b"A", 0x1_0000), vec!["10000  41                                               |A|"]);
+    }
+
+
+    #[test]
+    fn balance_changes_tolerate_missing_keys() {
+        // A v0 transaction with one loaded address: three balances, two static keys
+        let changes = balance_changes(&["payer", "program"], &[10, 1, 5], &[4, 1, 11]);
+        assert_eq!(
+            changes,
+            vec![(0, Some("payer"), 10, 4), (1, Some("program"), 1, 1), (2, None, 5, 11)]
+        );
+        assert!(balance_changes(&["payer"], &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn account_keys_carry_their_flags() {
+        let key = |pubkey: &str, signer: bool, writable: bool, source: &str| {
+            serde_json::json!({ "pubkey": pubkey, "signer": signer, "writable": writable, "source": source })
+        };
+        let meta = serde_json::json!({
+            "accountKeys": [
+                key("payer", true, true, "transaction"),
+                key("cosigner", true, false, "transaction"),
+                key("program", false, false, "transaction"),
+                key("pool", false, true, "lookupTable"),
+                key("oracle", false, false, "lookupTable"),
+            ],
+        });
+        let keys = AccountKeys::from_meta(&meta);
+        assert_eq!(keys.keys, ["payer", "cosigner", "program", "pool", "oracle"]);
+        assert_eq!(keys.get(4), Some("oracle"));
+        let flags: Vec<(bool, bool)> = (0..6).map(|index| (keys.is_signer(index), keys.is_writable(index))).collect();
+        assert_eq!(
+            flags,
+            [(true, true), (true, false), (false, false), (false, true), (false, false), (false, false)]
+        );
+        assert_eq!(keys.loaded_as(1), None);
+        assert_eq!(keys.loaded_as(3), Some("loaded writable"));
+        assert_eq!(keys.loaded_as(4), Some("loaded readonly"));
+        assert_eq!(keys.loaded_as(5), None);
+        assert_eq!(AccountKeys::from_meta(&serde_json::json!({})).keys, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn lookup_table_addresses_resolve_to_account_keys() {
+        // Bare static keys: the loaded addresses follow them, writable ones first
+        let meta = serde_json::json!({
+            "accountKeys": ["payer", "program"],
+            "loadedAddresses": { "writable": ["pool", "vault"], "readonly": ["oracle"] },
+            "addressTableLookups": [
+                { "accountKey": "tableA", "writableIndexes": [7], "readonlyIndexes": [2] },
+                { "accountKey": "tableB", "writableIndexes": [0], "readonlyIndexes": [] },
+            ],
+        });
+        let keys = AccountKeys::from_meta(&meta);
+        assert_eq!(keys.keys, ["payer", "program", "pool", "vault", "oracle"]);
+        assert_eq!(
+            (keys.loaded_as(1), keys.loaded_as(3), keys.loaded_as(4)),
+            (None, Some("loaded writable"), Some("loaded readonly"))
+        );
+        let lookups = keys.resolve_lookups(meta["addressTableLookups"].as_array().unwrap());
+        assert_eq!(
+            lookups,
+            [
+                ResolvedLookup {
+                    table: "tableA",
+                    writable: vec![(7, 2)],
+                    readonly: vec![(2, 4)],
+                },
+                ResolvedLookup {
+                    table: "tableB",
+                    writable: vec![(0, 3)],
+                    readonly: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn inconsistent_transactions_show_unknown_accounts() {
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let mut pane = Pane {
+            query: "2dg13SEjq2sHTdQUwCyFJ4TrComWtnDprNVPrtbvN9SmQPPu33cdsQVLDPjaYgebQr2LSRsVerQuDh2RuQur3GLM".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(rpc::transaction_details(&transaction)),
+            ..Pane::default()
+        };
+        let app = App::default();
+        let cells = |pane: &Pane| -> Vec<String> {
+            app.search_rows(pane)
+                .rows
+                .iter()
+                .flat_map(|row| row.cells.iter().map(|cell| cell.to_string()))
+                .collect()
+        };
+        assert!(cells(&pane).contains(&"3 → 79311T5…".to_string()));
+        assert!(cells(&pane).contains(&"0 → EPjFWdd…".to_string()));
+
+        // A node that sent fewer keys than the instructions and tables refer to
+        let meta = &mut pane.json_response.as_mut().unwrap()["meta"];
+        meta["accountKeys"].as_array_mut().unwrap().truncate(5);
+        meta["loadedAddresses"] = Value::Null;
+        meta["instructions"][2]["accounts"] = serde_json::json!([0, 9]);
+        let cells = cells(&pane);
+        assert!(cells.contains(&"jdqgPK8…, unknown[9]".to_string()));
+        assert!(cells.contains(&"3 → unknown[5]".to_string()));
+        assert!(cells.contains(&"0 → unknown[6]".to_string()));
+    }
+
+    #[test]
+    fn account_keys_show_their_owners_once_looked_up() {
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let mut pane = Pane {
+            query: "2dg13SEjq2sHTdQUwCyFJ4TrComWtnDprNVPrtbvN9SmQPPu33cdsQVLDPjaYgebQr2LSRsVerQuDh2RuQur3GLM".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(rpc::transaction_details(&transaction)),
+            ..Pane::default()
+        };
+        let keys = unresolved_account_keys(pane.json_response.as_ref().unwrap()).unwrap();
+        assert_eq!(keys.len(), 7);
+        assert_eq!(keys[0], "jdqgPK8DMCK3LNDWsBhcSG1fMNJG6jL2t5HFD1HXdeR");
+        assert_eq!(keys[6], "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+        let app = App::default();
+        let payer = |pane: &Pane| -> Vec<String> {
+            let rows = app.search_rows(pane).rows;
+            let row = rows.iter().find(|row| row.cells.len() == 4 && row.cells[1].to_string() == "#0");
+            row.unwrap().cells.iter().map(|cell| cell.to_string()).collect()
+        };
+        // Nothing is shown until the owners arrive
+        assert_eq!(payer(&pane)[3], "");
+
+        let meta = &mut pane.json_response.as_mut().unwrap()["meta"];
+        meta["keyAccounts"] = serde_json::json!([
+            { "owner": "11111111111111111111111111111111", "executable": false },
+            null,
+            { "owner": "NativeLoader1111111111111111111111111111111", "executable": true },
+        ]);
+        assert_eq!(payer(&pane)[3], "Owned by System Program");
+        let cells: Vec<String> = app
+            .search_rows(&pane)
+            .rows
+            .iter()
+            .flat_map(|row| row.cells.iter().map(|cell| cell.to_string()))
+            .collect();
+        assert!(cells.contains(&"No account".to_string()));
+        assert!(cells.contains(&"Program of NativeLoader1111111…".to_string()));
+        // Looked up once
+        assert_eq!(unresolved_account_keys(pane.json_response.as_ref().unwrap()), None);
+    }
+
+    #[test]
+    fn hex_viewer_offsets() {
+        assert_eq!(parse_offset("0x1f0"), Some(0x1f0));
+        assert_eq!(parse_offset(" 512 "), Some(512));
+        assert_eq!(parse_offset("0xzz"), None);
+
+        let mut viewer = HexViewer {
+            data: vec![0; 100],
+            page_lines: 3,
+            ..HexViewer::default()
+        };
+        // 7 lines of 16 bytes, 3 visible: the last page starts at line 4
+        viewer.scroll_to(usize::MAX);
+        assert_eq!(viewer.top_line, 4);
+        viewer.scroll_to(2);
+        assert_eq!(viewer.top_line, 2);
+    }
+
+
+    #[test]
+    fn token_balance_changes_join_by_account() {
+        let balance = |account_index, amount| TokenBalance {
+            account_index,
+            mint: "mint".to_string(),
+            owner: None,
+            amount,
+            decimals: 6,
+        };
+        // Account 1 is closed by the transaction, account 3 is a new ATA
+        let changes = token_balance_changes(&[balance(2, 10), balance(1, 7)], &[balance(2, 4), balance(3, 6)]);
+        let amounts: Vec<(usize, u128, u128)> =
+            changes.iter().map(|change| (change.account_index, change.pre, change.post)).collect();
+        assert_eq!(amounts, vec![(1, 7, 0), (2, 10, 4), (3, 0, 6)]);
+    }
+
+    #[test]
+    fn token_holdings_parse_json_parsed_accounts() {
+        let holding = |mint: &str, amount: &str, decimals: u64, state: &str| {
+            TokenHolding::from_value(&serde_json::json!({
+                "pubkey": "account",
+                "account": { "data": { "parsed": { "info": {
+                    "mint": mint,
+                    "state": state,
+                    "tokenAmount": { "amount": amount, "decimals": decimals },
+                } } } },
+            }))
+            .unwrap()
+        };
+        let usdc = holding("usdc", "2500000", 6, "initialized");
+        assert_eq!(usdc.ui_amount(), 2.5);
+        assert!(!usdc.frozen);
+        assert!(holding("bonk", "1", 0, "frozen").frozen);
+        assert!(TokenHolding::from_value(&serde_json::json!({ "account": {} })).is_none());
+    }
+
+    #[tokio::test]
+    async fn vote_accounts_include_their_identity() {
+        let vote_account = |vote: &str, node: &str, stake: u64| {
+            serde_json::json!({
+                "votePubkey": vote,
+                "nodePubkey": node,
+                "activatedStake": stake,
+                "commission": 5,
+                "lastVote": 300_000_000u64,
+                "epochVoteAccount": true,
+                "epochCredits": [],
+                "rootSlot": 299_999_968u64,
+            })
+        };
+        let (url, _) = recording_rpc(vec![(
+            "getVoteAccounts",
+            serde_json::json!({
+                "current": [vote_account("vote1", "node1", 10), vote_account("vote2", "node2", 30)],
+                "delinquent": [vote_account("vote3", "node3", 20)],
+            }),
+        )])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let validators = App::fetch_vote_accounts(&rpc).await.unwrap();
+        let summary: Vec<_> = validators
+            .iter()
+            .map(|validator| (validator.vote_pubkey.as_str(), validator.node_pubkey.as_str(), validator.delinquent))
+            .collect();
+        assert_eq!(summary, [("vote2", "node2", false), ("vote3", "node3", true), ("vote1", "node1", false)]);
+    }
+
+    #[tokio::test]
+    async fn block_production_counts_leader_slots_per_identity() {
+        let (url, _) = recording_rpc(vec![(
+            "getBlockProduction",
+            serde_json::json!({
+                "context": { "slot": 1200 },
+                "value": {
+                    "byIdentity": { "node1": [40, 38], "node2": [4, 4] },
+                    "range": { "firstSlot": 1000, "lastSlot": 1200 },
+                },
+            }),
+        )])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let production = App::fetch_block_production(&rpc).await.unwrap();
+        assert_eq!((production.first_slot, production.last_slot), (1000, 1200));
+        assert_eq!(production.total(), (44, 2));
+        assert_eq!(production.skip_rate("node1"), Some(500));
+        assert_eq!(production.skip_rate("node2"), Some(0));
+        assert_eq!(production.skip_rate("node3"), None);
+    }
+
+    #[test]
+    fn validators_sort_filter_and_open() {
+        let validator = |vote: &str, stake: u64, commission, last_vote, delinquent| VoteAccountSummary {
+            vote_pubkey: vote.to_string(),
+            node_pubkey: format!("{}-node", vote),
+            activated_stake: stake * LAMPORTS_PER_SOL,
+            commission,
+            last_vote,
+            delinquent,
+        };
+        let mut app = App {
+            tab: Tab::Validators,
+            ..App::default()
+        };
+        let screen = app.validators_screen_mut();
+        screen.validators = TabData::Loaded(vec![
+            validator("big", 3_000_000, 10, 100, false),
+            validator("late", 500_000, 0, 50, true),
+            validator("small", 1_500_000, 5, 101, false),
+        ]);
+        screen.block_production = TabData::Loaded(BlockProduction {
+            by_identity: [("big-node".to_string(), (100, 99)), ("late-node".to_string(), (20, 10))].into_iter().collect(),
+            first_slot: 1000,
+            last_slot: 1200,
+        });
+        let rows = |app: &App| -> Vec<Vec<String>> {
+            app.result_rows(&app.pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        let votes = |app: &App| -> Vec<String> { rows(app).iter().skip(4).map(|row| row[0].clone()).collect() };
+
+        let shown = rows(&app);
+        assert_eq!(shown[0], ["Vote Accounts:", "2 current, 1 delinquent", "◎ 5,000,000"]);
+        assert_eq!(shown[1], ["Skip Rate:", "9.17%", "11 of 120 leader slots skipped, slots 1,000 to 1,200"]);
+        assert_eq!(
+            shown[4],
+            ["big", "big-node", "◎ 3,000,000", "60.00%", "10%", "100", "99/100", "1.00%", "Current"]
+        );
+        assert_eq!(shown[5][6..], ["–", "–", "Current"]);
+        assert_eq!(
+            shown[6],
+            ["late", "late-node", "◎ 500,000", "10.00%", "0%", "50", "10/20", "50.00%", "Delinquent"]
+        );
+        // Only the worst skip rates stand out
+        let production = match &app.validators_screen().block_production {
+            TabData::Loaded(production) => production,
+            _ => unreachable!(),
+        };
+        assert!(production.skips_badly("late-node"));
+        assert!(!production.skips_badly("big-node"));
+
+        app.perform(AppAction::CycleHistorySort);
+        assert_eq!(votes(&app), ["late", "small", "big"]);
+        app.perform(AppAction::CycleHistorySort);
+        assert_eq!(votes(&app), ["small", "big", "late"]);
+        app.perform(AppAction::CycleHistorySort);
+        assert_eq!(votes(&app), ["late", "big", "small"]);
+        app.perform(AppAction::CycleHistorySort);
+        assert_eq!(app.validators_screen().sort, ValidatorSort::Stake);
+
+        app.perform(AppAction::CycleHistoryFilter);
+        assert_eq!(votes(&app), ["big", "small"]);
+        app.perform(AppAction::CycleHistoryFilter);
+        assert_eq!(votes(&app), ["late"]);
+        // Shares stay of the total stake
+        assert_eq!(rows(&app)[4][3], "10.00%");
+
+        assert!(app.move_selection(true));
+        assert_eq!(app.selected_address().as_deref(), Some("late"));
+        app.set_tab(Tab::Search);
+        assert_eq!(app.pane.selected_history, None);
+    }
+
+    #[tokio::test]
+    async fn largest_holders_resolve_their_owners() {
+        let (ata1, ata2, whale) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        // An initialized token account of the whale
+        let mut data = vec![0; token::ACCOUNT_LEN];
+        data[32..64].copy_from_slice(whale.as_ref());
+        data[108] = 1;
+        let token_account = solana_sdk::account::Account {
+            lamports: 2_039_280,
+            data,
+            owner: Pubkey::from_str(token::TOKEN_PROGRAM_ID).unwrap(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let encoded = solana_account_decoder::encode_ui_account(
+            &ata1,
+            &token_account,
+            solana_account_decoder::UiAccountEncoding::Base64,
+            None,
+            None,
+        );
+        let (url, requests) = recording_rpc(vec![
+            (
+                "getTokenLargestAccounts",
+                serde_json::json!({ "value": [
+                    { "address": ata1.to_string(), "amount": "7500000", "decimals": 6, "uiAmountString": "7.5" },
+                    { "address": ata2.to_string(), "amount": "2500000", "decimals": 6, "uiAmountString": "2.5" },
+                ] }),
+            ),
+            ("getMultipleAccounts", serde_json::json!({ "context": { "slot": 1 }, "value": [encoded, null] })),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let holders = App::fetch_largest_holders(&rpc, "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")
+            .await
+            .unwrap();
+        assert_eq!(
+            holders,
+            [
+                TokenHolder {
+                    account: ata1.to_string(),
+                    owner: Some(whale.to_string()),
+                    amount: 7_500_000,
+                    decimals: 6,
+                },
+                TokenHolder {
+                    account: ata2.to_string(),
+                    owner: None,
+                    amount: 2_500_000,
+                    decimals: 6,
+                },
+            ]
+        );
+        assert_eq!(*requests.lock().unwrap(), ["getTokenLargestAccounts", "getMultipleAccounts"]);
+    }
+
+    #[test]
+    fn mint_view_states_disabled_authorities() {
+        let mut pane = Pane {
+            query: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(serde_json::json!({
+                "lamports": 1_461_600,
+                "owner": token::TOKEN_PROGRAM_ID,
+                "space": 82,
+                "executable": false,
+                "mint": { "supply": 10_000_000, "decimals": 6, "mintAuthority": null, "freezeAuthority": null },
+            })),
+            ..Pane::default()
+        };
+        assert_eq!(pane.breadcrumbs(), "USDC mint");
+        let app = App::default();
+        let cells = |pane: &Pane| -> Vec<Vec<String>> {
+            app.search_rows(pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        let rows = cells(&pane);
+        assert!(rows.contains(&vec!["Mint Authority:".to_string(), "None (fixed supply)".to_string()]));
+        assert!(rows.contains(&vec!["Freeze Authority:".to_string(), "None (freezing disabled)".to_string()]));
+        assert!(rows.contains(&vec!["Largest Holders".to_string(), "Press 'h' to rank them".to_string()]));
+        assert!(!rows.iter().any(|row| row[0] == "Token Holdings"));
+
+        pane.largest_holders = TabData::Loaded(vec![]);
+        assert!(cells(&pane).contains(&vec!["Largest Holders".to_string(), "0 loaded, press 'h' to rank them".to_string()]));
+    }
+
+    #[test]
+    fn holders_view_ranks_holders_and_opens_their_owners() {
+        let holder = |account: &str, owner: Option<&str>, amount| TokenHolder {
+            account: account.to_string(),
+            owner: owner.map(str::to_string),
+            amount,
+            decimals: 6,
+        };
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        app.pane = Pane {
+            query: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(serde_json::json!({
+                "lamports": 1_461_600,
+                "owner": token::TOKEN_PROGRAM_ID,
+                "space": 82,
+                "mint": { "supply": 3_000_000, "decimals": 6, "mintAuthority": null, "freezeAuthority": null },
+            })),
+            largest_holders: TabData::Loaded(vec![holder("ata1", Some("whale"), 1_000_000), holder("ata2", None, 1_000_000)]),
+            holders_view: true,
+            ..Pane::default()
+        };
+        let rows: Vec<Vec<String>> = app
+            .search_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let row = |cells: &[&str]| cells.iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+        assert_eq!(rows[0][0], "Largest Holders of USDC");
+        assert!(rows.contains(&row(&["1", "whale", "ata1", "1", "33.34%"])));
+        assert!(rows.contains(&row(&["2", "Unknown", "ata2", "1", "33.33%"])));
+        assert!(rows.contains(&row(&["", "Everyone else", "", "1", "33.33%"])));
+
+        // Holders are selectable; without an owner the token account is opened
+        assert!(app.move_selection(true));
+        assert_eq!(app.selected_address().as_deref(), Some("whale"));
+        assert!(app.move_selection(true));
+        assert_eq!(app.selected_address().as_deref(), Some("ata2"));
+        assert!(app.go_back());
+        assert!(!app.pane.holders_view);
+        assert_eq!(app.selected_address(), None);
+    }
+
+    #[test]
+    fn supply_shares_add_up_to_the_whole_supply() {
+        assert_eq!(supply_shares(&[1, 1, 1], 3), Some(vec![3334, 3333, 3333, 0]));
+        assert_eq!(supply_shares(&[7_500_000], 10_000_000), Some(vec![7500, 2500]));
+        // Tiny holders round to nothing rather than taking hundredths from the rest
+        let shares = supply_shares(&[999_990, 5, 5], 1_000_000).unwrap();
+        assert_eq!(shares, [10_000, 0, 0, 0]);
+        // More held than the supply read: shares of what is held
+        assert_eq!(supply_shares(&[3, 1], 2), Some(vec![7500, 2500, 0]));
+        assert_eq!(supply_shares(&[], 0), None);
+        assert_eq!(supply_shares(&[0, 0], 0), None);
+        for (amounts, supply) in [(vec![1, 2, 3, 4, 5, 6, 7], 29u128), (vec![u64::MAX as u128; 20], u64::MAX as u128 * 21)] {
+            assert_eq!(supply_shares(&amounts, supply).unwrap().iter().sum::<u64>(), 10_000);
+        }
+    }
+
+    #[tokio::test]
+    async fn inflation_rewards_cover_the_last_completed_epochs() {
+        let vote_account = "Vote111111111111111111111111111111111111111";
+        let (url, _) = recording_rpc(vec![
+            ("getEpochInfo", serde_json::json!({ "epoch": 3, "slotIndex": 0, "slotsInEpoch": 432000 })),
+            (
+                "getInflationReward",
+                serde_json::json!([{ "epoch": 2, "effectiveSlot": 1, "amount": 2500, "postBalance": 1_002_500, "commission": 7 }]),
+            ),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let rewards = App::fetch_inflation_rewards(&rpc, vote_account).await.unwrap();
+        // Epoch 3 is still running
+        assert_eq!(rewards.iter().map(|reward| reward.epoch).collect::<Vec<_>>(), [2, 1, 0]);
+        assert_eq!(
+            rewards[0].reward,
+            Some(InflationReward {
+                amount: 2500,
+                post_balance: 1_002_500,
+                commission: Some(7),
+            })
+        );
+
+        let (url, _) = recording_rpc(vec![
+            ("getEpochInfo", serde_json::json!({ "epoch": 700, "slotIndex": 0, "slotsInEpoch": 432000 })),
+            ("getInflationReward", serde_json::json!([null])),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let rewards = App::fetch_inflation_rewards(&rpc, vote_account).await.unwrap();
+        assert_eq!(rewards.len(), REWARD_EPOCHS as usize);
+        assert!(rewards.iter().all(|reward| reward.reward.is_none()));
+
+        let app = App {
+            tab: Tab::Search,
+            pane: Pane {
+                query: vote_account.to_string(),
+                state: QueryState::Loaded,
+                json_response: Some(serde_json::json!({
+                    "lamports": 1_002_500,
+                    "owner": crate::stake::VOTE_PROGRAM_ID,
+                    "space": 3762,
+                    "executable": false,
+                })),
+                inflation_rewards: TabData::Loaded(vec![
+                    EpochReward {
+                        epoch: 699,
+                        reward: Some(InflationReward {
+                            amount: 2500,
+                            post_balance: 1_002_500,
+                            commission: None,
+                        }),
+                    },
+                    EpochReward { epoch: 698, reward: None },
+                ]),
+                ..Pane::default()
+            },
+            ..App::default()
+        };
+        let rows: Vec<Vec<String>> = app
+            .search_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert!(rows.contains(&vec!["699".to_string(), "◎ 0.000002500".to_string(), "◎ 0.001002500".to_string(), "N/A".to_string()]));
+        assert!(rows.contains(&vec!["698".to_string(), "no reward".to_string()]));
+    }
+
+    #[test]
+    fn rent_status_compares_lamports() {
+        let app = App::default();
+        let rent = |lamports: u64, minimum: Option<u64>| {
+            let account = serde_json::json!({ "lamports": lamports, "rentExemptMinimum": minimum });
+            app.rent_row(account.as_object().unwrap()).cells.iter().map(|cell| cell.to_string()).collect::<Vec<_>>()
+        };
+        assert_eq!(rent(1_500_000_000, Some(2_039_280)), ["Rent:", "exempt (needs ◎ 0.00203928, has ◎ 1.5)"]);
+        assert_eq!(rent(2_039_280, Some(2_039_280)), ["Rent:", "exempt (needs ◎ 0.00203928, has ◎ 0.00203928)"]);
+        assert_eq!(rent(1_639_280, Some(2_039_280)), ["Rent:", "NOT rent-exempt, short by ◎ 0.0004"]);
+        assert_eq!(rent(0, Some(890_880)), ["Rent:", "NOT rent-exempt, short by ◎ 0.00089088"]);
+        assert_eq!(rent(5, None), ["Rent:", "Unknown"]);
+    }
+
+    #[tokio::test]
+    async fn fee_estimates_degrade_to_the_base_fee() {
+        let blockhash = serde_json::json!({
+            "context": { "slot": 1 },
+            "value": { "blockhash": solana_sdk::hash::Hash::default().to_string(), "lastValidBlockHeight": 100 },
+        });
+        let (url, _) = recording_rpc(vec![
+            ("getLatestBlockhash", blockhash.clone()),
+            ("getFeeForMessage", serde_json::json!({ "context": { "slot": 1 }, "value": 5000 })),
+            (
+                "getRecentPrioritizationFees",
+                serde_json::json!([{ "slot": 1, "prioritizationFee": 0 }, { "slot": 2, "prioritizationFee": 2000 }]),
+            ),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let estimate = App::fetch_fee_estimate(&rpc).await.unwrap();
+        assert_eq!(estimate.lamports_per_signature, 5000);
+        assert_eq!(estimate.priority.map(|priority| (priority.median, priority.max)), Some((0, 2000)));
+
+        // Without getRecentPrioritizationFees, nor getFeeForMessage
+        let (url, _) = recording_rpc(vec![(
+            "getFees",
+            serde_json::json!({ "context": { "slot": 1 }, "value": { "feeCalculator": { "lamportsPerSignature": 10000 } } }),
+        )])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let estimate = App::fetch_fee_estimate(&rpc).await.unwrap();
+        assert_eq!((estimate.lamports_per_signature, estimate.priority), (10000, None));
+
+        let mut app = App::default();
+        app.dashboard_screen_mut().data.fees = Some(estimate);
+        let rows: Vec<Vec<String>> = app
+            .result_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert_eq!(rows, [["Base Fee:", "◎ 0.000010000 per signature"]]);
+
+        let (url, _) = recording_rpc(vec![]).await;
+        assert!(App::fetch_fee_estimate(&RpcApi::new(url.as_str(), CommitmentConfig::default())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn dashboard_shows_the_inflation_rate() {
+        let (url, _) = recording_rpc(vec![
+            ("getSlot", serde_json::json!(100)),
+            ("getInflationRate", serde_json::json!({ "epoch": 700, "total": 0.0456, "validator": 0.0456, "foundation": 0.0 })),
+            ("getInflationGovernor", serde_json::json!({
+                "initial": 0.08, "terminal": 0.015, "taper": 0.15, "foundation": 0.05, "foundationTerm": 7.0,
+            })),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let (data, _) = App::fetch_initial_blockchain_data(&rpc).await.unwrap();
+        let mut app = App::default();
+        app.dashboard_screen_mut().data = data;
+        let rows: Vec<Vec<String>> = app
+            .result_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert!(rows.contains(&vec![
+            "Inflation:".to_string(),
+            "4.56% total".to_string(),
+            "4.56% validators, 0.00% foundation in epoch 700".to_string(),
+        ]));
+        assert!(rows.contains(&vec![
+            "Inflation Schedule:".to_string(),
+            "8.00% initial, 1.50% terminal, tapering 15% a year".to_string(),
+        ]));
+    }
+
+    #[tokio::test]
+    async fn dashboard_shows_snapshots_and_block_height() {
+        let (url, _) = recording_rpc(vec![
+            ("getSlot", serde_json::json!(200_000)),
+            ("getBlockHeight", serde_json::json!(180_000)),
+            ("getHighestSnapshotSlot", serde_json::json!({ "full": 100_000, "incremental": 140_000 })),
+            ("getMaxRetransmitSlot", serde_json::json!(200_004)),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let (data, _) = App::fetch_initial_blockchain_data(&rpc).await.unwrap();
+        let mut app = App::default();
+        app.dashboard_screen_mut().data = data;
+        let rows = |app: &App| -> Vec<Vec<String>> {
+            app.result_rows(&app.pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        let shown = rows(&app);
+        assert!(shown.contains(&vec![
+            "Block Height:".to_string(),
+            "180,000".to_string(),
+            "20,000 slots skipped".to_string()
+        ]));
+        assert!(shown.contains(&vec![
+            "Snapshots:".to_string(),
+            "full 100,000, incremental 140,000".to_string(),
+            "60,000 slots behind".to_string(),
+        ]));
+        assert!(shown.contains(&vec![
+            "Max Retransmit Slot:".to_string(),
+            "200,004".to_string(),
+            "4 ahead of the slot".to_string(),
+        ]));
+        assert_eq!(app.ledger_rows(app.dashboard_screen())[1].cells[2].spans[0].style.fg, app.theme.error.fg);
+
+        // Endpoints without the methods leave the rows out
+        app.dashboard_screen_mut().data.block_height = None;
+        app.dashboard_screen_mut().data.snapshot = None;
+        app.dashboard_screen_mut().data.max_retransmit_slot = None;
+        assert!(app.ledger_rows(app.dashboard_screen()).is_empty());
+    }
+
+    #[test]
+    fn non_circulating_accounts_are_listed_on_demand() {
+        let mut app = App::default();
+        app.dashboard_screen_mut().data.supply_info = Some(serde_json::json!({
+            "context": { "slot": 1 },
+            "value": {
+                "total": 600_000_000_400_000_000u64,
+                "circulating": 500_000_000_000_000_000u64,
+                "nonCirculating": 100_000_000_400_000_000u64,
+                "nonCirculatingAccounts": ["locked1", "locked2"],
+            },
+        }));
+        let rows = |app: &App| -> Vec<Vec<String>> {
+            app.result_rows(&app.pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        let shown = rows(&app);
+        assert!(shown.contains(&vec![
+            "Circulating Supply:".to_string(),
+            "◎ 500,000,000 / ◎ 600,000,000".to_string()
+        ]));
+        assert!(shown.contains(&vec![
+            "Non-Circulating Supply:".to_string(),
+            "◎ 100,000,000".to_string(),
+            "in 2 accounts, 'c' lists them".to_string(),
+        ]));
+        assert!(!app.move_selection(true));
+
+        app.perform(AppAction::ToggleNonCirculating);
+        let shown = rows(&app);
+        assert_eq!(shown[shown.len() - 2], ["1.", "locked1", ""]);
+        assert!(app.move_selection(true));
+        assert!(app.move_selection(true));
+        assert_eq!(app.selected_address().as_deref(), Some("locked2"));
+    }
+
+    #[test]
+    fn dashboard_shows_how_far_back_the_ledger_goes() {
+        let mut app = App::default();
+        app.dashboard_screen_mut().data.first_available_block = Some(40_000);
+        let row = |app: &App| -> Vec<String> {
+            let rows = app.result_rows(&app.pane).rows;
+            let row = rows.iter().find(|row| row.cells[0].to_string() == "First Available Block:").unwrap();
+            row.cells.iter().map(|cell| cell.to_string()).collect()
+        };
+        assert_eq!(row(&app), ["First Available Block:", "40,000", ""]);
+
+        app.dashboard_screen_mut().data.slot_info = Some(100_000);
+        app.dashboard_screen_mut().data.performance_samples = Some(vec![PerformanceSample {
+            num_transactions: 0,
+            num_slots: 150,
+            sample_period_secs: 60,
+        }]);
+        assert_eq!(row(&app), ["First Available Block:", "40,000", "60,000 slots of history, about 6h 40m"]);
+    }
+
+    #[test]
+    fn stake_accounts_show_their_delegation() {
+        let (staker, voter) = (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([4; 32]));
+        let meta = crate::stake::Meta {
+            rent_exempt_reserve: 2_282_880,
+            staker,
+            withdrawer: staker,
+            lockup: crate::stake::Lockup {
+                unix_timestamp: 0,
+                epoch: 0,
+                custodian: Pubkey::default(),
+            },
+        };
+        let delegation = crate::stake::Delegation {
+            voter,
+            stake: 5_000_000_000,
+            activation_epoch: 500,
+            deactivation_epoch: 610,
+        };
+        let state = crate::stake::StakeState::Stake(meta, delegation);
+        let pane = Pane {
+            query: "stake".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(serde_json::json!({
+                "lamports": 5_002_282_880u64,
+                "owner": crate::stake::STAKE_PROGRAM_ID,
+                "space": 200,
+                "executable": false,
+                "stake": rpc::stake_details(&state, Some(610), 1_700_000_000),
+            })),
+            ..Pane::default()
+        };
+        let app = App::default();
+        let rows: Vec<Vec<String>> = app
+            .search_rows(&pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let row = |cells: &[&str]| cells.iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+        assert!(rows.contains(&row(&["State:", "Delegated"])));
+        assert!(rows.contains(&row(&["Rent-Exempt Reserve:", "◎ 0.002282880"])));
+        assert!(rows.contains(&row(&["Staker:", &staker.to_string()])));
+        assert!(rows.contains(&row(&["Lockup:", "None"])));
+        assert!(!rows.iter().any(|row| row[0] == "Lockup Custodian:"));
+        assert!(rows.contains(&row(&["Delegated Vote Account:", &voter.to_string()])));
+        assert!(rows.contains(&row(&["Activation Epoch:", "500"])));
+        assert!(rows.contains(&row(&["Deactivation Epoch:", "610"])));
+        assert!(rows.contains(&row(&["Status:", "deactivating in epoch 610"])));
+        assert!(rows.contains(&row(&["Active Stake:", "◎ 5.000000000"])));
+        assert!(rows.contains(&row(&["Activating Stake:", "◎ 0.000000000"])));
+        assert!(rows.contains(&row(&["Deactivating Stake:", "◎ 5.000000000"])));
+
+        // Accounts with nothing but a state name
+        let pane = Pane {
+            state: QueryState::Loaded,
+            json_response: Some(serde_json::json!({
+                "lamports": 1,
+                "owner": crate::stake::STAKE_PROGRAM_ID,
+                "space": 200,
+                "stake": rpc::stake_details(&crate::stake::StakeState::RewardsPool, None, 0),
+            })),
+            ..pane
+        };
+        let rows: Vec<Vec<String>> = app
+            .search_rows(&pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert!(rows.contains(&row(&["State:", "Rewards Pool"])));
+        assert!(!rows.iter().any(|row| row[0] == "Staker:"));
+    }
+
+    #[test]
+    fn nft_mints_show_their_metadata() {
+        let mut pane = Pane {
+            query: "nftmint".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(serde_json::json!({
+                "lamports": 1_461_600,
+                "owner": token::TOKEN_PROGRAM_ID,
+                "space": 82,
+                "executable": false,
+                "mint": { "supply": 1, "decimals": 0, "mintAuthority": "edition", "freezeAuthority": "edition" },
+                "metadata": {
+                    "name": "Degen Ape #1",
+                    "symbol": "DAPE",
+                    "uri": "https://example.com/1.json",
+                    "sellerFeeBasisPoints": 500,
+                    "creators": [{ "address": "artist", "verified": true, "share": 100 }],
+                    "updateAuthority": "updater",
+                    "isMutable": true,
+                    "offChain": {
+                        "image": "https://example.com/1.png",
+                        "attributes": [{ "trait_type": "Fur", "value": "Gold" }],
+                    },
+                },
+            })),
+            ..Pane::default()
+        };
+        let app = App::default();
+        let cells = |pane: &Pane| -> Vec<Vec<String>> {
+            app.search_rows(pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        let rows = cells(&pane);
+        let row = |cells: &[&str]| cells.iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+        assert!(rows.contains(&row(&["Token Mint (NFT)"])));
+        assert!(rows.contains(&row(&["Name:", "Degen Ape #1"])));
+        assert!(rows.contains(&row(&["Symbol:", "DAPE"])));
+        assert!(rows.contains(&row(&["Royalties:", "5.00% (500 bps)"])));
+        assert!(rows.contains(&row(&["Mutable:", "Yes"])));
+        assert!(rows.contains(&row(&["Update Authority:", "updater"])));
+        assert!(rows.contains(&row(&["artist", "100%", "Yes"])));
+        assert!(rows.contains(&row(&["Image:", "https://example.com/1.png"])));
+        assert!(rows.contains(&row(&["Fur", "Gold"])));
+
+        pane.json_response.as_mut().unwrap()["metadata"] = serde_json::json!({ "error": "account data truncated at name" });
+        let rows = cells(&pane);
+        assert!(rows.contains(&row(&["Error:", "Undecodable (account data truncated at name)"])));
+    }
+
+    #[test]
+    fn block_view_lists_transactions() {
+        let block = serde_json::json!({
+            "blockhash": "hash",
+            "parentSlot": 99,
+            "blockHeight": 90,
+            "blockTime": 1_700_000_000,
+            "rewards": [{ "pubkey": "leader", "lamports": 5000, "rewardType": "Fee" }],
+            "transactions": [
+                { "transaction": { "signatures": ["sig1"] }, "meta": { "fee": 5000, "err": null } },
+                { "transaction": { "signatures": ["sig2"] }, "meta": { "fee": 7000, "err": { "InstructionError": [0, "Custom"] } } },
+            ],
+        });
+        let info = block_info(100, &block);
+        assert_eq!(info["block"]["totalFees"], 12_000);
+        assert_eq!(info["block"]["transactions"][1]["signature"], "sig2");
+
+        let pane = Pane {
+            query: "100".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(info),
+            ..Pane::default()
+        };
+        let app = App::default();
+        let table = app.search_rows(&pane);
+        assert_eq!(table.history_signatures, vec!["sig1", "sig2"]);
+        assert_eq!(table.selectable_rows().map(|(_, len)| len), Some(2));
+    }
+
+    #[test]
+    fn transaction_view_nests_inner_instructions() {
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let mut pane = Pane {
+            query: "2dg13SEjq2sHTdQUwCyFJ4TrComWtnDprNVPrtbvN9SmQPPu33cdsQVLDPjaYgebQr2LSRsVerQuDh2RuQur3GLM".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(rpc::transaction_details(&transaction)),
+            ..Pane::default()
+        };
+        let mut app = App::default();
+        let instruction_rows = |app: &App, pane: &Pane| -> Vec<String> {
+            let table = app.search_rows(pane);
+            let (start, len) = table.selectable_rows().unwrap();
+            table.rows[start..start + len].iter().map(|row| row.cells[0].to_string()).collect()
+        };
+        assert_eq!(
+            instruction_rows(&app, &pane),
+            [
+                "#0 Compute Budget (Comp…)",
+                "#1 SPL Token (Toke…)",
+                "#2 7PfVZpa…",
+                "↳ #2.0 SPL Token (Toke…)",
+                "  ↳ #2.1 Compute Budget (Comp…)",
+            ]
+        );
+
+        app.search_screen_mut().show_inner_instructions = false;
+        assert_eq!(instruction_rows(&app, &pane)[2], "#2 7PfVZpa… +2 inner");
+
+        // Nodes with inner instruction recording disabled send null
+        pane.json_response.as_mut().unwrap()["meta"]["innerInstructions"] = Value::Null;
+        let table = app.search_rows(&pane);
+        assert!(table
+            .rows
+            .iter()
+            .any(|row| row.cells[0].to_string() == "Inner instructions not recorded by this RPC node"));
+    }
+
+    #[test]
+    fn transaction_view_breaks_down_compute_and_fees() {
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let pane = Pane {
+            query: "2dg13SEjq2sHTdQUwCyFJ4TrComWtnDprNVPrtbvN9SmQPPu33cdsQVLDPjaYgebQr2LSRsVerQuDh2RuQur3GLM".to_string(),
+            state: QueryState::Loaded,
+            json_response: Some(rpc::transaction_details(&transaction)),
+            ..Pane::default()
+        };
+        let app = App::default();
+        let table = app.search_rows(&pane);
+        let value_of = |label: &str| {
+            table
+                .rows
+                .iter()
+                .find(|row| row.cells[0].to_string() == format!("  {}", label))
+                .map(|row| row.cells[1].to_string())
+        };
+        assert_eq!(value_of("Units consumed").as_deref(), Some("6,291"));
+        assert_eq!(value_of("Unit limit").as_deref(), Some("60,000 (requested)"));
+        assert_eq!(value_of("Utilization").as_deref(), Some("10.5%"));
+        assert_eq!(value_of("Unit price").as_deref(), Some("0 micro-lamports"));
+        assert_eq!(
+            value_of("Base fee"),
+            Some(format!("{} (1 × 5000)", format_lamports(5000, app.currency_unit)))
+        );
+        assert_eq!(value_of("Priority fee"), Some(format_lamports(0, app.currency_unit)));
+    }
+
+    #[tokio::test]
+    async fn help_popup_swallows_keys_and_shows_custom_bindings() {
+        let press = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let mut app = App::default();
+        app.keymap.bind((KeyCode::Char('x'), KeyModifiers::CONTROL), AppAction::Quit);
+        assert!(app
+            .help_lines()
+            .iter()
+            .any(|line| line.to_string().contains("Ctrl+X") && line.to_string().contains("Quit application")));
+
+        app.handle_event(press(KeyCode::Char('?')));
+        assert!(matches!(app.popup, Some(Popup::Help { scroll: 0 })));
+        // Keys that normally act are ignored while the help is open
+        app.handle_event(press(KeyCode::Char('q')));
+        assert!(!app.exit);
+        app.handle_event(press(KeyCode::Char('2')));
+        assert_eq!(app.tab, Tab::Dashboard);
+        app.handle_event(press(KeyCode::Down));
+        assert!(matches!(app.popup, Some(Popup::Help { scroll: 1 })));
+
+        app.handle_event(press(KeyCode::Esc));
+        assert!(app.popup.is_none());
+        assert!(!app.exit);
+    }
+
+    #[tokio::test]
+    async fn keys_are_handled_while_a_query_waits_on_a_slow_rpc() {
+        // A single runtime thread: a fetch blocking it would hold up everything below
+        let slow = mock_rpc(Duration::from_millis(800), serde_json::json!({ "blockhash": "slow" })).await;
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        app.pane.query = "100".to_string();
+        let previous = app.snapshot_view();
+        let rpc = RpcApi::new(slow, CommitmentConfig::default());
+        app.spawn_fetch_from(Arc::new(rpc), previous, false, false);
+
+        // Past the debounce, with the request in flight
+        let started_at = Instant::now();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE)));
+        assert!(started_at.elapsed() < Duration::from_millis(600));
+        assert!(matches!(app.popup, Some(Popup::Help { .. })));
+        assert!(app.pane.fetch_task.is_some());
+
+        // and the answer still arrives
+        apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+        assert_eq!(app.pane.json_response.as_ref().unwrap()["block"]["blockhash"], "slow");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn superseded_queries_never_replace_the_latest_result() {
+        let slow = mock_rpc(Duration::from_millis(800), serde_json::json!({ "blockhash": "slow" })).await;
+        let fast = mock_rpc(Duration::from_millis(50), serde_json::json!({ "blockhash": "fast" })).await;
+        let mut app = App::default();
+
+        // The first query is still loading (or waiting out the debounce) when the second starts
+        for (query, url, pause) in [("100", &slow, 150), ("200", &fast, 0)] {
+            app.pane.query = query.to_string();
+            let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+            let previous = app.snapshot_view();
+            app.spawn_fetch_from(Arc::new(rpc), previous, false, false);
+            tokio::time::sleep(Duration::from_millis(pause)).await;
+        }
+
+        apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+        // Wait past the slow answer too, applying anything it still sends
+        tokio::time::sleep(Duration::from_millis(1_200)).await;
+        while let Ok(update) = app.update_queue.as_mut().unwrap().try_recv() {
+            app.apply(update);
+        }
+        assert_eq!(app.pane.query, "200");
+        let response = app.pane.json_response.as_ref().expect("no result shown");
+        assert_eq!(response["block"]["blockhash"], "fast");
+        assert_eq!(response["block"]["slot"], 200);
+        assert!(app.error.is_none());
+        assert!(matches!(app.pane.state, QueryState::Loaded));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn failed_queries_keep_showing_their_error() {
+        // Every method fails on this node
+        let (url, _) = recording_rpc(vec![]).await;
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        let rows = |app: &App| -> Vec<Vec<String>> {
+            let table = app.search_rows(&app.pane);
+            table.rows.iter().map(|row| row.cells.iter().map(|cell| cell.to_string()).collect()).collect()
+        };
+        app.pane.query = "100".to_string();
+        assert_eq!(rows(&app)[0], ["Status:", "Not searched yet (press 'r' to search)"]);
+        let previous = app.snapshot_view();
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        app.spawn_fetch_from(Arc::new(rpc), previous, false, true);
+        assert!(matches!(&app.pane.state, QueryState::Loading { query, .. } if query == "100"));
+        assert!(rows(&app)[0][0].contains("Fetching block…"));
+
+        apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+        assert!(matches!(&app.pane.state, QueryState::Failed { query, .. } if query == "100"));
+        let cells = rows(&app);
+        assert!(cells[0][1].starts_with("Failed: "));
+        assert_eq!(cells[1][1], "Press 'r' to retry or 'e' to edit the query");
+
+        // Editing the query doesn't turn the failure into an endless load
+        app.pane.query = "200".to_string();
+        let cells = rows(&app);
+        assert!(cells[0][1].starts_with("Failed: "));
+        assert!(cells.contains(&vec!["Query:".to_string(), "100".to_string()]));
+        assert!(!cells.iter().flatten().any(|cell| cell.contains("Loading")));
+    }
+
+    // Applies what the background tasks report, as the event loop would, until `done` holds
+    async fn apply_updates_until(app: &mut App, done: impl Fn(&App) -> bool) {
+        let mut updates = app.update_queue.take().expect("the update queue is taken");
+        while !done(app) {
+            let update = tokio::time::timeout(Duration::from_secs(5), updates.recv()).await;
+            app.apply(update.expect("no update arrived in time").expect("the update channel closed"));
+        }
+        app.update_queue = Some(updates);
+    }
+
+    // getSignaturesForAddress entries for finalized transactions in slot 9
+    fn signature_list(signatures: &[&str]) -> Value {
+        signatures
+            .iter()
+            .map(|signature| {
+                serde_json::json!({
+                    "signature": signature,
+                    "slot": 9,
+                    "err": null,
+                    "memo": null,
+                    "blockTime": 1_700_000_000,
+                    "confirmationStatus": "finalized",
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn queries_not_found_can_be_searched_on_other_networks() {
+        let chain = MockChainClient::default().time_out("getTransaction");
+        assert!(!App::locate(&chain, &Pubkey::new_unique().to_string()).await.unwrap());
+        // Without a status the transaction itself is asked for, which times out here
+        let signature = Signature::from([7; 64]).to_string();
+        assert!(matches!(App::locate(&chain, &signature).await, Err(AppError::Timeout)));
+
+        let mut app = App {
+            tab: Tab::Search,
+            current_rpc_network: RpcNetwork::Testnet,
+            ..App::default()
+        };
+        assert_eq!(
+            app.other_networks(),
+            [RpcNetwork::Devnet, RpcNetwork::Mainnet, RpcNetwork::SolanaMainnet]
+        );
+        app.pane.query = signature.clone();
+        app.pane.state = QueryState::Failed {
+            query: signature,
+            error: AppError::NotFound("transaction".to_string()),
+        };
+        app.pane.network_search = TabData::Loaded(vec![
+            NetworkPresence {
+                network: RpcNetwork::Devnet,
+                found: Ok(false),
+            },
+            NetworkPresence {
+                network: RpcNetwork::SolanaMainnet,
+                found: Ok(true),
+            },
+            NetworkPresence {
+                network: RpcNetwork::Mainnet,
+                found: Err("The RPC timed out".to_string()),
+            },
+        ]);
+        let cells: Vec<Vec<String>> = app
+            .search_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert_eq!(
+            cells[0],
+            ["Status:", "Not found on Testnet (press 'a' to search all configured networks)"]
+        );
+        assert!(cells.contains(&vec!["Devnet:".to_string(), "Not found".to_string()]));
+        assert!(cells.contains(&vec!["Solana Mainnet:".to_string(), "Found (press 'n' to switch networks)".to_string()]));
+        assert!(cells.contains(&vec!["Mainnet:".to_string(), "Failed: The RPC timed out".to_string()]));
+
+        // 'a' searches rather than toggling the balances of a transaction
+        app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert!(!app.search_screen().show_unchanged_balances);
+        app.perform(AppAction::ToggleUnchangedBalances);
+        assert!(app.search_screen().show_unchanged_balances);
+    }
+
+    #[test]
+    fn failed_transactions_name_the_failing_instruction() {
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let mut details = rpc::transaction_details(&transaction);
+        assert_eq!(details["meta"]["status"], "Ok(())");
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        app.pane.query = "sig".to_string();
+        app.pane.state = QueryState::Loaded;
+        app.pane.json_response = Some(details.clone());
+        let status = |app: &App| {
+            let table = app.search_rows(&app.pane);
+            let row = table.rows.into_iter().find(|row| row.cells[0].to_string() == "Status:").unwrap();
+            (row.cells[1].to_string(), row.cells[1].spans[0].style.fg)
+        };
+        assert_eq!(status(&app), ("SUCCESS".to_string(), app.theme.success.fg));
+
+        details["meta"]["status"] = Value::from("Err(InstructionError(2, Custom(6001)))");
+        details["meta"]["err"] = serde_json::json!({ "InstructionError": [2, { "Custom": 6001 }] });
+        app.pane.json_response = Some(details);
+        assert_eq!(status(&app), ("FAILED".to_string(), app.theme.error.fg));
+        let cells: Vec<Vec<String>> = app
+            .search_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert!(cells.contains(&vec![
+            "Error:".to_string(),
+            "Instruction #2 failed: custom program error 0x1771 (6001)".to_string()
+        ]));
+        assert!(cells.contains(&vec!["Failed Instruction:".to_string(), "#2 (press 'F' to jump to it)".to_string()]));
+
+        app.perform(AppAction::JumpToFailedInstruction);
+        let table = app.search_rows(&app.pane);
+        let selected = table.instruction_start.unwrap() + app.pane.selected_history.unwrap();
+        assert!(table.rows[selected].cells[0].to_string().starts_with("#2 "));
+    }
+
+    #[tokio::test]
+    async fn fetches_report_back_through_updates() {
+        let address = Pubkey::new_unique();
+        let chain = MockChainClient::default()
+            .answer("getAccountInfo", address, serde_json::json!({ "lamports": 5, "owner": address.to_string() }));
+        let mut app = App {
+            tab: Tab::Search,
+            chain: Some(Arc::new(chain)),
+            ..App::default()
+        };
+        // Without an event loop draining the queue the updates can be looked at
+        let mut updates = app.update_queue.take().unwrap();
+        let search = |app: &mut App, query: &str| {
+            app.pane.query = query.to_string();
+            app.submit_query();
+        };
+
+        search(&mut app, &address.to_string());
+        let started = updates.recv().await.unwrap();
+        assert!(matches!(started, AppUpdate::FetchStarted { pane_id: 0, generation: 1 }));
+        let completed = updates.recv().await.unwrap();
+        let fetched = address.to_string();
+        assert!(matches!(&completed, AppUpdate::FetchCompleted { query, cached: false, .. } if *query == fetched));
+        assert!(matches!(app.pane.state, QueryState::Loading { .. }));
+        app.apply(started);
+        app.apply(completed);
+        assert!(matches!(app.pane.state, QueryState::Loaded));
+        assert!(app.pane.fetch_task.is_none());
+        assert_eq!(app.pane.json_response.as_ref().unwrap()["lamports"], 5);
+
+        // The cache answers without a request going out
+        search(&mut app, &address.to_string());
+        let completed = updates.recv().await.unwrap();
+        assert!(matches!(completed, AppUpdate::FetchCompleted { generation: 2, cached: true, .. }));
+        app.apply(completed);
+
+        search(&mut app, "not a query");
+        assert!(matches!(updates.recv().await, Some(AppUpdate::FetchStarted { generation: 3, .. })));
+        let failed = updates.recv().await.unwrap();
+        assert!(matches!(&failed, AppUpdate::FetchFailed { error: AppError::InvalidQuery(_), .. }));
+        // A stale update changes nothing
+        app.apply(AppUpdate::FetchStarted { pane_id: 0, generation: 2 });
+        app.apply(failed);
+        assert!(matches!(app.pane.state, QueryState::Failed { error: AppError::InvalidQuery(_), .. }));
+    }
+
+    #[tokio::test]
+    async fn searches_are_answered_by_the_injected_chain() {
+        let address = Pubkey::new_unique();
+        let signature = Signature::from([7; 64]).to_string();
+        let chain = MockChainClient::default()
+            .answer("getAccountInfo", address, serde_json::json!({ "lamports": 5, "owner": address.to_string() }))
+            .answer(
+                "getSignaturesForAddress",
+                address,
+                serde_json::json!([{ "signature": signature, "slot": 9, "err": null, "memo": null }]),
+            );
+        let chain = Arc::new(chain);
+        let mut app = App {
+            tab: Tab::Search,
+            chain: Some(chain.clone()),
+            ..App::default()
+        };
+        app.pane.query = address.to_string();
+        app.submit_query();
+        apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+
+        assert!(matches!(app.pane.state, QueryState::Loaded));
+        assert_eq!(app.pane.json_response.as_ref().unwrap()["lamports"], 5);
+        assert_eq!(app.pane.address_sign.as_ref().unwrap()[0].signature, signature);
+        assert_eq!(chain.calls(), ["getAccountInfo", "getSignaturesForAddress"]);
+    }
+
+    #[tokio::test]
+    async fn transactions_and_invalid_queries_are_fetched_through_the_chain() {
+        let signature = Signature::from([7; 64]);
+        let chain = MockChainClient::default().answer("getTransaction", signature, serde_json::json!({ "slot": 9 }));
+        let transaction = App::fetch_data(&chain, &signature.to_string()).await;
+        assert!(transaction.error.is_none());
+        assert_eq!(transaction.json_response.unwrap()["slot"], 9);
+        assert_eq!(chain.calls(), ["getTransaction"]);
+
+        let chain = MockChainClient::default();
+        let nonsense = App::fetch_data(&chain, "not a query").await;
+        assert!(matches!(nonsense.error, Some(AppError::InvalidQuery(_))));
+        assert!(nonsense.json_response.is_none());
+        assert!(chain.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn the_dashboard_keeps_what_the_chain_answers() {
+        let chain = MockChainClient::default()
+            .answer("getSlot", "", serde_json::json!(100))
+            .answer("getTransactionCount", "", serde_json::json!(7))
+            .answer("getEpochInfo", "", serde_json::json!({ "epoch": 3, "slotIndex": 4, "slotsInEpoch": 32 }))
+            .answer("getFeeForMessage", "", serde_json::json!(5_000));
+        let (data, errors) = App::fetch_initial_blockchain_data(&chain).await.unwrap();
+        assert_eq!(data.slot_info, Some(100));
+        assert_eq!(data.transaction_info, Some(7));
+        assert!(data.supply_info.is_none());
+        assert_eq!(data.epoch_info.map(|epoch| epoch.slot_index), Some(4));
+        assert_eq!(data.fees.map(|fees| fees.lamports_per_signature), Some(5_000));
+        assert!(matches!(errors.as_slice(), [AppError::RpcResponse { code: -32601, .. }]));
+
+        // Nothing the dashboard needs most: the refresh fails
+        assert!(App::fetch_initial_blockchain_data(&MockChainClient::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn missing_accounts_show_what_they_are_and_any_history() {
+        let closed = Pubkey::new_unique();
+        let chain = MockChainClient::default().answer("getSignaturesForAddress", closed, signature_list(&["closing"]));
+        let result = App::fetch_data(&chain, &closed.to_string()).await;
+        assert!(result.error.is_none());
+        assert_eq!(result.json_response.as_ref().unwrap()["missingAccount"], closed.to_string());
+
+        let mut app = App {
+            tab: Tab::Search,
+            current_rpc_network: RpcNetwork::Devnet,
+            ..App::default()
+        };
+        app.pane.query = closed.to_string();
+        app.pane.state = QueryState::Loaded;
+        app.pane.json_response = result.json_response;
+        app.pane.address_sign = result.address_sign;
+        let table = app.search_rows(&app.pane);
+        let cells: Vec<Vec<String>> =
+            table.rows.iter().map(|row| row.cells.iter().map(|cell| cell.to_string()).collect()).collect();
+        assert!(cells.contains(&vec![
+            "Status:".to_string(),
+            "Account not found on Devnet — it has no SOL and no data (it may still receive funds at this address)"
+                .to_string()
+        ]));
+        // A closed account keeps its transactions
+        assert_eq!(table.history_signatures, ["closing"]);
+        assert_eq!(app.pane.breadcrumbs(), "account");
+        assert!(app.pane.shows_not_found());
+
+        // An address that never had one says so and offers the other networks
+        app.pane.address_sign = Some(vec![]);
+        let cells: Vec<Vec<String>> = app
+            .search_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert!(cells.contains(&vec![
+            "History:".to_string(),
+            "No transactions (press 'a' to search all configured networks)".to_string()
+        ]));
+    }
+
+    // The fixture was recorded with --record; replaying it needs no network
+    #[tokio::test]
+    async fn recorded_account_queries_replay_end_to_end() {
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/replay/account");
+        let mut app = App {
+            tab: Tab::Search,
+            transport: Transport::Replay(fixtures),
+            ..App::default()
+        };
+        for query in ["9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM", "11111111111111111111111111111111"] {
+            app.pane.query = query.to_string();
+            app.submit_query();
+            apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+
+            let cells: Vec<Vec<String>> = app
+                .search_rows(&app.pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect();
+            if query == "11111111111111111111111111111111" {
+                // Never recorded: said so rather than asked of the network
+                assert!(matches!(app.pane.state, QueryState::Failed { .. }));
+                assert!(cells[0][1].contains("No recorded answer to getAccountInfo"), "{:?}", cells[0]);
+                continue;
+            }
+            assert!(matches!(app.pane.state, QueryState::Loaded));
+            assert!(cells.contains(&vec!["Balance:".to_string(), "◎ 2.500000000".to_string()]), "{:?}", cells);
+            assert_eq!(app.pane.address_sign.as_ref().map(Vec::len), Some(2));
+            let buffer = render_sized(&mut app, 120, 30);
+            assert_eq!(style_of(&buffer, "Failed").fg, app.theme.error.fg);
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_transactions_fall_back_to_their_status() {
+        let signature = Signature::from([7; 64]);
+        let (url, requests) = recording_rpc(vec![
+            ("getTransaction", Value::Null),
+            (
+                "getSignatureStatuses",
+                serde_json::json!({ "context": { "slot": 120 }, "value": [{
+                    "slot": 118, "confirmations": 2, "err": null, "status": { "Ok": null },
+                    "confirmationStatus": "confirmed",
+                }] }),
+            ),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let result = App::fetch_data(&rpc, &signature.to_string()).await;
+        assert!(result.error.is_none());
+        let calls = requests.lock().unwrap().clone();
+        assert_eq!(calls.iter().filter(|method| *method == "getTransaction").count(), 1 + TRANSACTION_RETRY_DELAYS.len());
+        assert!(calls.iter().any(|method| method == "getSignatureStatuses"));
+
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        app.pane.query = signature.to_string();
+        app.pane.json_response = result.json_response;
+        app.pane.state = QueryState::Loaded;
+        let cells: Vec<Vec<String>> = app
+            .search_rows(&app.pane)
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        assert!(cells.contains(&vec!["Slot:".to_string(), "118".to_string()]));
+        assert!(cells.contains(&vec!["Confirmations:".to_string(), "2".to_string()]));
+        assert!(cells.contains(&vec!["Confirmation Status:".to_string(), "confirmed".to_string()]));
+        assert!(cells.contains(&vec!["Full details not yet available — press r to retry".to_string()]));
+
+        // A status older than the ledger of the node means the block was pruned
+        let (url, _) = recording_rpc(vec![
+            ("getTransaction", Value::Null),
+            (
+                "getSignatureStatuses",
+                serde_json::json!({ "context": { "slot": 120 }, "value": [{
+                    "slot": 18, "confirmations": null, "err": null, "status": { "Ok": null },
+                    "confirmationStatus": "finalized",
+                }] }),
+            ),
+            ("getFirstAvailableBlock", serde_json::json!(100)),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let result = App::fetch_data(&rpc, &signature.to_string()).await;
+        let error = result.error.unwrap();
+        assert!(matches!(error, AppError::Pruned { first_available: 100 }));
+        assert_eq!(
+            error.to_string(),
+            "This RPC only serves blocks ≥ 100; the transaction may exist but is pruned here"
+        );
+
+        // Without a status either the signature is simply not found
+        let (url, _) = recording_rpc(vec![
+            ("getTransaction", Value::Null),
+            ("getSignatureStatuses", serde_json::json!({ "context": { "slot": 120 }, "value": [null] })),
+        ])
+        .await;
+        let rpc = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        let result = App::fetch_data(&rpc, &signature.to_string()).await;
+        assert!(matches!(result.error, Some(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn block_view_shows_the_slot_leader_once_looked_up() {
+        let identity = Pubkey::new_unique();
+        let schedule = serde_json::json!({ "slotsPerEpoch": 32, "leaderScheduleSlotOffset": 32, "warmup": false });
+        let chain = MockChainClient::default()
+            .answer("getEpochSchedule", "", schedule)
+            .answer("getSlotLeaders", 40, Value::from(identity.to_string()));
+        // Epochs of 32 slots: slot 40 is the ninth of epoch 1
+        let leader = App::fetch_slot_leader(&chain, 40).await.unwrap();
+        assert_eq!(
+            leader,
+            serde_json::json!({ "identity": identity.to_string(), "epoch": 1, "slotIndex": 8 })
+        );
+        let unknown = App::fetch_slot_leader(&chain, 100).await.unwrap();
+        assert_eq!(unknown["identity"], Value::Null);
+
+        let mut app = App {
+            tab: Tab::Search,
+            ..App::default()
+        };
+        app.pane.query = "40".to_string();
+        app.pane.json_response = Some(block_info(40, &serde_json::json!({ "blockhash": "hash" })));
+        app.pane.state = QueryState::Loaded;
+        let cells = |app: &App| -> Vec<Vec<String>> {
+            app.search_rows(&app.pane)
+                .rows
+                .iter()
+                .map(|row| row.cells.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        assert!(cells(&app).contains(&vec!["Leader:".to_string(), "Press 'P' to look it up".to_string()]));
+
+        app.pane.json_response.as_mut().unwrap()["block"]["leader"] = leader;
+        assert!(cells(&app).contains(&vec![
+            "Leader:".to_string(),
+            identity.to_string(),
+            "epoch 1, slot 8 of the epoch".to_string(),
+        ]));
+
+        app.pane.json_response = Some(serde_json::json!({ "block": { "slot": 100, "skipped": true, "leader": unknown } }));
+        app.pane.state = QueryState::Loaded;
+        assert!(cells(&app).contains(&vec![
+            "Scheduled Leader:".to_string(),
+            "Unknown, the node has no schedule for this epoch".to_string(),
+            "epoch 3, slot 4 of the epoch".to_string(),
+        ]));
+    }
+
+    #[tokio::test]
+    async fn queries_are_fetched_through_the_chain_api() {
+        let address = Pubkey::new_unique();
+        let missing = Pubkey::new_unique();
+        let overview =
+            serde_json::json!({ "lamports": 5, "owner": address.to_string(), "space": 0, "executable": false });
+        let chain = MockChainClient::default()
+            .answer("getAccountInfo", address, overview.clone())
+            .answer("getMultipleAccounts", address, overview)
+            .answer("getSignaturesForAddress", address, signature_list(&["sig1", "sig2"]))
+            .answer("getSignaturesForAddress", missing, signature_list(&["sig1", "sig2"]))
+            .time_out("getTransaction");
+
+        let account = App::fetch_data(&chain, &address.to_string()).await;
+        assert!(account.error.is_none());
+        assert_eq!(account.json_response.unwrap()["lamports"], 5);
+        let history = account.address_sign.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].signature, "sig1");
+        assert_eq!(history[0].confirmation_status.as_deref(), Some("finalized"));
+
+        let block = App::fetch_data(&chain, "42").await;
+        assert_eq!(block.json_response.unwrap()["block"], serde_json::json!({ "slot": 42, "skipped": true }));
+
+        // A missing account isn't a failure; its history is still looked up
+        let closed = App::fetch_data(&chain, &missing.to_string()).await;
+        assert!(closed.error.is_none());
+        assert!(closed.json_response.unwrap().get("missingAccount").is_some());
+        assert_eq!(closed.address_sign.unwrap().len(), 2);
+
+        let transaction = App::fetch_data(&chain, &Signature::default().to_string()).await;
+        assert!(matches!(transaction.error, Some(AppError::Timeout)));
+
+        let owners = App::fetch_key_accounts(&chain, &[address.to_string(), missing.to_string()]).await.unwrap();
+        assert_eq!(
+            owners,
+            [serde_json::json!({ "owner": address.to_string(), "executable": false }), Value::Null]
+        );
+        assert!(matches!(
+            App::fetch_key_accounts(&chain, &["payer".to_string()]).await,
+            Err(AppError::PubkeyParse(_))
+        ));
+
+        let nonsense = App::fetch_data(&chain, "not a query").await;
+        assert!(matches!(nonsense.error, Some(AppError::InvalidQuery(ref query)) if query == "not a query"));
+    }
+
+    #[tokio::test]
+    async fn the_dashboard_and_tabs_are_fetched_through_the_chain_api() {
+        let chain = Arc::new(
+            MockChainClient::default()
+                .answer("getSlot", "", serde_json::json!(100))
+                .answer("getHealth", "", serde_json::json!("ok"))
+                .answer("getGenesisHash", "", serde_json::json!("genesis"))
+                .answer("getBlocks", "", serde_json::json!([99, 100]))
+                .answer("getBlock", "", serde_json::json!({ "blockhash": "hash", "signatures": ["a", "b"] }))
+                .answer("getVoteAccounts", "", serde_json::json!({ "current": [], "delinquent": [] }))
+                .answer("getBlockProduction", "", serde_json::json!({ "range": { "firstSlot": 64, "lastSlot": 100 } })),
+        );
+        let mut app = App {
+            chain: Some(chain.clone()),
+            ..App::default()
+        };
+        app.refresh_dashboard();
+        apply_updates_until(&mut app, |app| app.dashboard_screen().last_refresh.is_some()).await;
+        assert_eq!(app.dashboard_screen().data.slot_info, Some(100));
+        assert_eq!(app.health, ClusterHealth::Ok);
+        assert_eq!(app.genesis_hash.as_deref(), Some("genesis"));
+
+        for tab in [Tab::Blocks, Tab::Validators] {
+            app.set_tab(tab);
+        }
+        apply_updates_until(&mut app, |app| {
+            matches!(app.blocks_screen().blocks, TabData::Loaded(_))
+                && matches!(app.validators_screen().validators, TabData::Loaded(_))
+                && matches!(app.validators_screen().block_production, TabData::Loaded(_))
+        })
+        .await;
+        let TabData::Loaded(blocks) = &app.blocks_screen().blocks else {
+            panic!("recent blocks not loaded");
+        };
+        let blocks: Vec<(u64, usize)> = blocks.iter().map(|block| (block.slot, block.transaction_count)).collect();
+        assert_eq!(blocks, [(100, 2), (99, 2)]);
+        assert!(matches!(app.validators_screen().validators, TabData::Loaded(ref validators) if validators.is_empty()));
+        let production = &app.validators_screen().block_production;
+        assert!(matches!(production, TabData::Loaded(production) if production.first_slot == 64));
+    }
+
+    #[test]
+    fn failures_are_summed_up_by_kind() {
+        assert_eq!(
+            failure_status(&AppError::NotFound("Account x".to_string()), "account"),
+            (StatusLevel::Warning, "No such account".to_string())
+        );
+        assert_eq!(
+            failure_status(&AppError::InvalidQuery("x".to_string()), "query"),
+            (StatusLevel::Warning, "Not a slot, address or signature".to_string())
+        );
+        assert_eq!(
+            failure_status(&AppError::Timeout, "block"),
+            (StatusLevel::Error, "The RPC timed out".to_string())
+        );
+        let response = AppError::RpcResponse { code: -32602, message: "Invalid params".to_string() };
+        assert_eq!(failure_status(&response, "block"), (StatusLevel::Error, "Failed to load block".to_string()));
+    }
+
+    #[tokio::test]
+    async fn dashboard_requests_go_to_the_selected_network() {
+        let answers = || {
+            vec![
+                ("getSlot", serde_json::json!(42)),
+                ("getSupply", serde_json::json!({ "value": { "total": 1 } })),
+                ("getTransactionCount", serde_json::json!(7)),
+            ]
+        };
+        let (first, first_requests) = recording_rpc(answers()).await;
+        let (second, second_requests) = recording_rpc(answers()).await;
+        let mut app = App {
+            current_rpc_network: RpcNetwork::Custom(first),
+            ..App::default()
+        };
+
+        app.refresh_dashboard();
+        apply_updates_until(&mut app, |app| app.dashboard_screen().last_refresh.is_some()).await;
+        app.dashboard_screen_mut().last_refresh = None;
+        // Switching networks refetches the dashboard from the new one
+        app.set_rpc_network(RpcNetwork::Custom(second));
+        apply_updates_until(&mut app, |app| app.dashboard_screen().last_refresh.is_some()).await;
+
+        for requests in [first_requests, second_requests] {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.iter().filter(|method| *method == "getTransactionCount").count(), 1);
+        }
+        assert_eq!(app.dashboard_screen().data.transaction_info, Some(7));
+    }
+
+    #[tokio::test]
+    async fn a_failed_dashboard_request_keeps_the_rest() {
+        // getSupply goes unanswered
+        let (url, _) = recording_rpc(vec![
+            ("getSlot", serde_json::json!(42)),
+            ("getTransactionCount", serde_json::json!(7)),
+        ])
+        .await;
+        let mut app = App {
+            current_rpc_network: RpcNetwork::Custom(url),
+            ..App::default()
+        };
+        app.dashboard_screen_mut().data.supply_info = Some(serde_json::json!({ "value": { "total": 1 } }));
+
+        app.refresh_dashboard();
+        apply_updates_until(&mut app, |app| app.dashboard_screen().last_refresh.is_some()).await;
+        assert_eq!(app.dashboard_screen().data.slot_info, Some(42));
+        assert_eq!(app.dashboard_screen().data.transaction_info, Some(7));
+        assert_eq!(app.dashboard_screen().data.supply_info, Some(serde_json::json!({ "value": { "total": 1 } })));
+        assert!(app.dashboard_screen().last_refresh.is_some());
+        let status = app.status.as_ref().unwrap();
+        assert_eq!(status.level, StatusLevel::Warning);
+        assert_eq!(status.text, "Dashboard partly refreshed: RPC error -32601: Method not found");
+    }
+
+    #[tokio::test]
+    async fn searches_use_the_selected_network() {
+        let block = || vec![("getBlock", serde_json::json!({ "blockhash": "hash" }))];
+        let (first, first_requests) = recording_rpc(block()).await;
+        let (second, second_requests) = recording_rpc(block()).await;
+        let mut app = App {
+            current_rpc_network: RpcNetwork::Custom(first),
+            ..App::default()
+        };
+        async fn search(app: &mut App, query: &str) {
+            app.pane.query = query.to_string();
+            app.submit_query();
+            // Behind the dashboard refresh's requests to the same rate limit
+            apply_updates_until(app, |app| app.pane.fetch_task.is_none()).await;
+        }
+        let get_blocks = |requests: &Arc<std::sync::Mutex<Vec<String>>>| {
+            requests.lock().unwrap().iter().filter(|method| *method == "getBlock").count()
+        };
+
+        search(&mut app, "100").await;
+        assert_eq!(get_blocks(&first_requests), 1);
+        assert!(app.pane.json_response.is_some());
+
+        // The block shown came from the first network, so switching clears it
+        app.set_rpc_network(RpcNetwork::Custom(second));
+        assert!(app.pane.json_response.is_none());
+        assert_eq!(app.pane.query, "100");
+        search(&mut app, "100").await;
+        assert_eq!(get_blocks(&first_requests), 1);
+        assert_eq!(get_blocks(&second_requests), 1);
+        assert_eq!(app.pane.json_response.as_ref().unwrap()["block"]["blockhash"], "hash");
+    }
+
+    #[tokio::test]
+    async fn pasted_links_are_searched_for_their_query() {
+        let (url, _) = recording_rpc(vec![("getBlock", serde_json::json!({ "blockhash": "hash" }))]).await;
+        let mut app = App {
+            current_rpc_network: RpcNetwork::Custom(url),
+            ..App::default()
+        };
+        app.pane.query = " https://explorer.solana.com/block/100?cluster=devnet\n".to_string();
+        app.submit_query();
+        assert_eq!(app.pane.query, "100");
+        assert_eq!(app.status.as_ref().map(|status| status.text.as_str()), Some("Interpreted as block 100"));
+    }
+
+    #[tokio::test]
+    async fn repeated_searches_are_answered_from_the_cache() {
+        let (url, requests) = recording_rpc(vec![("getBlock", serde_json::json!({ "blockhash": "hash" }))]).await;
+        let mut app = App {
+            current_rpc_network: RpcNetwork::Custom(url),
+            ..App::default()
+        };
+        let get_blocks = || requests.lock().unwrap().iter().filter(|method| *method == "getBlock").count();
+
+        for _ in 0..2 {
+            app.pane.query = "100".to_string();
+            app.submit_query();
+            apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+        }
+        assert_eq!(get_blocks(), 1);
+        assert_eq!(app.status.as_ref().unwrap().text, "Loaded block from the cache");
+        {
+            let cache = app.query_cache.lock().unwrap();
+            assert_eq!((cache.hits, cache.misses), (1, 1));
+        }
+
+        // 'r' goes to the RPC again
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE)));
+        apply_updates_until(&mut app, |app| app.pane.fetch_task.is_none()).await;
+        assert_eq!(get_blocks(), 2);
+        assert_eq!(app.pane.json_response.as_ref().unwrap()["block"]["blockhash"], "hash");
+    }
+
+    #[tokio::test]
+    async fn quit_stops_fetches_waiting_on_the_network() {
+        // A server that accepts connections and never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        let mut app = App {
+            current_rpc_network: RpcNetwork::Custom(url),
+            ..App::default()
+        };
+
+        app.refresh_dashboard();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let dashboard = app.dashboard_task.take().unwrap();
+        assert!(!dashboard.is_finished());
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(app.exit);
+        tokio::time::timeout(Duration::from_secs(2), dashboard)
+            .await
+            .expect("fetch still waiting after quit")
+            .unwrap();
+        // and nothing came of it
+        assert!(app.update_queue.as_mut().unwrap().try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn split_view_keeps_a_query_per_pane() {
+        let press = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let block = |slot, signature: &str| {
+            block_info(slot, &serde_json::json!({ "transactions": [{ "transaction": { "signatures": [signature] } }] }))
+        };
+        let mut app = App {
+            tab: Tab::Search,
+            pane: Pane {
+                query: "100".to_string(),
+                state: QueryState::Loaded,
+                json_response: Some(block(100, "leftsig")),
+                ..Pane::default()
+            },
+            ..App::default()
+        };
+
+        // The new pane opens on the right with focus
+        app.handle_event(press(KeyCode::Char('|')));
+        assert_eq!(app.pane.id, 1);
+        assert!(app.pane.query.is_empty());
+        app.pane.query = "200".to_string();
+        app.pane.json_response = Some(block(200, "rightsig"));
+        app.pane.state = QueryState::Loaded;
+        let buffer = render_sized(&mut app, 120, 30);
+        let left = app.layout.other_pane_area;
+        let right = app.layout.results_area;
+        assert!(left.x < right.x);
+        for (text, area) in [("leftsig", left), ("rightsig", right)] {
+            let column = (0..buffer.area.height)
+                .find_map(|y| {
+                    let line: String = (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect();
+                    line.find(text).map(|i| line[..i].chars().count() as u16)
+                })
+                .unwrap();
+            assert!(column >= area.x && column < area.right(), "{} outside its pane", text);
+        }
+
+        // Tab moves focus, and navigation follows it
+        app.handle_event(press(KeyCode::Tab));
+        app.handle_event(press(KeyCode::Down));
+        assert_eq!((app.pane.id, app.pane.query.as_str()), (0, "100"));
+        assert_eq!(app.pane.selected_history, Some(0));
+        assert_eq!(app.split.as_ref().unwrap().selected_history, None);
+        assert_eq!(app.tab, Tab::Search);
+
+        // Closing keeps the focused pane
+        app.handle_event(press(KeyCode::Char('|')));
+        assert!(app.split.is_none());
+        assert_eq!(app.pane.query, "100");
+    }
+
+    #[test]
+    fn slot_events_switch_between_live_and_polling() {
+        let mut app = App::default();
+        app.apply(AppUpdate::SlotTick(SlotEvent::Connected));
+        app.apply(AppUpdate::SlotTick(SlotEvent::Slot(41)));
+        app.apply(AppUpdate::SlotTick(SlotEvent::Slot(42)));
+        assert_eq!(app.slot_feed, SlotFeed::Live);
+        assert_eq!(app.dashboard_screen().data.slot_info, Some(42));
+        style_of(&render(&mut app), "● live");
+
+        app.apply(AppUpdate::SlotTick(SlotEvent::Disconnected("connection closed".to_string())));
+        assert_eq!(app.slot_feed, SlotFeed::Polling);
+        assert_eq!(app.status.as_ref().map(|status| status.level), Some(StatusLevel::Warning));
+        style_of(&render(&mut app), "polling");
+    }
+
+    #[tokio::test]
+    async fn watched_transactions_go_on_top_of_the_history() {
+        let entry = |signature: &str, slot| HistoryEntry {
+            signature: signature.to_string(),
+            slot,
+            block_time: None,
+            confirmation_status: None,
+            err: None,
+            memo: None,
+        };
+        let address = Pubkey::new_unique();
+        let mut app = App {
+            tab: Tab::Search,
+            pane: Pane {
+                query: address.to_string(),
+                address_sign: Some(vec![entry("second", 2), entry("first", 1)]),
+                selected_history: Some(1),
+                watch: Some(AddressWatch {
+                    handle: tokio::spawn(async {}),
+                    live: true,
+                }),
+                ..Pane::default()
+            },
+            ..App::default()
+        };
+
+        // Known signatures are skipped and the selection stays on "first"
+        app.add_watched_transactions(0, &address, vec![entry("third", 3), entry("second", 2)]);
+        let signatures: Vec<&str> = app.visible_history(&app.pane).iter().map(|entry| entry.signature.as_str()).collect();
+        assert_eq!(signatures, ["third", "second", "first"]);
+        assert_eq!(app.pane.selected_history, Some(2));
+        assert!(app.pane.arrivals.contains_key("third"));
+        assert_eq!(app.activity().as_deref(), Some("watching live"));
+
+        // What a watch for another account, or one that was stopped, finds is dropped
+        app.add_watched_transactions(0, &Pubkey::new_unique(), vec![entry("other", 5)]);
+        assert_eq!(app.pane.address_sign.as_ref().map(Vec::len), Some(3));
+        app.set_watch_mode(0, false, Some("connection closed".to_string()));
+        assert_eq!(app.status.as_ref().map(|status| status.level), Some(StatusLevel::Warning));
+        assert_eq!(app.activity().as_deref(), Some("watching, polling"));
+        assert!(app.pane.stop_watch());
+        app.add_watched_transactions(0, &address, vec![entry("fourth", 4)]);
+        assert_eq!(app.pane.address_sign.as_ref().map(Vec::len), Some(3));
+        assert!(app.pane.arrivals.is_empty());
+    }
+
+    #[test]
+    fn signature_progress_follows_the_status() {
+        use solana_sdk::transaction::TransactionError;
+        let status = |level, err: Option<TransactionError>| TransactionStatus {
+            slot: 7,
+            confirmations: Some(1),
+            status: err.clone().map_or(Ok(()), Err),
+            err,
+            confirmation_status: level,
+        };
+        assert_eq!(SignatureProgress::from_status(None), SignatureProgress::NotFound);
+        let processed = status(Some(TransactionConfirmationStatus::Processed), Some(TransactionError::AccountInUse));
+        assert_eq!(SignatureProgress::from_status(Some(&processed)), SignatureProgress::Processed);
+        let confirmed = status(Some(TransactionConfirmationStatus::Confirmed), None);
+        assert_eq!(SignatureProgress::from_status(Some(&confirmed)), SignatureProgress::Confirmed);
+        let failed = status(Some(TransactionConfirmationStatus::Finalized), Some(TransactionError::AccountInUse));
+        assert!(matches!(SignatureProgress::from_status(Some(&failed)), SignatureProgress::Failed(_)));
+        // Without a level, a rooted slot means finalized
+        let rooted = TransactionStatus {
+            confirmations: None,
+            ..status(None, None)
+        };
+        assert_eq!(SignatureProgress::from_status(Some(&rooted)), SignatureProgress::Finalized);
+        assert!(!SignatureProgress::Processed.is_done());
+    }
+
+    #[tokio::test]
+    async fn finished_signature_watches_ring_and_show_a_banner() {
+        let press = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let watch = |signature: &str, network| SignatureWatch {
+            signature: signature.to_string(),
+            network,
+            progress: SignatureProgress::NotFound,
+            slot: None,
+            handle: tokio::spawn(async {}),
+        };
+        let mut app = App {
+            signature_watches: vec![watch("pendingsig", RpcNetwork::Devnet), watch("landingsig", RpcNetwork::Testnet)],
+            current_rpc_network: RpcNetwork::Devnet,
+            ..App::default()
+        };
+        app.apply(AppUpdate::SignatureStatus {
+            signature: "pendingsig".to_string(),
+            progress: SignatureProgress::Processed,
+            slot: Some(9),
+        });
+        assert!(!app.bell);
+        app.apply(AppUpdate::SignatureStatus {
+            signature: "landingsig".to_string(),
+            progress: SignatureProgress::Confirmed,
+            slot: Some(10),
+        });
+        assert!(app.bell);
+        // A watch that was dropped meanwhile is ignored
+        app.update_signature_watch("unknownsig", SignatureProgress::Confirmed, None);
+        assert_eq!(app.signature_watches.len(), 2);
+        assert_eq!(app.finished_watch.as_deref(), Some("landingsig"));
+
+        let buffer = render(&mut app);
+        assert_eq!(style_of(&buffer, "Transaction confirmed").fg, app.theme.success.fg);
+        style_of(&buffer, "confirmed on Testnet in slot 10");
+        style_of(&buffer, "pendings… Devnet");
+
+        // Any other key is swallowed by the banner; Esc drops the finished watch
+        app.handle_event(press(KeyCode::Char('e')));
+        app.handle_event(press(KeyCode::Esc));
+        assert!(matches!(app.input.mode, InputMode::Normal));
+        assert!(app.finished_watch.is_none());
+        let signatures: Vec<&str> = app.signature_watches.iter().map(|watch| watch.signature.as_str()).collect();
+        assert_eq!(signatures, ["pendingsig"]);
+    }
+
+    #[tokio::test]
+    async fn raw_view_shows_the_result_as_a_tree() {
+        let press = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        let mut app = App {
+            tab: Tab::Search,
+            pane: Pane {
+                query: "100".to_string(),
+                state: QueryState::Loaded,
+                json_response: Some(serde_json::json!({ "fee": 5000, "meta": { "logs": ["hello"] } })),
+                ..Pane::default()
+            },
+            ..App::default()
+        };
+
+        // Down moves the tree cursor and Enter folds result.meta instead of opening a row
+        for c in ['J', 'j', 'j'] {
+            app.handle_event(press(c));
+        }
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert_eq!(app.pane.scroll_offset, 0);
+        let buffer = render(&mut app);
+        style_of(&buffer, "raw JSON");
+        style_of(&buffer, "▸ meta: {1 key}");
+        assert_eq!(style_of(&buffer, "5000").fg, app.theme.value.fg);
+
+        app.toggle_raw_view();
+        assert!(app.pane.raw_view.is_none());
+    }
+
+    #[test]
+    fn history_exports_as_csv() {
+        let entry = HistoryEntry {
+            signature: "sig1".to_string(),
+            slot: 42,
+            block_time: Some(1_700_000_000),
+            confirmation_status: Some("finalized".to_string()),
+            err: Some(serde_json::json!({ "InstructionError": [0, "Custom"] })),
+            memo: Some("hi, \"you\"".to_string()),
+        };
+        assert_eq!(
+            history_csv(&[&entry]),
+            "signature,slot,block_time,confirmation_status,result,error,memo\n\
+             sig1,42,2023-11-14T22:13:20+00:00,finalized,failed,\"{\"\"InstructionError\"\":[0,\"\"Custom\"\"]}\",\"hi, \"\"you\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn export_describes_itself_and_asks_before_overwriting() {
+        let path = std::env::temp_dir().join(format!("soonscan-export-{}.json", std::process::id()));
+        let path_text = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+        let mut app = App {
+            pane: Pane {
+                query: "100".to_string(),
+                state: QueryState::Loaded,
+                json_response: Some(serde_json::json!({ "slot": 100 })),
+                ..Pane::default()
+            },
+            ..App::default()
+        };
+
+        app.export_result(&path_text);
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["result"]["slot"], 100);
+        assert_eq!(written["export"]["query"], "100");
+        assert_eq!(written["export"]["rpcUrl"], DEVNET_RPC);
+        assert!(written["export"]["exportedAt"].is_string());
+
+        // The file exists now: anything but 'y' keeps it
+        app.pane.json_response = Some(serde_json::json!({ "slot": 200 }));
+        app.pane.state = QueryState::Loaded;
+        app.export_result(&path_text);
+        assert!(matches!(&app.input.prompt, Some(Prompt::ConfirmExport(path)) if *path == path_text));
+        style_of(&render(&mut app), "Overwrite soonscan-export-");
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("100"));
+
+        app.export_result(&path_text);
+        app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("200"));
+        std::fs::remove_file(&path).unwrap();
+
+        // Errors from the file system end up in the status bar
+        app.export_result("/nonexistent-dir/result.json");
+        assert_eq!(app.status.as_ref().map(|status| status.level), Some(StatusLevel::Error));
+        app.export_result("history.csv");
+        assert!(app.status.as_ref().unwrap().text.contains("CSV"));
+    }
+
+    #[tokio::test]
+    async fn terminal_paste_arrives_in_one_event() {
+        let mut app = App::default();
+        // Pasting in normal mode starts editing, without the trailing newline
+        app.handle_event(Event::Paste("  5VfYmGBj\n".to_string()));
+        app.handle_event(Event::Paste("Vw2\r\n".to_string()));
+        assert!(matches!(app.input.mode, InputMode::Editing));
+        assert_eq!((app.pane.query.as_str(), app.input.cursor), ("5VfYmGBjVw2", 11));
+
+        // Open prompts take the paste instead of the query
+        app.input.mode = InputMode::Normal;
+        app.input.prompt = Some(Prompt::Command("export ".to_string()));
+        app.handle_paste("out.json\n");
+        assert!(matches!(&app.input.prompt, Some(Prompt::Command(input)) if input == "export out.json"));
+        app.input.prompt = None;
+        app.popup = Some(Popup::HistoryDetail(HistoryEntry {
+            signature: "sig".to_string(),
+            slot: 1,
+            block_time: None,
+            confirmation_status: None,
+            err: None,
+            memo: None,
+        }));
+        app.handle_paste("ignored");
+        assert_eq!(app.pane.query, "5VfYmGBjVw2");
+    }
+
+    #[tokio::test]
+    async fn vim_keys_drive_the_app() {
+        let press = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        let transactions: Vec<Value> = (0..40)
+            .map(|i| serde_json::json!({ "transaction": { "signatures": [format!("sig{}", i)] }, "meta": { "fee": 5000 } }))
+            .collect();
+        let mut app = App {
+            tab: Tab::Search,
+            pane: Pane {
+                query: "100".to_string(),
+                state: QueryState::Loaded,
+                json_response: Some(block_info(100, &serde_json::json!({ "transactions": transactions }))),
+                results_height: 10,
+                ..Pane::default()
+            },
+            ..App::default()
+        };
+
+        for c in ['j', 'j', 'k'] {
+            app.handle_event(press(c));
+            assert!(!app.exit);
+        }
+        assert_eq!(app.pane.selected_history, Some(0));
+
+        app.handle_event(press('G'));
+        let bottom = app.max_scroll_offset();
+        assert_eq!(app.pane.scroll_offset, bottom);
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL)));
+        assert_eq!(app.pane.scroll_offset, bottom - 5);
+        app.handle_event(press('g'));
+        assert_eq!(app.pane.scroll_offset, 0);
+
+        app.handle_event(press('q'));
+        assert!(app.exit);
+    }
+
+    #[tokio::test]
+    async fn command_line_runs_actions() {
+        let mut app = App::default();
+        let type_command = |text: &str| {
+            let mut events = vec![Event::Key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE))];
+            events.extend(text.chars().map(|c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))));
+            events.push(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+            events
+        };
+
+        for event in type_command("commitment confirmed") {
+            app.handle_event(event);
+        }
+        assert_eq!(app.commitment, CommitmentConfig::confirmed());
+
+        for event in type_command("frobnicate") {
+            app.handle_event(event);
+        }
+        let status = app.status.clone().unwrap();
+        assert_eq!(status.level, StatusLevel::Error);
+        assert_eq!(status.text, "Unknown command: frobnicate");
+        assert!(app.input.prompt.is_none());
+    }
+
+    #[test]
+    fn status_bar_shows_network_and_messages() {
+        let mut app = App::default();
+        app.notify(StatusLevel::Error, "Unknown command: foo");
+        app.genesis_hash = Some("EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG".to_string());
+        let buffer = render_sized(&mut app, 120, 12);
+        assert_eq!(style_of(&buffer, "genesis EtWTRABZ ").fg, app.theme.title.fg);
+        assert_eq!(style_of(&buffer, "Devnet (").fg, app.theme.success.fg);
+        assert_eq!(style_of(&buffer, "rpc.devnet.soo.network").fg, app.theme.title.fg);
+        assert_eq!(style_of(&buffer, "Unknown command: foo").fg, app.theme.error.fg);
+        let screen: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(!screen.contains("throttled"));
+
+        // A 429 holds requests back for a while
+        app.rate_limiter.pause(Duration::from_secs(5));
+        let buffer = render_sized(&mut app, 120, 12);
+        assert_eq!(style_of(&buffer, "throttled").fg, app.theme.value.fg);
+    }
 }
@@ -0,0 +1,109 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::app::RpcNetwork;
+use crate::storage;
+
+// A saved query together with the network it belongs to
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub label: String,
+    pub query: String,
+    pub network: RpcNetwork,
+}
+
+// Bookmarks saved as a JSON array, in the order they were added
+#[derive(Debug, Default)]
+pub struct Bookmarks {
+    entries: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    fn path() -> Option<PathBuf> {
+        Some(storage::data_dir()?.join("bookmarks.json"))
+    }
+
+    // Load the saved bookmarks, skipping malformed entries
+    pub fn load() -> Self {
+        let saved: Option<Value> = Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok());
+
+        let entries = saved
+            .as_ref()
+            .and_then(|saved| saved.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        Some(Bookmark {
+                            label: entry.get("label")?.as_str()?.to_string(),
+                            query: entry.get("query")?.as_str()?.to_string(),
+                            network: RpcNetwork::from_name(entry.get("network")?.as_str()?)?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { entries }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let entries: Vec<Value> = self
+            .entries
+            .iter()
+            .map(|bookmark| {
+                serde_json::json!({
+                    "label": bookmark.label,
+                    "query": bookmark.query,
+                    "network": bookmark.network.saved_name(),
+                })
+            })
+            .collect();
+        let contents = serde_json::to_string_pretty(&entries).map_err(io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    // Add a bookmark, replacing an existing one for the same query and network
+    pub fn add(&mut self, bookmark: Bookmark) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.query == bookmark.query && entry.network == bookmark.network)
+        {
+            Some(entry) => entry.label = bookmark.label,
+            None => self.entries.push(bookmark),
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Bookmark> {
+        self.entries.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Bookmark> {
+        self.entries.iter()
+    }
+}
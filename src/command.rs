@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use solana_sdk::commitment_config::CommitmentLevel;
+
+use crate::app::{parse_rpc_url, RpcNetwork};
+use crate::keymap::{AppAction, ACTION_NAMES};
+
+// Commands that take arguments; every keymap action name is also a command
+const COMMANDS: [(&str, &str); 5] = [
+    ("network", "network <devnet|testnet|mainnet|solana>"),
+    ("rpc", "rpc <http(s) URL>"),
+    ("commitment", "commitment <processed|confirmed|finalized>"),
+    ("export", "export <path>"),
+    ("bookmark", "bookmark <save [label]|list>"),
+];
+
+fn command_names() -> impl Iterator<Item = &'static str> {
+    COMMANDS
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(ACTION_NAMES.iter().map(|(name, _)| *name))
+}
+
+// The command a name refers to: an exact name, or the only command starting with it
+fn resolve(name: &str) -> Result<&'static str, String> {
+    if let Some(exact) = command_names().find(|command| *command == name) {
+        return Ok(exact);
+    }
+    let matches: Vec<&str> = command_names().filter(|command| command.starts_with(name)).collect();
+    match matches.as_slice() {
+        [command] => Ok(command),
+        [] => Err(format!("Unknown command: {}", name)),
+        _ => Err(format!("Ambiguous command {}: {}", name, matches.join(", "))),
+    }
+}
+
+// Parse a command line (without the ':') into the action it runs
+pub fn parse(input: &str) -> Result<AppAction, String> {
+    let input = input.trim();
+    let (name, args) = input.split_once(' ').unwrap_or((input, ""));
+    let args = args.trim();
+    if name.is_empty() {
+        return Err("Empty command".to_string());
+    }
+    let command = resolve(name)?;
+    let usage = || {
+        let usage = COMMANDS
+            .iter()
+            .find(|(name, _)| *name == command)
+            .map_or(command, |(_, usage)| usage);
+        format!("Usage: :{}", usage)
+    };
+
+    match command {
+        "network" => {
+            let wanted = args.to_ascii_lowercase().replace(['-', '_', ' '], "");
+            RpcNetwork::ALL
+                .into_iter()
+                .find(|network| network.name().to_ascii_lowercase().replace(' ', "") == wanted)
+                .or_else(|| (wanted == "solana").then_some(RpcNetwork::SolanaMainnet))
+                .map(AppAction::SetNetwork)
+                .ok_or_else(usage)
+        }
+        "rpc" if args.is_empty() => Err(usage()),
+        "rpc" => parse_rpc_url(args).map(|url| AppAction::SetNetwork(RpcNetwork::Custom(url))),
+        "commitment" => CommitmentLevel::from_str(args)
+            .map(AppAction::SetCommitment)
+            .map_err(|_| usage()),
+        "export" if args.is_empty() => Err(usage()),
+        "export" => Ok(AppAction::Export(args.to_string())),
+        "bookmark" => match args.split_once(' ').unwrap_or((args, "")) {
+            ("save", label) => Ok(AppAction::SaveBookmark(label.trim().to_string())),
+            ("list", "") => Ok(AppAction::OpenBookmarks),
+            _ => Err(usage()),
+        },
+        // Keymap actions take no arguments
+        _ if !args.is_empty() => Err(format!(":{} takes no arguments", command)),
+        _ => AppAction::from_name(command).ok_or_else(usage),
+    }
+}
+
+// Complete the command name being typed: to the name itself when only one command
+// matches, otherwise as far as all matching names agree
+pub fn complete(input: &str) -> Option<String> {
+    if input.contains(' ') {
+        return None;
+    }
+    let matches: Vec<&str> = command_names().filter(|command| command.starts_with(input)).collect();
+    let first = matches.first()?;
+    if let [command] = matches.as_slice() {
+        return Some(format!("{} ", command));
+    }
+    let common = matches.iter().fold(first.len(), |len, command| {
+        first
+            .bytes()
+            .zip(command.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    (common > input.len()).then(|| first[..common].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_with_arguments() {
+        assert_eq!(parse("network testnet"), Ok(AppAction::SetNetwork(RpcNetwork::Testnet)));
+        assert_eq!(parse("net Solana-Mainnet"), Ok(AppAction::SetNetwork(RpcNetwork::SolanaMainnet)));
+        assert_eq!(
+            parse("rpc https://rpc.example.com"),
+            Ok(AppAction::SetNetwork(RpcNetwork::Custom("https://rpc.example.com".to_string())))
+        );
+        assert_eq!(parse("commitment finalized"), Ok(AppAction::SetCommitment(CommitmentLevel::Finalized)));
+        assert_eq!(parse("export result.json"), Ok(AppAction::Export("result.json".to_string())));
+        assert_eq!(parse("bookmark save my label"), Ok(AppAction::SaveBookmark("my label".to_string())));
+        assert_eq!(parse("q"), Ok(AppAction::Quit));
+        assert_eq!(parse("top"), Ok(AppAction::Top));
+    }
+
+    #[test]
+    fn reports_bad_commands() {
+        assert_eq!(parse("frobnicate"), Err("Unknown command: frobnicate".to_string()));
+        assert!(parse("network moon").unwrap_err().starts_with("Usage: :network"));
+        assert!(parse("commitment max").unwrap_err().starts_with("Usage: :commitment"));
+        assert!(parse("rpc ftp://example.com").is_err());
+        assert!(parse("export").is_err());
+        assert!(parse("quit now").is_err());
+        assert!(parse("ne").unwrap_err().starts_with("Ambiguous command ne"));
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn completes_command_names() {
+        assert_eq!(complete("commi").as_deref(), Some("commitment "));
+        assert_eq!(complete("half").as_deref(), Some("half_page_"));
+        assert_eq!(complete("next_t"), None); // next_tab or next_theme
+        assert_eq!(complete("toggle_r").as_deref(), Some("toggle_relative_times "));
+        assert_eq!(complete("xyz"), None);
+        assert_eq!(complete("rpc https"), None);
+    }
+}
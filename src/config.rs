@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::format::CurrencyUnit;
+use crate::keymap::{self, AppAction};
+use crate::storage;
+use crate::theme::Theme;
+
+// User settings read from ~/.config/soonscan/config.json, e.g.
+// { "theme": "light", "unit": "lamports", "labels": { "<pubkey>": "My Wallet" },
+//   "keys": { "ctrl+n": "next_network" }, "cache_ttl": 10, "rate_limit": 5 }
+#[derive(Debug, Default)]
+pub struct Config {
+    pub theme: Option<Theme>,
+    pub currency_unit: Option<CurrencyUnit>,
+    pub labels: HashMap<String, String>,
+    pub keys: Vec<((KeyCode, KeyModifiers), AppAction)>, // Bindings added to the default keymap
+    pub cache_ttl: Option<Duration>, // How long looked up accounts are reused
+    pub rate_limit: Option<u32>, // Requests per second sent to the RPC
+}
+
+impl Config {
+    // Load the config file; a missing or malformed file gives the defaults
+    pub fn load() -> Self {
+        let Some(config) = storage::config_dir()
+            .and_then(|dir| fs::read_to_string(dir.join("config.json")).ok())
+            .and_then(|contents| serde_json::from_str::<Value>(&contents).ok())
+        else {
+            return Self::default();
+        };
+
+        let labels = config
+            .get("labels")
+            .and_then(|labels| labels.as_object())
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|(pubkey, label)| Some((pubkey.clone(), label.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let theme = config
+            .get("theme")
+            .and_then(|theme| theme.as_str())
+            .and_then(Theme::from_name);
+
+        let currency_unit = config
+            .get("unit")
+            .and_then(|unit| unit.as_str())
+            .and_then(CurrencyUnit::from_name);
+
+        // Unknown keys or action names are skipped
+        let keys = config
+            .get("keys")
+            .and_then(|keys| keys.as_object())
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|(key, action)| {
+                        Some((keymap::parse_key(key)?, AppAction::from_name(action.as_str()?)?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let cache_ttl = config
+            .get("cache_ttl")
+            .and_then(|seconds| seconds.as_u64())
+            .map(Duration::from_secs);
+
+        let rate_limit = config
+            .get("rate_limit")
+            .and_then(|rate| rate.as_u64())
+            .filter(|rate| *rate > 0)
+            .and_then(|rate| u32::try_from(rate).ok());
+
+        Self {
+            theme,
+            currency_unit,
+            labels,
+            keys,
+            cache_ttl,
+            rate_limit,
+        }
+    }
+}
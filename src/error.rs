@@ -0,0 +1,111 @@
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::RpcError;
+use thiserror::Error;
+
+// Everything that can go wrong looking something up, from a mistyped query to
+// the RPC being unreachable
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("'{0}' is neither a slot, a public key nor a transaction signature")]
+    InvalidQuery(String),
+    #[error("Invalid transaction signature '{0}'")]
+    SignatureParse(String),
+    #[error("Invalid public key '{0}'")]
+    PubkeyParse(String),
+    #[error("Invalid slot '{0}'")]
+    SlotParse(String),
+    #[error("Could not reach the RPC: {0}")]
+    RpcTransport(reqwest::Error),
+    #[error("RPC error {code}: {message}")]
+    RpcResponse { code: i64, message: String },
+    // What was looked up, such as "Account <address>"
+    #[error("{0} not found")]
+    NotFound(String),
+    // Known to the node, but in a block older than its ledger keeps
+    #[error("This RPC only serves blocks ≥ {first_available}; the transaction may exist but is pruned here")]
+    Pruned { first_available: u64 },
+    #[error("The RPC did not answer in time")]
+    Timeout,
+    #[error("Clipboard unavailable: {0}")]
+    ClipboardUnavailable(String),
+    // The node answered with something that isn't what the method returns
+    #[error("Unexpected RPC response: {0}")]
+    UnexpectedResponse(String),
+    // Replaying, a request that is missing from the recorded fixtures
+    #[error("No recorded answer to {method} (expected {path})")]
+    NotRecorded { method: String, path: String },
+    #[error("Could not record the RPC answer in {0}")]
+    FixtureWrite(String),
+    #[error("Request interrupted: {0}")]
+    Interrupted(#[from] tokio::task::JoinError),
+}
+
+impl AppError {
+    // Exit status of the command line: 2 for bad input, 3 when nothing was
+    // found, 4 when the RPC couldn't be reached and 5 when it answered with an error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::InvalidQuery(_)
+            | AppError::SignatureParse(_)
+            | AppError::PubkeyParse(_)
+            | AppError::SlotParse(_) => 2,
+            AppError::NotFound(_) | AppError::Pruned { .. } => 3,
+            AppError::RpcTransport(_) | AppError::Timeout => 4,
+            AppError::RpcResponse { .. } | AppError::UnexpectedResponse(_) => 5,
+            AppError::ClipboardUnavailable(_)
+            | AppError::NotRecorded { .. }
+            | AppError::FixtureWrite(_)
+            | AppError::Interrupted(_) => 1,
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            AppError::Timeout
+        } else {
+            AppError::RpcTransport(err)
+        }
+    }
+}
+
+impl From<ClientError> for AppError {
+    fn from(err: ClientError) -> Self {
+        match err.kind {
+            ClientErrorKind::Reqwest(err) => err.into(),
+            // Errors of the fixture transports come back through the Solana client as they were
+            ClientErrorKind::Io(err) => match err.downcast::<AppError>() {
+                Ok(err) => err,
+                Err(err) if err.kind() == std::io::ErrorKind::TimedOut => AppError::Timeout,
+                Err(err) => AppError::UnexpectedResponse(err.to_string()),
+            },
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+                AppError::RpcResponse { code, message }
+            }
+            kind => AppError::UnexpectedResponse(kind.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_errors_keep_the_rpc_code() {
+        let err: AppError = ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32009,
+            message: "Slot 5 was skipped".to_string(),
+            data: solana_client::rpc_request::RpcResponseErrorData::Empty,
+        }))
+        .into();
+        assert!(matches!(err, AppError::RpcResponse { code: -32009, .. }));
+        assert_eq!(err.to_string(), "RPC error -32009: Slot 5 was skipped");
+        assert_eq!(err.exit_code(), 5);
+
+        let err: AppError = ClientError::from(ClientErrorKind::Io(std::io::ErrorKind::TimedOut.into())).into();
+        assert!(matches!(err, AppError::Timeout));
+        assert_eq!(err.exit_code(), 4);
+    }
+}
@@ -0,0 +1,91 @@
+use serde_json::Value;
+
+// What sending a transaction costs now: the base fee per signature and what recent
+// transactions paid for priority
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeEstimate {
+    pub lamports_per_signature: u64,
+    pub priority: Option<FeePercentiles>, // None when the RPC doesn't offer getRecentPrioritizationFees
+}
+
+// Prioritization fees over recent slots, in micro-lamports per compute unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeePercentiles {
+    pub slots: usize,
+    pub min: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub max: u64,
+}
+
+// The value at `percent` of sorted fees by the nearest-rank method: the smallest
+// fee that at least `percent`% of the fees are at or below
+fn nearest_rank(sorted: &[u64], percent: usize) -> u64 {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+// Percentiles of per-slot fees; None without any slots
+pub fn percentiles(fees: &[u64]) -> Option<FeePercentiles> {
+    if fees.is_empty() {
+        return None;
+    }
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+    Some(FeePercentiles {
+        slots: sorted.len(),
+        min: sorted[0],
+        median: nearest_rank(&sorted, 50),
+        p75: nearest_rank(&sorted, 75),
+        max: sorted[sorted.len() - 1],
+    })
+}
+
+// The fees of a getRecentPrioritizationFees answer, one per slot
+pub fn prioritization_fees(response: &Value) -> Vec<u64> {
+    response
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("prioritizationFee").and_then(Value::as_u64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let fees: Vec<u64> = (1..=10).rev().map(|fee| fee * 100).collect();
+        assert_eq!(
+            percentiles(&fees),
+            Some(FeePercentiles {
+                slots: 10,
+                min: 100,
+                median: 500,
+                p75: 800,
+                max: 1000,
+            })
+        );
+
+        // Mostly idle slots keep the median at zero while the top paid a lot
+        let fees = [0, 0, 0, 0, 0, 10_000, 250_000];
+        let stats = percentiles(&fees).unwrap();
+        assert_eq!((stats.min, stats.median, stats.p75, stats.max), (0, 0, 10_000, 250_000));
+
+        assert_eq!(percentiles(&[7]).map(|stats| (stats.min, stats.median, stats.p75, stats.max)), Some((7, 7, 7, 7)));
+        assert_eq!(percentiles(&[]), None);
+    }
+
+    #[test]
+    fn reads_the_fee_of_each_slot() {
+        let response = serde_json::json!([
+            { "slot": 1, "prioritizationFee": 0 },
+            { "slot": 2, "prioritizationFee": 1500 },
+            { "slot": 3 },
+        ]);
+        assert_eq!(prioritization_fees(&response), [0, 1500]);
+        assert_eq!(prioritization_fees(&Value::Null), Vec::<u64>::new());
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::rpc::RpcResult;
+
+// Where JSON-RPC requests go: to the node, to the node with every exchange written
+// to a directory (--record), or to a directory of recorded exchanges with no
+// network at all (--replay)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Transport {
+    #[default]
+    Network,
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+// File of a request in a fixture directory, named by its method and a hash of its
+// params, e.g. getAccountInfo-3f29c0a1d2b4e5f6.json
+pub fn fixture_path(dir: &Path, method: &str, params: &Value) -> PathBuf {
+    dir.join(format!("{}-{:016x}.json", method, fnv1a(params.to_string().as_bytes())))
+}
+
+// Write the whole response to a request, with the request beside it so the file
+// can be read on its own
+pub fn save(dir: &Path, method: &str, params: &Value, response: &Value) -> RpcResult<()> {
+    let fixture = serde_json::json!({ "method": method, "params": params, "response": response });
+    let text = serde_json::to_string_pretty(&fixture).unwrap_or_default() + "\n";
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(fixture_path(dir, method, params), text))
+        .map_err(|err| AppError::FixtureWrite(format!("{}: {}", dir.display(), err)))
+}
+
+// The recorded response to a request; a request that wasn't recorded is an error
+// rather than a trip to the network
+pub fn load(dir: &Path, method: &str, params: &Value) -> RpcResult<Value> {
+    let path = fixture_path(dir, method, params);
+    let not_recorded = || AppError::NotRecorded {
+        method: method.to_string(),
+        path: path.display().to_string(),
+    };
+    let text = fs::read_to_string(&path).map_err(|_| not_recorded())?;
+    let mut fixture: Value = serde_json::from_str(&text).map_err(|_| not_recorded())?;
+    Ok(fixture["response"].take())
+}
+
+// 64-bit FNV-1a, which unlike the standard library's hasher is the same in every
+// build, so recorded file names stay valid
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures_are_named_by_method_and_params() {
+        let params = serde_json::json!(["11111111111111111111111111111111", { "encoding": "base64" }]);
+        let path = fixture_path(Path::new("fixtures"), "getAccountInfo", &params);
+        assert_eq!(path, fixture_path(Path::new("fixtures"), "getAccountInfo", &params.clone()));
+        assert_ne!(path, fixture_path(Path::new("fixtures"), "getAccountInfo", &serde_json::json!([])));
+        assert!(path.to_string_lossy().starts_with("fixtures/getAccountInfo-"));
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        let dir = std::env::temp_dir().join(format!("soonscan-fixtures-{}", std::process::id()));
+        let response = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": 42 });
+        save(&dir, "getSlot", &serde_json::json!([]), &response).unwrap();
+        assert_eq!(load(&dir, "getSlot", &serde_json::json!([])).unwrap(), response);
+        let missing = load(&dir, "getSlot", &serde_json::json!([{ "commitment": "processed" }]));
+        assert!(matches!(missing, Err(AppError::NotRecorded { method, .. }) if method == "getSlot"));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
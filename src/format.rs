@@ -0,0 +1,254 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+// How SOL amounts are displayed, toggled with 'u'
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CurrencyUnit {
+    #[default]
+    Sol,
+    Lamports,
+}
+
+impl CurrencyUnit {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sol" => Some(CurrencyUnit::Sol),
+            "lamports" => Some(CurrencyUnit::Lamports),
+            _ => None,
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            CurrencyUnit::Sol => CurrencyUnit::Lamports,
+            CurrencyUnit::Lamports => CurrencyUnit::Sol,
+        }
+    }
+}
+
+// Format an amount of lamports as "◎ 1.234567890" or "1,234,567,890 lamports".
+// Integer math keeps every digit exact, even for u64::MAX.
+pub fn format_lamports(lamports: u64, unit: CurrencyUnit) -> String {
+    match unit {
+        CurrencyUnit::Sol => format!(
+            "◎ {}.{:09}",
+            lamports / LAMPORTS_PER_SOL,
+            lamports % LAMPORTS_PER_SOL
+        ),
+        CurrencyUnit::Lamports => format!("{} lamports", group_digits(lamports)),
+    }
+}
+
+// Raw token units with the mint's decimals applied, e.g. (1_500_000, 6) -> "1.5"
+pub fn format_token_amount(amount: u128, decimals: u8) -> String {
+    let scale = 10u128.pow(decimals.min(38) as u32);
+    let fraction = format!("{:0width$}", amount % scale, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    let whole = group_digits_u128(amount / scale);
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+// Signed difference between two token amounts, e.g. "+1.5" or "-0.25"
+pub fn format_token_change(pre: u128, post: u128, decimals: u8) -> String {
+    let sign = match post.cmp(&pre) {
+        std::cmp::Ordering::Greater => "+",
+        std::cmp::Ordering::Less => "-",
+        std::cmp::Ordering::Equal => "",
+    };
+    format!("{}{}", sign, format_token_amount(post.abs_diff(pre), decimals))
+}
+
+// Signed difference between two lamport amounts, e.g. "+◎ 0.500000000" or "-1,000 lamports"
+pub fn format_lamport_change(pre: u64, post: u64, unit: CurrencyUnit) -> String {
+    let sign = match post.cmp(&pre) {
+        std::cmp::Ordering::Greater => "+",
+        std::cmp::Ordering::Less => "-",
+        std::cmp::Ordering::Equal => "",
+    };
+    format!("{}{}", sign, format_lamports(post.abs_diff(pre), unit))
+}
+
+// Lamports without trailing zeros, e.g. "◎ 0.00203928"
+pub fn format_lamports_short(lamports: u64, unit: CurrencyUnit) -> String {
+    match unit {
+        CurrencyUnit::Sol => format!("◎ {}", format_token_amount(lamports.into(), 9)),
+        CurrencyUnit::Lamports => format_lamports(lamports, unit),
+    }
+}
+
+// `part` of `whole` in hundredths of a percent, rounded; None when `whole` is zero
+pub fn ratio(part: u128, whole: u128) -> Option<u64> {
+    (whole > 0).then(|| (part.saturating_mul(10_000).saturating_add(whole / 2) / whole) as u64)
+}
+
+// e.g. "12.34%", or "N/A" of nothing
+pub fn format_ratio(part: u128, whole: u128) -> String {
+    ratio(part, whole).map_or("N/A".to_string(), |ratio| format!("{}.{:02}%", ratio / 100, ratio % 100))
+}
+
+// Stake to the whole SOL, e.g. "◎ 12,345,678", or in grouped lamports
+pub fn format_stake(lamports: u64, unit: CurrencyUnit) -> String {
+    match unit {
+        CurrencyUnit::Sol => {
+            let rounded = lamports / LAMPORTS_PER_SOL + u64::from(lamports % LAMPORTS_PER_SOL >= LAMPORTS_PER_SOL / 2);
+            format!("◎ {}", group_digits(rounded))
+        }
+        CurrencyUnit::Lamports => format_lamports(lamports, unit),
+    }
+}
+
+// Insert thousands separators, e.g. 1234567 -> "1,234,567"
+pub fn group_digits(number: u64) -> String {
+    group_digits_u128(number.into())
+}
+
+pub fn group_digits_u128(number: u128) -> String {
+    let digits = number.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+// Describe how long ago `timestamp` was at `now` (both unix seconds), e.g. "15 min ago".
+// Timestamps ahead of `now` (clock skew) read as "in 5s".
+pub fn format_relative(timestamp: i64, now: i64) -> String {
+    let diff = now.saturating_sub(timestamp);
+    let secs = diff.unsigned_abs();
+    let amount = match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{} min", secs / 60),
+        3_600..=86_399 => plural(secs / 3_600, "hour"),
+        _ => plural(secs / 86_400, "day"),
+    };
+    if diff < 0 {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
+
+pub fn plural(count: u64, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+// Preview of `text` in at most `max_chars` characters, ending in "…" when cut.
+// Counts user-perceived characters, so accents and emoji are never split.
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if grapheme_count(text) <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.graphemes(true).take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+// Backspace in the single-line prompts: remove the last whole character
+pub fn pop_grapheme(text: &mut String) {
+    if let Some((index, _)) = text.grapheme_indices(true).next_back() {
+        text.truncate(index);
+    }
+}
+
+// Format a number of seconds as e.g. "1d 2h 3m", "2h 3m" or "3m 20s"
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes, seconds) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60, secs % 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m {}s", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_relative_times() {
+        let now = 1_700_000_000;
+        assert_eq!(format_relative(now, now), "0s ago");
+        assert_eq!(format_relative(now - 42, now), "42s ago");
+        assert_eq!(format_relative(now - 15 * 60 - 5, now), "15 min ago");
+        assert_eq!(format_relative(now - 3_600, now), "1 hour ago");
+        assert_eq!(format_relative(now - 5 * 3_600, now), "5 hours ago");
+        assert_eq!(format_relative(now - 3 * 86_400, now), "3 days ago");
+        // Clock skew puts the block slightly in the future
+        assert_eq!(format_relative(now + 5, now), "in 5s");
+        // Extreme values saturate instead of overflowing
+        assert_eq!(format_relative(i64::MIN, i64::MAX), format!("{} days ago", i64::MAX / 86_400));
+    }
+
+    #[test]
+    fn format_lamports_in_sol() {
+        assert_eq!(format_lamports(0, CurrencyUnit::Sol), "◎ 0.000000000");
+        assert_eq!(format_lamports(1, CurrencyUnit::Sol), "◎ 0.000000001");
+        assert_eq!(format_lamports(1_234_567_890, CurrencyUnit::Sol), "◎ 1.234567890");
+        // No float rounding at the top of the range
+        assert_eq!(format_lamports(u64::MAX, CurrencyUnit::Sol), "◎ 18446744073.709551615");
+    }
+
+    #[test]
+    fn format_lamports_in_lamports() {
+        assert_eq!(format_lamports(0, CurrencyUnit::Lamports), "0 lamports");
+        assert_eq!(format_lamports(999, CurrencyUnit::Lamports), "999 lamports");
+        assert_eq!(format_lamports(1_234_567_890, CurrencyUnit::Lamports), "1,234,567,890 lamports");
+        assert_eq!(
+            format_lamports(u64::MAX, CurrencyUnit::Lamports),
+            "18,446,744,073,709,551,615 lamports"
+        );
+    }
+
+    #[test]
+    fn previews_never_split_characters() {
+        assert_eq!(truncate("", 5), "");
+        assert_eq!(truncate("short", 24), "short");
+        // Exactly as long as allowed is shown whole; one more is cut
+        assert_eq!(truncate("exact", 5), "exact");
+        assert_eq!(truncate("exacts", 5), "exac…");
+        assert_eq!(truncate("5VfYmGBjVw2", 5), "5VfY…");
+        // Accented and emoji characters count once, however many bytes they take
+        assert_eq!(truncate("ééééé", 5), "ééééé");
+        assert_eq!(truncate("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+        assert_eq!(truncate("👍🏽👍🏽👍🏽", 2), "👍🏽…");
+
+        let mut text = "a👍🏽".to_string();
+        pop_grapheme(&mut text);
+        assert_eq!(text, "a");
+    }
+
+    #[test]
+    fn lamport_changes_are_signed() {
+        assert_eq!(format_lamport_change(100, 1_000_000_100, CurrencyUnit::Sol), "+◎ 1.000000000");
+        assert_eq!(format_lamport_change(5_000, 0, CurrencyUnit::Lamports), "-5,000 lamports");
+        assert_eq!(format_lamport_change(7, 7, CurrencyUnit::Lamports), "0 lamports");
+    }
+
+    #[test]
+    fn token_amounts_apply_decimals() {
+        assert_eq!(format_token_amount(1_500_000, 6), "1.5");
+        assert_eq!(format_token_amount(1_234_000_000_000, 6), "1,234,000");
+        assert_eq!(format_token_amount(1, 9), "0.000000001");
+        assert_eq!(format_token_amount(42, 0), "42");
+        assert_eq!(format_token_change(2_000_000, 500_000, 6), "-1.5");
+    }
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::storage;
+
+// Number of submitted queries kept on disk
+const MAX_HISTORY: usize = 200;
+
+// Submitted queries, most recent first and without duplicates
+#[derive(Debug, Default)]
+pub struct QueryHistory {
+    entries: Vec<String>,
+}
+
+impl QueryHistory {
+    fn path() -> Option<PathBuf> {
+        Some(storage::data_dir()?.join("history"))
+    }
+
+    // Load the saved history; a missing or unreadable file gives an empty history
+    pub fn load() -> Self {
+        let entries = Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .take(MAX_HISTORY)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { entries }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut contents = self.entries.join("\n");
+        contents.push('\n');
+        fs::write(path, contents)
+    }
+
+    // Move a submitted query to the front
+    pub fn add(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.insert(0, query.to_string());
+        self.entries.truncate(MAX_HISTORY);
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+}
@@ -0,0 +1,751 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::app::AccountKeys;
+use crate::format::{format_lamports, truncate, CurrencyUnit};
+use crate::labels;
+use crate::system::{SystemInstruction, SYSTEM_PROGRAM_ID};
+use crate::token::{self, TokenInstruction, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+// Fee of every signature, the base fee of a transaction
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
+// Compute units granted to each instruction when the transaction sets no limit,
+// and the most a transaction can ask for
+const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u64 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+// An instruction of a transaction message; indices point into the account keys.
+// Instructions of programs the node knows come decoded, without accounts or data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstructionInfo {
+    pub program_index: usize,
+    pub accounts: Vec<InstructionAccount>,
+    pub data: String, // Base58
+    pub parsed: Option<ParsedInfo>,
+    pub stack_height: Option<usize>, // 1 for top-level instructions, 2 for those they invoke, ...
+}
+
+// An account an instruction names: an index into the account keys, or the address
+// itself when the node gave one that isn't among them
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstructionAccount {
+    Index(usize),
+    Address(String),
+}
+
+impl InstructionAccount {
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::Index(index) => Some(*index),
+            Self::Address(_) => None,
+        }
+    }
+
+    // None for an index past the keys
+    pub fn address<'k>(&'k self, keys: &AccountKeys<'k>) -> Option<&'k str> {
+        match self {
+            Self::Index(index) => keys.get(*index),
+            Self::Address(address) => Some(address),
+        }
+    }
+
+    // The address, or unknown[<index>] for an index past the keys
+    pub fn name(&self, keys: &AccountKeys) -> String {
+        match self {
+            Self::Index(index) => keys.get(*index).map_or(format!("unknown[{}]", index), str::to_string),
+            Self::Address(address) => address.clone(),
+        }
+    }
+}
+
+// A row of the instruction list: a top-level instruction, or one it invoked through
+// a cross-program call
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedInstruction {
+    pub number: String, // "2" for a top-level instruction, "2.0" for the first one it invoked
+    pub depth: usize, // Invoking depth, 1 for top-level instructions
+    pub inner_count: usize, // Instructions a top-level instruction invoked
+    pub instruction: InstructionInfo,
+}
+
+// What the node decoded an instruction to: the program's name, the instruction
+// type and its named fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedInfo {
+    pub program: String, // e.g. "system", "spl-token"
+    pub kind: String, // e.g. "transferChecked"; empty for memos
+    pub info: Value, // The memo text for memos
+}
+
+impl InstructionInfo {
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let parsed = value.get("parsed").map(|parsed| ParsedInfo {
+            program: value.get("program").and_then(Value::as_str).unwrap_or_default().to_string(),
+            kind: parsed.get("type").and_then(Value::as_str).unwrap_or_default().to_string(),
+            info: parsed.get("info").unwrap_or(parsed).clone(),
+        });
+        Some(Self {
+            program_index: value.get("programIdIndex")?.as_u64()? as usize,
+            accounts: value
+                .get("accounts")
+                .and_then(|accounts| accounts.as_array())
+                .map(|accounts| {
+                    accounts
+                        .iter()
+                        .filter_map(|account| match account {
+                            Value::String(address) => Some(InstructionAccount::Address(address.clone())),
+                            _ => account.as_u64().map(|index| InstructionAccount::Index(index as usize)),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            data: value.get("data").and_then(Value::as_str).unwrap_or_default().to_string(),
+            parsed,
+            stack_height: value.get("stackHeight").and_then(Value::as_u64).map(|height| height as usize),
+        })
+    }
+
+    // Decode the raw data of instructions the node didn't parse, for the programs
+    // decoded here, and give token amounts their mint's decimals where known
+    fn decode_locally(mut self, keys: &AccountKeys, mints: &TokenMints) -> Self {
+        if self.parsed.is_none() {
+            let accounts: Vec<String> = self.accounts.iter().map(|account| account.name(keys)).collect();
+            let data = self.data_bytes();
+            self.parsed = match keys.get(self.program_index) {
+                Some(SYSTEM_PROGRAM_ID) => SystemInstruction::decode(&data).map(|decoded| decoded.to_parsed(&accounts)),
+                Some(TOKEN_PROGRAM_ID) => {
+                    TokenInstruction::decode(&data, false).map(|decoded| decoded.to_parsed("spl-token", &accounts))
+                }
+                Some(TOKEN_2022_PROGRAM_ID) => {
+                    TokenInstruction::decode(&data, true).map(|decoded| decoded.to_parsed("spl-token-2022", &accounts))
+                }
+                _ => None,
+            };
+        }
+        if let Some(parsed) = &mut self.parsed {
+            mints.apply_decimals(parsed);
+        }
+        self
+    }
+
+    // Decoded instruction data; empty if the base58 is malformed
+    pub fn data_bytes(&self) -> Vec<u8> {
+        solana_sdk::bs58::decode(&self.data).into_vec().unwrap_or_default()
+    }
+
+    // What the instruction does in words, e.g. "Transfer ◎ 0.5 from A to B", for
+    // parsed and compute budget instructions
+    pub fn describe(&self, program_id: Option<&str>, unit: CurrencyUnit) -> Option<String> {
+        self.phrases(program_id, unit).map(|phrases| phrases.join(" "))
+    }
+
+    // The description cut into the action and its "from" and "to" parts, which the
+    // results table shows in separate columns
+    pub fn phrases(&self, program_id: Option<&str>, unit: CurrencyUnit) -> Option<Vec<String>> {
+        match &self.parsed {
+            Some(parsed) => Some(parsed.phrases(unit)),
+            None if program_id == Some(COMPUTE_BUDGET_PROGRAM_ID) => {
+                describe_compute_budget(&self.data_bytes()).map(|action| vec![action])
+            }
+            None => None,
+        }
+    }
+}
+
+impl ParsedInfo {
+    fn phrases(&self, unit: CurrencyUnit) -> Vec<String> {
+        let account = |name: &str| self.info.get(name).and_then(Value::as_str).map_or("?".to_string(), short_name);
+        let number = |name: &str| self.info.get(name).and_then(Value::as_u64).unwrap_or(0);
+        // With the mint's decimals when known, otherwise in base units
+        let token_amount = || match self.info.pointer("/tokenAmount/uiAmountString").and_then(Value::as_str) {
+            Some(amount) => format!("{} {}", amount, account("mint")),
+            None => format!("{} units", self.info.get("amount").and_then(Value::as_str).unwrap_or("?")),
+        };
+        let from_to = |action: String| {
+            vec![
+                action,
+                format!("from {}", account("source")),
+                format!("to {}", account("destination")),
+            ]
+        };
+        match (self.program.as_str(), self.kind.as_str()) {
+            ("spl-memo", _) => vec![format!("Memo \"{}\"", self.info.as_str().unwrap_or_default())],
+            ("system", "transfer" | "transferWithSeed") => {
+                from_to(format!("Transfer {}", format_lamports(number("lamports"), unit)))
+            }
+            ("system", "createAccount" | "createAccountWithSeed") => vec![
+                format!("CreateAccount {}", account("newAccount")),
+                format!("with {}, {} bytes,", format_lamports(number("lamports"), unit), number("space")),
+                format!("owned by {}", account("owner")),
+            ],
+            ("spl-token" | "spl-token-2022", kind) => match kind {
+                "transfer" | "transferChecked" => from_to(format!("{} {}", capitalize(kind), token_amount())),
+                "mintTo" | "mintToChecked" => vec![
+                    format!("{} {}", capitalize(kind), token_amount()),
+                    format!("to {}", account("account")),
+                ],
+                "burn" | "burnChecked" => vec![
+                    format!("{} {}", capitalize(kind), token_amount()),
+                    format!("from {}", account("account")),
+                ],
+                "approve" | "approveChecked" => vec![
+                    format!("{} {}", capitalize(kind), token_amount()),
+                    format!("of {}", account("source")),
+                    format!("to delegate {}", account("delegate")),
+                ],
+                "closeAccount" => vec![
+                    format!("CloseAccount {}", account("account")),
+                    format!("rent to {}", account("destination")),
+                ],
+                "initializeMint" | "initializeMint2" => vec![
+                    format!("InitializeMint {}", account("mint")),
+                    format!("{} decimals", number("decimals")),
+                    format!("authority {}", account("mintAuthority")),
+                ],
+                "initializeAccount" | "initializeAccount2" | "initializeAccount3" => vec![
+                    format!("InitializeAccount {}", account("account")),
+                    format!("of {}", account("mint")),
+                    format!("owner {}", account("owner")),
+                ],
+                _ => vec![format!("{} ({})", capitalize(kind), self.program)],
+            },
+            (program, kind) => vec![format!("{} ({})", capitalize(kind), program)],
+        }
+    }
+}
+
+// The instructions of a transaction's meta, each followed by the instructions it
+// invoked unless `collapsed`
+pub fn list_instructions(meta: &Value, collapsed: bool) -> Option<Vec<ListedInstruction>> {
+    let keys = AccountKeys::from_meta(meta);
+    let mints = TokenMints::from_meta(meta, &keys);
+    let parse = |instructions: &Value| -> Vec<InstructionInfo> {
+        instructions
+            .as_array()
+            .map(|instructions| {
+                instructions
+                    .iter()
+                    .filter_map(InstructionInfo::from_value)
+                    .map(|instruction| instruction.decode_locally(&keys, &mints))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let inner_of = |index: usize| {
+        meta.get("innerInstructions")
+            .and_then(|inner| inner.as_array())
+            .into_iter()
+            .flatten()
+            .find(|inner| inner.get("index").and_then(Value::as_u64) == Some(index as u64))
+            .map(|inner| parse(&inner["instructions"]))
+            .unwrap_or_default()
+    };
+
+    let mut listed = Vec::new();
+    for (index, instruction) in parse(meta.get("instructions")?).into_iter().enumerate() {
+        let inner = inner_of(index);
+        listed.push(ListedInstruction {
+            number: index.to_string(),
+            depth: 1,
+            inner_count: inner.len(),
+            instruction,
+        });
+        if collapsed {
+            continue;
+        }
+        for (position, instruction) in inner.into_iter().enumerate() {
+            listed.push(ListedInstruction {
+                number: format!("{}.{}", index, position),
+                // Nodes that predate stack heights only say the call was nested
+                depth: instruction.stack_height.unwrap_or(2).max(2),
+                inner_count: 0,
+                instruction,
+            });
+        }
+    }
+    Some(listed)
+}
+
+// The mints of the token accounts in a transaction's token balances, with their
+// decimals
+#[derive(Debug, Default)]
+struct TokenMints<'a> {
+    mint_of: HashMap<&'a str, &'a str>, // Token account to mint
+    decimals: HashMap<&'a str, u8>, // Mint to decimals
+}
+
+impl<'a> TokenMints<'a> {
+    fn from_meta(meta: &'a Value, keys: &AccountKeys<'a>) -> Self {
+        let mut mints = Self::default();
+        let balances = ["preTokenBalances", "postTokenBalances"]
+            .into_iter()
+            .filter_map(|name| meta.get(name).and_then(Value::as_array))
+            .flatten();
+        for balance in balances {
+            let Some(mint) = balance.get("mint").and_then(Value::as_str) else {
+                continue;
+            };
+            let account = balance.get("accountIndex").and_then(Value::as_u64);
+            if let Some(account) = account.and_then(|index| keys.get(index as usize)) {
+                mints.mint_of.insert(account, mint);
+            }
+            let decimals = balance.pointer("/uiTokenAmount/decimals").and_then(Value::as_u64);
+            if let Some(decimals) = decimals.and_then(|decimals| u8::try_from(decimals).ok()) {
+                mints.decimals.insert(mint, decimals);
+            }
+        }
+        mints
+    }
+
+    // Give the bare amount of a token instruction its mint and decimals, as checked
+    // instructions have them
+    fn apply_decimals(&self, parsed: &mut ParsedInfo) {
+        if !matches!(parsed.program.as_str(), "spl-token" | "spl-token-2022")
+            || !matches!(parsed.kind.as_str(), "transfer" | "approve" | "mintTo" | "burn")
+        {
+            return;
+        }
+        let Some(info) = parsed.info.as_object_mut() else {
+            return;
+        };
+        let Some(amount) = info.get("amount").and_then(Value::as_str).and_then(|amount| amount.parse().ok()) else {
+            return;
+        };
+        let mint = info.get("mint").and_then(Value::as_str).map(str::to_string).or_else(|| {
+            let account = info.get("source").or_else(|| info.get("account"))?.as_str()?;
+            self.mint_of.get(account).map(|mint| mint.to_string())
+        });
+        let Some((mint, &decimals)) = mint.and_then(|mint| Some((mint.clone(), self.decimals.get(mint.as_str())?))) else {
+            return;
+        };
+        info.insert("tokenAmount".to_string(), token::token_amount(amount, decimals));
+        info.entry("mint").or_insert(Value::from(mint));
+    }
+}
+
+// True when the node left out inner instructions, as it does with their recording
+// disabled, rather than the transaction having none
+pub fn inner_instructions_unrecorded(meta: &Value) -> bool {
+    meta.get("innerInstructions").is_some_and(Value::is_null)
+}
+
+// Instructions of the compute budget program
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComputeBudgetInstruction {
+    RequestHeapFrame(u32),
+    SetComputeUnitLimit(u32),
+    SetComputeUnitPrice(u64), // Micro-lamports per compute unit
+    SetLoadedAccountsDataSizeLimit(u32),
+}
+
+impl ComputeBudgetInstruction {
+    // The node doesn't parse them; the data is a one byte tag and a little-endian argument
+    fn decode(data: &[u8]) -> Option<Self> {
+        let (&tag, argument) = data.split_first()?;
+        let u32_argument = || Some(u32::from_le_bytes(argument.get(..4)?.try_into().ok()?));
+        match tag {
+            1 => Some(Self::RequestHeapFrame(u32_argument()?)),
+            2 => Some(Self::SetComputeUnitLimit(u32_argument()?)),
+            3 => Some(Self::SetComputeUnitPrice(u64::from_le_bytes(
+                argument.get(..8)?.try_into().ok()?,
+            ))),
+            4 => Some(Self::SetLoadedAccountsDataSizeLimit(u32_argument()?)),
+            _ => None,
+        }
+    }
+}
+
+fn describe_compute_budget(data: &[u8]) -> Option<String> {
+    Some(match ComputeBudgetInstruction::decode(data)? {
+        ComputeBudgetInstruction::RequestHeapFrame(bytes) => format!("RequestHeapFrame {} bytes", bytes),
+        ComputeBudgetInstruction::SetComputeUnitLimit(units) => format!("SetComputeUnitLimit {}", units),
+        ComputeBudgetInstruction::SetComputeUnitPrice(price) => {
+            format!("SetComputeUnitPrice {} micro-lamports", price)
+        }
+        ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(bytes) => {
+            format!("SetLoadedAccountsDataSizeLimit {} bytes", bytes)
+        }
+    })
+}
+
+// The compute units a transaction may use and what it pays for each, as its compute
+// budget instructions set them or by default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputeBudget {
+    pub unit_limit: u64,
+    pub limit_requested: bool, // Set by SetComputeUnitLimit rather than the default
+    pub unit_price: u64,       // Micro-lamports per unit, 0 without SetComputeUnitPrice
+}
+
+impl ComputeBudget {
+    // From the top-level instructions of a transaction and their program ids
+    pub fn from_instructions<'a>(
+        instructions: impl IntoIterator<Item = (Option<&'a str>, &'a InstructionInfo)>,
+    ) -> Self {
+        let mut requested_limit = None;
+        let mut unit_price = 0;
+        let mut other_instructions: u64 = 0;
+        for (program_id, instruction) in instructions {
+            if program_id != Some(COMPUTE_BUDGET_PROGRAM_ID) {
+                other_instructions += 1;
+                continue;
+            }
+            match ComputeBudgetInstruction::decode(&instruction.data_bytes()) {
+                Some(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => requested_limit = Some(u64::from(units)),
+                Some(ComputeBudgetInstruction::SetComputeUnitPrice(price)) => unit_price = price,
+                _ => {}
+            }
+        }
+        let default_limit = other_instructions
+            .checked_mul(DEFAULT_INSTRUCTION_COMPUTE_UNITS)
+            .unwrap_or(MAX_COMPUTE_UNIT_LIMIT);
+        Self {
+            unit_limit: requested_limit.unwrap_or(default_limit).min(MAX_COMPUTE_UNIT_LIMIT),
+            limit_requested: requested_limit.is_some(),
+            unit_price,
+        }
+    }
+
+    // Lamports paid for priority: the price of every unit of the limit, rounded up.
+    // None if it doesn't fit in a u64.
+    pub fn priority_fee(&self) -> Option<u64> {
+        let micro_lamports = u128::from(self.unit_price).checked_mul(u128::from(self.unit_limit))?;
+        u64::try_from(micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT)).ok()
+    }
+
+    // Percentage of the limit the transaction used
+    pub fn utilization(&self, units_consumed: u64) -> Option<f64> {
+        (self.unit_limit > 0).then(|| units_consumed as f64 * 100.0 / self.unit_limit as f64)
+    }
+}
+
+// The fee for a transaction's signatures, before any priority fee
+pub fn base_fee(signatures: usize) -> Option<u64> {
+    u64::try_from(signatures).ok()?.checked_mul(LAMPORTS_PER_SIGNATURE)
+}
+
+// Index of the top-level instruction a transaction error blames, from the node's form
+// of it: {"InstructionError": [2, {"Custom": 6001}]}
+pub fn failed_instruction(err: &Value) -> Option<usize> {
+    usize::try_from(err.get("InstructionError")?.get(0)?.as_u64()?).ok()
+}
+
+// A transaction error in words, e.g. InstructionError(2, Custom(6001)) reads as
+// "Instruction #2 failed: custom program error 0x1771 (6001)"
+pub fn describe_transaction_error(err: &Value) -> String {
+    let Some(index) = failed_instruction(err) else {
+        return capitalize(&describe_variant(err));
+    };
+    let reason = match err.pointer("/InstructionError/1") {
+        Some(reason) => match reason.get("Custom").and_then(Value::as_u64) {
+            Some(code) => format!("custom program error {:#x} ({})", code, code),
+            None => describe_variant(reason),
+        },
+        None => "unknown error".to_string(),
+    };
+    format!("Instruction #{} failed: {}", index, reason)
+}
+
+// An error variant as serde writes it: a unit variant by name, others as an object
+// of their name and fields, e.g. {"InsufficientFundsForRent": {"account_index": 3}}
+fn describe_variant(err: &Value) -> String {
+    match err {
+        Value::String(name) => split_words(name),
+        Value::Object(variant) => match variant.iter().next() {
+            Some((name, fields)) if variant.len() == 1 => {
+                let detail = match fields {
+                    Value::Object(inner) => match inner.get("account_index") {
+                        Some(index) => format!("account {}", index),
+                        None => fields.to_string(),
+                    },
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                format!("{} ({})", split_words(name), detail)
+            }
+            _ => err.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+// "InsufficientFundsForFee" -> "insufficient funds for fee"
+fn split_words(name: &str) -> String {
+    let mut words = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            words.push(' ');
+        }
+        words.extend(c.to_lowercase());
+    }
+    words
+}
+
+// The label of a known address, else its first characters
+fn short_name(pubkey: &str) -> String {
+    labels::lookup(pubkey).map_or_else(|| truncate(pubkey, 8), str::to_string)
+}
+
+// "transferChecked" -> "TransferChecked"
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::transaction_details;
+    use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+
+    // Descriptions of the instructions of a captured getTransaction answer
+    fn describe_fixture(json: &str, unit: CurrencyUnit) -> Vec<Option<String>> {
+        let transaction: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(json).unwrap();
+        let details = transaction_details(&transaction);
+        let keys = details["meta"]["accountKeys"].as_array().unwrap();
+        details["meta"]["instructions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| {
+                let instruction = InstructionInfo::from_value(value).unwrap();
+                let program_id = keys[instruction.program_index]["pubkey"].as_str();
+                instruction.describe(program_id, unit)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parsed_system_memo_and_compute_budget_instructions() {
+        let descriptions = describe_fixture(include_str!("../tests/fixtures/sol_transfer.json"), CurrencyUnit::Sol);
+        assert_eq!(
+            descriptions,
+            [
+                Some("SetComputeUnitLimit 200000".to_string()),
+                Some("SetComputeUnitPrice 1000 micro-lamports".to_string()),
+                Some("Transfer ◎ 0.500000000 from 92vBXvp… to 86oFo1u…".to_string()),
+                Some("Memo \"thanks for lunch\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_system_instructions_are_decoded_here() {
+        let transfer = solana_sdk::bs58::encode([[2, 0, 0, 0].as_slice(), &5000u64.to_le_bytes()].concat()).into_string();
+        let unknown = solana_sdk::bs58::encode([99, 0, 0, 0]).into_string();
+        let meta = serde_json::json!({
+            "accountKeys": [
+                "92vBXvpUZeRYJAKEdaTKLnaMtgi8DmWFGxgbW4e8KZ1Q",
+                "86oFo1uoVEhvtTrQjW8ooy2tNeTYh1VnTYXmiMLRDVkk",
+                SYSTEM_PROGRAM_ID,
+            ],
+            "instructions": [
+                { "programIdIndex": 2, "accounts": [0, 1], "data": transfer },
+                { "programIdIndex": 2, "accounts": [0], "data": unknown },
+            ],
+        });
+        let listed = list_instructions(&meta, true).unwrap();
+        let system = Some(SYSTEM_PROGRAM_ID);
+        assert_eq!(
+            listed[0].instruction.describe(system, CurrencyUnit::Lamports).as_deref(),
+            Some("Transfer 5,000 lamports from 92vBXvp… to 86oFo1u…")
+        );
+        // The raw data stays for the detail popup
+        assert_eq!(listed[0].instruction.data, transfer);
+        assert_eq!(listed[1].instruction.parsed, None);
+        assert_eq!(listed[1].instruction.describe(system, CurrencyUnit::Lamports), None);
+    }
+
+    #[test]
+    fn raw_token_instructions_take_decimals_from_token_balances() {
+        let data = |bytes: Vec<u8>| solana_sdk::bs58::encode(bytes).into_string();
+        let mut meta = serde_json::json!({
+            "accountKeys": [
+                "payer",
+                "source",
+                "dest",
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                TOKEN_PROGRAM_ID,
+                TOKEN_2022_PROGRAM_ID,
+            ],
+            "instructions": [
+                { "programIdIndex": 4, "accounts": [1, 2, 0], "data": data([vec![3], 1_500_000u64.to_le_bytes().to_vec()].concat()) },
+                { "programIdIndex": 4, "accounts": [3, 2, 0], "data": data([vec![7], 2_000_000u64.to_le_bytes().to_vec()].concat()) },
+                { "programIdIndex": 4, "accounts": [1, 0, 0], "data": data(vec![9]) },
+                { "programIdIndex": 5, "accounts": [3], "data": data(vec![26, 1]) },
+            ],
+        });
+        let describe = |meta: &Value| -> Vec<String> {
+            let listed = list_instructions(meta, true).unwrap();
+            listed.iter().map(|listed| listed.instruction.describe(None, CurrencyUnit::Sol).unwrap()).collect()
+        };
+        assert_eq!(
+            describe(&meta),
+            [
+                "Transfer 1500000 units from source to dest",
+                "MintTo 2000000 units to dest",
+                "CloseAccount source rent to payer",
+                "TransferFeeExtension (spl-token-2022)",
+            ]
+        );
+
+        meta["postTokenBalances"] = serde_json::json!([{
+            "accountIndex": 1,
+            "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "uiTokenAmount": { "amount": "0", "decimals": 6 },
+        }]);
+        assert_eq!(describe(&meta)[..2], ["Transfer 1.5 USDC from source to dest", "MintTo 2 USDC to dest"]);
+    }
+
+    #[test]
+    fn parsed_token_transfers_and_unknown_programs() {
+        let descriptions =
+            describe_fixture(include_str!("../tests/fixtures/token_transfer_v0.json"), CurrencyUnit::Lamports);
+        assert_eq!(
+            descriptions,
+            [
+                Some("SetComputeUnitLimit 60000".to_string()),
+                Some("TransferChecked 12.5 USDC from F7nBcd7… to 79311T5…".to_string()),
+                // An unknown program keeps the raw rendering
+                None,
+            ]
+        );
+
+        let parsed = ParsedInfo {
+            program: "spl-token".to_string(),
+            kind: "syncNative".to_string(),
+            info: serde_json::json!({}),
+        };
+        assert_eq!(parsed.phrases(CurrencyUnit::Sol), ["SyncNative (spl-token)"]);
+        assert_eq!(describe_compute_budget(&[2, 1]), None);
+    }
+
+    // The compute budget of a captured transaction, with its signature count and fee
+    fn fixture_budget(json: &str) -> (ComputeBudget, usize, u64) {
+        let transaction: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(json).unwrap();
+        let details = transaction_details(&transaction);
+        let meta = &details["meta"];
+        let keys = meta["accountKeys"].as_array().unwrap();
+        let listed = list_instructions(meta, true).unwrap();
+        let budget = ComputeBudget::from_instructions(listed.iter().map(|listed| {
+            let program_id = keys[listed.instruction.program_index]["pubkey"].as_str();
+            (program_id, &listed.instruction)
+        }));
+        (budget, meta["signatures"].as_array().unwrap().len(), meta["fee"].as_u64().unwrap())
+    }
+
+    #[test]
+    fn priority_fees_of_known_transactions() {
+        // Limit 200,000 at 1,000 micro-lamports: 200 lamports on top of one signature
+        let (budget, signatures, fee) = fixture_budget(include_str!("../tests/fixtures/sol_transfer.json"));
+        assert_eq!(
+            budget,
+            ComputeBudget {
+                unit_limit: 200_000,
+                limit_requested: true,
+                unit_price: 1000,
+            }
+        );
+        assert_eq!(budget.priority_fee(), Some(200));
+        assert_eq!(base_fee(signatures), Some(5000));
+        assert_eq!(base_fee(signatures).unwrap() + budget.priority_fee().unwrap(), fee);
+        assert_eq!(budget.utilization(450), Some(0.225));
+
+        // A limit but no price
+        let (budget, signatures, fee) = fixture_budget(include_str!("../tests/fixtures/token_transfer_v0.json"));
+        assert_eq!((budget.unit_limit, budget.unit_price), (60_000, 0));
+        assert_eq!(budget.priority_fee(), Some(0));
+        assert_eq!(base_fee(signatures), Some(fee));
+    }
+
+    #[test]
+    fn compute_budget_defaults_and_overflow() {
+        let transfer = InstructionInfo::from_value(&serde_json::json!({ "programIdIndex": 1, "data": "" })).unwrap();
+        let budget = ComputeBudget::from_instructions([(Some("11111111111111111111111111111111"), &transfer); 2]);
+        assert_eq!(
+            budget,
+            ComputeBudget {
+                unit_limit: 400_000,
+                limit_requested: false,
+                unit_price: 0,
+            }
+        );
+        // The default never goes past the maximum
+        let budget = ComputeBudget::from_instructions([(None, &transfer); 10]);
+        assert_eq!(budget.unit_limit, MAX_COMPUTE_UNIT_LIMIT);
+
+        // A price too high to pay
+        let budget = ComputeBudget {
+            unit_price: u64::MAX,
+            ..budget
+        };
+        assert_eq!(budget.priority_fee(), None);
+        assert_eq!(ComputeBudget { unit_limit: 0, ..budget }.utilization(10), None);
+        assert_eq!(base_fee(usize::MAX), None);
+    }
+
+    #[test]
+    fn inner_instructions_follow_their_parent() {
+        let transaction: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let details = transaction_details(&transaction);
+        let meta = &details["meta"];
+
+        let listed = list_instructions(meta, false).unwrap();
+        let rows: Vec<(&str, usize, usize)> = listed
+            .iter()
+            .map(|listed| (listed.number.as_str(), listed.depth, listed.inner_count))
+            .collect();
+        assert_eq!(rows, [("0", 1, 0), ("1", 1, 0), ("2", 1, 2), ("2.0", 2, 0), ("2.1", 3, 0)]);
+        assert_eq!(
+            listed[3].instruction.describe(None, CurrencyUnit::Sol).as_deref(),
+            // The source's token balance gives the amount its decimals
+            Some("Transfer 0.001 USDC from F7nBcd7… to 79311T5…")
+        );
+
+        let collapsed = list_instructions(meta, true).unwrap();
+        assert_eq!(collapsed.len(), 3);
+        assert_eq!(collapsed[2].inner_count, 2);
+        assert!(!inner_instructions_unrecorded(meta));
+
+        // A node that doesn't record inner instructions sends null
+        let mut unrecorded = meta.clone();
+        unrecorded["innerInstructions"] = Value::Null;
+        assert!(inner_instructions_unrecorded(&unrecorded));
+        assert_eq!(list_instructions(&unrecorded, false).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn transaction_errors_read_as_words() {
+        use solana_sdk::instruction::InstructionError;
+        use solana_sdk::transaction::TransactionError;
+        let describe = |err: TransactionError| {
+            let value = serde_json::to_value(err).unwrap();
+            (describe_transaction_error(&value), failed_instruction(&value))
+        };
+        assert_eq!(
+            describe(TransactionError::InstructionError(2, InstructionError::Custom(6001))),
+            ("Instruction #2 failed: custom program error 0x1771 (6001)".to_string(), Some(2))
+        );
+        assert_eq!(
+            describe(TransactionError::InstructionError(0, InstructionError::InvalidAccountData)),
+            ("Instruction #0 failed: invalid account data".to_string(), Some(0))
+        );
+        assert_eq!(
+            describe(TransactionError::InsufficientFundsForFee),
+            ("Insufficient funds for fee".to_string(), None)
+        );
+        assert_eq!(
+            describe(TransactionError::InsufficientFundsForRent { account_index: 3 }),
+            ("Insufficient funds for rent (account 3)".to_string(), None)
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Styled},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use serde_json::Value;
+
+use crate::theme::Theme;
+
+// Arrays longer than this start collapsed
+const COLLAPSE_OVER: usize = 20;
+
+// A JSON document shown as an indented tree whose objects and arrays fold open
+// and closed, with a cursor row
+#[derive(Debug, Clone, Default)]
+pub struct JsonTree {
+    root: Value,
+    collapsed: HashSet<String>, // JSON Pointer paths of folded objects and arrays
+    cursor: usize,
+    scroll: usize, // First visible row
+}
+
+// Name of a node within its parent
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Key<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+// One visible line of the tree; only the rows in view are turned into text
+struct Row<'a> {
+    depth: usize,
+    key: Key<'a>,
+    value: &'a Value,
+    path: Option<String>, // Set on objects and arrays, which can fold
+}
+
+impl JsonTree {
+    pub fn new(root: Value) -> Self {
+        let mut collapsed = HashSet::new();
+        collapse_long_arrays(&root, &mut String::new(), &mut collapsed);
+        Self {
+            root,
+            collapsed,
+            cursor: 0,
+            scroll: 0,
+        }
+    }
+
+    // Show a new version of the document, keeping what is folded
+    pub fn set_root(&mut self, root: Value) {
+        self.root = root;
+        self.cursor = self.cursor.min(self.rows().len().saturating_sub(1));
+    }
+
+    // Move the cursor by `delta` rows, keeping it inside a window of `height` rows
+    pub fn move_cursor(&mut self, delta: isize, height: usize) {
+        let last = self.rows().len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+        let height = height.max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+    }
+
+    // Fold or unfold the object or array under the cursor
+    pub fn toggle(&mut self) {
+        let Some(path) = self.rows().into_iter().nth(self.cursor).and_then(|row| row.path) else {
+            return;
+        };
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+    }
+
+    // The rows left visible by the folds, in order
+    fn rows(&self) -> Vec<Row<'_>> {
+        let mut rows = Vec::new();
+        let mut path = String::new();
+        for (key, child) in children(&self.root) {
+            visit(child, key, 0, &mut path, &self.collapsed, &mut rows);
+        }
+        rows
+    }
+
+    fn line<'a>(&self, row: &Row<'a>, theme: &Theme) -> Line<'a> {
+        let marker = match &row.path {
+            Some(path) if self.collapsed.contains(path) => "▸ ",
+            Some(_) => "▾ ",
+            None => "  ",
+        };
+        let key = match row.key {
+            Key::Name(name) => format!("{}: ", name).set_style(theme.title),
+            Key::Index(index) => format!("[{}] ", index).set_style(theme.border),
+        };
+        let value = match row.value {
+            Value::Object(map) => Span::raw(format!("{{{}}}", count(map.len(), "key"))),
+            Value::Array(items) => Span::raw(format!("[{}]", count(items.len(), "item"))),
+            Value::String(text) => format!("\"{}\"", text).set_style(theme.success),
+            Value::Number(number) => number.to_string().set_style(theme.value),
+            Value::Bool(flag) => flag.to_string().set_style(theme.highlight),
+            Value::Null => "null".set_style(theme.border),
+        };
+        Line::from(vec!["  ".repeat(row.depth).into(), marker.into(), key, value])
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" })
+}
+
+// Object members or array items of a value, with their keys
+fn children(value: &Value) -> Box<dyn Iterator<Item = (Key<'_>, &Value)> + '_> {
+    match value {
+        Value::Object(map) => Box::new(map.iter().map(|(name, child)| (Key::Name(name), child))),
+        Value::Array(items) => Box::new(items.iter().enumerate().map(|(index, child)| (Key::Index(index), child))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+// Extend a JSON Pointer with one key, escaping '~' and '/' in names
+fn push_key(path: &mut String, key: Key) {
+    path.push('/');
+    match key {
+        Key::Name(name) => path.push_str(&name.replace('~', "~0").replace('/', "~1")),
+        Key::Index(index) => {
+            let _ = write!(path, "{}", index);
+        }
+    }
+}
+
+fn visit<'a>(
+    value: &'a Value,
+    key: Key<'a>,
+    depth: usize,
+    path: &mut String,
+    collapsed: &HashSet<String>,
+    rows: &mut Vec<Row<'a>>,
+) {
+    let parent_len = path.len();
+    push_key(path, key);
+    let is_container = matches!(value, Value::Object(_) | Value::Array(_));
+    rows.push(Row {
+        depth,
+        key,
+        value,
+        path: is_container.then(|| path.clone()),
+    });
+    if is_container && !collapsed.contains(path.as_str()) {
+        for (key, child) in children(value) {
+            visit(child, key, depth + 1, path, collapsed, rows);
+        }
+    }
+    path.truncate(parent_len);
+}
+
+fn collapse_long_arrays(value: &Value, path: &mut String, collapsed: &mut HashSet<String>) {
+    if matches!(value, Value::Array(items) if items.len() > COLLAPSE_OVER) {
+        collapsed.insert(path.clone());
+    }
+    for (key, child) in children(value) {
+        let parent_len = path.len();
+        push_key(path, key);
+        collapse_long_arrays(child, path, collapsed);
+        path.truncate(parent_len);
+    }
+}
+
+// The tree drawn with the app theme, the cursor row highlighted
+pub struct JsonTreeView<'a> {
+    pub tree: &'a JsonTree,
+    pub theme: &'a Theme,
+}
+
+impl Widget for JsonTreeView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let JsonTreeView { tree, theme } = self;
+        let rows = tree.rows();
+        let height = area.height as usize;
+        // A shorter window than the keys last saw still shows the cursor
+        let scroll = tree
+            .scroll
+            .max((tree.cursor + 1).saturating_sub(height))
+            .min(rows.len().saturating_sub(height));
+        let lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(height)
+            .map(|(index, row)| {
+                let line = tree.line(row, theme);
+                if index == tree.cursor {
+                    line.style(theme.highlight)
+                } else {
+                    line.style(Style::default())
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(tree: &JsonTree) -> Vec<String> {
+        tree.rows()
+            .iter()
+            .map(|row| tree.line(row, &Theme::default()).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn folds_objects_and_arrays() {
+        let mut tree = JsonTree::new(serde_json::json!({
+            "slot": 7,
+            "meta": { "err": null, "logs": ["a", "b"] },
+        }));
+        assert_eq!(
+            texts(&tree),
+            [
+                "▾ meta: {2 keys}",
+                "    err: null",
+                "  ▾ logs: [2 items]",
+                "      [0] \"a\"",
+                "      [1] \"b\"",
+                "  slot: 7",
+            ]
+        );
+
+        // Enter on the array folds it, and the cursor can't go past the end
+        tree.move_cursor(2, 10);
+        tree.toggle();
+        assert_eq!(texts(&tree)[2], "  ▸ logs: [2 items]");
+        tree.move_cursor(10, 10);
+        assert_eq!(tree.cursor, 3);
+        tree.move_cursor(-10, 10);
+        tree.toggle();
+        assert_eq!(texts(&tree), ["▸ meta: {2 keys}", "  slot: 7"]);
+    }
+
+    #[test]
+    fn long_arrays_start_folded_and_folds_survive_updates() {
+        let items: Vec<u64> = (0..100).collect();
+        let mut tree = JsonTree::new(serde_json::json!({ "items": items, "a/b": [1] }));
+        assert_eq!(texts(&tree), ["▾ a/b: [1 item]", "    [0] 1", "▸ items: [100 items]"]);
+        assert!(tree.collapsed.contains("/items"));
+
+        tree.toggle();
+        tree.set_root(serde_json::json!({ "items": [], "a/b": [1, 2] }));
+        assert!(tree.collapsed.contains("/a~1b"));
+        assert_eq!(texts(&tree), ["▸ a/b: [2 items]", "▸ items: [0 items]"]);
+    }
+
+    #[test]
+    fn cursor_scrolls_the_window() {
+        let items: Vec<u64> = (0..10).collect();
+        let mut tree = JsonTree::new(serde_json::json!({ "items": items }));
+        tree.move_cursor(8, 4);
+        assert_eq!((tree.cursor, tree.scroll), (8, 5));
+        tree.move_cursor(-7, 4);
+        assert_eq!((tree.cursor, tree.scroll), (1, 1));
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 4));
+        JsonTreeView {
+            tree: &tree,
+            theme: &Theme::default(),
+        }
+        .render(buffer.area, &mut buffer);
+        let first: String = (0..20).map(|x| buffer[(x, 0)].symbol()).collect();
+        assert_eq!(first.trim_end(), "    [0] 0");
+        assert_eq!(buffer[(4, 0)].style().bg, Theme::default().highlight.bg);
+    }
+}
@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use solana_sdk::commitment_config::CommitmentLevel;
+
+use crate::app::RpcNetwork;
+
+// Everything a key or a ':' command can do in normal mode; popups and the query input
+// handle their own keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppAction {
+    Quit,
+    EditQuery,
+    NextNetwork,
+    CustomRpc,
+    NextTab,
+    PreviousTab,
+    SelectTab(usize), // Index into Tab::ALL
+    Back,
+    Open,
+    ToggleHelp,
+    ToggleDebugLog,
+    NextTheme,
+    ToggleUnit,
+    ToggleRelativeTimes,
+    Copy,
+    CycleHistoryFilter,
+    CycleHistorySort,
+    DateFilter,
+    LoadMoreHistory,
+    HistoryDetail,
+    HexViewer,
+    TokenHoldings,
+    LargestHolders,
+    SlotLeader,
+    InflationRewards,
+    ToggleNonCirculating,
+    ToggleUnchangedBalances,
+    SearchAllNetworks,
+    ToggleLogs,
+    ToggleInnerInstructions,
+    JumpToFailedInstruction,
+    ScrollLogsUp,
+    ScrollLogsDown,
+    Search,
+    Bookmark,
+    OpenBookmarks,
+    ToggleSplit,
+    Watch,
+    ToggleRawView,
+    Refresh,
+    SaveExport,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    Top,
+    Bottom,
+    CommandLine,
+    // Only reachable from the command line, as they take arguments
+    SetNetwork(RpcNetwork),
+    SetCommitment(CommitmentLevel),
+    Export(String),
+    SaveBookmark(String),
+}
+
+// Names used for actions in the config file and on the command line
+pub const ACTION_NAMES: [(&str, AppAction); 53] = [
+    ("quit", AppAction::Quit),
+    ("edit_query", AppAction::EditQuery),
+    ("next_network", AppAction::NextNetwork),
+    ("custom_rpc", AppAction::CustomRpc),
+    ("next_tab", AppAction::NextTab),
+    ("previous_tab", AppAction::PreviousTab),
+    ("tab_1", AppAction::SelectTab(0)),
+    ("tab_2", AppAction::SelectTab(1)),
+    ("tab_3", AppAction::SelectTab(2)),
+    ("tab_4", AppAction::SelectTab(3)),
+    ("back", AppAction::Back),
+    ("open", AppAction::Open),
+    ("help", AppAction::ToggleHelp),
+    ("debug_log", AppAction::ToggleDebugLog),
+    ("next_theme", AppAction::NextTheme),
+    ("toggle_unit", AppAction::ToggleUnit),
+    ("toggle_relative_times", AppAction::ToggleRelativeTimes),
+    ("copy", AppAction::Copy),
+    ("cycle_history_filter", AppAction::CycleHistoryFilter),
+    ("cycle_history_sort", AppAction::CycleHistorySort),
+    ("date_filter", AppAction::DateFilter),
+    ("load_more_history", AppAction::LoadMoreHistory),
+    ("history_detail", AppAction::HistoryDetail),
+    ("hex_viewer", AppAction::HexViewer),
+    ("token_holdings", AppAction::TokenHoldings),
+    ("largest_holders", AppAction::LargestHolders),
+    ("slot_leader", AppAction::SlotLeader),
+    ("inflation_rewards", AppAction::InflationRewards),
+    ("toggle_non_circulating", AppAction::ToggleNonCirculating),
+    ("toggle_unchanged_balances", AppAction::ToggleUnchangedBalances),
+    ("search_all_networks", AppAction::SearchAllNetworks),
+    ("toggle_logs", AppAction::ToggleLogs),
+    ("toggle_inner_instructions", AppAction::ToggleInnerInstructions),
+    ("jump_to_failed_instruction", AppAction::JumpToFailedInstruction),
+    ("scroll_logs_up", AppAction::ScrollLogsUp),
+    ("scroll_logs_down", AppAction::ScrollLogsDown),
+    ("search", AppAction::Search),
+    ("bookmark", AppAction::Bookmark),
+    ("open_bookmarks", AppAction::OpenBookmarks),
+    ("split", AppAction::ToggleSplit),
+    ("watch", AppAction::Watch),
+    ("raw_view", AppAction::ToggleRawView),
+    ("refresh", AppAction::Refresh),
+    ("save", AppAction::SaveExport),
+    ("up", AppAction::Up),
+    ("down", AppAction::Down),
+    ("page_up", AppAction::PageUp),
+    ("page_down", AppAction::PageDown),
+    ("half_page_up", AppAction::HalfPageUp),
+    ("half_page_down", AppAction::HalfPageDown),
+    ("top", AppAction::Top),
+    ("bottom", AppAction::Bottom),
+    ("command_line", AppAction::CommandLine),
+];
+
+impl AppAction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTION_NAMES
+            .iter()
+            .find(|(action_name, _)| *action_name == name)
+            .map(|(_, action)| action.clone())
+    }
+
+    // What the action does, for the help popup
+    pub fn description(&self) -> &'static str {
+        match self {
+            AppAction::Quit => "Quit application",
+            AppAction::EditQuery => "Edit the query",
+            AppAction::NextNetwork => "Cycle Devnet, Testnet, Mainnet and Solana Mainnet",
+            AppAction::CustomRpc => "Connect to a custom RPC URL",
+            AppAction::NextTab => "Next tab, or the other pane when split",
+            AppAction::PreviousTab => "Previous tab, or the other pane when split",
+            AppAction::SelectTab(0) => "Dashboard tab",
+            AppAction::SelectTab(1) => "Search tab",
+            AppAction::SelectTab(2) => "Blocks tab",
+            AppAction::SelectTab(_) => "Validators tab",
+            AppAction::Back => "Back to the previous view",
+            AppAction::Open => "Open the selected transaction or instruction",
+            AppAction::ToggleHelp => "Show or hide this help",
+            AppAction::ToggleDebugLog => "Show or hide the latest log lines",
+            AppAction::NextTheme => "Switch between the dark and light themes",
+            AppAction::ToggleUnit => "Show amounts in SOL or lamports",
+            AppAction::ToggleRelativeTimes => "Show history times as dates or \"time ago\"",
+            AppAction::Copy => "Copy the selected signature or the query",
+            AppAction::CycleHistoryFilter => "Filter history by result, or validators by status",
+            AppAction::CycleHistorySort => "Sort history newest, oldest or failed first, or validators",
+            AppAction::DateFilter => "Filter history by date",
+            AppAction::LoadMoreHistory => "Load more account history",
+            AppAction::HistoryDetail => "Details of the selected transaction",
+            AppAction::HexViewer => "View the raw data of the queried account",
+            AppAction::TokenHoldings => "Load the token accounts of the queried address, or a mint's largest holders",
+            AppAction::LargestHolders => "Rank the largest holders of the queried mint",
+            AppAction::SlotLeader => "Look up the leader of the block's slot",
+            AppAction::InflationRewards => "Load the recent inflation rewards of a stake or vote account",
+            AppAction::ToggleNonCirculating => "List or hide the non-circulating supply accounts on the dashboard",
+            AppAction::ToggleUnchangedBalances => "Show or hide unchanged balances of a transaction",
+            AppAction::SearchAllNetworks => "Ask the other networks for a query not found",
+            AppAction::ToggleLogs => "Show or hide transaction logs",
+            AppAction::ToggleInnerInstructions => "Show or hide the inner instructions of a transaction",
+            AppAction::JumpToFailedInstruction => "Select the instruction a failed transaction failed at",
+            AppAction::ScrollLogsUp => "Scroll the logs up",
+            AppAction::ScrollLogsDown => "Scroll the logs down",
+            AppAction::Search => "Search the results",
+            AppAction::Bookmark => "Bookmark the current query",
+            AppAction::OpenBookmarks => "Open bookmarks",
+            AppAction::ToggleSplit => "Split the results to compare two queries",
+            AppAction::Watch => "Watch the account for new transactions, or the transaction until it confirms",
+            AppAction::ToggleRawView => "Show the raw JSON of the result as a tree",
+            AppAction::Refresh => "Refetch the query or tab, skipping the cache",
+            AppAction::SaveExport => "Export the result to a file, asking for its name",
+            AppAction::Up => "Scroll up / select the previous row",
+            AppAction::Down => "Scroll down / select the next row",
+            AppAction::PageUp => "Scroll up a page",
+            AppAction::PageDown => "Scroll down a page",
+            AppAction::HalfPageUp => "Scroll up half a page",
+            AppAction::HalfPageDown => "Scroll down half a page",
+            AppAction::Top => "Jump to the top",
+            AppAction::Bottom => "Jump to the bottom",
+            AppAction::CommandLine => "Run a command, e.g. :network testnet",
+            AppAction::SetNetwork(_) => "Switch networks",
+            AppAction::SetCommitment(_) => "Set the query commitment",
+            AppAction::Export(_) => "Export the current result",
+            AppAction::SaveBookmark(_) => "Bookmark the current query",
+        }
+    }
+}
+
+// Default bindings: the original keys plus vim-style navigation. A key listed twice
+// does the first of its actions that applies.
+const DEFAULT_BINDINGS: [(KeyCode, KeyModifiers, AppAction); 59] = [
+    (KeyCode::Char('q'), KeyModifiers::NONE, AppAction::Quit),
+    (KeyCode::Char('e'), KeyModifiers::NONE, AppAction::EditQuery),
+    (KeyCode::Char('n'), KeyModifiers::NONE, AppAction::NextNetwork),
+    (KeyCode::Char('N'), KeyModifiers::NONE, AppAction::CustomRpc),
+    (KeyCode::Tab, KeyModifiers::NONE, AppAction::NextTab),
+    (KeyCode::BackTab, KeyModifiers::NONE, AppAction::PreviousTab),
+    (KeyCode::Char('1'), KeyModifiers::NONE, AppAction::SelectTab(0)),
+    (KeyCode::Char('2'), KeyModifiers::NONE, AppAction::SelectTab(1)),
+    (KeyCode::Char('3'), KeyModifiers::NONE, AppAction::SelectTab(2)),
+    (KeyCode::Char('4'), KeyModifiers::NONE, AppAction::SelectTab(3)),
+    (KeyCode::Esc, KeyModifiers::NONE, AppAction::Back),
+    (KeyCode::Char('b'), KeyModifiers::NONE, AppAction::Back),
+    (KeyCode::Backspace, KeyModifiers::NONE, AppAction::Back),
+    (KeyCode::Enter, KeyModifiers::NONE, AppAction::Open),
+    (KeyCode::Char('?'), KeyModifiers::NONE, AppAction::ToggleHelp),
+    (KeyCode::Char('D'), KeyModifiers::NONE, AppAction::ToggleDebugLog),
+    (KeyCode::Char('t'), KeyModifiers::NONE, AppAction::NextTheme),
+    (KeyCode::Char('u'), KeyModifiers::NONE, AppAction::ToggleUnit),
+    (KeyCode::Char('z'), KeyModifiers::NONE, AppAction::ToggleRelativeTimes),
+    (KeyCode::Char('y'), KeyModifiers::NONE, AppAction::Copy),
+    (KeyCode::Char('f'), KeyModifiers::NONE, AppAction::CycleHistoryFilter),
+    (KeyCode::Char('s'), KeyModifiers::NONE, AppAction::CycleHistorySort),
+    (KeyCode::Char('d'), KeyModifiers::NONE, AppAction::DateFilter),
+    (KeyCode::Char('L'), KeyModifiers::NONE, AppAction::LoadMoreHistory),
+    (KeyCode::Char(' '), KeyModifiers::NONE, AppAction::HistoryDetail),
+    (KeyCode::Char('x'), KeyModifiers::NONE, AppAction::HexViewer),
+    (KeyCode::Char('T'), KeyModifiers::NONE, AppAction::TokenHoldings),
+    (KeyCode::Char('h'), KeyModifiers::NONE, AppAction::LargestHolders),
+    (KeyCode::Char('P'), KeyModifiers::NONE, AppAction::SlotLeader),
+    (KeyCode::Char('R'), KeyModifiers::NONE, AppAction::InflationRewards),
+    (KeyCode::Char('c'), KeyModifiers::NONE, AppAction::ToggleNonCirculating),
+    (KeyCode::Char('a'), KeyModifiers::NONE, AppAction::SearchAllNetworks),
+    (KeyCode::Char('a'), KeyModifiers::NONE, AppAction::ToggleUnchangedBalances),
+    (KeyCode::Char('l'), KeyModifiers::NONE, AppAction::ToggleLogs),
+    (KeyCode::Char('i'), KeyModifiers::NONE, AppAction::ToggleInnerInstructions),
+    (KeyCode::Char('F'), KeyModifiers::NONE, AppAction::JumpToFailedInstruction),
+    (KeyCode::Char('['), KeyModifiers::NONE, AppAction::ScrollLogsUp),
+    (KeyCode::Char(']'), KeyModifiers::NONE, AppAction::ScrollLogsDown),
+    (KeyCode::Char('/'), KeyModifiers::NONE, AppAction::Search),
+    (KeyCode::Char(':'), KeyModifiers::NONE, AppAction::CommandLine),
+    (KeyCode::Char('m'), KeyModifiers::NONE, AppAction::Bookmark),
+    (KeyCode::Char('M'), KeyModifiers::NONE, AppAction::OpenBookmarks),
+    (KeyCode::Char('|'), KeyModifiers::NONE, AppAction::ToggleSplit),
+    (KeyCode::Char('w'), KeyModifiers::NONE, AppAction::Watch),
+    (KeyCode::Char('J'), KeyModifiers::NONE, AppAction::ToggleRawView),
+    (KeyCode::Char('r'), KeyModifiers::NONE, AppAction::Refresh),
+    (KeyCode::Char('s'), KeyModifiers::CONTROL, AppAction::SaveExport),
+    (KeyCode::Up, KeyModifiers::NONE, AppAction::Up),
+    (KeyCode::Down, KeyModifiers::NONE, AppAction::Down),
+    (KeyCode::PageUp, KeyModifiers::NONE, AppAction::PageUp),
+    (KeyCode::PageDown, KeyModifiers::NONE, AppAction::PageDown),
+    (KeyCode::Home, KeyModifiers::NONE, AppAction::Top),
+    (KeyCode::End, KeyModifiers::NONE, AppAction::Bottom),
+    (KeyCode::Char('k'), KeyModifiers::NONE, AppAction::Up),
+    (KeyCode::Char('j'), KeyModifiers::NONE, AppAction::Down),
+    (KeyCode::Char('g'), KeyModifiers::NONE, AppAction::Top),
+    (KeyCode::Char('G'), KeyModifiers::NONE, AppAction::Bottom),
+    (KeyCode::Char('u'), KeyModifiers::CONTROL, AppAction::HalfPageUp),
+    (KeyCode::Char('d'), KeyModifiers::CONTROL, AppAction::HalfPageDown),
+];
+
+// Maps key presses to actions, most keys to one
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<(KeyCode, KeyModifiers), Vec<AppAction>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings: HashMap<_, Vec<AppAction>> = HashMap::new();
+        for (code, modifiers, action) in DEFAULT_BINDINGS {
+            bindings.entry(normalize(code, modifiers)).or_default().push(action);
+        }
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    // Add or replace a binding; the key does only `action` from now on
+    pub fn bind(&mut self, key: (KeyCode, KeyModifiers), action: AppAction) {
+        self.bindings.insert(normalize(key.0, key.1), vec![action]);
+    }
+
+    // Keys bound to an action as shown in the help, special keys before letters, e.g. ["↓", "j"]
+    pub fn keys_for(&self, action: &AppAction) -> Vec<String> {
+        let mut keys: Vec<&(KeyCode, KeyModifiers)> = self
+            .bindings
+            .iter()
+            .filter(|(_, bound)| bound.contains(action))
+            .map(|(key, _)| key)
+            .collect();
+        keys.sort_by_key(|(code, modifiers)| (matches!(code, KeyCode::Char(_)), !modifiers.is_empty(), key_name(*code, *modifiers)));
+        keys.into_iter().map(|(code, modifiers)| key_name(*code, *modifiers)).collect()
+    }
+
+    // The first action of a key, for popups that only look for one
+    pub fn action(&self, key_event: &KeyEvent) -> Option<AppAction> {
+        self.actions(key_event).first().cloned()
+    }
+
+    pub fn actions(&self, key_event: &KeyEvent) -> &[AppAction] {
+        self.bindings
+            .get(&normalize(key_event.code, key_event.modifiers))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+// Shift is already part of uppercase letters, symbols and BackTab, and terminals
+// disagree on whether they also report it as a modifier
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    (code, modifiers - KeyModifiers::SHIFT)
+}
+
+// A key as shown to the user, e.g. "j", "Ctrl+D", "PgDn" or "Shift+Tab"
+pub fn key_name(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let key = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if modifiers.is_empty() => c.to_string(),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        code => format!("{:?}", code),
+    };
+    let mut name = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        name.push_str("Ctrl+");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        name.push_str("Alt+");
+    }
+    name + &key
+}
+
+// Parse a key as written in the config file, e.g. "j", "G", "ctrl+d", "pagedown" or "shift+tab"
+pub fn parse_key(text: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut parts: Vec<&str> = text.split('+').collect();
+    // "+" on its own, or as the last key of "ctrl++"
+    let key = match parts.pop()? {
+        "" if parts.last() == Some(&"") => {
+            parts.pop();
+            "+"
+        }
+        key => key,
+    };
+    for modifier in parts {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next()?, chars.next()) {
+        (c, None) => KeyCode::Char(c),
+        _ => match key.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            _ => return None,
+        },
+    };
+    Some((code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn vim_keys_sit_alongside_the_originals() {
+        let keymap = Keymap::default();
+        for (key, action) in [
+            (press(KeyCode::Down, KeyModifiers::NONE), AppAction::Down),
+            (press(KeyCode::Char('j'), KeyModifiers::NONE), AppAction::Down),
+            (press(KeyCode::Char('k'), KeyModifiers::NONE), AppAction::Up),
+            (press(KeyCode::Char('g'), KeyModifiers::NONE), AppAction::Top),
+            (press(KeyCode::Char('G'), KeyModifiers::SHIFT), AppAction::Bottom),
+            (press(KeyCode::Char('d'), KeyModifiers::CONTROL), AppAction::HalfPageDown),
+            (press(KeyCode::Char('d'), KeyModifiers::NONE), AppAction::DateFilter),
+            (press(KeyCode::BackTab, KeyModifiers::SHIFT), AppAction::PreviousTab),
+        ] {
+            assert_eq!(keymap.action(&key), Some(action), "{:?}", key);
+        }
+        assert_eq!(keymap.action(&press(KeyCode::Char('d'), KeyModifiers::ALT)), None);
+    }
+
+    #[test]
+    fn config_bindings_override_defaults() {
+        let mut keymap = Keymap::default();
+        keymap.bind(parse_key("ctrl+n").unwrap(), AppAction::from_name("next_network").unwrap());
+        keymap.bind(parse_key("j").unwrap(), AppAction::from_name("bottom").unwrap());
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            Some(AppAction::NextNetwork)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('j'), KeyModifiers::NONE)),
+            Some(AppAction::Bottom)
+        );
+    }
+
+    #[test]
+    fn lists_keys_for_help() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.keys_for(&AppAction::Down), vec!["↓", "j"]);
+        assert_eq!(keymap.keys_for(&AppAction::HalfPageDown), vec!["Ctrl+D"]);
+        // 'a' asks the other networks before it toggles balances, until a config takes the key
+        let a = press(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(keymap.actions(&a), [AppAction::SearchAllNetworks, AppAction::ToggleUnchangedBalances]);
+        assert_eq!(keymap.keys_for(&AppAction::ToggleUnchangedBalances), vec!["a"]);
+        keymap.bind(parse_key("a").unwrap(), AppAction::ToggleUnchangedBalances);
+        assert_eq!(keymap.actions(&a), [AppAction::ToggleUnchangedBalances]);
+        assert!(keymap.keys_for(&AppAction::SearchAllNetworks).is_empty());
+        keymap.bind(parse_key("shift+tab").unwrap(), AppAction::Top);
+        assert_eq!(keymap.keys_for(&AppAction::Top), vec!["Home", "Shift+Tab", "g"]);
+    }
+
+    #[test]
+    fn parses_config_keys() {
+        assert_eq!(parse_key("G"), Some((KeyCode::Char('G'), KeyModifiers::NONE)));
+        assert_eq!(parse_key("Ctrl+u"), Some((KeyCode::Char('u'), KeyModifiers::CONTROL)));
+        assert_eq!(parse_key("pgdn"), Some((KeyCode::PageDown, KeyModifiers::NONE)));
+        assert_eq!(parse_key("shift+tab"), Some((KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert_eq!(parse_key("+"), Some((KeyCode::Char('+'), KeyModifiers::NONE)));
+        assert_eq!(parse_key("ctrl++"), Some((KeyCode::Char('+'), KeyModifiers::CONTROL)));
+        assert_eq!(parse_key("hyper+x"), None);
+        assert_eq!(parse_key("enterr"), None);
+        assert_eq!(parse_key(""), None);
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// Well-known program IDs and token mints, and their names
+const BUILTIN_LABELS: [(&str, &str); 13] = [
+    ("11111111111111111111111111111111", "System Program"),
+    ("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "SPL Token"),
+    ("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", "Token-2022"),
+    ("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL", "Associated Token"),
+    ("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr", "Memo"),
+    ("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo", "Memo v1"),
+    ("Stake11111111111111111111111111111111111111", "Stake Program"),
+    ("Vote111111111111111111111111111111111111111", "Vote Program"),
+    ("BPFLoaderUpgradeab1e11111111111111111111111", "BPF Loader Upgradeable"),
+    ("ComputeBudget111111111111111111111111111111", "Compute Budget"),
+    ("So11111111111111111111111111111111111111112", "Wrapped SOL"),
+    ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "USDC"),
+    ("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", "USDT"),
+];
+
+static LABELS: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+fn builtin_labels() -> HashMap<String, String> {
+    BUILTIN_LABELS
+        .iter()
+        .map(|(pubkey, label)| (pubkey.to_string(), label.to_string()))
+        .collect()
+}
+
+// Add user-defined labels to the built-in ones; only the first call has an effect
+pub fn init(user_labels: HashMap<String, String>) {
+    let mut labels = builtin_labels();
+    labels.extend(user_labels);
+    let _ = LABELS.set(labels);
+}
+
+pub fn lookup(pubkey: &str) -> Option<&'static str> {
+    LABELS
+        .get_or_init(builtin_labels)
+        .get(pubkey)
+        .map(String::as_str)
+}
+
+// "Label (abbrev…)" for a labelled pubkey
+pub fn display(pubkey: &str) -> Option<String> {
+    let label = lookup(pubkey)?;
+    let abbrev: String = pubkey.chars().take(4).collect();
+    Some(format!("{} ({}…)", label, abbrev))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_programs_resolve() {
+        for (pubkey, label) in BUILTIN_LABELS {
+            assert_eq!(lookup(pubkey), Some(label));
+        }
+        assert_eq!(
+            display("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").as_deref(),
+            Some("SPL Token (Toke…)")
+        );
+    }
+
+    #[test]
+    fn unknown_pubkey_has_no_label() {
+        assert_eq!(lookup("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"), None);
+        assert_eq!(display(""), None);
+    }
+}
@@ -0,0 +1,26 @@
+// The explorer as a library: the TUI in `app`, with queries, RPC access and formatting
+// usable without it. The soonscan binary only parses its arguments and wires these up.
+pub mod app;
+pub mod bookmarks;
+pub mod command;
+pub mod config;
+pub mod error;
+pub mod fees;
+pub mod fixtures;
+pub mod format;
+pub mod history;
+pub mod instruction;
+pub mod json_tree;
+pub mod keymap;
+pub mod labels;
+pub mod live;
+pub mod loader;
+pub mod logging;
+pub mod metadata;
+pub mod query;
+pub mod rpc;
+pub mod stake;
+pub mod storage;
+pub mod system;
+pub mod theme;
+pub mod token;
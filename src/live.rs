@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::Value;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use tokio::sync::mpsc::UnboundedSender;
+
+// Wait before the first reconnect, doubled after every failed attempt
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+// What the slot subscription reports to the app
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotEvent {
+    Connected,
+    Slot(u64),
+    Disconnected(String), // Why; the dashboard polling carries on meanwhile
+}
+
+// What a logsSubscribe for one address reports to the app
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    Connected,
+    // A confirmed transaction mentioning the address
+    Transaction {
+        signature: String,
+        slot: u64,
+        err: Option<Value>,
+    },
+    Disconnected(String),
+}
+
+// The WebSocket endpoint of an http(s) RPC URL, on the same host and path
+pub fn ws_url(rpc_url: &str) -> Option<String> {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        Some(format!("wss://{}", rest))
+    } else {
+        rpc_url.strip_prefix("http://").map(|rest| format!("ws://{}", rest))
+    }
+}
+
+// Delay before reconnect attempt `attempt` (counting from 0)
+pub fn backoff(attempt: u32) -> Duration {
+    MIN_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_BACKOFF)
+}
+
+// Stream slotSubscribe notifications into `events`, reconnecting with backoff
+// whenever the socket can't be opened or drops. Returns once nobody listens.
+pub async fn subscribe_slots(url: String, events: UnboundedSender<SlotEvent>) {
+    let mut attempt = 0;
+    loop {
+        let reason = match PubsubClient::new(&url).await {
+            Ok(client) => match client.slot_subscribe().await {
+                Ok((mut slots, _unsubscribe)) => {
+                    attempt = 0;
+                    if events.send(SlotEvent::Connected).is_err() {
+                        return;
+                    }
+                    while let Some(info) = slots.next().await {
+                        if events.send(SlotEvent::Slot(info.slot)).is_err() {
+                            return;
+                        }
+                    }
+                    "connection closed".to_string()
+                }
+                Err(e) => e.to_string(),
+            },
+            Err(e) => e.to_string(),
+        };
+        tracing::warn!(%reason, attempt, "Slot subscription lost, falling back to polling");
+        if events.send(SlotEvent::Disconnected(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(backoff(attempt)).await;
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+// Stream confirmed transactions that mention `address` into `events`, with the
+// same reconnect backoff as the slot feed. Returns once nobody listens.
+pub async fn subscribe_logs(url: String, address: String, events: UnboundedSender<WatchEvent>) {
+    let mut attempt = 0;
+    loop {
+        let reason = match PubsubClient::new(&url).await {
+            Ok(client) => {
+                let filter = RpcTransactionLogsFilter::Mentions(vec![address.clone()]);
+                let config = RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::confirmed()),
+                };
+                match client.logs_subscribe(filter, config).await {
+                    Ok((mut logs, _unsubscribe)) => {
+                        attempt = 0;
+                        if events.send(WatchEvent::Connected).is_err() {
+                            return;
+                        }
+                        while let Some(response) = logs.next().await {
+                            let transaction = WatchEvent::Transaction {
+                                signature: response.value.signature,
+                                slot: response.context.slot,
+                                err: response.value.err.and_then(|err| serde_json::to_value(err).ok()),
+                            };
+                            if events.send(transaction).is_err() {
+                                return;
+                            }
+                        }
+                        "connection closed".to_string()
+                    }
+                    Err(e) => e.to_string(),
+                }
+            }
+            Err(e) => e.to_string(),
+        };
+        tracing::warn!(%address, %reason, attempt, "Logs subscription lost, falling back to polling");
+        if events.send(WatchEvent::Disconnected(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(backoff(attempt)).await;
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ws_urls_follow_the_rpc_scheme() {
+        assert_eq!(
+            ws_url("https://rpc.devnet.soo.network/rpc").as_deref(),
+            Some("wss://rpc.devnet.soo.network/rpc")
+        );
+        assert_eq!(ws_url("http://127.0.0.1:8899").as_deref(), Some("ws://127.0.0.1:8899"));
+        assert_eq!(ws_url("ftp://example.com"), None);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_a_cap() {
+        assert_eq!(backoff(0), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(6), MAX_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoints_report_a_disconnect() {
+        // Nothing listens on a port that was just released
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(subscribe_slots(url, sender));
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap();
+        assert!(matches!(event, Some(SlotEvent::Disconnected(_))));
+
+        // The task stops once the app stops listening
+        drop(receiver);
+        tokio::time::timeout(Duration::from_secs(5), task).await.unwrap().unwrap();
+    }
+}
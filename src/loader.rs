@@ -0,0 +1,98 @@
+use solana_sdk::pubkey::Pubkey;
+
+pub const BPF_LOADER_UPGRADEABLE_ID: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+// Bytes before the executable data of a ProgramData account: tag, slot, and an optional authority
+pub const PROGRAM_DATA_METADATA_LEN: usize = 4 + 8 + 1 + 32;
+
+// Accounts owned by the upgradeable BPF loader that carry program information
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpgradeableState {
+    // A program account, pointing at the account that holds its code
+    Program { program_data: Pubkey },
+    // The code of a program, with the slot it was last deployed in.
+    // No upgrade authority means the program is immutable.
+    ProgramData {
+        slot: u64,
+        upgrade_authority: Option<Pubkey>,
+        executable_len: usize,
+    },
+}
+
+// Decode the bincode-serialized loader state; buffers and unknown data give None
+pub fn unpack(data: &[u8]) -> Option<UpgradeableState> {
+    let tag = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+    match tag {
+        2 => Some(UpgradeableState::Program {
+            program_data: Pubkey::new_from_array(data.get(4..36)?.try_into().ok()?),
+        }),
+        3 => {
+            let slot = u64::from_le_bytes(data.get(4..12)?.try_into().ok()?);
+            let upgrade_authority = match data.get(12)? {
+                0 => None,
+                1 => Some(Pubkey::new_from_array(data.get(13..45)?.try_into().ok()?)),
+                _ => return None,
+            };
+            Some(UpgradeableState::ProgramData {
+                slot,
+                upgrade_authority,
+                executable_len: data.len().saturating_sub(PROGRAM_DATA_METADATA_LEN),
+            })
+        }
+        _ => None,
+    }
+}
+
+// The ProgramData address the loader derives for a program
+pub fn program_data_address(program_id: &Pubkey) -> Pubkey {
+    let loader = Pubkey::from_str_const(BPF_LOADER_UPGRADEABLE_ID);
+    Pubkey::find_program_address(&[program_id.as_ref()], &loader).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpacks_program_account() {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend([9; 32]);
+        assert_eq!(
+            unpack(&data),
+            Some(UpgradeableState::Program {
+                program_data: Pubkey::new_from_array([9; 32])
+            })
+        );
+    }
+
+    #[test]
+    fn unpacks_program_data() {
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend(123_456u64.to_le_bytes());
+        data.push(1);
+        data.extend([4; 32]);
+        data.extend([0xAA; 100]);
+        assert_eq!(
+            unpack(&data),
+            Some(UpgradeableState::ProgramData {
+                slot: 123_456,
+                upgrade_authority: Some(Pubkey::new_from_array([4; 32])),
+                executable_len: 100,
+            })
+        );
+
+        // An immutable program keeps the padding where the authority was
+        data[12] = 0;
+        assert!(matches!(
+            unpack(&data),
+            Some(UpgradeableState::ProgramData { upgrade_authority: None, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_other_states() {
+        assert_eq!(unpack(&[]), None);
+        assert_eq!(unpack(&1u32.to_le_bytes()), None); // Buffer
+        assert_eq!(unpack(&[2, 0, 0, 0, 1, 2]), None); // Truncated
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::env;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use tracing::Level;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::storage;
+
+// Name of the log file in the state directory
+pub const LOG_FILE_NAME: &str = "soonscan.log";
+
+// Lines kept for the debug popup
+const LOG_BUFFER_LINES: usize = 200;
+
+// Size past which the log file is moved aside and a new one started
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+// Older log files kept, soonscan.log.1 being the newest
+const ROTATED_LOG_FILES: usize = 3;
+
+// The latest log lines, oldest first, as the debug popup shows them
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<String>>>);
+
+impl LogBuffer {
+    pub fn push(&self, line: impl Into<String>) {
+        if let Ok(mut lines) = self.0.lock() {
+            if lines.len() == LOG_BUFFER_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line.into());
+        }
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.0.lock().map(|lines| lines.iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+// Each formatted event arrives in one write
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in String::from_utf8_lossy(buf).lines().filter(|line| !line.trim().is_empty()) {
+            self.push(line);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+// A log file that is moved to <name>.1 once it passes `max_bytes`, the older ones
+// shifting to <name>.2 and so on, up to ROTATED_LOG_FILES of them
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+}
+
+impl RotatingFile {
+    pub fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            written: file.metadata()?.len(),
+            file,
+            max_bytes,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..ROTATED_LOG_FILES).rev() {
+            let _ = fs::rename(rotated_path(&self.path, index), rotated_path(&self.path, index + 1));
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+// soonscan.log.<index>
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+// Local time of day, short enough for the debug popup
+struct TimeOfDay;
+
+impl FormatTime for TimeOfDay {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        write!(w, "{}", chrono::Local::now().format("%H:%M:%S%.3f"))
+    }
+}
+
+// What RUST_LOG asks for, e.g. "debug" or "soonscan=debug,reqwest=info"; None when
+// it isn't set or can't be read
+fn env_filter() -> Option<Targets> {
+    env::var("RUST_LOG").ok()?.parse().ok()
+}
+
+// Events as the debug popup shows them: time of day, level, spans and message
+fn buffer_layer<S>(buffer: &LogBuffer, filter: Targets) -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_target(false)
+        .with_timer(TimeOfDay)
+        .with_writer(buffer.clone())
+        .with_filter(filter)
+}
+
+// Logging for the TUI, which owns the terminal: into `buffer` for the debug popup,
+// and into soonscan.log in the state directory when `log_file` is set or RUST_LOG
+// asks for logs. Returns the file written to.
+pub fn init_tui(buffer: &LogBuffer, log_file: bool) -> Option<PathBuf> {
+    let filter = env_filter().unwrap_or_else(|| Targets::new().with_target("soonscan", Level::DEBUG));
+    let path = storage::state_dir()
+        .filter(|_| log_file || env::var_os("RUST_LOG").is_some())
+        .map(|dir| dir.join(LOG_FILE_NAME));
+    let file = path.as_ref().map(|path| RotatingFile::open(path, MAX_LOG_BYTES));
+    let buffer_layer = buffer_layer(buffer, filter.clone());
+    let (file_layer, file_error) = match file {
+        Some(Ok(file)) => {
+            let layer = tracing_subscriber::fmt::layer().with_ansi(false).with_writer(Mutex::new(file));
+            (Some(layer.with_filter(filter)), None)
+        }
+        Some(Err(err)) => (None, Some(err)),
+        None => (None, None),
+    };
+    let subscriber = tracing_subscriber::registry().with(buffer_layer).with(file_layer);
+    let _ = tracing::subscriber::set_global_default(subscriber);
+    // Still seen in the debug popup
+    if let (Some(path), Some(err)) = (&path, file_error) {
+        tracing::warn!("Could not open the log file {}: {}", path.display(), err);
+        return None;
+    }
+    path
+}
+
+// Logging for the command line: warnings, or whatever RUST_LOG asks for, on stderr
+pub fn init_cli() {
+    let filter = env_filter().unwrap_or_else(|| Targets::new().with_target("soonscan", Level::WARN));
+    let layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .without_time()
+        .with_target(false)
+        .with_writer(io::stderr)
+        .with_filter(filter);
+    let _ = tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_buffer_keeps_the_latest_lines() {
+        let mut buffer = LogBuffer::default();
+        buffer.write_all(b"first\nsecond\n\n").unwrap();
+        assert_eq!(buffer.lines(), ["first", "second"]);
+        for index in 0..LOG_BUFFER_LINES {
+            buffer.push(index.to_string());
+        }
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), LOG_BUFFER_LINES);
+        assert_eq!(lines[0], "0");
+        assert_eq!(lines.last().unwrap(), &(LOG_BUFFER_LINES - 1).to_string());
+    }
+
+    #[test]
+    fn events_reach_the_buffer_with_their_span() {
+        let buffer = LogBuffer::default();
+        let filter = Targets::new().with_target("soonscan", Level::DEBUG);
+        let subscriber = tracing_subscriber::registry().with(buffer_layer(&buffer, filter));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("query", query = %"42", kind = "block");
+            let _entered = span.enter();
+            tracing::warn!(method = "getBlock", "RPC request failed");
+            tracing::trace!("Below the filter");
+        });
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains(" WARN query{query=42 kind=\"block\"}: RPC request failed method=\"getBlock\""));
+    }
+
+    #[test]
+    fn log_files_are_rotated_past_their_size() {
+        let dir = env::temp_dir().join(format!("soonscan-logs-{}", std::process::id()));
+        let path = dir.join(LOG_FILE_NAME);
+        let mut file = RotatingFile::open(&path, 10).unwrap();
+        for line in ["one line\n", "two line\n", "three ln\n", "four ln\n", "five ln\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "five ln\n");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 1)).unwrap(), "four ln\n");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 3)).unwrap(), "two line\n");
+        // The oldest is dropped
+        assert!(!rotated_path(&path, 4).exists());
+
+        // Appended to when opened again
+        let mut file = RotatingFile::open(&path, 100).unwrap();
+        file.write_all(b"six\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "five ln\nsix\n");
+        let _ = fs::remove_dir_all(dir);
+    }
+}
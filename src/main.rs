@@ -2,135 +2,455 @@ use std::env;
 use std::io;
 use std::process;
 use std::sync::Arc;
+use std::time::Duration;
 
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::signature::Signature;
-use tokio::sync::Mutex;
+use solana_sdk::commitment_config::CommitmentConfig;
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
-mod app;
+use soonscan::error::AppError;
+use soonscan::fixtures::Transport;
+use soonscan::rpc::{RateLimiter, RpcApi};
+use soonscan::{app, bookmarks, config, history, labels, logging, query, storage, theme};
 
-#[tokio::main]
-async fn main() -> io::Result<()> {
+fn main() -> io::Result<()> {
+    tokio::runtime::Runtime::new()?.block_on(run())
+}
+
+async fn run() -> io::Result<()> {
     // Parse command-line arguments
-    let args: Vec<String> = env::args().collect();
-    
-    // Function to select RPC URL
-    fn select_rpc_url(flag: &str) -> String {
-        match flag {
-            "-D" => "https://rpc.devnet.soo.network/rpc".to_string(),
-            "-T" => "https://rpc.testnet.soo.network/rpc".to_string(),
-            "-M" => "https://api.mainnet-beta.solana.com".to_string(),
-            _ => "https://api.mainnet-beta.solana.com".to_string(), // default to mainnet
+    let mut args: Vec<String> = env::args().collect();
+
+    // Dashboard refresh interval: --refresh <seconds>
+    let mut refresh_secs = app::DEFAULT_REFRESH_SECS;
+    if let Some(pos) = args.iter().position(|arg| arg == "--refresh") {
+        match args.get(pos + 1).and_then(|secs| secs.parse::<u64>().ok()) {
+            Some(secs) if secs > 0 => refresh_secs = secs,
+            _ => {
+                eprintln!("--refresh expects a number of seconds greater than zero");
+                process::exit(1);
+            }
         }
+        args.drain(pos..=pos + 1);
     }
+    let refresh_interval = Duration::from_secs(refresh_secs);
 
-    // Determine action based on arguments
-    match args.len() {
-        1 => {
-            // No arguments - run TUI
-            run_tui().await
-        },
-        2 => {
-            // Check if first arg is a flag or transaction
-            if ["-D", "-T", "-M"].contains(&args[1].as_str()) {
-                println!("Error: Transaction hash is required when using RPC flag");
-                println!("Usage: {} [flag] <transaction_signature>", args[0]);
-                println!("Flags: -D (devnet), -T (testnet), -M (mainnet)");
-                run_tui().await
-            } else {
-                // Assume it's a transaction signature on mainnet
-                let rpc_url = "https://api.mainnet-beta.solana.com".to_string();
-                check_transaction(rpc_url, &args[1]).await
+    // Color theme: --theme <dark|light>, overriding the config file
+    let mut theme = None;
+    if let Some(pos) = args.iter().position(|arg| arg == "--theme") {
+        match args.get(pos + 1).and_then(|name| theme::Theme::from_name(name)) {
+            Some(selected) => theme = Some(selected),
+            None => {
+                eprintln!("--theme expects one of: dark, light");
+                process::exit(1);
             }
-        },
-        3 => {
-            // RPC flag and transaction signature
-            let rpc_url = select_rpc_url(&args[1]);
-            check_transaction(rpc_url, &args[2]).await
-        },
-        _ => {
-            println!("Too many arguments");
-            println!("Usage: {} [flag] <transaction_signature>", args[0]);
-            println!("Flags: -D (devnet), -T (testnet), -M (mainnet)");
-            run_tui().await
         }
+        args.drain(pos..=pos + 1);
     }
-}
 
-// Separate function to check transaction status
-async fn check_transaction(rpc_url: String, signature_str: &str) -> io::Result<()> {
-    // Parse the transaction signature
-    let signature = match signature_str.parse::<Signature>() {
-        Ok(sig) => sig,
-        Err(_) => {
-            eprintln!("Invalid transaction signature format");
-            process::exit(1);
+    // Live slot updates over WebSocket, on unless --no-live is given
+    let live_slots = match args.iter().position(|arg| arg == "--no-live") {
+        Some(pos) => {
+            args.remove(pos);
+            false
+        }
+        None => true,
+    };
+
+    // Also log to a file in the state directory, as RUST_LOG does
+    let log_file = match args.iter().position(|arg| arg == "--log-file") {
+        Some(pos) => {
+            args.remove(pos);
+            true
         }
+        None => false,
     };
 
-    // Create RPC client
-    let client = RpcClient::new(rpc_url.clone());
-
-    // Fetch transaction statuses
-    match client.get_signature_statuses_with_history(&[signature]) {
-        Ok(response) => {
-            println!("Using RPC: {}", rpc_url);
-            if let Some(status) = response.value.first() {
-                match status {
-                    Some(tx_status) => {
-                        println!("Transaction Status Details:");
-                        println!("Slot: {}", tx_status.slot);
-                        println!("Confirmations: {:?}", tx_status.confirmations);
-                        println!("Confirmation Status: {:?}", tx_status.confirmation_status);
-                        
-                        // Check for transaction success
-                        if tx_status.status.is_ok() {
-                            println!("Transaction Status: Successful ✅");
-                        } else {
-                            println!("Transaction Status: Failed ❌");
-                            if let Some(err) = &tx_status.err {
-                                println!("Error: {:?}", err);
-                            }
-                        }
-                    },
-                    None => {
-                        println!("Transaction not found or does not exist");
-                    }
-                }
-            } else {
-                println!("No status information available");
+    // Write every RPC exchange to a directory (--record <dir>), or answer from one
+    // without the network (--replay <dir>)
+    let mut transport = Transport::Network;
+    for option in ["--record", "--replay"] {
+        let Some(pos) = args.iter().position(|arg| arg == option) else {
+            continue;
+        };
+        let Some(dir) = args.get(pos + 1).map(|dir| storage::expand_home(dir)) else {
+            eprintln!("{} expects a directory", option);
+            process::exit(1);
+        };
+        transport = match transport {
+            Transport::Network if option == "--record" => Transport::Record(dir),
+            Transport::Network if dir.is_dir() => Transport::Replay(dir),
+            Transport::Network => {
+                eprintln!("--replay expects a directory of recorded answers, {} isn't one", dir.display());
+                process::exit(1);
+            }
+            _ => {
+                eprintln!("--record and --replay can't be combined");
+                process::exit(1);
             }
-        },
+        };
+        args.drain(pos..=pos + 1);
+    }
+    // Slot updates come over WebSocket, which a replay has no recording of
+    let live_slots = live_slots && !matches!(transport, Transport::Replay(_));
+
+    // Without arguments the TUI runs and owns the terminal; subcommands and transaction
+    // checks print on it, so their logs can go to stderr
+    if args.len() > 1 {
+        logging::init_cli();
+    }
+
+    // Version subcommand: soonscan version [-D|-T|-M|-S] [--json]
+    if args.get(1).map(String::as_str) == Some("version") {
+        let flag = network_flag(&args[2..], &["--json"]).unwrap_or_else(|err| usage_error(&args[0], &err));
+        let json = args[2..].iter().any(|arg| arg == "--json");
+        return print_version(select_rpc_url(flag), json).await;
+    }
+
+    // Fees subcommand: soonscan fees [-D|-T|-M|-S] [--json]
+    if args.get(1).map(String::as_str) == Some("fees") {
+        let flag = network_flag(&args[2..], &["--json"]).unwrap_or_else(|err| usage_error(&args[0], &err));
+        let json = args[2..].iter().any(|arg| arg == "--json");
+        return print_fees(select_rpc_url(flag), json).await.or_else(exit_with);
+    }
+
+    // Leader subcommand: soonscan leader <SLOT> [-D|-T|-M|-S]
+    if args.get(1).map(String::as_str) == Some("leader") {
+        let flag = network_flag(&args[2..], &[]).unwrap_or_else(|err| usage_error(&args[0], &err));
+        let Some(slot) = args[2..].iter().find(|arg| !arg.starts_with('-')) else {
+            println!("Usage: {} leader <slot> [flag]", args[0]);
+            process::exit(2);
+        };
+        return print_slot_leader(select_rpc_url(flag), slot).await.or_else(exit_with);
+    }
+
+    match parse_invocation(&args[1..]) {
+        Ok(Invocation::Tui) => run_tui(refresh_interval, theme, live_slots, transport, log_file).await,
+        Ok(Invocation::Check { rpc_url, query }) => {
+            check_transaction(rpc_url, &query, transport).await.or_else(exit_with)
+        }
+        Err(err) => usage_error(&args[0], &err),
+    }
+}
+
+const NETWORK_FLAGS: [&str; 4] = ["-D", "-T", "-M", "-S"];
+
+fn select_rpc_url(flag: &str) -> String {
+    match flag {
+        "-D" => "https://rpc.devnet.soo.network/rpc".to_string(),
+        "-T" => "https://rpc.testnet.soo.network/rpc".to_string(),
+        "-S" => "https://api.mainnet-beta.solana.com".to_string(), // Solana L1 mainnet-beta
+        _ => "https://rpc.mainnet.soo.network/rpc".to_string(),
+    }
+}
+
+// What the command line asks for once the options are taken out
+#[derive(Debug, PartialEq)]
+enum Invocation {
+    Tui,
+    Check { rpc_url: String, query: String },
+}
+
+// A signature with an optional network flag before or after it, or nothing for the TUI
+fn parse_invocation(args: &[String]) -> Result<Invocation, String> {
+    let flag = network_flag(args, &[])?;
+    let mut queries = args.iter().filter(|arg| !NETWORK_FLAGS.contains(&arg.as_str()));
+    match (queries.next(), queries.next()) {
+        (None, _) if args.is_empty() => Ok(Invocation::Tui),
+        (None, _) => Err("Transaction hash is required when using RPC flag".to_string()),
+        (Some(query), None) => Ok(Invocation::Check {
+            rpc_url: select_rpc_url(flag),
+            query: query.clone(),
+        }),
+        (Some(_), Some(_)) => Err("Too many arguments".to_string()),
+    }
+}
+
+// The one network flag among `args`, "-M" when there is none. Flags other than
+// those and `extra` are rejected rather than run against the default network.
+fn network_flag<'a>(args: &'a [String], extra: &[&str]) -> Result<&'a str, String> {
+    let mut flags = args.iter().map(String::as_str).filter(|arg| arg.starts_with('-') && !extra.contains(arg));
+    if let Some(unknown) = flags.clone().find(|flag| !NETWORK_FLAGS.contains(flag)) {
+        return Err(format!("Unknown flag {}", unknown));
+    }
+    match (flags.next(), flags.next()) {
+        (_, Some(_)) => Err("Only one network flag can be given".to_string()),
+        (flag, None) => Ok(flag.unwrap_or("-M")),
+    }
+}
+
+fn usage_error(program: &str, err: &str) -> ! {
+    eprintln!("Error: {}", err);
+    eprintln!("Usage: {} [flag] <transaction_signature>", program);
+    eprintln!("Flags: -D (devnet), -T (testnet), -M (mainnet), -S (Solana mainnet-beta)");
+    process::exit(2);
+}
+
+// Print a command line error and exit with the status for its kind
+fn exit_with(err: AppError) -> io::Result<()> {
+    eprintln!("Error: {}", err);
+    process::exit(err.exit_code());
+}
+
+// Print the status of the transaction a command line query names
+async fn check_transaction(rpc_url: String, signature_str: &str, transport: Transport) -> Result<(), AppError> {
+    let api = RpcApi::new(rpc_url.clone(), CommitmentConfig::default()).with_transport(transport);
+    let tx_status = query::check_transaction(&api, signature_str).await?;
+    println!("Using RPC: {}", rpc_url);
+    println!("Transaction Status Details:");
+    println!("Slot: {}", tx_status.slot);
+    println!("Confirmations: {:?}", tx_status.confirmations);
+    println!("Confirmation Status: {:?}", tx_status.confirmation_status);
+
+    // Check for transaction success
+    if tx_status.status.is_ok() {
+        println!("Transaction Status: Successful ✅");
+    } else {
+        println!("Transaction Status: Failed ❌");
+        if let Some(err) = &tx_status.err {
+            println!("Error: {:?}", err);
+        }
+    }
+
+    Ok(())
+}
+
+// Print the base fee and recent prioritization fees, to pick a compute unit price
+async fn print_fees(rpc_url: String, json: bool) -> Result<(), AppError> {
+    let api = RpcApi::new(rpc_url.clone(), CommitmentConfig::default());
+    let estimate = app::App::fetch_fee_estimate(&api).await?;
+
+    if json {
+        let output = serde_json::json!({
+            "rpc": rpc_url,
+            "lamportsPerSignature": estimate.lamports_per_signature,
+            "prioritizationFees": estimate.priority.map(|priority| serde_json::json!({
+                "slots": priority.slots,
+                "min": priority.min,
+                "median": priority.median,
+                "p75": priority.p75,
+                "max": priority.max,
+            })),
+        });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+        return Ok(());
+    }
+
+    println!("Using RPC: {}", rpc_url);
+    println!("Base Fee: {} lamports per signature", estimate.lamports_per_signature);
+    match estimate.priority {
+        Some(priority) => {
+            println!("Prioritization Fees (micro-lamports per compute unit, last {} slots):", priority.slots);
+            println!("  Min: {}", priority.min);
+            println!("  Median: {}", priority.median);
+            println!("  P75: {}", priority.p75);
+            println!("  Max: {}", priority.max);
+        }
+        None => println!("Prioritization Fees: not available from this RPC"),
+    }
+
+    Ok(())
+}
+
+// Print the validator scheduled to produce a slot, with its label when known
+async fn print_slot_leader(rpc_url: String, slot_str: &str) -> Result<(), AppError> {
+    let slot = slot_str
+        .parse::<u64>()
+        .map_err(|_| AppError::SlotParse(slot_str.to_string()))?;
+    labels::init(config::Config::load().labels);
+
+    let api = RpcApi::new(rpc_url.clone(), CommitmentConfig::default());
+    let leader = app::App::fetch_slot_leader(&api, slot).await?;
+    let identity = leader["identity"]
+        .as_str()
+        .ok_or_else(|| AppError::NotFound(format!("Leader schedule for slot {}", slot)))?;
+    println!("Using RPC: {}", rpc_url);
+    println!("Slot: {}", slot);
+    println!("Epoch: {} (slot {} of the epoch)", leader["epoch"], leader["slotIndex"]);
+    match labels::lookup(identity) {
+        Some(name) => println!("Leader: {} ({})", identity, name),
+        None => println!("Leader: {}", identity),
+    }
+
+    Ok(())
+}
+
+// Print the local crate version and the node version reported by the RPC
+async fn print_version(rpc_url: String, json: bool) -> io::Result<()> {
+    let crate_version = env!("CARGO_PKG_VERSION");
+
+    let api = RpcApi::new(rpc_url.clone(), CommitmentConfig::default());
+    let node_version = api.get_version().await;
+
+    if json {
+        let mut output = serde_json::json!({
+            "soonscan": crate_version,
+            "rpc": rpc_url,
+            "node": null,
+        });
+        match &node_version {
+            Ok(version) => {
+                output["node"] = serde_json::json!({
+                    "solana-core": version.solana_core,
+                    "feature-set": version.feature_set,
+                });
+            }
+            Err(e) => {
+                output["warning"] = format!("Could not reach RPC: {}", e).into();
+            }
+        }
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+        return Ok(());
+    }
+
+    println!("soonscan {}", crate_version);
+    println!("Using RPC: {}", rpc_url);
+    match node_version {
+        Ok(version) => {
+            println!("Node Version: {}", version.solana_core);
+            match version.feature_set {
+                Some(feature_set) => println!("Feature Set: {}", feature_set),
+                None => println!("Feature Set: unknown"),
+            }
+        }
         Err(e) => {
-            eprintln!("Error fetching transaction status: {}", e);
-            process::exit(1);
+            tracing::warn!("Could not fetch node version: {}", e);
         }
     }
 
     Ok(())
 }
 
+// Put the terminal back before the default hook prints the panic and its backtrace,
+// so a drawing bug can't leave the shell in raw mode on the alternate screen.
+// The event loop and drawing run on the main thread; a panic in a background task
+// only ends that task and the TUI keeps going.
+fn install_panic_hook() {
+    let report = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            let _ = crossterm::execute!(
+                io::stdout(),
+                crossterm::event::DisableMouseCapture,
+                crossterm::event::DisableBracketedPaste,
+                crossterm::terminal::LeaveAlternateScreen,
+                crossterm::cursor::Show
+            );
+            let _ = crossterm::terminal::disable_raw_mode();
+        }
+        report(info);
+    }));
+}
+
 // Separate function to run TUI
-async fn run_tui() -> io::Result<()> {
-    // Initialize terminal
+async fn run_tui(
+    refresh_interval: Duration,
+    theme: Option<theme::Theme>,
+    live_slots: bool,
+    transport: Transport,
+    log_file: bool,
+) -> io::Result<()> {
+    // Initialize terminal on the alternate screen, leaving the shell's scrollback alone
+    install_panic_hook();
+    crossterm::terminal::enable_raw_mode()?;
+    crossterm::execute!(
+        io::stdout(),
+        crossterm::terminal::EnterAlternateScreen,
+        crossterm::event::EnableMouseCapture,
+        crossterm::event::EnableBracketedPaste
+    )?;
     let stdout = io::stdout();
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
-    crossterm::terminal::enable_raw_mode()?;
     
-    // Create app state
-    let app = Arc::new(Mutex::new(app::App::default()));
+    // Labels from the config file are shown next to the built-in program names
+    let config = config::Config::load();
+    labels::init(config.labels);
+
+    // Create app state; nothing may write to the terminal from here, so logs go to
+    // the debug popup and the log file
+    let mut app = app::App::default();
+    app.log_file = logging::init_tui(&app.log, log_file);
+    app.refresh_interval = refresh_interval;
+    app.live_slots = live_slots;
+    app.transport = transport;
+    app.theme = theme.or(config.theme).unwrap_or_default();
+    app.currency_unit = config.currency_unit.unwrap_or_default();
+    app.cache_ttl = config.cache_ttl.unwrap_or(app::DEFAULT_CACHE_TTL);
+    if let Some(rate) = config.rate_limit {
+        app.rate_limiter = Arc::new(RateLimiter::new(rate));
+    }
+    for (key, action) in config.keys {
+        app.keymap.bind(key, action);
+    }
+    app.query_history = history::QueryHistory::load();
+    app.bookmarks = bookmarks::Bookmarks::load();
     
     // Run app
-    let result = app::App::run(app, &mut terminal).await;
+    let (app, result) = app::App::run(app, &mut terminal).await;
     
-    // Cleanup
-    crossterm::terminal::disable_raw_mode()?;
-    terminal.clear()?;
-    terminal.show_cursor()?;
+    // Cleanup, also when the app stopped on an error; every step runs even if one fails
+    let cleanup: io::Result<()> = [
+        crossterm::execute!(
+            io::stdout(),
+            crossterm::event::DisableMouseCapture,
+            crossterm::event::DisableBracketedPaste,
+            crossterm::terminal::LeaveAlternateScreen
+        ),
+        crossterm::terminal::disable_raw_mode(),
+        terminal.show_cursor(),
+    ]
+    .into_iter()
+    .collect();
+
+    if let Err(err) = app.query_history.save() {
+        eprintln!("Warning: could not save query history: {}", err);
+    }
     
-    result
+    result.and(cleanup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn the_network_flag_goes_before_or_after_the_signature() {
+        let devnet = Ok(Invocation::Check {
+            rpc_url: "https://rpc.devnet.soo.network/rpc".to_string(),
+            query: "sig".to_string(),
+        });
+        assert_eq!(parse_invocation(&args("-D sig")), devnet);
+        assert_eq!(parse_invocation(&args("sig -D")), devnet);
+        assert_eq!(
+            parse_invocation(&args("sig")),
+            Ok(Invocation::Check {
+                rpc_url: "https://rpc.mainnet.soo.network/rpc".to_string(),
+                query: "sig".to_string(),
+            })
+        );
+        assert_eq!(parse_invocation(&[]), Ok(Invocation::Tui));
+    }
+
+    #[test]
+    fn unknown_flags_are_usage_errors() {
+        assert_eq!(parse_invocation(&args("sig -X")), Err("Unknown flag -X".to_string()));
+        assert_eq!(parse_invocation(&args("-devnet sig")), Err("Unknown flag -devnet".to_string()));
+        assert_eq!(parse_invocation(&args("-D -T sig")), Err("Only one network flag can be given".to_string()));
+        assert_eq!(parse_invocation(&args("sig other")), Err("Too many arguments".to_string()));
+        assert_eq!(
+            parse_invocation(&args("-D")),
+            Err("Transaction hash is required when using RPC flag".to_string())
+        );
+
+        // Subcommands only add their own options
+        assert_eq!(network_flag(&args("-T --json"), &["--json"]), Ok("-T"));
+        assert_eq!(network_flag(&args("--json"), &["--json"]), Ok("-M"));
+        assert_eq!(network_flag(&args("--json"), &[]), Err("Unknown flag --json".to_string()));
+    }
 }
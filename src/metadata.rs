@@ -0,0 +1,344 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+pub const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+// Account discriminator of a metadata account
+const KEY_METADATA_V1: u8 = 4;
+
+// Metaplex allows at most this many creators
+const MAX_CREATORS: u32 = 5;
+
+// Limits of the off-chain JSON fetch; it is only a nicety
+const OFF_CHAIN_TIMEOUT: Duration = Duration::from_secs(5);
+const OFF_CHAIN_MAX_BYTES: usize = 64 * 1024;
+
+// Metaplex token metadata of a mint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16, // Royalties, 100 = 1%
+    pub creators: Vec<Creator>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8, // Percent of the royalties
+}
+
+// Why a metadata account didn't decode; `field` is the one being read
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DecodeError {
+    #[error("not a metadata account (key {0})")]
+    NotMetadata(u8),
+    #[error("metadata ends before its {field}")]
+    Truncated { field: &'static str },
+    #[error("invalid UTF-8 in the {field}")]
+    InvalidUtf8 { field: &'static str },
+    #[error("invalid tag {tag} in the {field}")]
+    InvalidTag { field: &'static str, tag: u8 },
+    #[error("{0} creators, more than Metaplex allows")]
+    TooManyCreators(u32),
+}
+
+// The metadata account of a mint, a PDA of the metadata program
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(METADATA_PROGRAM_ID).expect("valid program id");
+    Pubkey::find_program_address(&[b"metadata", program_id.as_ref(), mint.as_ref()], &program_id).0
+}
+
+// Reads borsh-encoded fields, naming the field that failed
+struct BorshReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BorshReader<'a> {
+    fn take(&mut self, len: usize, field: &'static str) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() < len {
+            return Err(DecodeError::Truncated { field });
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self, field: &'static str) -> Result<u8, DecodeError> {
+        Ok(self.take(1, field)?[0])
+    }
+
+    fn u16(&mut self, field: &'static str) -> Result<u16, DecodeError> {
+        let bytes = self.take(2, field)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self, field: &'static str) -> Result<u32, DecodeError> {
+        let bytes = self.take(4, field)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn bool(&mut self, field: &'static str) -> Result<bool, DecodeError> {
+        match self.u8(field)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(DecodeError::InvalidTag { field, tag }),
+        }
+    }
+
+    fn pubkey(&mut self, field: &'static str) -> Result<Pubkey, DecodeError> {
+        let bytes = self.take(32, field)?;
+        Ok(Pubkey::new_from_array(bytes.try_into().expect("32 bytes")))
+    }
+
+    // A u32 length and UTF-8; Metaplex pads names, symbols and URIs with NULs
+    fn string(&mut self, field: &'static str) -> Result<String, DecodeError> {
+        let len = self.u32(field)? as usize;
+        let bytes = self.take(len, field)?;
+        let text = std::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8 { field })?;
+        Ok(text.trim_end_matches('\0').to_string())
+    }
+}
+
+// Decode a metadata account up to its mutability flag; the fields after it were
+// added over time and aren't shown
+pub fn decode(data: &[u8]) -> Result<Metadata, DecodeError> {
+    let mut reader = BorshReader { data };
+    let key = reader.u8("key")?;
+    if key != KEY_METADATA_V1 {
+        return Err(DecodeError::NotMetadata(key));
+    }
+    let update_authority = reader.pubkey("update authority")?;
+    let mint = reader.pubkey("mint")?;
+    let name = reader.string("name")?;
+    let symbol = reader.string("symbol")?;
+    let uri = reader.string("uri")?;
+    let seller_fee_basis_points = reader.u16("seller fee")?;
+    let creators = if reader.bool("creators")? {
+        let count = reader.u32("creators")?;
+        if count > MAX_CREATORS {
+            return Err(DecodeError::TooManyCreators(count));
+        }
+        (0..count)
+            .map(|_| {
+                Ok(Creator {
+                    address: reader.pubkey("creator")?,
+                    verified: reader.bool("creator")?,
+                    share: reader.u8("creator")?,
+                })
+            })
+            .collect::<Result<_, DecodeError>>()?
+    } else {
+        Vec::new()
+    };
+    Ok(Metadata {
+        update_authority,
+        mint,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        creators,
+        primary_sale_happened: reader.bool("primary sale flag")?,
+        is_mutable: reader.bool("mutability flag")?,
+    })
+}
+
+// What the off-chain JSON at a metadata URI shows
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffChainMetadata {
+    pub image: Option<String>,
+    pub attributes: Vec<(String, String)>, // Trait type and value
+}
+
+impl OffChainMetadata {
+    pub fn from_value(value: &Value) -> Self {
+        let text = |value: &Value| match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        Self {
+            image: value.get("image").and_then(Value::as_str).map(str::to_string),
+            attributes: value
+                .get("attributes")
+                .and_then(Value::as_array)
+                .map(|attributes| {
+                    attributes
+                        .iter()
+                        .filter_map(|attribute| {
+                            Some((text(attribute.get("trait_type")?), text(attribute.get("value")?)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Fetch the JSON at an http(s) metadata URI; None if it's slow, too large, or not JSON
+pub async fn fetch_off_chain(client: &Client, uri: &str) -> Option<OffChainMetadata> {
+    if !uri.starts_with("https://") && !uri.starts_with("http://") {
+        return None;
+    }
+    let mut response = client
+        .get(uri)
+        .timeout(OFF_CHAIN_TIMEOUT)
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?;
+    if response.content_length().is_some_and(|len| len > OFF_CHAIN_MAX_BYTES as u64) {
+        return None;
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.ok()? {
+        body.extend_from_slice(&chunk);
+        if body.len() > OFF_CHAIN_MAX_BYTES {
+            return None;
+        }
+    }
+    let value: Value = serde_json::from_slice(&body).ok()?;
+    Some(OffChainMetadata::from_value(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn borsh_string(text: &str, padded_len: usize) -> Vec<u8> {
+        let mut bytes = (padded_len as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.resize(4 + padded_len, 0);
+        bytes
+    }
+
+    // Metadata as Metaplex writes it, with the optional fields that follow mutability
+    fn metadata_fixture(name: &str, symbol: &str, seller_fee: u16, creators: &[([u8; 32], bool, u8)]) -> Vec<u8> {
+        let mut data = vec![KEY_METADATA_V1];
+        data.extend([1; 32]);
+        data.extend([2; 32]);
+        data.extend(borsh_string(name, 32));
+        data.extend(borsh_string(symbol, 10));
+        data.extend(borsh_string("https://example.com/meta.json", 200));
+        data.extend(seller_fee.to_le_bytes());
+        if creators.is_empty() {
+            data.push(0);
+        } else {
+            data.push(1);
+            data.extend((creators.len() as u32).to_le_bytes());
+            for (address, verified, share) in creators {
+                data.extend(address);
+                data.extend([u8::from(*verified), *share]);
+            }
+        }
+        data.extend([1, 1]); // Primary sale happened, mutable
+        data.extend([1, 255, 1, 0]); // Edition nonce, token standard
+        data
+    }
+
+    #[test]
+    fn decodes_fungible_metadata() {
+        let metadata = decode(&metadata_fixture("USD Coin", "USDC", 0, &[])).unwrap();
+        assert_eq!(metadata.name, "USD Coin");
+        assert_eq!(metadata.symbol, "USDC");
+        assert_eq!(metadata.uri, "https://example.com/meta.json");
+        assert_eq!(metadata.update_authority, Pubkey::new_from_array([1; 32]));
+        assert_eq!(metadata.mint, Pubkey::new_from_array([2; 32]));
+        assert!(metadata.creators.is_empty());
+        assert!(metadata.is_mutable);
+    }
+
+    #[test]
+    fn decodes_nft_metadata_with_creators() {
+        let data = metadata_fixture("Degen #42", "DGN", 500, &[([5; 32], true, 70), ([6; 32], false, 30)]);
+        let metadata = decode(&data).unwrap();
+        assert_eq!(metadata.seller_fee_basis_points, 500);
+        assert_eq!(
+            metadata.creators,
+            [
+                Creator {
+                    address: Pubkey::new_from_array([5; 32]),
+                    verified: true,
+                    share: 70,
+                },
+                Creator {
+                    address: Pubkey::new_from_array([6; 32]),
+                    verified: false,
+                    share: 30,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rogue_metadata_is_an_error() {
+        let data = metadata_fixture("Degen #42", "DGN", 500, &[([5; 32], true, 100)]);
+        // Cut anywhere before the mutability flag
+        assert_eq!(decode(&data[..100]), Err(DecodeError::Truncated { field: "name" }));
+        assert_eq!(decode(&data[..200]), Err(DecodeError::Truncated { field: "uri" }));
+        assert_eq!(decode(&[]), Err(DecodeError::Truncated { field: "key" }));
+        assert_eq!(decode(&[6; 10]), Err(DecodeError::NotMetadata(6)));
+
+        // A name longer than the account
+        let mut long_name = data.clone();
+        long_name[65..69].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(decode(&long_name), Err(DecodeError::Truncated { field: "name" }));
+
+        let mut bad_utf8 = data.clone();
+        bad_utf8[69] = 0xff;
+        assert_eq!(decode(&bad_utf8), Err(DecodeError::InvalidUtf8 { field: "name" }));
+
+        // Creators flag 7, then a creator count of a million
+        let creators_at = 1 + 32 + 32 + 36 + 14 + 204 + 2;
+        let mut bad_flag = data.clone();
+        bad_flag[creators_at] = 7;
+        assert_eq!(decode(&bad_flag), Err(DecodeError::InvalidTag { field: "creators", tag: 7 }));
+        let mut many = data.clone();
+        many[creators_at + 1..creators_at + 5].copy_from_slice(&1_000_000u32.to_le_bytes());
+        assert_eq!(decode(&many), Err(DecodeError::TooManyCreators(1_000_000)));
+        assert_eq!(DecodeError::TooManyCreators(9).to_string(), "9 creators, more than Metaplex allows");
+    }
+
+    #[test]
+    fn off_chain_json_attributes() {
+        let off_chain = OffChainMetadata::from_value(&serde_json::json!({
+            "name": "Degen #42",
+            "image": "https://example.com/42.png",
+            "attributes": [
+                { "trait_type": "Background", "value": "Blue" },
+                { "trait_type": "Level", "value": 7 },
+                { "value": "no trait" },
+            ],
+        }));
+        assert_eq!(off_chain.image.as_deref(), Some("https://example.com/42.png"));
+        assert_eq!(
+            off_chain.attributes,
+            [
+                ("Background".to_string(), "Blue".to_string()),
+                ("Level".to_string(), "7".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_address_is_the_metaplex_pda() {
+        // USDC's metadata account
+        let usdc = Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+        assert_eq!(
+            metadata_address(&usdc).to_string(),
+            "5x38Kp4hvdomTCnCrAny4UtMUt5rQBdB6px2K1Ui45Wq"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status_client_types::TransactionStatus;
+
+use crate::error::AppError;
+use crate::rpc::{ChainApi, RpcResult};
+
+// What a query names. Addresses and signatures are told apart by how many bytes
+// their base58 decodes to, whatever its length in characters; slots are all digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryKind {
+    Address(Pubkey),
+    Signature(Signature),
+    Slot(u64),
+}
+
+impl QueryKind {
+    pub fn parse(query: &str) -> RpcResult<Self> {
+        if let Ok(address) = Pubkey::from_str(query) {
+            Ok(QueryKind::Address(address))
+        } else if let Ok(signature) = Signature::from_str(query) {
+            Ok(QueryKind::Signature(signature))
+        } else if let Some(slot) = parse_slot(query) {
+            Ok(QueryKind::Slot(slot))
+        } else {
+            Err(AppError::InvalidQuery(query.to_string()))
+        }
+    }
+
+    // What the query opens, for messages such as "Loaded account in 0.3s"
+    pub fn label(&self) -> &'static str {
+        match self {
+            QueryKind::Address(_) => "account",
+            QueryKind::Signature(_) => "transaction",
+            QueryKind::Slot(_) => "block",
+        }
+    }
+}
+
+// The query in what was typed or pasted: surrounding whitespace and quotes are
+// dropped, and an explorer link such as https://explorer.solana.com/tx/<signature>?cluster=devnet
+// gives the signature, address or slot in its path. Anything else is kept as it is.
+pub fn normalize_query(input: &str) -> String {
+    let query = input.trim().trim_matches(['"', '\'', '`']).trim();
+    // A link starts with a scheme or a host name
+    let link = query.split_once("://").map_or(query, |(_, rest)| rest);
+    let Some((_, path)) = link.split_once('/').filter(|(host, _)| host.contains('.')) else {
+        return query.to_string();
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit('/')
+        .find(|segment| QueryKind::parse(segment).is_ok())
+        .unwrap_or(query)
+        .to_string()
+}
+
+// A slot number; only plain digits count, and the 32 to 44 characters of an address
+// are too many for a u64
+pub fn parse_slot(query: &str) -> Option<u64> {
+    if query.is_empty() || !query.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    query.parse().ok()
+}
+
+// The status of the transaction a command line query names, as `soonscan <signature>` prints it.
+// The query is normalized like one typed in the TUI, so explorer links work too.
+pub async fn check_transaction(api: &dyn ChainApi, input: &str) -> RpcResult<TransactionStatus> {
+    let signature = match QueryKind::parse(&normalize_query(input)) {
+        Ok(QueryKind::Signature(signature)) => signature,
+        _ => return Err(AppError::SignatureParse(input.to_string())),
+    };
+    api.get_signature_status(&signature)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Transaction {}", signature)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::tests::MockChainClient;
+
+    #[test]
+    fn explorer_links_give_their_query() {
+        let signature = Signature::from([7; 64]).to_string();
+        let usdc = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        let cases = [
+            (format!("  {}\n", signature), signature.clone()),
+            (format!("\"{}\"", signature), signature.clone()),
+            (format!("'{}'", usdc), usdc.to_string()),
+            (format!("https://explorer.solana.com/tx/{}?cluster=devnet", signature), signature.clone()),
+            (format!("https://explorer.solana.com/address/{}/tokens", usdc), usdc.to_string()),
+            ("https://explorer.solana.com/block/301234567".to_string(), "301234567".to_string()),
+            (format!("https://solscan.io/tx/{}#instructions", signature), signature.clone()),
+            (format!("https://solscan.io/token/{}", usdc), usdc.to_string()),
+            (format!("https://solana.fm/address/{}?cluster=mainnet-alpha", usdc), usdc.to_string()),
+            (format!("https://xray.helius.xyz/tx/{}", signature), signature.clone()),
+            (format!("https://explorer.soo.network/tx/{}", signature), signature.clone()),
+            (format!("solscan.io/account/{}", usdc), usdc.to_string()),
+            // Nothing to find in the link, or not a link at all
+            ("https://explorer.solana.com/".to_string(), "https://explorer.solana.com/".to_string()),
+            ("12/05".to_string(), "12/05".to_string()),
+            ("hello".to_string(), "hello".to_string()),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize_query(&input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn digit_queries_are_slots() {
+        assert_eq!(parse_slot("301234567"), Some(301_234_567));
+        assert_eq!(parse_slot("0"), Some(0));
+        assert_eq!(parse_slot(""), None);
+        assert_eq!(parse_slot("+12"), None);
+        assert_eq!(parse_slot("12a"), None);
+        assert_eq!(parse_slot("99999999999999999999999"), None);
+        assert_eq!(parse_slot("11111111111111111111111111111111"), None);
+    }
+
+    #[test]
+    fn queries_are_classified_by_what_they_decode_to() {
+        let signature = Signature::from([7; 64]).to_string();
+        // Leading zero bytes make base58 strings short, and all ones
+        let zero_signature = Signature::default().to_string();
+        let cases: [(&str, &str); 16] = [
+            ("11111111111111111111111111111111", "account"),
+            ("Vote111111111111111111111111111111111111111", "account"),
+            ("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "account"),
+            ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "account"),
+            ("So11111111111111111111111111111111111111112", "account"),
+            (&signature, "transaction"),
+            (&zero_signature, "transaction"),
+            ("0", "block"),
+            ("301234567", "block"),
+            ("18446744073709551615", "block"),
+            ("18446744073709551616", "invalid"),
+            ("", "invalid"),
+            ("hello world", "invalid"),
+            ("0x3f", "invalid"),
+            ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v1111", "invalid"),
+            ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1l", "invalid"),
+        ];
+        for (query, expected) in cases {
+            let kind = QueryKind::parse(query);
+            assert_eq!(kind.as_ref().map_or("invalid", QueryKind::label), expected, "{}", query);
+            if expected == "invalid" {
+                assert!(matches!(kind, Err(AppError::InvalidQuery(_))));
+            }
+        }
+        assert_eq!(zero_signature.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn transactions_are_checked_on_the_given_chain() {
+        let signature = Signature::from([7; 64]);
+        let status = serde_json::json!({
+            "slot": 10,
+            "confirmations": null,
+            "status": { "Ok": null },
+            "err": null,
+            "confirmationStatus": "finalized",
+        });
+        let chain = MockChainClient::default().answer("getSignatureStatuses", signature, status);
+        let found = check_transaction(&chain, &signature.to_string()).await.unwrap();
+        assert_eq!(found.slot, 10);
+        assert!(matches!(
+            check_transaction(&chain, &Signature::default().to_string()).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(check_transaction(&chain, "42").await, Err(AppError::SignatureParse(_))));
+        assert_eq!(chain.calls(), ["getSignatureStatuses", "getSignatureStatuses"]);
+    }
+}
@@ -0,0 +1,1697 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, Response, StatusCode};
+use serde_json::Value;
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClientConfig};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_custom_error::{
+    JSON_RPC_SERVER_ERROR_BLOCK_CLEANED_UP, JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED,
+    JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+};
+use solana_client::rpc_request::{RpcError, RpcRequest, RpcResponseErrorData};
+use solana_client::rpc_response::{RpcConfirmedTransactionStatusWithSignature, RpcLeaderSchedule, RpcVersionInfo};
+use solana_rpc_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::epoch_schedule::EpochSchedule;
+use solana_sdk::message::Message;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction::Json, TransactionStatus, UiInstruction,
+    UiMessage::Parsed, UiParsedInstruction, UiTransactionEncoding,
+};
+
+use crate::error::AppError;
+use crate::fixtures::{self, Transport};
+use crate::loader::{self, UpgradeableState};
+use crate::metadata;
+use crate::stake::{self, StakeState, StakeStatus};
+use crate::token;
+
+// Number of request latencies kept for the rolling average
+const LATENCY_SAMPLES: usize = 20;
+
+// Requests still unanswered after this fail with AppError::Timeout
+pub const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Requests per second sent to one RPC unless the config sets `rate_limit`; the
+// public SOON endpoints answer much faster bursts with 429 Too Many Requests
+pub const DEFAULT_RATE_LIMIT: u32 = 5;
+
+// Times a request answered with 429 is sent again before its error is returned
+const MAX_RATE_LIMITED_RETRIES: u32 = 3;
+
+// Seconds of requests that can go out at once, like a dashboard refresh; public
+// endpoints count requests over windows of several seconds
+const RATE_LIMIT_BURST_SECS: u32 = 4;
+
+// Wait after a 429 that doesn't say how long to back off with Retry-After
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+// Most accounts a node returns from one getMultipleAccounts request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+// Code of a request the node rejects as malformed, such as one asking for more
+// accounts than it allows
+const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+
+pub type RpcResult<T> = Result<T, AppError>;
+
+// Minimum balances for rent exemption by RPC URL and data length. They only change
+// with the cluster's rent parameters, so each is fetched once per run.
+static RENT_EXEMPT_MINIMUMS: LazyLock<std::sync::Mutex<HashMap<(String, usize), u64>>> = LazyLock::new(Default::default);
+
+// First block each RPC URL still serves, asked for once per run
+static FIRST_AVAILABLE_BLOCKS: LazyLock<std::sync::Mutex<HashMap<String, u64>>> = LazyLock::new(Default::default);
+
+// What the app reads from the chain. RpcApi talks to a node; tests substitute
+// canned answers.
+#[async_trait]
+pub trait ChainApi: Send + Sync + std::fmt::Debug {
+    // Where the answers come from; cached results are kept under it
+    fn url(&self) -> &str;
+
+    // Any JSON-RPC method, returning the whole response with its "error" object
+    // when the node answered with one
+    async fn request(&self, method: &str, params: Value) -> RpcResult<Value>;
+
+    // Any JSON-RPC method, returning its "result" field
+    async fn call(&self, method: &str, params: Value) -> RpcResult<Value>;
+
+    // Hash of the network's first block, which tells networks apart
+    async fn get_genesis_hash(&self) -> RpcResult<String>;
+
+    // Lamports charged per signature
+    async fn get_base_fee(&self) -> RpcResult<u64>;
+
+    async fn get_slot(&self) -> RpcResult<u64>;
+
+    // The getSupply value: total, circulating and non-circulating lamports, with the
+    // accounts holding the non-circulating supply
+    async fn get_supply(&self) -> RpcResult<Value>;
+
+    async fn get_transaction_count(&self) -> RpcResult<u64>;
+
+    // Slots per epoch and the shorter warmup epochs before them
+    async fn get_epoch_schedule(&self) -> RpcResult<EpochSchedule>;
+
+    // Identity of the validator scheduled to produce `slot`, or None when the node
+    // no longer, or not yet, has the schedule of its epoch
+    async fn get_slot_leader(&self, slot: u64) -> RpcResult<Option<Pubkey>>;
+
+    // Lowest slot whose block the node still has; older ones were pruned from its ledger
+    async fn get_first_available_block(&self) -> RpcResult<u64>;
+
+    // The block at `slot`, or None when the slot was skipped or pruned
+    async fn get_block(&self, slot: u64) -> RpcResult<Option<Value>>;
+
+    async fn get_account(&self, address: &Pubkey) -> RpcResult<Account>;
+
+    // The accounts at `addresses`, in the same order; None where there is no account
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> RpcResult<Vec<Option<Account>>>;
+
+    // Lamports, owner and size of an account, with token mints and accounts and
+    // upgradeable programs decoded
+    async fn get_account_overview(&self, address: &Pubkey) -> RpcResult<Value>;
+
+    // Up to `limit` signatures of an address, newest first, between `until` and `before`
+    async fn get_signatures(
+        &self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> RpcResult<Vec<RpcConfirmedTransactionStatusWithSignature>>;
+
+    // The transaction with its status, balances, instructions and logs
+    async fn get_transaction_details(&self, signature: &Signature) -> RpcResult<Value>;
+
+    // None until the node has seen the transaction; searches the whole history
+    async fn get_signature_status(&self, signature: &Signature) -> RpcResult<Option<TransactionStatus>>;
+}
+
+// Recent RPC round-trip times, oldest first
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    samples: VecDeque<Duration>,
+}
+
+impl LatencyTracker {
+    pub fn record(&mut self, latency: Duration) {
+        if self.samples.len() == LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        let count = self.samples.len() as u32;
+        (count > 0).then(|| self.samples.iter().sum::<Duration>() / count)
+    }
+}
+
+// Recently looked up results keyed by network and query, least recently used
+// first. Each entry is kept until its time to live runs out, or forever without one.
+#[derive(Debug)]
+pub struct ResponseCache<V> {
+    capacity: usize,
+    entries: VecDeque<CacheEntry<V>>,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry<V> {
+    network: String,
+    query: String,
+    value: V,
+    expires: Option<Instant>,
+}
+
+impl<V: Clone> ResponseCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    // The cached result, counting the lookup as a hit or a miss; expired entries are dropped
+    pub fn get(&mut self, network: &str, query: &str) -> Option<V> {
+        let now = Instant::now();
+        self.entries.retain(|entry| entry.expires.is_none_or(|expires| expires > now));
+        let Some(index) = self.position(network, query) else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        let entry = self.entries.remove(index)?;
+        let value = entry.value.clone();
+        self.entries.push_back(entry);
+        Some(value)
+    }
+
+    // Store a result, evicting the least recently used one when full
+    pub fn insert(&mut self, network: &str, query: &str, value: V, ttl: Option<Duration>) {
+        if let Some(index) = self.position(network, query) {
+            self.entries.remove(index);
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(CacheEntry {
+            network: network.to_string(),
+            query: query.to_string(),
+            value,
+            expires: ttl.map(|ttl| Instant::now() + ttl),
+        });
+    }
+
+    fn position(&self, network: &str, query: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.network == network && entry.query == query)
+    }
+}
+
+// Token bucket each request to an endpoint takes a token from. It holds a few
+// seconds' worth of requests and refills at `rate` per second, so short bursts go
+// out at once and longer ones are spread out. A 429 pauses it for as long as the
+// node asks.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rate: u32,
+    bucket: std::sync::Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64, // Below zero once requests are waiting for tokens not yet refilled
+    refilled_at: Instant,
+    paused_until: Option<Instant>, // Set by a 429; nothing is sent before
+    throttled_until: Option<Instant>, // When the last request held back goes out
+}
+
+impl RateLimiter {
+    pub fn new(rate: u32) -> Self {
+        let rate = rate.max(1);
+        Self {
+            rate,
+            bucket: std::sync::Mutex::new(Bucket {
+                tokens: f64::from(rate.saturating_mul(RATE_LIMIT_BURST_SECS)),
+                refilled_at: Instant::now(),
+                paused_until: None,
+                throttled_until: None,
+            }),
+        }
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    // Wait for this request's turn
+    pub async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            tracing::debug!(wait_ms = wait.as_millis() as u64, "Request held back by the rate limit");
+        }
+        tokio::time::sleep(wait).await;
+        // A 429 answered meanwhile holds back requests that already had a token too
+        while let Some(wait) = self.paused(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    // Take the next token, which may not be refilled yet, and return how long until it is
+    fn reserve(&self, now: Instant) -> Duration {
+        let Ok(mut bucket) = self.bucket.lock() else {
+            return Duration::ZERO;
+        };
+        let rate = f64::from(self.rate);
+        let refilled = now.saturating_duration_since(bucket.refilled_at).as_secs_f64() * rate;
+        bucket.tokens = (bucket.tokens + refilled).min(rate * f64::from(RATE_LIMIT_BURST_SECS)) - 1.0;
+        bucket.refilled_at = now;
+        let mut ready = now + Duration::from_secs_f64((-bucket.tokens).max(0.0) / rate);
+        if let Some(paused_until) = bucket.paused_until.filter(|paused_until| *paused_until > ready) {
+            ready = paused_until;
+        }
+        if ready > now {
+            bucket.throttled_until = bucket.throttled_until.max(Some(ready));
+        }
+        ready - now
+    }
+
+    // Send nothing for `wait`, as a 429's Retry-After asks
+    pub fn pause(&self, wait: Duration) {
+        if let Ok(mut bucket) = self.bucket.lock() {
+            let until = Some(Instant::now() + wait);
+            bucket.paused_until = bucket.paused_until.max(until);
+            bucket.throttled_until = bucket.throttled_until.max(until);
+        }
+    }
+
+    fn paused(&self, now: Instant) -> Option<Duration> {
+        let bucket = self.bucket.lock().ok()?;
+        bucket.paused_until.filter(|until| *until > now).map(|until| until - now)
+    }
+
+    // Whether requests are being held back, by the rate or by a 429
+    pub fn is_throttled(&self) -> bool {
+        self.bucket
+            .lock()
+            .is_ok_and(|bucket| bucket.throttled_until.is_some_and(|until| until > Instant::now()))
+    }
+}
+
+// Client for one RPC endpoint; every request sent through it is rate limited and timed
+#[derive(Debug, Clone)]
+pub struct RpcApi {
+    client: Client,
+    url: String,
+    latencies: Arc<std::sync::Mutex<LatencyTracker>>,
+    limiter: Arc<RateLimiter>,
+    commitment: CommitmentConfig,
+    transport: Transport,
+}
+
+impl RpcApi {
+    pub fn new(url: impl Into<String>, commitment: CommitmentConfig) -> Self {
+        Self::shared(http_client(), url, Arc::default()).with_commitment(commitment)
+    }
+
+    // An endpoint reusing the app's HTTP connections and latency statistics
+    pub fn shared(
+        client: Client,
+        url: impl Into<String>,
+        latencies: Arc<std::sync::Mutex<LatencyTracker>>,
+    ) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            latencies,
+            limiter: Arc::new(RateLimiter::new(DEFAULT_RATE_LIMIT)),
+            commitment: CommitmentConfig::default(),
+            transport: Transport::Network,
+        }
+    }
+
+    pub fn with_commitment(self, commitment: CommitmentConfig) -> Self {
+        Self { commitment, ..self }
+    }
+
+    pub fn with_transport(self, transport: Transport) -> Self {
+        Self { transport, ..self }
+    }
+
+    // Share a rate limit with the other clients of the same endpoint
+    pub fn with_rate_limiter(self, limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter, ..self }
+    }
+
+    // Solana client for the same endpoint at this endpoint's commitment, sending
+    // through `request` like every other call
+    fn rpc_client(&self) -> RpcClient {
+        RpcClient::new_sender(ApiSender(self.clone()), RpcClientConfig::with_commitment(self.commitment))
+    }
+
+    fn record(&self, latency: Duration) {
+        if let Ok(mut latencies) = self.latencies.lock() {
+            latencies.record(latency);
+        }
+    }
+
+    // Send a request to the node, logging how long the answer took, waits for the
+    // rate limit included
+    async fn post(&self, method: &str, params: &Value) -> RpcResult<Value> {
+        let started_at = Instant::now();
+        let response = self.send_with_retries(method, params).await;
+        let elapsed_ms = started_at.elapsed().as_millis() as u64;
+        match &response {
+            Ok(_) => tracing::debug!(method, elapsed_ms, url = %self.url, "RPC answered"),
+            Err(err) => tracing::warn!(method, elapsed_ms, url = %self.url, error = %err, "RPC request failed"),
+        }
+        response
+    }
+
+    async fn send_with_retries(&self, method: &str, params: &Value) -> RpcResult<Value> {
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let mut retries = 0;
+        loop {
+            self.limiter.acquire().await;
+            let started_at = Instant::now();
+            let response = self
+                .client
+                .post(&self.url)
+                .header("Content-Type", "application/json")
+                .json(&payload)
+                .send()
+                .await?;
+            // Only a 429 is worth another try; anything else would fail the same way again
+            if response.status() == StatusCode::TOO_MANY_REQUESTS && retries < MAX_RATE_LIMITED_RETRIES {
+                retries += 1;
+                let wait = retry_after(&response).unwrap_or(DEFAULT_RETRY_AFTER);
+                tracing::warn!(method, retry_after_ms = wait.as_millis() as u64, retries, "Rate limited by the RPC");
+                self.limiter.pause(wait);
+                continue;
+            }
+            let response_json: Value = response.error_for_status()?.json().await?;
+            self.record(started_at.elapsed());
+            return Ok(response_json);
+        }
+    }
+
+    // The Metaplex metadata of a mint with what its URI points to, or the reason it
+    // didn't decode; None if the mint has none
+    async fn get_token_metadata(&self, mint: &Pubkey) -> Option<Value> {
+        let account = self.get_account(&metadata::metadata_address(mint)).await.ok()?;
+        let metadata = match metadata::decode(&account.data) {
+            Ok(metadata) => metadata,
+            Err(err) => return Some(serde_json::json!({ "error": err.to_string() })),
+        };
+        // Replays stay off the network, off-chain metadata included
+        let off_chain = match self.transport {
+            Transport::Replay(_) => None,
+            _ => metadata::fetch_off_chain(&self.client, &metadata.uri).await,
+        };
+        Some(serde_json::json!({
+            "name": metadata.name,
+            "symbol": metadata.symbol,
+            "uri": metadata.uri,
+            "sellerFeeBasisPoints": metadata.seller_fee_basis_points,
+            "creators": metadata
+                .creators
+                .iter()
+                .map(|creator| serde_json::json!({
+                    "address": creator.address.to_string(),
+                    "verified": creator.verified,
+                    "share": creator.share,
+                }))
+                .collect::<Vec<_>>(),
+            "updateAuthority": metadata.update_authority.to_string(),
+            "isMutable": metadata.is_mutable,
+            "offChain": off_chain.map(|off_chain| serde_json::json!({
+                "image": off_chain.image,
+                "attributes": off_chain
+                    .attributes
+                    .iter()
+                    .map(|(trait_type, value)| serde_json::json!({ "trait_type": trait_type, "value": value }))
+                    .collect::<Vec<_>>(),
+            })),
+        }))
+    }
+
+    // Lamports an account of `data_len` bytes needs to be exempt from rent; zero bytes
+    // still need the threshold of the account's own storage overhead
+    pub async fn get_rent_exempt_minimum(&self, data_len: usize) -> RpcResult<u64> {
+        let key = (self.url.clone(), data_len);
+        if let Some(minimum) = RENT_EXEMPT_MINIMUMS.lock().ok().and_then(|minimums| minimums.get(&key).copied()) {
+            return Ok(minimum);
+        }
+        let client = self.rpc_client();
+        let minimum = client.get_minimum_balance_for_rent_exemption(data_len).await?;
+        if let Ok(mut minimums) = RENT_EXEMPT_MINIMUMS.lock() {
+            minimums.insert(key, minimum);
+        }
+        Ok(minimum)
+    }
+
+    // Version of the software the node runs
+    pub async fn get_version(&self) -> RpcResult<RpcVersionInfo> {
+        self.rpc_client().get_version().await.map_err(AppError::from)
+    }
+}
+
+// Sends the Solana client's requests through RpcApi::request, so the rate limit,
+// recording and replaying cover them too
+struct ApiSender(RpcApi);
+
+#[async_trait]
+impl RpcSender for ApiSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let mut response = self.0.request(&request.to_string(), params).await.map_err(std::io::Error::other)?;
+        match response.get("error") {
+            Some(error) => Err(RpcError::RpcResponseError {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or("unknown error").to_string(),
+                data: RpcResponseErrorData::Empty,
+            }
+            .into()),
+            None => Ok(response["result"].take()),
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        self.0.url.clone()
+    }
+}
+
+#[async_trait]
+impl ChainApi for RpcApi {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    // Send a JSON-RPC request and return the whole response object
+    async fn request(&self, method: &str, params: Value) -> RpcResult<Value> {
+        match &self.transport {
+            Transport::Network => self.post(method, &params).await,
+            Transport::Record(dir) => {
+                let response = self.post(method, &params).await?;
+                fixtures::save(dir, method, &params, &response)?;
+                Ok(response)
+            }
+            Transport::Replay(dir) => {
+                tracing::debug!(method, dir = %dir.display(), "Replaying a recorded answer");
+                fixtures::load(dir, method, &params)
+            }
+        }
+    }
+
+    // Send a JSON-RPC request and return its "result" field
+    async fn call(&self, method: &str, params: Value) -> RpcResult<Value> {
+        let response_json = self.request(method, params).await?;
+        if let Some(error) = response_json.get("error") {
+            return Err(AppError::RpcResponse {
+                code: error.get("code").and_then(|c| c.as_i64()).unwrap_or_default(),
+                message: error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("unknown error")
+                    .to_string(),
+            });
+        }
+
+        Ok(response_json.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    // Hash of the network's first block, which tells networks apart whatever their URL
+    async fn get_genesis_hash(&self) -> RpcResult<String> {
+        Ok(self.rpc_client().get_genesis_hash().await?.to_string())
+    }
+
+    // Lamports charged per signature, priced with getFeeForMessage on a message with
+    // one signer and no instructions. Nodes before getFeeForMessage answer getFees.
+    async fn get_base_fee(&self) -> RpcResult<u64> {
+        let client = self.rpc_client();
+        let fee = async {
+            let blockhash = client.get_latest_blockhash().await?;
+            let payer = Pubkey::new_from_array([1; 32]);
+            let message = Message::new_with_blockhash(&[], Some(&payer), &blockhash);
+            client.get_fee_for_message(&message).await.map_err(AppError::from)
+        };
+        match fee.await {
+            Ok(fee) => Ok(fee),
+            Err(err) => {
+                let Ok(fees) = self.call("getFees", serde_json::json!([])).await else {
+                    return Err(err);
+                };
+                fees.pointer("/value/feeCalculator/lamportsPerSignature")
+                    .and_then(Value::as_u64)
+                    .ok_or(err)
+            }
+        }
+    }
+
+    async fn get_slot(&self) -> RpcResult<u64> {
+        let slot = self.call("getSlot", serde_json::json!([])).await?;
+        slot.as_u64()
+            .ok_or_else(|| AppError::UnexpectedResponse(format!("getSlot returned {}", slot)))
+    }
+
+    async fn get_supply(&self) -> RpcResult<Value> {
+        self.call("getSupply", serde_json::json!([{ "excludeNonCirculatingAccountsList": false }])).await
+    }
+
+    async fn get_transaction_count(&self) -> RpcResult<u64> {
+        let count = self.call("getTransactionCount", serde_json::json!([])).await?;
+        count
+            .as_u64()
+            .ok_or_else(|| AppError::UnexpectedResponse(format!("getTransactionCount returned {}", count)))
+    }
+
+    async fn get_epoch_schedule(&self) -> RpcResult<EpochSchedule> {
+        let client = self.rpc_client();
+        client.get_epoch_schedule().await.map_err(AppError::from)
+    }
+
+    async fn get_first_available_block(&self) -> RpcResult<u64> {
+        if let Some(slot) = FIRST_AVAILABLE_BLOCKS.lock().ok().and_then(|slots| slots.get(&self.url).copied()) {
+            return Ok(slot);
+        }
+        let client = self.rpc_client();
+        // Nodes without getFirstAvailableBlock still tell the lowest slot of their ledger
+        let slot = match client.get_first_available_block().await.map_err(AppError::from) {
+            Ok(slot) => slot,
+            Err(err) => {
+                tracing::warn!(error = %err, "getFirstAvailableBlock failed, falling back to minimumLedgerSlot");
+                client.minimum_ledger_slot().await.map_err(|_| err)?
+            }
+        };
+        if let Ok(mut slots) = FIRST_AVAILABLE_BLOCKS.lock() {
+            slots.insert(self.url.clone(), slot);
+        }
+        Ok(slot)
+    }
+
+    async fn get_slot_leader(&self, slot: u64) -> RpcResult<Option<Pubkey>> {
+        // getSlotLeaders only covers the epochs whose schedule the node keeps at
+        // hand. Outside them the whole schedule of the slot's epoch is fetched.
+        let client = self.rpc_client();
+        if let Ok(leaders) = client.get_slot_leaders(slot, 1).await {
+            if let Some(leader) = leaders.first() {
+                return Ok(Some(*leader));
+            }
+        }
+        let epoch_schedule = self.get_epoch_schedule().await?;
+        let Some(schedule) = client.get_leader_schedule(Some(slot)).await? else {
+            return Ok(None);
+        };
+        Ok(leader_in_schedule(&schedule, &epoch_schedule, slot))
+    }
+
+    async fn get_block(&self, slot: u64) -> RpcResult<Option<Value>> {
+        let params = serde_json::json!([slot, {
+            "encoding": "json",
+            "transactionDetails": "accounts",
+            "rewards": true,
+            "maxSupportedTransactionVersion": 0,
+        }]);
+        let client = self.rpc_client();
+        match client.send::<Value>(RpcRequest::GetBlock, params).await.map_err(AppError::from) {
+            Ok(block) => Ok(Some(block)),
+            Err(err) if is_missing_block(&err) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn get_account(&self, address: &Pubkey) -> RpcResult<Account> {
+        let client = self.rpc_client();
+        client.get_account_with_commitment(address, self.commitment)
+            .await?
+            .value
+            .ok_or_else(|| AppError::NotFound(format!("Account {}", address)))
+    }
+
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> RpcResult<Vec<Option<Account>>> {
+        let client = self.rpc_client();
+        let client = &client;
+        fetch_in_batches(addresses, MAX_MULTIPLE_ACCOUNTS, |batch| async move {
+            let accounts = client.get_multiple_accounts_with_commitment(&batch, self.commitment);
+            Ok(accounts.await?.value)
+        })
+        .await
+    }
+
+    async fn get_account_overview(&self, address: &Pubkey) -> RpcResult<Value> {
+        let account = self.get_account(address).await?;
+        let mut account_info = serde_json::json!({
+            "lamports": account.lamports,
+            "owner": account.owner.to_string(),
+            "space": account.data.len(),
+            "executable": account.executable,
+        });
+        // Rent status is left out rather than failing the whole view
+        if let Ok(minimum) = self.get_rent_exempt_minimum(account.data.len()).await {
+            account_info["rentExemptMinimum"] = Value::from(minimum);
+        }
+        // Decode SPL Token and Token-2022 accounts and mints; anything else keeps the
+        // generic view
+        if token::is_token_program(&account.owner.to_string()) {
+            if let Some(mint) = token::unpack_mint(&account.data) {
+                account_info["mint"] = serde_json::json!({
+                    "supply": mint.supply,
+                    "decimals": mint.decimals,
+                    "mintAuthority": mint.mint_authority.map(|key| key.to_string()),
+                    "freezeAuthority": mint.freeze_authority.map(|key| key.to_string()),
+                });
+                if let Some(metadata) = self.get_token_metadata(address).await {
+                    account_info["metadata"] = metadata;
+                }
+            } else if let Some(token_account) = token::unpack_account(&account.data) {
+                // The amount is in raw units; the mint knows the decimals
+                let decimals = self
+                    .get_account(&token_account.mint)
+                    .await
+                    .ok()
+                    .and_then(|mint| token::unpack_mint(&mint.data))
+                    .map(|mint| mint.decimals);
+                account_info["tokenAccount"] = serde_json::json!({
+                    "mint": token_account.mint.to_string(),
+                    "owner": token_account.owner.to_string(),
+                    "amount": token_account.amount,
+                    "decimals": decimals,
+                    "delegate": token_account.delegate.map(|key| key.to_string()),
+                    "delegatedAmount": token_account.delegated_amount,
+                    "state": token_account.state.name(),
+                    "isNative": token_account.is_native.is_some(),
+                    "closeAuthority": token_account.close_authority.map(|key| key.to_string()),
+                });
+            }
+        }
+        // Stake accounts, with their delegation as of the current epoch
+        if account.owner.to_string() == stake::STAKE_PROGRAM_ID {
+            if let Some(state) = stake::unpack(&account.data) {
+                let epoch = self
+                    .call("getEpochInfo", serde_json::json!([]))
+                    .await
+                    .ok()
+                    .and_then(|info| info.get("epoch").and_then(Value::as_u64));
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |now| now.as_secs() as i64);
+                account_info["stake"] = stake_details(&state, epoch, now);
+            }
+        }
+        // Upgradeable programs: follow the program account to its ProgramData
+        if account.owner.to_string() == loader::BPF_LOADER_UPGRADEABLE_ID {
+            let (program_data, state) = match loader::unpack(&account.data) {
+                Some(UpgradeableState::Program { program_data }) => {
+                    let state = self
+                        .get_account(&program_data)
+                        .await
+                        .ok()
+                        .and_then(|data| loader::unpack(&data.data));
+                    (Some(program_data), state)
+                }
+                state => (None, state),
+            };
+            if let Some(UpgradeableState::ProgramData {
+                slot,
+                upgrade_authority,
+                executable_len,
+            }) = state
+            {
+                account_info["programData"] = serde_json::json!({
+                    "address": program_data.map(|address| address.to_string()),
+                    "derivedAddress": program_data.map(|_| loader::program_data_address(address).to_string()),
+                    "lastDeploySlot": slot,
+                    "upgradeAuthority": upgrade_authority.map(|key| key.to_string()),
+                    "executableLen": executable_len,
+                });
+            } else if let Some(program_data) = program_data {
+                // The ProgramData account could not be read; still show where it is
+                account_info["programData"] = serde_json::json!({
+                    "address": program_data.to_string(),
+                    "derivedAddress": loader::program_data_address(address).to_string(),
+                });
+            }
+        }
+        Ok(account_info)
+    }
+
+    async fn get_signatures(
+        &self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> RpcResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit: Some(limit),
+            ..Default::default()
+        };
+        let client = self.rpc_client();
+        client.get_signatures_for_address_with_config(address, config).await.map_err(AppError::from)
+    }
+
+    async fn get_transaction_details(&self, signature: &Signature) -> RpcResult<Value> {
+        // Without a supported version the node refuses v0 transactions. The request is
+        // sent by hand as getTransaction answers null for an unknown signature.
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            commitment: None,
+            max_supported_transaction_version: Some(0),
+        };
+        let params = serde_json::json!([signature.to_string(), config]);
+        let client = self.rpc_client();
+        let request =
+            client.send::<Option<EncodedConfirmedTransactionWithStatusMeta>>(RpcRequest::GetTransaction, params);
+        let transaction = request.await?
+            .ok_or_else(|| AppError::NotFound(format!("Transaction {}", signature)))?;
+        Ok(transaction_details(&transaction))
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> RpcResult<Option<TransactionStatus>> {
+        let client = self.rpc_client();
+        let response = client.get_signature_statuses_with_history(&[*signature]).await?;
+        Ok(response.value.into_iter().next().flatten())
+    }
+}
+
+// Fetch accounts `batch_size` at a time and return them in the order of
+// `addresses`. A node allowing fewer accounts per request rejects the batch as
+// invalid; it is then halved and tried again.
+async fn fetch_in_batches<F, Fut>(
+    addresses: &[Pubkey],
+    batch_size: usize,
+    mut fetch: F,
+) -> RpcResult<Vec<Option<Account>>>
+where
+    F: FnMut(Vec<Pubkey>) -> Fut,
+    Fut: std::future::Future<Output = RpcResult<Vec<Option<Account>>>>,
+{
+    let mut accounts = Vec::with_capacity(addresses.len());
+    let mut batch_size = batch_size.max(1);
+    while accounts.len() < addresses.len() {
+        let batch = &addresses[accounts.len()..addresses.len().min(accounts.len() + batch_size)];
+        match fetch(batch.to_vec()).await {
+            Ok(fetched) if fetched.len() == batch.len() => accounts.extend(fetched),
+            Ok(fetched) => {
+                return Err(AppError::UnexpectedResponse(format!(
+                    "getMultipleAccounts returned {} accounts for {} addresses",
+                    fetched.len(),
+                    batch.len()
+                )));
+            }
+            Err(AppError::RpcResponse {
+                code: JSON_RPC_INVALID_PARAMS,
+                ..
+            }) if batch.len() > 1 => {
+                batch_size = batch.len() / 2;
+                tracing::warn!(batch_size, "getMultipleAccounts batch refused, retrying with smaller batches");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(accounts)
+}
+
+// Find the leader of `slot` in the schedule of its epoch, which lists the slots
+// of each identity counted from the first slot of the epoch
+pub fn leader_in_schedule(schedule: &RpcLeaderSchedule, epoch_schedule: &EpochSchedule, slot: u64) -> Option<Pubkey> {
+    let (_, slot_index) = epoch_schedule.get_epoch_and_slot_index(slot);
+    schedule
+        .iter()
+        .find(|(_, slots)| slots.iter().any(|scheduled| *scheduled as u64 == slot_index))
+        .and_then(|(identity, _)| identity.parse().ok())
+}
+
+// A decoded stake account as the account view shows it. Without the current epoch
+// the delegation has no status.
+pub fn stake_details(state: &StakeState, epoch: Option<u64>, now: i64) -> Value {
+    let mut details = serde_json::json!({ "state": state.name(), "epoch": epoch });
+    let epoch_value = |epoch: u64| (epoch != stake::NO_EPOCH).then_some(epoch);
+    if let Some(meta) = state.meta() {
+        details["rentExemptReserve"] = Value::from(meta.rent_exempt_reserve);
+        details["staker"] = Value::from(meta.staker.to_string());
+        details["withdrawer"] = Value::from(meta.withdrawer.to_string());
+        details["lockup"] = serde_json::json!({
+            "unixTimestamp": meta.lockup.unix_timestamp,
+            "epoch": meta.lockup.epoch,
+            "custodian": meta.lockup.custodian.to_string(),
+            "inForce": epoch.map(|epoch| meta.lockup.is_active(now, epoch)),
+        });
+    }
+    if let StakeState::Stake(_, delegation) = state {
+        let status = epoch.map(|epoch| delegation.status(epoch));
+        let stake_when = |wanted: &[StakeStatus]| status.map(|status| if wanted.contains(&status) { delegation.stake } else { 0 });
+        details["delegation"] = serde_json::json!({
+            "voter": delegation.voter.to_string(),
+            "stake": delegation.stake,
+            "activationEpoch": epoch_value(delegation.activation_epoch),
+            "deactivationEpoch": epoch_value(delegation.deactivation_epoch),
+            "status": status.map(|status| status.name()),
+            // Deactivating stake still earns rewards until the epoch ends
+            "active": stake_when(&[StakeStatus::Active, StakeStatus::Deactivating]),
+            "activating": stake_when(&[StakeStatus::Activating]),
+            "deactivating": stake_when(&[StakeStatus::Deactivating]),
+        });
+    }
+    details
+}
+
+// The parts of a jsonParsed getTransaction answer the transaction view shows
+pub fn transaction_details(transaction: &EncodedConfirmedTransactionWithStatusMeta) -> Value {
+    let meta = transaction.transaction.meta.as_ref();
+    let (signatures, message) = match &transaction.transaction.transaction {
+        Json(ui_transaction) => (
+            ui_transaction.signatures.clone(),
+            match &ui_transaction.message {
+                Parsed(parsed_message) => Some(parsed_message),
+                _ => None,
+            },
+        ),
+        _ => (vec![], None),
+    };
+    // Static keys come first, then the addresses loaded from lookup tables
+    let keys: Vec<&str> = message
+        .map(|message| message.account_keys.iter().map(|key| key.pubkey.as_str()).collect())
+        .unwrap_or_default();
+    serde_json::json!({
+        "slot": transaction.slot,
+        "blockTime": transaction.block_time,
+        // "legacy" or 0; nodes that predate versions send none
+        "version": transaction.transaction.version.clone().map_or(serde_json::json!("legacy"), |version| serde_json::json!(version)),
+        "meta": {
+            "status": meta.map(|m| format!("{:?}", m.status)),
+            "err": meta.and_then(|m| m.err.clone()),
+            "fee": meta.map(|m| m.fee).unwrap_or(0),
+            "preBalances": meta.map(|m| m.pre_balances.clone()),
+            "postBalances": meta.map(|m| m.post_balances.clone()),
+            "signatures": signatures,
+            // Each key with its signer and writable flags and where it came from
+            "accountKeys": message.map(|message| message.account_keys.clone()).unwrap_or_default(),
+            "recentBlockhash": message.map(|message| message.recent_blockhash.clone()).unwrap_or_default(),
+            "instructions": message
+                .map(|message| message.instructions.iter().map(|instruction| instruction_value(instruction, &keys)).collect())
+                .unwrap_or_else(Vec::new),
+            // Instructions invoked by each top-level instruction, by its index; null when
+            // the node doesn't record them
+            "innerInstructions": meta.and_then(|m| {
+                m.inner_instructions.as_ref().map(|inner| {
+                    inner
+                        .iter()
+                        .map(|inner| {
+                            serde_json::json!({
+                                "index": inner.index,
+                                "instructions": inner
+                                    .instructions
+                                    .iter()
+                                    .map(|instruction| instruction_value(instruction, &keys))
+                                    .collect::<Vec<_>>(),
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+            }),
+            // v0 messages: the lookup tables the loaded addresses came from
+            "addressTableLookups": message.and_then(|message| message.address_table_lookups.clone()),
+            // ...and the addresses loaded from them, writable ones first
+            "loadedAddresses": meta.and_then(|m| m.loaded_addresses.as_ref().map(Clone::clone)),
+            // Fields the node left out are null
+            "logMessages": meta.and_then(|m| m.log_messages.as_ref().map(Clone::clone)),
+            "preTokenBalances": meta.and_then(|m| m.pre_token_balances.as_ref().map(Clone::clone)),
+            "postTokenBalances": meta.and_then(|m| m.post_token_balances.as_ref().map(Clone::clone)),
+            "computeUnitsConsumed": meta.and_then(|m| m.compute_units_consumed.as_ref().map(Clone::clone)),
+        },
+    })
+}
+
+// An instruction with its program and accounts as indices into `keys`, as the raw
+// encoding has them; an account that isn't among the keys keeps its address so the
+// others stay in place. Instructions the node decoded keep their program name and
+// `parsed` fields instead of accounts and data.
+fn instruction_value(instruction: &UiInstruction, keys: &[&str]) -> Value {
+    let index = |pubkey: &str| keys.iter().position(|key| *key == pubkey);
+    match instruction {
+        UiInstruction::Compiled(compiled) => serde_json::json!({
+            "programIdIndex": compiled.program_id_index,
+            "accounts": compiled.accounts,
+            "data": compiled.data,
+            "stackHeight": compiled.stack_height,
+        }),
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(decoded)) => serde_json::json!({
+            "programIdIndex": index(&decoded.program_id),
+            "accounts": decoded
+                .accounts
+                .iter()
+                .map(|account| index(account).map_or_else(|| Value::from(account.as_str()), Value::from))
+                .collect::<Vec<_>>(),
+            "data": decoded.data,
+            "stackHeight": decoded.stack_height,
+        }),
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => serde_json::json!({
+            "programIdIndex": index(&parsed.program_id),
+            "program": parsed.program,
+            "parsed": parsed.parsed,
+            "stackHeight": parsed.stack_height,
+        }),
+    }
+}
+
+// HTTP client for RPC requests, giving up on unanswered ones
+pub fn http_client() -> Client {
+    Client::builder().timeout(RPC_TIMEOUT).build().unwrap_or_default()
+}
+
+// How long a 429 asks to wait, when it says in seconds
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds).min(RPC_TIMEOUT))
+}
+
+// Errors getBlock returns for a slot without a block: skipped, or pruned from the node's ledger
+fn is_missing_block(err: &AppError) -> bool {
+    matches!(
+        err,
+        AppError::RpcResponse { code, .. }
+            if [
+                JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+                JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED,
+                JSON_RPC_SERVER_ERROR_BLOCK_CLEANED_UP,
+            ]
+            .contains(code)
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    // A JSON-RPC server that answers every request with `result` after `delay`
+    pub async fn mock_rpc(delay: Duration, result: Value) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let body = body.clone();
+                tokio::spawn(async move {
+                    let mut request = [0; 8192];
+                    let _ = stream.read(&mut request).await;
+                    tokio::time::sleep(delay).await;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        url
+    }
+
+    // A JSON-RPC server answering each method in `answers` with its result and
+    // anything else with a "Method not found" error. Returns its URL and the
+    // methods it was asked for, in order.
+    pub async fn recording_rpc(answers: Vec<(&'static str, Value)>) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::AsyncWriteExt;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Arc<std::sync::Mutex<Vec<String>>> = Arc::default();
+        let log = Arc::clone(&requests);
+        let answers = Arc::new(answers);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let (answers, log) = (Arc::clone(&answers), Arc::clone(&log));
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    let method = request["method"].as_str().unwrap_or_default().to_string();
+                    log.lock().unwrap().push(method.clone());
+                    let body = match answers.iter().find(|(name, _)| *name == method) {
+                        Some((_, result)) => serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                        None => serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "error": { "code": -32601, "message": "Method not found" },
+                        }),
+                    }
+                    .to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        (url, requests)
+    }
+
+    // A JSON-RPC server answering the nth request with the nth of `statuses` (the
+    // last one once they run out) and a Retry-After header when one is given; a 200
+    // carries `result`. Returns its URL and the number of requests it got.
+    async fn status_rpc(statuses: Vec<(u16, Option<&'static str>)>, result: Value) -> (String, Arc<AtomicUsize>) {
+        use tokio::io::AsyncWriteExt;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicUsize::new(0));
+        let received = Arc::clone(&count);
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                if read_request(&mut stream).await.is_none() {
+                    continue;
+                }
+                let index = received.fetch_add(1, Ordering::SeqCst).min(statuses.len() - 1);
+                let (status, retry_after) = statuses[index];
+                let body = if status == 200 { body.as_str() } else { "" };
+                let retry_after = retry_after.map_or(String::new(), |secs| format!("Retry-After: {}\r\n", secs));
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    retry_after,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, count)
+    }
+
+    // The JSON body of an HTTP request, read up to its Content-Length
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<Value> {
+        use tokio::io::AsyncReadExt;
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let read = stream.read(&mut buffer).await.ok()?;
+            if read == 0 {
+                return None;
+            }
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request);
+            let Some(end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length: usize = text[..end].lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse().ok())?
+            })?;
+            let body = end + 4;
+            if request.len() >= body + length {
+                return serde_json::from_slice(&request[body..body + length]).ok();
+            }
+        }
+    }
+
+    // A ChainApi serving canned answers that records each call by its JSON-RPC method.
+    // Answers are keyed by method and what it is about (an address, signature or
+    // slot, "" for nothing); without one, accounts and transactions are not found,
+    // slots are skipped and other methods are unknown to the node. Methods can be
+    // made to time out instead.
+    #[derive(Debug, Default)]
+    pub struct MockChainClient {
+        answers: HashMap<(&'static str, String), Value>,
+        timeouts: Vec<&'static str>,
+        calls: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    impl MockChainClient {
+        pub fn answer(mut self, method: &'static str, key: impl ToString, result: Value) -> Self {
+            self.answers.insert((method, key.to_string()), result);
+            self
+        }
+
+        pub fn time_out(mut self, method: &'static str) -> Self {
+            self.timeouts.push(method);
+            self
+        }
+
+        // The methods called so far, in order
+        pub fn calls(&self) -> Vec<&'static str> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn lookup(&self, method: &'static str, key: impl ToString) -> RpcResult<Option<Value>> {
+            self.calls.lock().unwrap().push(method);
+            if self.timeouts.contains(&method) {
+                return Err(AppError::Timeout);
+            }
+            Ok(self.answers.get(&(method, key.to_string())).cloned())
+        }
+
+        // The answer to a method about nothing in particular
+        fn answered(&self, method: &'static str) -> RpcResult<Value> {
+            self.lookup(method, "")?.ok_or_else(|| method_not_found(method))
+        }
+
+        fn number(&self, method: &'static str) -> RpcResult<u64> {
+            let result = self.answered(method)?;
+            result.as_u64().ok_or_else(|| AppError::UnexpectedResponse(result.to_string()))
+        }
+    }
+
+    fn method_not_found(method: &str) -> AppError {
+        AppError::RpcResponse {
+            code: -32601,
+            message: format!("Method not found: {}", method),
+        }
+    }
+
+    // The account an overview describes
+    fn overview_account(overview: &Value) -> Account {
+        Account {
+            lamports: overview["lamports"].as_u64().unwrap_or_default(),
+            owner: overview["owner"].as_str().and_then(|owner| owner.parse().ok()).unwrap_or_default(),
+            executable: overview["executable"].as_bool().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    #[async_trait]
+    impl ChainApi for MockChainClient {
+        fn url(&self) -> &str {
+            "mock"
+        }
+
+        async fn request(&self, method: &str, params: Value) -> RpcResult<Value> {
+            match self.call(method, params).await {
+                Ok(result) => Ok(serde_json::json!({ "jsonrpc": "2.0", "result": result, "id": 1 })),
+                Err(AppError::RpcResponse { code, message }) => Ok(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "error": { "code": code, "message": message },
+                    "id": 1,
+                })),
+                Err(err) => Err(err),
+            }
+        }
+
+        async fn call(&self, method: &str, _params: Value) -> RpcResult<Value> {
+            let known = self.answers.keys().find(|(name, key)| *name == method && key.is_empty());
+            match known {
+                Some((name, _)) => self.answered(name),
+                None => Err(method_not_found(method)),
+            }
+        }
+
+        async fn get_genesis_hash(&self) -> RpcResult<String> {
+            let hash = self.answered("getGenesisHash")?;
+            hash.as_str().map(str::to_string).ok_or_else(|| AppError::UnexpectedResponse(hash.to_string()))
+        }
+
+        async fn get_base_fee(&self) -> RpcResult<u64> {
+            self.number("getFeeForMessage")
+        }
+
+        async fn get_slot(&self) -> RpcResult<u64> {
+            self.number("getSlot")
+        }
+
+        async fn get_supply(&self) -> RpcResult<Value> {
+            self.answered("getSupply")
+        }
+
+        async fn get_transaction_count(&self) -> RpcResult<u64> {
+            self.number("getTransactionCount")
+        }
+
+        async fn get_epoch_schedule(&self) -> RpcResult<EpochSchedule> {
+            let schedule = self.answered("getEpochSchedule")?;
+            Ok(EpochSchedule::custom(
+                schedule["slotsPerEpoch"].as_u64().unwrap_or_default(),
+                schedule["leaderScheduleSlotOffset"].as_u64().unwrap_or_default(),
+                schedule["warmup"].as_bool().unwrap_or_default(),
+            ))
+        }
+
+        async fn get_slot_leader(&self, slot: u64) -> RpcResult<Option<Pubkey>> {
+            Ok(self
+                .lookup("getSlotLeaders", slot)?
+                .and_then(|leader| leader.as_str().and_then(|leader| leader.parse().ok())))
+        }
+
+        async fn get_first_available_block(&self) -> RpcResult<u64> {
+            self.number("getFirstAvailableBlock")
+        }
+
+        async fn get_block(&self, slot: u64) -> RpcResult<Option<Value>> {
+            self.lookup("getBlock", slot)
+        }
+
+        async fn get_account(&self, address: &Pubkey) -> RpcResult<Account> {
+            self.lookup("getAccountInfo", address)?
+                .map(|overview| overview_account(&overview))
+                .ok_or_else(|| AppError::NotFound(format!("Account {}", address)))
+        }
+
+        async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> RpcResult<Vec<Option<Account>>> {
+            addresses
+                .iter()
+                .map(|address| {
+                    let overview = self.lookup("getMultipleAccounts", address)?;
+                    Ok(overview.map(|overview| overview_account(&overview)))
+                })
+                .collect()
+        }
+
+        async fn get_account_overview(&self, address: &Pubkey) -> RpcResult<Value> {
+            self.lookup("getAccountInfo", address)?
+                .ok_or_else(|| AppError::NotFound(format!("Account {}", address)))
+        }
+
+        async fn get_signatures(
+            &self,
+            address: &Pubkey,
+            _before: Option<Signature>,
+            _until: Option<Signature>,
+            limit: usize,
+        ) -> RpcResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+            let Some(signatures) = self.lookup("getSignaturesForAddress", address)? else {
+                return Ok(vec![]);
+            };
+            let signatures: Vec<RpcConfirmedTransactionStatusWithSignature> =
+                serde_json::from_value(signatures).map_err(|err| AppError::UnexpectedResponse(err.to_string()))?;
+            Ok(signatures.into_iter().take(limit).collect())
+        }
+
+        async fn get_transaction_details(&self, signature: &Signature) -> RpcResult<Value> {
+            self.lookup("getTransaction", signature)?
+                .ok_or_else(|| AppError::NotFound(format!("Transaction {}", signature)))
+        }
+
+        async fn get_signature_status(&self, signature: &Signature) -> RpcResult<Option<TransactionStatus>> {
+            match self.lookup("getSignatureStatuses", signature)? {
+                Some(status) => {
+                    serde_json::from_value(status).map_err(|err| AppError::UnexpectedResponse(err.to_string()))
+                }
+                None => Ok(None),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_answers_are_replayed_without_the_network() {
+        let dir = std::env::temp_dir().join(format!("soonscan-record-{}", std::process::id()));
+        let (url, requests) = recording_rpc(vec![("getSlot", serde_json::json!(42))]).await;
+        let recording =
+            RpcApi::new(url.as_str(), CommitmentConfig::default()).with_transport(Transport::Record(dir.clone()));
+        assert_eq!(recording.get_slot().await.unwrap(), 42);
+        // Errors are recorded like answers
+        assert!(matches!(
+            recording.call("getEpochInfo", serde_json::json!([])).await,
+            Err(AppError::RpcResponse { code: -32601, .. })
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        let offline = RpcApi::new("http://127.0.0.1:9", CommitmentConfig::default());
+        let replaying = offline.with_transport(Transport::Replay(dir.clone()));
+        assert_eq!(replaying.get_slot().await.unwrap(), 42);
+        assert!(matches!(
+            replaying.call("getEpochInfo", serde_json::json!([])).await,
+            Err(AppError::RpcResponse { code: -32601, .. })
+        ));
+        assert!(matches!(
+            replaying.get_transaction_count().await,
+            Err(AppError::NotRecorded { method, .. }) if method == "getTransactionCount"
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn requests_beyond_the_rate_wait_for_their_token() {
+        let limiter = RateLimiter::new(5);
+        let now = Instant::now();
+        // A burst goes out at once
+        for _ in 0..20 {
+            assert_eq!(limiter.reserve(now), Duration::ZERO);
+        }
+        assert!(!limiter.is_throttled());
+        assert_eq!(limiter.reserve(now), Duration::from_millis(200));
+        assert_eq!(limiter.reserve(now), Duration::from_millis(400));
+        assert!(limiter.is_throttled());
+        // Refilled at the rate, up to a burst
+        assert_eq!(limiter.reserve(now + Duration::from_millis(600)), Duration::ZERO);
+        let later = now + Duration::from_secs(10);
+        for _ in 0..20 {
+            assert_eq!(limiter.reserve(later), Duration::ZERO);
+        }
+        assert_eq!(limiter.reserve(later), Duration::from_millis(200));
+        assert_eq!(RateLimiter::new(0).rate(), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_requests_are_retried_after_the_wait_asked_for() {
+        let (url, count) = status_rpc(vec![(429, Some("1")), (200, None)], serde_json::json!(42)).await;
+        let api = RpcApi::new(url, CommitmentConfig::default());
+        let started_at = Instant::now();
+        assert_eq!(api.get_slot().await.unwrap(), 42);
+        assert!(started_at.elapsed() >= Duration::from_secs(1));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        // A node that keeps refusing gets a few more tries, then its error is returned
+        let (url, count) = status_rpc(vec![(429, Some("0"))], Value::Null).await;
+        let api = RpcApi::new(url, CommitmentConfig::default());
+        let refused = api.call("getSlot", serde_json::json!([])).await;
+        let too_many = Some(StatusCode::TOO_MANY_REQUESTS);
+        assert!(matches!(refused, Err(AppError::RpcTransport(err)) if err.status() == too_many));
+        assert_eq!(count.load(Ordering::SeqCst), 1 + MAX_RATE_LIMITED_RETRIES as usize);
+    }
+
+    #[tokio::test]
+    async fn other_http_errors_are_not_retried() {
+        for status in [500, 503, 403] {
+            let (url, count) = status_rpc(vec![(status, Some("0")), (200, None)], serde_json::json!(42)).await;
+            let api = RpcApi::new(url, CommitmentConfig::default());
+            let failed = api.call("getSlot", serde_json::json!([])).await;
+            let failed_with = |err: &reqwest::Error| err.status().map(|code| code.as_u16()) == Some(status);
+            assert!(matches!(failed, Err(AppError::RpcTransport(err)) if failed_with(&err)));
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+        }
+    }
+
+    #[test]
+    fn latency_average_covers_the_recent_samples() {
+        let mut latencies = LatencyTracker::default();
+        assert_eq!(latencies.average(), None);
+        for millis in 0..LATENCY_SAMPLES as u64 + 10 {
+            latencies.record(Duration::from_millis(millis));
+        }
+        assert_eq!(latencies.last(), Some(Duration::from_millis(29)));
+        assert_eq!(latencies.average(), Some(Duration::from_micros(19_500)));
+    }
+
+    #[test]
+    fn cache_evicts_the_least_recently_used_result() {
+        let mut cache = ResponseCache::new(2);
+        cache.insert("devnet", "a", 1, None);
+        cache.insert("devnet", "b", 2, None);
+        // Reading "a" makes "b" the oldest
+        assert_eq!(cache.get("devnet", "a"), Some(1));
+        cache.insert("devnet", "c", 3, None);
+        assert_eq!(cache.get("devnet", "b"), None);
+        assert_eq!(cache.get("devnet", "a"), Some(1));
+        assert_eq!(cache.get("devnet", "c"), Some(3));
+        // The same query on another network is a different entry
+        assert_eq!(cache.get("testnet", "c"), None);
+        assert_eq!((cache.hits, cache.misses), (3, 2));
+    }
+
+    #[test]
+    fn cache_entries_expire_after_their_ttl() {
+        let mut cache = ResponseCache::new(10);
+        cache.insert("devnet", "account", "old", Some(Duration::ZERO));
+        cache.insert("devnet", "transaction", "final", None);
+        cache.insert("devnet", "block", "recent", Some(Duration::from_secs(3600)));
+        assert_eq!(cache.get("devnet", "account"), None);
+        assert_eq!(cache.get("devnet", "transaction"), Some("final"));
+        assert_eq!(cache.get("devnet", "block"), Some("recent"));
+        assert_eq!(cache.entries.len(), 2);
+
+        // A refreshed result replaces the expired one
+        cache.insert("devnet", "account", "new", Some(Duration::from_secs(3600)));
+        assert_eq!(cache.get("devnet", "account"), Some("new"));
+    }
+
+    // An account for each address, with as many lamports as the address's first byte
+    fn numbered_accounts(addresses: &[Pubkey]) -> Vec<Option<Account>> {
+        addresses
+            .iter()
+            .map(|address| {
+                Some(Account {
+                    lamports: u64::from(address.to_bytes()[0]),
+                    ..Account::default()
+                })
+            })
+            .collect()
+    }
+
+    fn addresses(count: u8) -> Vec<Pubkey> {
+        (0..count).map(|index| Pubkey::new_from_array([index; 32])).collect()
+    }
+
+    #[tokio::test]
+    async fn multiple_accounts_are_fetched_in_batches_of_the_limit() {
+        for (count, expected) in [
+            (0, vec![]),
+            (1, vec![1]),
+            (100, vec![100]),
+            (101, vec![100, 1]),
+            (250, vec![100, 100, 50]),
+        ] {
+            let mut batches = vec![];
+            let keys = addresses(count);
+            let accounts = fetch_in_batches(&keys, MAX_MULTIPLE_ACCOUNTS, |batch| {
+                batches.push(batch.len());
+                async move { Ok(numbered_accounts(&batch)) }
+            })
+            .await
+            .unwrap();
+            assert_eq!(batches, expected);
+            // In the order asked for, across batches
+            let lamports: Vec<u64> = accounts.iter().map(|account| account.as_ref().unwrap().lamports).collect();
+            assert_eq!(lamports, (0..u64::from(count)).collect::<Vec<_>>());
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_accounts_keep_their_place() {
+        let keys = addresses(5);
+        let accounts = fetch_in_batches(&keys, 2, |batch| async move {
+            let mut accounts = numbered_accounts(&batch);
+            // Odd addresses have no account
+            for (account, address) in accounts.iter_mut().zip(&batch) {
+                if address.to_bytes()[0] % 2 == 1 {
+                    *account = None;
+                }
+            }
+            Ok(accounts)
+        })
+        .await
+        .unwrap();
+        let lamports: Vec<Option<u64>> = accounts.iter().map(|account| account.as_ref().map(|a| a.lamports)).collect();
+        assert_eq!(lamports, [Some(0), None, Some(2), None, Some(4)]);
+
+        // An answer that doesn't line up with the addresses is an error
+        let short = fetch_in_batches(&keys, 2, |batch| async move { Ok(numbered_accounts(&batch[1..])) }).await;
+        assert!(matches!(short, Err(AppError::UnexpectedResponse(_))));
+    }
+
+    #[tokio::test]
+    async fn batches_shrink_to_the_node_account_limit() {
+        let mut batches = vec![];
+        let keys = addresses(100);
+        let accounts = fetch_in_batches(&keys, MAX_MULTIPLE_ACCOUNTS, |batch| {
+            batches.push(batch.len());
+            async move {
+                if batch.len() > 30 {
+                    return Err(AppError::RpcResponse {
+                        code: JSON_RPC_INVALID_PARAMS,
+                        message: "Too many inputs provided; max 30".to_string(),
+                    });
+                }
+                Ok(numbered_accounts(&batch))
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(batches, [100, 50, 25, 25, 25, 25]);
+        assert_eq!(accounts.len(), 100);
+        assert_eq!(accounts[99].as_ref().unwrap().lamports, 99);
+
+        // Other errors are not retried
+        let mut calls = 0;
+        let failed = fetch_in_batches(&keys, MAX_MULTIPLE_ACCOUNTS, |_| {
+            calls += 1;
+            async { Err(AppError::Timeout) }
+        })
+        .await;
+        assert!(matches!(failed, Err(AppError::Timeout)));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn stake_details_follow_the_current_epoch() {
+        let meta = stake::Meta {
+            rent_exempt_reserve: 2_282_880,
+            staker: Pubkey::new_from_array([1; 32]),
+            withdrawer: Pubkey::new_from_array([2; 32]),
+            lockup: stake::Lockup {
+                unix_timestamp: 1_800_000_000,
+                epoch: 0,
+                custodian: Pubkey::new_from_array([3; 32]),
+            },
+        };
+        let delegation = stake::Delegation {
+            voter: Pubkey::new_from_array([4; 32]),
+            stake: 100,
+            activation_epoch: 700,
+            deactivation_epoch: stake::NO_EPOCH,
+        };
+        let state = StakeState::Stake(meta, delegation);
+
+        let details = stake_details(&state, Some(700), 1_700_000_000);
+        assert_eq!(details["state"], "Delegated");
+        assert_eq!(details["lockup"]["inForce"], true);
+        assert_eq!(details["delegation"]["deactivationEpoch"], Value::Null);
+        assert_eq!(details["delegation"]["status"], "activating");
+        assert_eq!(
+            (&details["delegation"]["active"], &details["delegation"]["activating"]),
+            (&Value::from(0), &Value::from(100))
+        );
+
+        // Without the epoch nothing is guessed
+        let details = stake_details(&state, None, 1_900_000_000);
+        assert_eq!(details["delegation"]["status"], Value::Null);
+        assert_eq!(details["delegation"]["active"], Value::Null);
+        assert_eq!(details["lockup"]["inForce"], Value::Null);
+        assert_eq!(stake_details(&StakeState::Uninitialized, Some(1), 0), serde_json::json!({ "state": "Uninitialized", "epoch": 1 }));
+    }
+
+    #[test]
+    fn leaders_are_found_by_their_slot_in_the_epoch() {
+        let first = Pubkey::new_from_array([1; 32]);
+        let second = Pubkey::new_from_array([2; 32]);
+        let schedule: RpcLeaderSchedule =
+            [(first.to_string(), vec![0, 1, 2, 3]), (second.to_string(), vec![4, 5, 6, 7])].into_iter().collect();
+
+        // Epochs of 1000 slots from genesis: slot 5004 is the fifth of epoch 5
+        let epoch_schedule = EpochSchedule::custom(1000, 1000, false);
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 5004), Some(second));
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 5000), Some(first));
+        // The last slot of epoch 4, not slot 4999 of epoch 5
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 4999), None);
+
+        // With warmup, epochs start at 32 slots and double up to 256: epoch 3 starts at
+        // slot 224, and the normal epochs after it every 256 slots
+        let epoch_schedule = EpochSchedule::custom(256, 256, true);
+        assert_eq!(epoch_schedule.get_epoch_and_slot_index(229), (3, 5));
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 229), Some(second));
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 96), Some(first));
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 223), None);
+        assert_eq!(leader_in_schedule(&schedule, &epoch_schedule, 224 + 256 + 7), Some(second));
+    }
+
+    #[tokio::test]
+    async fn rent_exempt_minimums_are_fetched_once_per_length() {
+        let (url, requests) = recording_rpc(vec![("getMinimumBalanceForRentExemption", serde_json::json!(890_880))]).await;
+        let api = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        assert_eq!(api.get_rent_exempt_minimum(0).await.unwrap(), 890_880);
+        assert_eq!(api.get_rent_exempt_minimum(0).await.unwrap(), 890_880);
+        // Another client of the same endpoint shares the cache
+        let api = RpcApi::new(url.as_str(), CommitmentConfig::confirmed());
+        assert_eq!(api.get_rent_exempt_minimum(0).await.unwrap(), 890_880);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        api.get_rent_exempt_minimum(165).await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn first_available_block_falls_back_to_the_ledger_slot() {
+        let (url, requests) = recording_rpc(vec![("minimumLedgerSlot", serde_json::json!(5_000))]).await;
+        let api = RpcApi::new(url.as_str(), CommitmentConfig::default());
+        assert_eq!(api.get_first_available_block().await.unwrap(), 5_000);
+        assert_eq!(api.get_first_available_block().await.unwrap(), 5_000);
+        assert_eq!(*requests.lock().unwrap(), ["getFirstAvailableBlock", "minimumLedgerSlot"]);
+    }
+
+    #[tokio::test]
+    async fn typed_calls_parse_the_node_answers() {
+        let api = RpcApi::new(mock_rpc(Duration::ZERO, serde_json::json!(42)).await, CommitmentConfig::confirmed());
+        assert_eq!(api.get_slot().await.unwrap(), 42);
+        assert_eq!(api.get_transaction_count().await.unwrap(), 42);
+        assert_eq!(api.latencies.lock().unwrap().samples.len(), 2);
+        // One sample per exchange for calls made through the Solana client too
+        api.get_epoch_schedule().await.unwrap_err();
+        assert_eq!(api.latencies.lock().unwrap().samples.len(), 3);
+
+        // A transaction the node hasn't seen has no status
+        let api = RpcApi::new(
+            mock_rpc(Duration::ZERO, serde_json::json!({ "context": { "slot": 1 }, "value": [null] })).await,
+            CommitmentConfig::confirmed(),
+        );
+        assert_eq!(api.get_signature_status(&Signature::default()).await.unwrap(), None);
+        assert!(api.get_slot().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn v0_transactions_keep_their_loaded_addresses() {
+        let fixture: Value = serde_json::from_str(include_str!("../tests/fixtures/token_transfer_v0.json")).unwrap();
+        let api = RpcApi::new(mock_rpc(Duration::ZERO, fixture.clone()).await, CommitmentConfig::confirmed());
+        let details = api.get_transaction_details(&Signature::default()).await.unwrap();
+        assert_eq!(details["version"], 0);
+        let keys = details["meta"]["accountKeys"].as_array().unwrap();
+        assert_eq!(keys.len(), 7);
+        assert_eq!(keys[6]["source"], "lookupTable");
+        assert_eq!(details["meta"]["addressTableLookups"][0]["writableIndexes"], serde_json::json!([3]));
+        assert_eq!(details["meta"]["loadedAddresses"]["readonly"][0], keys[6]["pubkey"]);
+
+        // Instructions point into the account keys, loaded addresses included
+        let instructions = details["meta"]["instructions"].as_array().unwrap();
+        assert_eq!(instructions[0]["programIdIndex"], 2);
+        assert_eq!(instructions[1]["program"], "spl-token");
+        assert_eq!(instructions[1]["parsed"]["type"], "transferChecked");
+        assert_eq!(instructions[2]["programIdIndex"], 4);
+        assert_eq!(instructions[2]["accounts"], serde_json::json!([0, 1]));
+        assert_eq!(instructions[2]["data"], "3Bxs4h24hBtQy9rw");
+
+        // An account missing from the keys, e.g. from a lookup table the node didn't
+        // load, keeps its address and the accounts after it their positions
+        let mut fixture = fixture;
+        let unlisted = Pubkey::new_from_array([9; 32]).to_string();
+        let accounts = fixture["transaction"]["message"]["instructions"][2]["accounts"].as_array_mut().unwrap();
+        accounts.insert(1, Value::from(unlisted.as_str()));
+        let transaction: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(fixture).unwrap();
+        let details = transaction_details(&transaction);
+        let instruction = &details["meta"]["instructions"][2];
+        assert_eq!(instruction["accounts"], serde_json::json!([0, unlisted, 1]));
+        let instruction = crate::instruction::InstructionInfo::from_value(instruction).unwrap();
+        assert_eq!(
+            instruction.accounts,
+            [
+                crate::instruction::InstructionAccount::Index(0),
+                crate::instruction::InstructionAccount::Address(unlisted),
+                crate::instruction::InstructionAccount::Index(1),
+            ]
+        );
+
+        let legacy: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../tests/fixtures/sol_transfer.json")).unwrap();
+        let details = transaction_details(&legacy);
+        assert_eq!(details["version"], "legacy");
+        assert_eq!(details["meta"]["logMessages"].as_array().unwrap().len(), 10);
+        assert!(details["meta"]["addressTableLookups"].is_null());
+    }
+}
@@ -0,0 +1,253 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::token::Reader;
+
+pub const STAKE_PROGRAM_ID: &str = "Stake11111111111111111111111111111111111111";
+
+// Vote accounts, like stake accounts, are paid inflation rewards every epoch
+pub const VOTE_PROGRAM_ID: &str = "Vote111111111111111111111111111111111111111";
+
+// Epoch fields set to this mean "never": not deactivated, or active since genesis
+pub const NO_EPOCH: u64 = u64::MAX;
+
+// Who may stake and withdraw, and until when withdrawals are locked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meta {
+    pub rent_exempt_reserve: u64,
+    pub staker: Pubkey,
+    pub withdrawer: Pubkey,
+    pub lockup: Lockup,
+}
+
+// Withdrawals wait for both the timestamp and the epoch, unless the custodian signs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: Pubkey,
+}
+
+impl Lockup {
+    // Whether withdrawals are still locked at this time and epoch; a zero lockup never is
+    pub fn is_active(&self, unix_timestamp: i64, epoch: u64) -> bool {
+        self.unix_timestamp > unix_timestamp || self.epoch > epoch
+    }
+}
+
+// The vote account stake is delegated to, and when it was (de)activated
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delegation {
+    pub voter: Pubkey,
+    pub stake: u64,
+    pub activation_epoch: u64,
+    pub deactivation_epoch: u64,
+}
+
+// Where delegated stake stands in an epoch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StakeStatus {
+    Activating,
+    Active,
+    Deactivating,
+    Inactive,
+}
+
+impl StakeStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StakeStatus::Activating => "activating",
+            StakeStatus::Active => "active",
+            StakeStatus::Deactivating => "deactivating",
+            StakeStatus::Inactive => "inactive",
+        }
+    }
+}
+
+impl Delegation {
+    // The status at `epoch`, taking warmup and cooldown to last the epoch the stake
+    // was (de)activated in. They take longer only when a large share of the
+    // cluster's stake moves at once.
+    pub fn status(&self, epoch: u64) -> StakeStatus {
+        if self.activation_epoch == NO_EPOCH {
+            // Genesis stake
+            return StakeStatus::Active;
+        }
+        if self.deactivation_epoch != NO_EPOCH && self.deactivation_epoch <= epoch {
+            // Stake deactivated in the epoch it was activated in never becomes effective
+            if self.deactivation_epoch == epoch && self.activation_epoch < epoch {
+                return StakeStatus::Deactivating;
+            }
+            return StakeStatus::Inactive;
+        }
+        if self.activation_epoch >= epoch {
+            StakeStatus::Activating
+        } else {
+            StakeStatus::Active
+        }
+    }
+}
+
+// The StakeStateV2 of an account owned by the Stake program
+#[derive(Debug, Clone, PartialEq)]
+pub enum StakeState {
+    Uninitialized,
+    Initialized(Meta),
+    Stake(Meta, Delegation),
+    RewardsPool,
+}
+
+impl StakeState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StakeState::Uninitialized => "Uninitialized",
+            StakeState::Initialized(_) => "Initialized",
+            StakeState::Stake(..) => "Delegated",
+            StakeState::RewardsPool => "Rewards Pool",
+        }
+    }
+
+    pub fn meta(&self) -> Option<&Meta> {
+        match self {
+            StakeState::Initialized(meta) | StakeState::Stake(meta, _) => Some(meta),
+            _ => None,
+        }
+    }
+}
+
+fn meta(reader: &mut Reader) -> Option<Meta> {
+    Some(Meta {
+        rent_exempt_reserve: reader.u64()?,
+        staker: reader.pubkey()?,
+        withdrawer: reader.pubkey()?,
+        lockup: Lockup {
+            unix_timestamp: reader.u64()? as i64,
+            epoch: reader.u64()?,
+            custodian: reader.pubkey()?,
+        },
+    })
+}
+
+// Decode the bincode-serialized stake state: a u32 variant index and its fields.
+// Credits, the deprecated warmup rate and the flags that follow a delegation are
+// skipped. None for data that isn't a stake state.
+pub fn unpack(data: &[u8]) -> Option<StakeState> {
+    let mut reader = Reader { data };
+    let state = match reader.u32()? {
+        0 => StakeState::Uninitialized,
+        1 => StakeState::Initialized(meta(&mut reader)?),
+        2 => {
+            let meta = meta(&mut reader)?;
+            let delegation = Delegation {
+                voter: reader.pubkey()?,
+                stake: reader.u64()?,
+                activation_epoch: reader.u64()?,
+                deactivation_epoch: reader.u64()?,
+            };
+            StakeState::Stake(meta, delegation)
+        }
+        3 => StakeState::RewardsPool,
+        _ => return None,
+    };
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bytes of a stake account: the largest state, padded
+    const STAKE_ACCOUNT_LEN: usize = 200;
+
+    fn meta_fixture() -> Vec<u8> {
+        let mut data = 2_282_880u64.to_le_bytes().to_vec();
+        data.extend([1; 32]);
+        data.extend([2; 32]);
+        data.extend(1_700_000_000i64.to_le_bytes());
+        data.extend(600u64.to_le_bytes());
+        data.extend([3; 32]);
+        data
+    }
+
+    fn delegated_fixture(activation_epoch: u64, deactivation_epoch: u64) -> Vec<u8> {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend(meta_fixture());
+        data.extend([4; 32]);
+        data.extend(5_000_000_000u64.to_le_bytes());
+        data.extend(activation_epoch.to_le_bytes());
+        data.extend(deactivation_epoch.to_le_bytes());
+        data.extend(0.25f64.to_le_bytes());
+        data.extend(12_345u64.to_le_bytes()); // Credits observed
+        data.push(0); // Flags
+        data.resize(STAKE_ACCOUNT_LEN, 0);
+        data
+    }
+
+    #[test]
+    fn unpacks_delegated_stake() {
+        let state = unpack(&delegated_fixture(500, NO_EPOCH)).unwrap();
+        let StakeState::Stake(meta, delegation) = &state else {
+            panic!("not delegated: {:?}", state);
+        };
+        assert_eq!(state.name(), "Delegated");
+        assert_eq!(meta.rent_exempt_reserve, 2_282_880);
+        assert_eq!(meta.staker, Pubkey::new_from_array([1; 32]));
+        assert_eq!(meta.withdrawer, Pubkey::new_from_array([2; 32]));
+        assert_eq!(
+            meta.lockup,
+            Lockup {
+                unix_timestamp: 1_700_000_000,
+                epoch: 600,
+                custodian: Pubkey::new_from_array([3; 32]),
+            }
+        );
+        assert!(meta.lockup.is_active(0, 599));
+        assert!(!meta.lockup.is_active(1_700_000_000, 600));
+        assert_eq!(
+            *delegation,
+            Delegation {
+                voter: Pubkey::new_from_array([4; 32]),
+                stake: 5_000_000_000,
+                activation_epoch: 500,
+                deactivation_epoch: NO_EPOCH,
+            }
+        );
+    }
+
+    #[test]
+    fn stateless_variants_have_names() {
+        let mut data = vec![0; STAKE_ACCOUNT_LEN];
+        assert_eq!(unpack(&data), Some(StakeState::Uninitialized));
+        data[0] = 3;
+        assert_eq!(unpack(&data).map(|state| state.name()), Some("Rewards Pool"));
+
+        let mut data = 1u32.to_le_bytes().to_vec();
+        data.extend(meta_fixture());
+        let state = unpack(&data).unwrap();
+        assert_eq!(state.name(), "Initialized");
+        assert_eq!(state.meta().unwrap().rent_exempt_reserve, 2_282_880);
+    }
+
+    #[test]
+    fn rejects_other_data() {
+        assert_eq!(unpack(&[]), None);
+        assert_eq!(unpack(&4u32.to_le_bytes()), None);
+        assert_eq!(unpack(&delegated_fixture(500, NO_EPOCH)[..100]), None);
+    }
+
+    #[test]
+    fn status_follows_the_epochs() {
+        let delegation = |activation_epoch, deactivation_epoch| Delegation {
+            voter: Pubkey::default(),
+            stake: 1,
+            activation_epoch,
+            deactivation_epoch,
+        };
+        assert_eq!(delegation(500, NO_EPOCH).status(500), StakeStatus::Activating);
+        assert_eq!(delegation(500, NO_EPOCH).status(501), StakeStatus::Active);
+        assert_eq!(delegation(500, 510).status(510), StakeStatus::Deactivating);
+        assert_eq!(delegation(500, 510).status(511), StakeStatus::Inactive);
+        // Deactivated before it became effective
+        assert_eq!(delegation(500, 500).status(500), StakeStatus::Inactive);
+        assert_eq!(delegation(NO_EPOCH, NO_EPOCH).status(0), StakeStatus::Active);
+    }
+}
@@ -0,0 +1,355 @@
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::instruction::ParsedInfo;
+use crate::token::Reader;
+
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+// An instruction of the System program
+#[derive(Debug, Clone, PartialEq)]
+pub enum SystemInstruction {
+    CreateAccount {
+        lamports: u64,
+        space: u64,
+        owner: Pubkey,
+    },
+    Assign {
+        owner: Pubkey,
+    },
+    Transfer {
+        lamports: u64,
+    },
+    CreateAccountWithSeed {
+        base: Pubkey,
+        seed: String,
+        lamports: u64,
+        space: u64,
+        owner: Pubkey,
+    },
+    AdvanceNonceAccount,
+    WithdrawNonceAccount {
+        lamports: u64,
+    },
+    InitializeNonceAccount {
+        authority: Pubkey,
+    },
+    AuthorizeNonceAccount {
+        authority: Pubkey,
+    },
+    Allocate {
+        space: u64,
+    },
+    AllocateWithSeed {
+        base: Pubkey,
+        seed: String,
+        space: u64,
+        owner: Pubkey,
+    },
+    AssignWithSeed {
+        base: Pubkey,
+        seed: String,
+        owner: Pubkey,
+    },
+    TransferWithSeed {
+        lamports: u64,
+        from_seed: String,
+        from_owner: Pubkey,
+    },
+    UpgradeNonceAccount,
+}
+
+// A bincode string: a u64 length and that many bytes of UTF-8
+fn seed(reader: &mut Reader) -> Option<String> {
+    let len = usize::try_from(reader.u64()?).ok()?;
+    String::from_utf8(reader.take(len)?.to_vec()).ok()
+}
+
+impl SystemInstruction {
+    // Decode instruction data as bincode lays it out: a u32 variant index and the
+    // fields in order. None for unknown variants and short data.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader { data };
+        let instruction = match reader.u32()? {
+            0 => Self::CreateAccount {
+                lamports: reader.u64()?,
+                space: reader.u64()?,
+                owner: reader.pubkey()?,
+            },
+            1 => Self::Assign {
+                owner: reader.pubkey()?,
+            },
+            2 => Self::Transfer {
+                lamports: reader.u64()?,
+            },
+            3 => Self::CreateAccountWithSeed {
+                base: reader.pubkey()?,
+                seed: seed(&mut reader)?,
+                lamports: reader.u64()?,
+                space: reader.u64()?,
+                owner: reader.pubkey()?,
+            },
+            4 => Self::AdvanceNonceAccount,
+            5 => Self::WithdrawNonceAccount {
+                lamports: reader.u64()?,
+            },
+            6 => Self::InitializeNonceAccount {
+                authority: reader.pubkey()?,
+            },
+            7 => Self::AuthorizeNonceAccount {
+                authority: reader.pubkey()?,
+            },
+            8 => Self::Allocate { space: reader.u64()? },
+            9 => Self::AllocateWithSeed {
+                base: reader.pubkey()?,
+                seed: seed(&mut reader)?,
+                space: reader.u64()?,
+                owner: reader.pubkey()?,
+            },
+            10 => Self::AssignWithSeed {
+                base: reader.pubkey()?,
+                seed: seed(&mut reader)?,
+                owner: reader.pubkey()?,
+            },
+            11 => Self::TransferWithSeed {
+                lamports: reader.u64()?,
+                from_seed: seed(&mut reader)?,
+                from_owner: reader.pubkey()?,
+            },
+            12 => Self::UpgradeNonceAccount,
+            _ => return None,
+        };
+        Some(instruction)
+    }
+
+    // The instruction as the node's jsonParsed encoding has it, with its accounts
+    // named, so it reads the same as the instructions the node decoded
+    pub fn to_parsed(&self, accounts: &[String]) -> ParsedInfo {
+        let mut info = serde_json::Map::new();
+        let mut field = |name: &str, value: Value| {
+            info.insert(name.to_string(), value);
+        };
+        let account_names: &[&str] = match self {
+            Self::CreateAccount { .. } | Self::CreateAccountWithSeed { .. } => &["source", "newAccount"],
+            Self::Assign { .. } | Self::Allocate { .. } => &["account"],
+            Self::Transfer { .. } => &["source", "destination"],
+            Self::AdvanceNonceAccount => &["nonceAccount", "recentBlockhashesSysvar", "nonceAuthority"],
+            Self::WithdrawNonceAccount { .. } => &[
+                "nonceAccount",
+                "destination",
+                "recentBlockhashesSysvar",
+                "rentSysvar",
+                "nonceAuthority",
+            ],
+            Self::InitializeNonceAccount { .. } => &["nonceAccount", "recentBlockhashesSysvar", "rentSysvar"],
+            Self::AuthorizeNonceAccount { .. } => &["nonceAccount", "nonceAuthority"],
+            Self::AllocateWithSeed { .. } | Self::AssignWithSeed { .. } => &["account", "base"],
+            Self::TransferWithSeed { .. } => &["source", "sourceBase", "destination"],
+            Self::UpgradeNonceAccount => &["nonceAccount"],
+        };
+        for (name, account) in account_names.iter().zip(accounts) {
+            field(name, Value::from(account.as_str()));
+        }
+        let pubkey = |pubkey: &Pubkey| Value::from(pubkey.to_string());
+        let kind = match self {
+            Self::CreateAccount { lamports, space, owner } => {
+                field("lamports", Value::from(*lamports));
+                field("space", Value::from(*space));
+                field("owner", pubkey(owner));
+                "createAccount"
+            }
+            Self::Assign { owner } => {
+                field("owner", pubkey(owner));
+                "assign"
+            }
+            Self::Transfer { lamports } => {
+                field("lamports", Value::from(*lamports));
+                "transfer"
+            }
+            Self::CreateAccountWithSeed {
+                base,
+                seed,
+                lamports,
+                space,
+                owner,
+            } => {
+                field("base", pubkey(base));
+                field("seed", Value::from(seed.as_str()));
+                field("lamports", Value::from(*lamports));
+                field("space", Value::from(*space));
+                field("owner", pubkey(owner));
+                "createAccountWithSeed"
+            }
+            Self::AdvanceNonceAccount => "advanceNonce",
+            Self::WithdrawNonceAccount { lamports } => {
+                field("lamports", Value::from(*lamports));
+                "withdrawFromNonce"
+            }
+            Self::InitializeNonceAccount { authority } => {
+                field("nonceAuthority", pubkey(authority));
+                "initializeNonce"
+            }
+            Self::AuthorizeNonceAccount { authority } => {
+                field("newAuthorized", pubkey(authority));
+                "authorizeNonce"
+            }
+            Self::Allocate { space } => {
+                field("space", Value::from(*space));
+                "allocate"
+            }
+            Self::AllocateWithSeed { base, seed, space, owner } => {
+                field("base", pubkey(base));
+                field("seed", Value::from(seed.as_str()));
+                field("space", Value::from(*space));
+                field("owner", pubkey(owner));
+                "allocateWithSeed"
+            }
+            Self::AssignWithSeed { base, seed, owner } => {
+                field("base", pubkey(base));
+                field("seed", Value::from(seed.as_str()));
+                field("owner", pubkey(owner));
+                "assignWithSeed"
+            }
+            Self::TransferWithSeed {
+                lamports,
+                from_seed,
+                from_owner,
+            } => {
+                field("lamports", Value::from(*lamports));
+                field("sourceSeed", Value::from(from_seed.as_str()));
+                field("sourceOwner", pubkey(from_owner));
+                "transferWithSeed"
+            }
+            Self::UpgradeNonceAccount => "upgradeNonce",
+        };
+        ParsedInfo {
+            program: "system".to_string(),
+            kind: kind.to_string(),
+            info: Value::Object(info),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(variant: u32, fields: &[&[u8]]) -> Vec<u8> {
+        let mut data = variant.to_le_bytes().to_vec();
+        for field in fields {
+            data.extend_from_slice(field);
+        }
+        data
+    }
+
+    fn bincode_string(text: &str) -> Vec<u8> {
+        let mut bytes = (text.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(text.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn decodes_every_variant() {
+        let (base, owner) = (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32]));
+        let lamports = 1_500_000_000u64.to_le_bytes();
+        let space = 165u64.to_le_bytes();
+        let seed = bincode_string("vault");
+        let cases = [
+            (
+                instruction(0, &[&lamports, &space, &[2; 32]]),
+                SystemInstruction::CreateAccount {
+                    lamports: 1_500_000_000,
+                    space: 165,
+                    owner,
+                },
+            ),
+            (instruction(1, &[&[2; 32]]), SystemInstruction::Assign { owner }),
+            (
+                instruction(2, &[&lamports]),
+                SystemInstruction::Transfer {
+                    lamports: 1_500_000_000,
+                },
+            ),
+            (
+                instruction(3, &[&[1; 32], &seed, &lamports, &space, &[2; 32]]),
+                SystemInstruction::CreateAccountWithSeed {
+                    base,
+                    seed: "vault".to_string(),
+                    lamports: 1_500_000_000,
+                    space: 165,
+                    owner,
+                },
+            ),
+            (instruction(4, &[]), SystemInstruction::AdvanceNonceAccount),
+            (
+                instruction(5, &[&lamports]),
+                SystemInstruction::WithdrawNonceAccount {
+                    lamports: 1_500_000_000,
+                },
+            ),
+            (
+                instruction(6, &[&[1; 32]]),
+                SystemInstruction::InitializeNonceAccount { authority: base },
+            ),
+            (
+                instruction(7, &[&[2; 32]]),
+                SystemInstruction::AuthorizeNonceAccount { authority: owner },
+            ),
+            (instruction(8, &[&space]), SystemInstruction::Allocate { space: 165 }),
+            (
+                instruction(9, &[&[1; 32], &seed, &space, &[2; 32]]),
+                SystemInstruction::AllocateWithSeed {
+                    base,
+                    seed: "vault".to_string(),
+                    space: 165,
+                    owner,
+                },
+            ),
+            (
+                instruction(10, &[&[1; 32], &seed, &[2; 32]]),
+                SystemInstruction::AssignWithSeed {
+                    base,
+                    seed: "vault".to_string(),
+                    owner,
+                },
+            ),
+            (
+                instruction(11, &[&lamports, &seed, &[2; 32]]),
+                SystemInstruction::TransferWithSeed {
+                    lamports: 1_500_000_000,
+                    from_seed: "vault".to_string(),
+                    from_owner: owner,
+                },
+            ),
+            (instruction(12, &[]), SystemInstruction::UpgradeNonceAccount),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(SystemInstruction::decode(&data), Some(expected));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_and_short_instructions() {
+        assert_eq!(SystemInstruction::decode(&instruction(13, &[])), None);
+        assert_eq!(SystemInstruction::decode(&[2, 0]), None);
+        assert_eq!(SystemInstruction::decode(&instruction(2, &[&[1, 2, 3]])), None);
+        // A seed longer than the data left
+        assert_eq!(SystemInstruction::decode(&instruction(10, &[&[1; 32], &[9, 0, 0, 0, 0, 0, 0, 0]])), None);
+    }
+
+    #[test]
+    fn decoded_instructions_read_like_parsed_ones() {
+        let parsed = SystemInstruction::Transfer { lamports: 5000 }.to_parsed(&["alice".to_string(), "bob".to_string()]);
+        assert_eq!(
+            parsed,
+            ParsedInfo {
+                program: "system".to_string(),
+                kind: "transfer".to_string(),
+                info: serde_json::json!({ "source": "alice", "destination": "bob", "lamports": 5000 }),
+            }
+        );
+        // Missing accounts are left out rather than misnamed
+        let parsed = SystemInstruction::Allocate { space: 10 }.to_parsed(&[]);
+        assert_eq!(parsed.info, serde_json::json!({ "space": 10 }));
+    }
+}
@@ -0,0 +1,57 @@
+use ratatui::style::{Color, Modifier, Style};
+
+// Styles used across the UI; the default matches the original dark-terminal colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub title: Style,     // Headings, hints and key names
+    pub value: Style,     // Fetched values
+    pub success: Style,   // Healthy and successful states
+    pub error: Style,     // Failures and warnings
+    pub highlight: Style, // Selected rows
+    pub border: Style,    // Popup borders
+    pub gauge: Style,     // Epoch progress gauge, filled on its background
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        name: "dark",
+        title: Style::new().fg(Color::Blue),
+        value: Style::new().fg(Color::Yellow),
+        success: Style::new().fg(Color::Green),
+        error: Style::new().fg(Color::Red),
+        highlight: Style::new().bg(Color::DarkGray).add_modifier(Modifier::BOLD),
+        border: Style::new().fg(Color::Red),
+        gauge: Style::new().fg(Color::Green).bg(Color::DarkGray),
+    };
+
+    // Darker colors that stay readable on a light background
+    pub const LIGHT: Theme = Theme {
+        name: "light",
+        title: Style::new().fg(Color::Rgb(0, 70, 160)),
+        value: Style::new().fg(Color::Rgb(140, 70, 0)),
+        success: Style::new().fg(Color::Rgb(0, 120, 40)),
+        error: Style::new().fg(Color::Rgb(180, 0, 0)),
+        highlight: Style::new().bg(Color::Rgb(210, 210, 220)).add_modifier(Modifier::BOLD),
+        border: Style::new().fg(Color::Rgb(0, 70, 160)),
+        gauge: Style::new().fg(Color::Rgb(0, 120, 40)).bg(Color::Rgb(210, 210, 220)),
+    };
+
+    pub const ALL: [Theme; 2] = [Theme::DARK, Theme::LIGHT];
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        Theme::ALL.into_iter().find(|theme| theme.name == name)
+    }
+
+    // The theme after this one, wrapping around
+    pub fn next(&self) -> Theme {
+        let index = Theme::ALL.iter().position(|theme| theme == self).unwrap_or(0);
+        Theme::ALL[(index + 1) % Theme::ALL.len()]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DARK
+    }
+}
@@ -0,0 +1,609 @@
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::format::format_token_amount;
+use crate::instruction::ParsedInfo;
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+// Sizes of the SPL Token account layouts
+pub const MINT_LEN: usize = 82;
+pub const ACCOUNT_LEN: usize = 165;
+
+// Token-2022 accounts with extensions are longer than ACCOUNT_LEN and say what they
+// are in the byte after it; mints are padded to that length first
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+// Whether `owner` is the Token or Token-2022 program
+pub fn is_token_program(owner: &str) -> bool {
+    owner == TOKEN_PROGRAM_ID || owner == TOKEN_2022_PROGRAM_ID
+}
+
+// The base layout of an account of type `account_type` with `len` bytes, extensions
+// aside; None if the data is something else
+fn base_layout(data: &[u8], len: usize, account_type: u8) -> Option<&[u8]> {
+    if data.len() == len || (data.len() > ACCOUNT_LEN && data[ACCOUNT_LEN] == account_type) {
+        Some(&data[..len])
+    } else {
+        None
+    }
+}
+
+// An SPL Token mint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mint {
+    pub mint_authority: Option<Pubkey>,
+    pub supply: u64,
+    pub decimals: u8,
+    pub freeze_authority: Option<Pubkey>,
+}
+
+// State of an SPL Token account
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountState {
+    Initialized,
+    Frozen,
+}
+
+impl AccountState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AccountState::Initialized => "Initialized",
+            AccountState::Frozen => "Frozen",
+        }
+    }
+}
+
+// An SPL Token account
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub delegate: Option<Pubkey>,
+    pub state: AccountState,
+    pub is_native: Option<u64>, // Rent-exempt reserve of a wrapped SOL account
+    pub delegated_amount: u64,
+    pub close_authority: Option<Pubkey>,
+}
+
+// Reads the fixed little-endian fields of a packed account or instruction, failing on
+// short or invalid data
+pub struct Reader<'a> {
+    pub data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(taken)
+    }
+
+    pub fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    pub fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    pub fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    pub fn pubkey(&mut self) -> Option<Pubkey> {
+        Some(Pubkey::new_from_array(self.take(32)?.try_into().ok()?))
+    }
+
+    // A COption is a u32 tag (0 or 1) followed by the value, which is present either way
+    pub fn option<T>(&mut self, read: impl FnOnce(&mut Self) -> Option<T>) -> Option<Option<T>> {
+        let tag = self.u32()?;
+        let value = read(self)?;
+        match tag {
+            0 => Some(None),
+            1 => Some(Some(value)),
+            _ => None,
+        }
+    }
+}
+
+// Decode a mint, with or without Token-2022 extensions; None if the data is not an
+// initialized mint
+pub fn unpack_mint(data: &[u8]) -> Option<Mint> {
+    let mut reader = Reader {
+        data: base_layout(data, MINT_LEN, ACCOUNT_TYPE_MINT)?,
+    };
+    let mint_authority = reader.option(Reader::pubkey)?;
+    let supply = reader.u64()?;
+    let decimals = reader.u8()?;
+    let is_initialized = reader.u8()?;
+    let freeze_authority = reader.option(Reader::pubkey)?;
+    if is_initialized != 1 {
+        return None;
+    }
+    Some(Mint {
+        mint_authority,
+        supply,
+        decimals,
+        freeze_authority,
+    })
+}
+
+// Decode a token account, with or without Token-2022 extensions; None if the data is
+// not an initialized token account
+pub fn unpack_account(data: &[u8]) -> Option<TokenAccount> {
+    let mut reader = Reader {
+        data: base_layout(data, ACCOUNT_LEN, ACCOUNT_TYPE_ACCOUNT)?,
+    };
+    let mint = reader.pubkey()?;
+    let owner = reader.pubkey()?;
+    let amount = reader.u64()?;
+    let delegate = reader.option(Reader::pubkey)?;
+    let state = match reader.u8()? {
+        1 => AccountState::Initialized,
+        2 => AccountState::Frozen,
+        _ => return None,
+    };
+    let is_native = reader.option(Reader::u64)?;
+    let delegated_amount = reader.u64()?;
+    let close_authority = reader.option(Reader::pubkey)?;
+    Some(TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate,
+        state,
+        is_native,
+        delegated_amount,
+        close_authority,
+    })
+}
+
+// Instruction names of the Token program by their tag, as the node's jsonParsed
+// encoding has them; Token-2022 adds the extensions after them
+const INSTRUCTION_NAMES: [&str; 25] = [
+    "initializeMint",
+    "initializeAccount",
+    "initializeMultisig",
+    "transfer",
+    "approve",
+    "revoke",
+    "setAuthority",
+    "mintTo",
+    "burn",
+    "closeAccount",
+    "freezeAccount",
+    "thawAccount",
+    "transferChecked",
+    "approveChecked",
+    "mintToChecked",
+    "burnChecked",
+    "initializeAccount2",
+    "syncNative",
+    "initializeAccount3",
+    "initializeMultisig2",
+    "initializeMint2",
+    "getAccountDataSize",
+    "initializeImmutableOwner",
+    "amountToUiAmount",
+    "uiAmountToAmount",
+];
+const EXTENSION_NAMES: [&str; 20] = [
+    "initializeMintCloseAuthority",
+    "transferFeeExtension",
+    "confidentialTransferExtension",
+    "defaultAccountStateExtension",
+    "reallocate",
+    "memoTransferExtension",
+    "createNativeMint",
+    "initializeNonTransferableMint",
+    "interestBearingMintExtension",
+    "cpiGuardExtension",
+    "initializePermanentDelegate",
+    "transferHookExtension",
+    "confidentialTransferFeeExtension",
+    "withdrawExcessLamports",
+    "metadataPointerExtension",
+    "groupPointerExtension",
+    "groupMemberPointerExtension",
+    "confidentialMintBurnExtension",
+    "scaledUiAmountExtension",
+    "pausableExtension",
+];
+
+// An instruction of the Token or Token-2022 program. Amounts are in base units.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenInstruction {
+    InitializeMint {
+        decimals: u8,
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+    InitializeMint2 {
+        decimals: u8,
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+    InitializeAccount,
+    Transfer {
+        amount: u64,
+    },
+    Approve {
+        amount: u64,
+    },
+    MintTo {
+        amount: u64,
+    },
+    Burn {
+        amount: u64,
+    },
+    CloseAccount,
+    TransferChecked {
+        amount: u64,
+        decimals: u8,
+    },
+    ApproveChecked {
+        amount: u64,
+        decimals: u8,
+    },
+    MintToChecked {
+        amount: u64,
+        decimals: u8,
+    },
+    BurnChecked {
+        amount: u64,
+        decimals: u8,
+    },
+    // Known by name only, such as the Token-2022 extensions
+    Other(&'static str),
+}
+
+// An optional pubkey of instruction data: a one byte tag, then the key if it's 1
+fn optional_pubkey(reader: &mut Reader) -> Option<Option<Pubkey>> {
+    match reader.u8()? {
+        0 => Some(None),
+        1 => Some(Some(reader.pubkey()?)),
+        _ => None,
+    }
+}
+
+impl TokenInstruction {
+    // Decode instruction data: a one byte tag and the fields in order. Extension
+    // instructions only exist in Token-2022. None for unknown tags and short data.
+    pub fn decode(data: &[u8], token_2022: bool) -> Option<Self> {
+        let mut reader = Reader { data };
+        let tag = reader.u8()?;
+        let instruction = match tag {
+            0 => Self::InitializeMint {
+                decimals: reader.u8()?,
+                mint_authority: reader.pubkey()?,
+                freeze_authority: optional_pubkey(&mut reader)?,
+            },
+            1 => Self::InitializeAccount,
+            3 => Self::Transfer { amount: reader.u64()? },
+            4 => Self::Approve { amount: reader.u64()? },
+            7 => Self::MintTo { amount: reader.u64()? },
+            8 => Self::Burn { amount: reader.u64()? },
+            9 => Self::CloseAccount,
+            12 => Self::TransferChecked {
+                amount: reader.u64()?,
+                decimals: reader.u8()?,
+            },
+            13 => Self::ApproveChecked {
+                amount: reader.u64()?,
+                decimals: reader.u8()?,
+            },
+            14 => Self::MintToChecked {
+                amount: reader.u64()?,
+                decimals: reader.u8()?,
+            },
+            15 => Self::BurnChecked {
+                amount: reader.u64()?,
+                decimals: reader.u8()?,
+            },
+            20 => Self::InitializeMint2 {
+                decimals: reader.u8()?,
+                mint_authority: reader.pubkey()?,
+                freeze_authority: optional_pubkey(&mut reader)?,
+            },
+            tag => {
+                let tag = usize::from(tag);
+                match INSTRUCTION_NAMES.get(tag) {
+                    Some(name) => Self::Other(name),
+                    None if token_2022 => Self::Other(EXTENSION_NAMES.get(tag - INSTRUCTION_NAMES.len())?),
+                    None => return None,
+                }
+            }
+        };
+        Some(instruction)
+    }
+
+    // The instruction as the node's jsonParsed encoding has it, with its accounts
+    // named; `program` is "spl-token" or "spl-token-2022"
+    pub fn to_parsed(&self, program: &str, accounts: &[String]) -> ParsedInfo {
+        let mut info = serde_json::Map::new();
+        let account_names: &[&str] = match self {
+            Self::InitializeMint { .. } => &["mint", "rentSysvar"],
+            Self::InitializeMint2 { .. } => &["mint"],
+            Self::InitializeAccount => &["account", "mint", "owner", "rentSysvar"],
+            Self::Transfer { .. } => &["source", "destination", "authority"],
+            Self::Approve { .. } => &["source", "delegate", "owner"],
+            Self::MintTo { .. } | Self::MintToChecked { .. } => &["mint", "account", "mintAuthority"],
+            Self::Burn { .. } | Self::BurnChecked { .. } => &["account", "mint", "authority"],
+            Self::CloseAccount => &["account", "destination", "owner"],
+            Self::TransferChecked { .. } => &["source", "mint", "destination", "authority"],
+            Self::ApproveChecked { .. } => &["source", "mint", "delegate", "owner"],
+            Self::Other(_) => &[],
+        };
+        for (name, account) in account_names.iter().zip(accounts) {
+            info.insert(name.to_string(), Value::from(account.as_str()));
+        }
+        let kind = match self {
+            Self::InitializeMint {
+                decimals,
+                mint_authority,
+                freeze_authority,
+            }
+            | Self::InitializeMint2 {
+                decimals,
+                mint_authority,
+                freeze_authority,
+            } => {
+                info.insert("decimals".to_string(), Value::from(*decimals));
+                info.insert("mintAuthority".to_string(), Value::from(mint_authority.to_string()));
+                if let Some(freeze_authority) = freeze_authority {
+                    info.insert("freezeAuthority".to_string(), Value::from(freeze_authority.to_string()));
+                }
+                if matches!(self, Self::InitializeMint { .. }) {
+                    "initializeMint"
+                } else {
+                    "initializeMint2"
+                }
+            }
+            Self::InitializeAccount => "initializeAccount",
+            Self::Transfer { amount } | Self::Approve { amount } | Self::MintTo { amount } | Self::Burn { amount } => {
+                // The node sends unchecked amounts as strings
+                info.insert("amount".to_string(), Value::from(amount.to_string()));
+                match self {
+                    Self::Transfer { .. } => "transfer",
+                    Self::Approve { .. } => "approve",
+                    Self::MintTo { .. } => "mintTo",
+                    _ => "burn",
+                }
+            }
+            Self::CloseAccount => "closeAccount",
+            Self::TransferChecked { amount, decimals }
+            | Self::ApproveChecked { amount, decimals }
+            | Self::MintToChecked { amount, decimals }
+            | Self::BurnChecked { amount, decimals } => {
+                info.insert("tokenAmount".to_string(), token_amount(*amount, *decimals));
+                match self {
+                    Self::TransferChecked { .. } => "transferChecked",
+                    Self::ApproveChecked { .. } => "approveChecked",
+                    Self::MintToChecked { .. } => "mintToChecked",
+                    _ => "burnChecked",
+                }
+            }
+            Self::Other(name) => name,
+        };
+        ParsedInfo {
+            program: program.to_string(),
+            kind: kind.to_string(),
+            info: Value::Object(info),
+        }
+    }
+}
+
+// An amount with its mint's decimals, shaped like the node's uiTokenAmount
+pub fn token_amount(amount: u64, decimals: u8) -> Value {
+    serde_json::json!({
+        "amount": amount.to_string(),
+        "decimals": decimals,
+        "uiAmountString": format_token_amount(u128::from(amount), decimals),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coption(value: Option<&[u8]>, len: usize) -> Vec<u8> {
+        let mut bytes = vec![u8::from(value.is_some()), 0, 0, 0];
+        bytes.extend(value.map_or(vec![0; len], <[u8]>::to_vec));
+        bytes
+    }
+
+    // A USDC-like mint: 6 decimals, a mint authority and no freeze authority
+    fn mint_fixture() -> Vec<u8> {
+        let mut data = coption(Some(&[7; 32]), 32);
+        data.extend(1_000_000_000u64.to_le_bytes());
+        data.extend([6, 1]);
+        data.extend(coption(None, 32));
+        data
+    }
+
+    // A frozen token account with a delegate and no close authority
+    fn account_fixture() -> Vec<u8> {
+        let mut data = vec![1; 32];
+        data.extend([2; 32]);
+        data.extend(2_500_000u64.to_le_bytes());
+        data.extend(coption(Some(&[3; 32]), 32));
+        data.push(2);
+        data.extend(coption(None, 8));
+        data.extend(500_000u64.to_le_bytes());
+        data.extend(coption(None, 32));
+        data
+    }
+
+    #[test]
+    fn unpacks_mint() {
+        let data = mint_fixture();
+        assert_eq!(data.len(), MINT_LEN);
+        assert_eq!(
+            unpack_mint(&data),
+            Some(Mint {
+                mint_authority: Some(Pubkey::new_from_array([7; 32])),
+                supply: 1_000_000_000,
+                decimals: 6,
+                freeze_authority: None,
+            })
+        );
+    }
+
+    #[test]
+    fn unpacks_token_account() {
+        let data = account_fixture();
+        assert_eq!(data.len(), ACCOUNT_LEN);
+        assert_eq!(
+            unpack_account(&data),
+            Some(TokenAccount {
+                mint: Pubkey::new_from_array([1; 32]),
+                owner: Pubkey::new_from_array([2; 32]),
+                amount: 2_500_000,
+                delegate: Some(Pubkey::new_from_array([3; 32])),
+                state: AccountState::Frozen,
+                is_native: None,
+                delegated_amount: 500_000,
+                close_authority: None,
+            })
+        );
+    }
+
+    fn instruction(tag: u8, fields: &[&[u8]]) -> Vec<u8> {
+        let mut data = vec![tag];
+        for field in fields {
+            data.extend_from_slice(field);
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_token_instructions() {
+        let amount = 2_500_000u64.to_le_bytes();
+        let authority = Pubkey::new_from_array([7; 32]);
+        let cases = [
+            (
+                instruction(0, &[&[6], &[7; 32], &[0]]),
+                TokenInstruction::InitializeMint {
+                    decimals: 6,
+                    mint_authority: authority,
+                    freeze_authority: None,
+                },
+            ),
+            (
+                instruction(20, &[&[9], &[7; 32], &[1], &[7; 32]]),
+                TokenInstruction::InitializeMint2 {
+                    decimals: 9,
+                    mint_authority: authority,
+                    freeze_authority: Some(authority),
+                },
+            ),
+            (instruction(1, &[]), TokenInstruction::InitializeAccount),
+            (instruction(3, &[&amount]), TokenInstruction::Transfer { amount: 2_500_000 }),
+            (instruction(4, &[&amount]), TokenInstruction::Approve { amount: 2_500_000 }),
+            (instruction(7, &[&amount]), TokenInstruction::MintTo { amount: 2_500_000 }),
+            (instruction(8, &[&amount]), TokenInstruction::Burn { amount: 2_500_000 }),
+            (instruction(9, &[]), TokenInstruction::CloseAccount),
+            (
+                instruction(12, &[&amount, &[6]]),
+                TokenInstruction::TransferChecked {
+                    amount: 2_500_000,
+                    decimals: 6,
+                },
+            ),
+            (
+                instruction(15, &[&amount, &[6]]),
+                TokenInstruction::BurnChecked {
+                    amount: 2_500_000,
+                    decimals: 6,
+                },
+            ),
+            (instruction(17, &[]), TokenInstruction::Other("syncNative")),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(TokenInstruction::decode(&data, false), Some(expected));
+        }
+    }
+
+    #[test]
+    fn token_2022_extensions_and_bad_instructions() {
+        // Extensions are named by Token-2022 only
+        assert_eq!(
+            TokenInstruction::decode(&[26, 1], true),
+            Some(TokenInstruction::Other("transferFeeExtension"))
+        );
+        assert_eq!(TokenInstruction::decode(&[26, 1], false), None);
+        assert_eq!(TokenInstruction::decode(&[200], true), None);
+        // Short amounts and a bad optional key tag
+        assert_eq!(TokenInstruction::decode(&[3, 1, 2], false), None);
+        assert_eq!(TokenInstruction::decode(&instruction(0, &[&[6], &[7; 32], &[2]]), false), None);
+    }
+
+    #[test]
+    fn decoded_token_instructions_read_like_parsed_ones() {
+        let accounts = ["src".to_string(), "mint".to_string(), "dst".to_string(), "owner".to_string()];
+        let parsed = TokenInstruction::TransferChecked {
+            amount: 1_250_000,
+            decimals: 6,
+        }
+        .to_parsed("spl-token-2022", &accounts);
+        assert_eq!(parsed.program, "spl-token-2022");
+        assert_eq!(parsed.kind, "transferChecked");
+        assert_eq!(
+            parsed.info,
+            serde_json::json!({
+                "source": "src",
+                "mint": "mint",
+                "destination": "dst",
+                "authority": "owner",
+                "tokenAmount": { "amount": "1250000", "decimals": 6, "uiAmountString": "1.25" },
+            })
+        );
+        let parsed = TokenInstruction::MintTo { amount: 42 }.to_parsed("spl-token", &accounts[..2]);
+        assert_eq!(parsed.info, serde_json::json!({ "mint": "src", "account": "mint", "amount": "42" }));
+    }
+
+    #[test]
+    fn unpacks_token_2022_accounts_with_extensions() {
+        // Mint padded to the account length, its type, then a mint close authority extension
+        let mut mint = mint_fixture();
+        mint.resize(ACCOUNT_LEN, 0);
+        mint.push(ACCOUNT_TYPE_MINT);
+        mint.extend([3, 0, 32, 0]);
+        mint.extend([9; 32]);
+        assert_eq!(unpack_mint(&mint).map(|mint| mint.decimals), Some(6));
+        assert_eq!(unpack_account(&mint), None);
+
+        let mut account = account_fixture();
+        account.push(ACCOUNT_TYPE_ACCOUNT);
+        account.extend([7, 0, 0, 0]); // Immutable owner, no data
+        assert_eq!(unpack_account(&account).map(|account| account.amount), Some(2_500_000));
+        assert_eq!(unpack_mint(&account), None);
+    }
+
+    #[test]
+    fn rejects_garbled_data() {
+        // Wrong length
+        assert_eq!(unpack_mint(&mint_fixture()[..81]), None);
+        assert_eq!(unpack_account(&[0; 10]), None);
+
+        // Invalid COption tag
+        let mut mint = mint_fixture();
+        mint[0] = 9;
+        assert_eq!(unpack_mint(&mint), None);
+
+        // Uninitialized mint and account
+        let mut mint = mint_fixture();
+        mint[45] = 0;
+        assert_eq!(unpack_mint(&mint), None);
+        let mut account = account_fixture();
+        account[108] = 0;
+        assert_eq!(unpack_account(&account), None);
+    }
+}
@@ -0,0 +1,301 @@
+// The library's public API as the binary and other callers use it
+use serde_json::{json, Value};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use soonscan::app::{parse_since, App, HistoryEntry, RpcNetwork, Tab};
+use soonscan::bookmarks::{Bookmark, Bookmarks};
+use soonscan::error::AppError;
+use soonscan::format::{
+    format_duration, format_lamport_change, format_lamports, format_ratio, format_relative, format_token_amount,
+    group_digits, truncate, CurrencyUnit, LAMPORTS_PER_SOL,
+};
+use soonscan::query::{check_transaction, normalize_query, parse_slot, QueryKind};
+use soonscan::keymap::{AppAction, Keymap};
+use soonscan::rpc::RpcApi;
+use soonscan::{
+    command, config, fees, fixtures, history, instruction, json_tree, keymap, labels, live, loader, logging, metadata,
+    stake, storage, system, theme, token,
+};
+
+// A JSON-RPC server that answers every request with `result`
+async fn rpc_answering(result: Value) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let body = body.clone();
+            tokio::spawn(async move {
+                let mut request = [0; 8192];
+                let _ = stream.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    url
+}
+
+#[test]
+fn queries_are_parsed_and_normalized() {
+    let signature = Signature::from([7; 64]).to_string();
+    let link = format!("https://explorer.solana.com/tx/{}?cluster=devnet", signature);
+    assert_eq!(normalize_query(&link), signature);
+    assert_eq!(QueryKind::parse(&signature).map(|kind| kind.label()).unwrap(), "transaction");
+    assert_eq!(QueryKind::parse("11111111111111111111111111111111").unwrap().label(), "account");
+    assert_eq!(QueryKind::parse("42").unwrap(), QueryKind::Slot(42));
+    assert!(matches!(QueryKind::parse("hello"), Err(AppError::InvalidQuery(_))));
+    assert_eq!(parse_slot("301234567"), Some(301_234_567));
+    assert_eq!(parse_slot("12a"), None);
+}
+
+#[test]
+fn amounts_and_times_are_formatted() {
+    assert_eq!(format_lamports(LAMPORTS_PER_SOL, CurrencyUnit::Sol), "◎ 1.000000000");
+    assert_eq!(format_lamports(1_234_567, CurrencyUnit::Lamports), "1,234,567 lamports");
+    assert_eq!(format_lamport_change(0, 1_000, CurrencyUnit::Lamports), "+1,000 lamports");
+    assert_eq!(format_token_amount(1_500_000, 6), "1.5");
+    assert_eq!(format_ratio(1, 4), "25.00%");
+    assert_eq!(group_digits(u64::MAX), "18,446,744,073,709,551,615");
+    assert_eq!(format_relative(100, 160), "1 min ago");
+    assert_eq!(format_duration(200), "3m 20s");
+    assert_eq!(truncate("explorer", 5), "expl…");
+    assert_eq!(CurrencyUnit::from_name("lamports").map(CurrencyUnit::toggle), Some(CurrencyUnit::Sol));
+}
+
+#[tokio::test]
+async fn transactions_are_checked_through_the_given_rpc() {
+    let signature = Signature::from([7; 64]).to_string();
+    let status = json!({
+        "context": { "slot": 12 },
+        "value": [{
+            "slot": 10,
+            "confirmations": null,
+            "err": null,
+            "status": { "Ok": null },
+            "confirmationStatus": "finalized",
+        }],
+    });
+    let api = RpcApi::new(rpc_answering(status).await, CommitmentConfig::default());
+    let found = check_transaction(&api, &format!("https://solscan.io/tx/{}", signature)).await.unwrap();
+    assert_eq!(found.slot, 10);
+    assert!(found.status.is_ok());
+
+    let missing = json!({ "context": { "slot": 12 }, "value": [null] });
+    let api = RpcApi::new(rpc_answering(missing).await, CommitmentConfig::default());
+    assert!(matches!(check_transaction(&api, &signature).await, Err(AppError::NotFound(_))));
+
+    // Not a signature: rejected before anything is sent
+    let api = RpcApi::new("http://127.0.0.1:9", CommitmentConfig::default());
+    assert!(matches!(check_transaction(&api, "42").await, Err(AppError::SignatureParse(_))));
+}
+
+#[tokio::test]
+async fn the_app_runs_without_a_terminal() {
+    assert_eq!(RpcNetwork::from_name("Testnet"), Some(RpcNetwork::Testnet));
+    assert_eq!(RpcNetwork::Devnet.next(), RpcNetwork::Testnet);
+    assert_eq!(Tab::Dashboard.next(), Tab::Search);
+    assert_eq!(parse_since("24h", 1_700_000_000), Some(1_700_000_000 - 86_400));
+    let entry = json!({ "signature": "sig", "slot": 3, "err": null, "memo": "hi" });
+    assert_eq!(HistoryEntry::from_value(&entry).unwrap().to_value()["memo"], "hi");
+
+    let mut app = App::default();
+    app.handle_event(crossterm::event::Event::Key(crossterm::event::KeyEvent::from(
+        crossterm::event::KeyCode::Char('q'),
+    )));
+    assert!(app.exit);
+
+    // A node that answers every method with a number
+    let api = RpcApi::new(rpc_answering(json!(42)).await, CommitmentConfig::default());
+    let (data, _) = App::fetch_initial_blockchain_data(&api).await.unwrap();
+    assert_eq!((data.slot_info, data.transaction_info), (Some(42), Some(42)));
+}
+
+#[test]
+fn bookmarks_and_history_keep_their_order() {
+    let mut bookmarks = Bookmarks::default();
+    let bookmark = |label: &str| Bookmark {
+        label: label.to_string(),
+        query: "42".to_string(),
+        network: RpcNetwork::Devnet,
+    };
+    bookmarks.add(bookmark("first"));
+    bookmarks.add(bookmark("renamed"));
+    assert_eq!(bookmarks.len(), 1);
+    assert_eq!(bookmarks.get(0).map(|bookmark| bookmark.label.as_str()), Some("renamed"));
+    assert_eq!(bookmarks.iter().count(), 1);
+    bookmarks.remove(0);
+    assert!(bookmarks.is_empty());
+
+    let mut history = history::QueryHistory::default();
+    history.add("42");
+    history.add(" 43 ");
+    history.add("42");
+    assert_eq!((history.get(0), history.get(1), history.get(2)), (Some("42"), Some("43"), None));
+}
+
+#[test]
+fn commands_keys_and_themes_resolve() {
+    assert_eq!(command::parse("network devnet"), Ok(AppAction::SetNetwork(RpcNetwork::Devnet)));
+    assert!(command::parse("").is_err());
+    assert_eq!(command::complete("commi").as_deref(), Some("commitment "));
+
+    let key = keymap::parse_key("ctrl+n").unwrap();
+    assert_eq!(keymap::key_name(key.0, key.1), "Ctrl+N");
+    let mut keymap = Keymap::default();
+    keymap.bind(key, AppAction::from_name("refresh").unwrap());
+    assert_eq!(keymap.action(&crossterm::event::KeyEvent::new(key.0, key.1)), Some(AppAction::Refresh));
+    assert_eq!(keymap.keys_for(&AppAction::Refresh), ["r", "Ctrl+N"]);
+    assert_eq!(AppAction::Quit.description(), "Quit application");
+    assert!(keymap::ACTION_NAMES.iter().all(|(name, action)| AppAction::from_name(name) == Some(action.clone())));
+
+    assert_eq!(theme::Theme::from_name("light"), Some(theme::Theme::LIGHT));
+    assert_eq!(theme::Theme::ALL.map(|theme| theme.next()), [theme::Theme::LIGHT, theme::Theme::DARK]);
+    assert!(config::Config::default().keys.is_empty());
+}
+
+#[test]
+fn labels_storage_and_logs() {
+    // Labels are set up once per process; other tests here may have looked one up already
+    labels::init(Default::default());
+    assert_eq!(labels::lookup(system::SYSTEM_PROGRAM_ID), Some("System Program"));
+    assert_eq!(labels::display("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").as_deref(), Some("SPL Token (Toke…)"));
+
+    assert_eq!(storage::expand_home("soonscan.json"), std::path::PathBuf::from("soonscan.json"));
+    for dir in [storage::data_dir(), storage::config_dir(), storage::state_dir()].into_iter().flatten() {
+        assert!(dir.ends_with("soonscan"), "{}", dir.display());
+    }
+
+    let buffer = logging::LogBuffer::default();
+    buffer.push("12:00:00.000  INFO started");
+    assert_eq!(buffer.lines(), ["12:00:00.000  INFO started"]);
+    let dir = std::env::temp_dir().join(format!("soonscan-api-logs-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    assert!(logging::RotatingFile::open(&dir.join(logging::LOG_FILE_NAME), 1024).is_ok());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn errors_fees_and_fixtures() {
+    assert_eq!(AppError::InvalidQuery("x".to_string()).exit_code(), 2);
+    assert_eq!(AppError::Timeout.exit_code(), 4);
+
+    let response = json!([{ "slot": 1, "prioritizationFee": 0 }, { "slot": 2, "prioritizationFee": 90 }]);
+    let fees = fees::prioritization_fees(&response);
+    assert_eq!(fees, [0, 90]);
+    assert_eq!(fees::percentiles(&fees).map(|percentiles| (percentiles.median, percentiles.max)), Some((0, 90)));
+    assert_eq!(fees::percentiles(&[]), None);
+
+    let dir = std::env::temp_dir().join(format!("soonscan-api-fixtures-{}", std::process::id()));
+    let params = json!([42]);
+    assert!(matches!(fixtures::load(&dir, "getBlock", &params), Err(AppError::NotRecorded { .. })));
+    fixtures::save(&dir, "getBlock", &params, &json!({ "result": 1 })).unwrap();
+    assert!(fixtures::fixture_path(&dir, "getBlock", &params).exists());
+    assert_eq!(fixtures::load(&dir, "getBlock", &params).unwrap(), json!({ "result": 1 }));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn instructions_are_listed_and_described() {
+    let transfer = solana_sdk::bs58::encode([[2, 0, 0, 0].as_slice(), &5000u64.to_le_bytes()].concat()).into_string();
+    let meta = json!({
+        "accountKeys": [
+            "92vBXvpUZeRYJAKEdaTKLnaMtgi8DmWFGxgbW4e8KZ1Q",
+            "86oFo1uoVEhvtTrQjW8ooy2tNeTYh1VnTYXmiMLRDVkk",
+            system::SYSTEM_PROGRAM_ID,
+        ],
+        "instructions": [{ "programIdIndex": 2, "accounts": [0, 1], "data": transfer }],
+        "innerInstructions": [],
+    });
+    let listed = instruction::list_instructions(&meta, false).unwrap();
+    assert_eq!(listed[0].number, "0");
+    assert_eq!(
+        listed[0].instruction.describe(Some(system::SYSTEM_PROGRAM_ID), CurrencyUnit::Lamports).as_deref(),
+        Some("Transfer 5,000 lamports from 92vBXvp… to 86oFo1u…")
+    );
+    assert!(!instruction::inner_instructions_unrecorded(&meta));
+
+    let budget = instruction::ComputeBudget::from_instructions([(None, &listed[0].instruction)]);
+    assert_eq!(budget.priority_fee(), Some(0));
+    assert_eq!(instruction::base_fee(2), Some(2 * instruction::LAMPORTS_PER_SIGNATURE));
+    let err = json!({ "InstructionError": [1, "InvalidAccountData"] });
+    assert_eq!(instruction::failed_instruction(&err), Some(1));
+    assert_eq!(instruction::describe_transaction_error(&err), "Instruction #1 failed: invalid account data");
+
+    let decoded = system::SystemInstruction::decode(&[[2, 0, 0, 0].as_slice(), &5000u64.to_le_bytes()].concat());
+    assert_eq!(decoded, Some(system::SystemInstruction::Transfer { lamports: 5000 }));
+    let parsed = decoded.unwrap().to_parsed(&["alice".to_string(), "bob".to_string()]);
+    assert_eq!((parsed.program.as_str(), parsed.kind.as_str()), ("system", "transfer"));
+}
+
+#[test]
+fn account_data_is_unpacked() {
+    use solana_sdk::pubkey::Pubkey;
+
+    // A mint with 6 decimals and neither authority
+    let mut mint = vec![0; 36];
+    mint.extend(1_000_000u64.to_le_bytes());
+    mint.extend([6, 1]);
+    mint.extend([0; 36]);
+    assert_eq!(mint.len(), token::MINT_LEN);
+    let unpacked = token::unpack_mint(&mint).unwrap();
+    assert_eq!((unpacked.supply, unpacked.decimals, unpacked.mint_authority), (1_000_000, 6, None));
+    assert_eq!(token::unpack_account(&mint), None);
+    assert!(token::is_token_program(token::TOKEN_2022_PROGRAM_ID));
+    assert_eq!(token::token_amount(1_500_000, 6)["uiAmountString"], "1.5");
+    let transfer = [[3].as_slice(), &42u64.to_le_bytes()].concat();
+    let decoded = token::TokenInstruction::decode(&transfer, false);
+    assert_eq!(decoded, Some(token::TokenInstruction::Transfer { amount: 42 }));
+    let mut reader = token::Reader { data: &mint[36..] };
+    assert_eq!(reader.u64(), Some(1_000_000));
+
+    let mut program = 2u32.to_le_bytes().to_vec();
+    program.extend([9; 32]);
+    let program_data = Pubkey::new_from_array([9; 32]);
+    assert_eq!(loader::unpack(&program), Some(loader::UpgradeableState::Program { program_data }));
+    let loader_id = Pubkey::from_str_const(loader::BPF_LOADER_UPGRADEABLE_ID);
+    let program_id = Pubkey::new_unique();
+    assert_eq!(
+        loader::program_data_address(&program_id),
+        Pubkey::find_program_address(&[program_id.as_ref()], &loader_id).0
+    );
+
+    let mut uninitialized = vec![0; 200];
+    assert_eq!(stake::unpack(&uninitialized).map(|state| state.name()), Some("Uninitialized"));
+    uninitialized[0] = 9;
+    assert_eq!(stake::unpack(&uninitialized), None);
+
+    let mint_address = Pubkey::new_unique();
+    let metadata_program = Pubkey::from_str_const(metadata::METADATA_PROGRAM_ID);
+    let seeds = [b"metadata".as_slice(), metadata_program.as_ref(), mint_address.as_ref()];
+    assert_eq!(metadata::metadata_address(&mint_address), Pubkey::find_program_address(&seeds, &metadata_program).0);
+    assert!(matches!(metadata::decode(&[9]), Err(metadata::DecodeError::NotMetadata(9))));
+    let off_chain = metadata::OffChainMetadata::from_value(&json!({ "image": "https://example.com/42.png" }));
+    assert_eq!(off_chain.image.as_deref(), Some("https://example.com/42.png"));
+}
+
+#[test]
+fn raw_json_folds_and_live_urls_follow_the_rpc() {
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let mut tree = json_tree::JsonTree::new(json!({ "meta": { "err": null }, "slot": 7 }));
+    tree.toggle();
+    tree.move_cursor(1, 10);
+    tree.set_root(json!({ "meta": { "err": null }, "slot": 8 }));
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 30, 3));
+    let theme = theme::Theme::default();
+    json_tree::JsonTreeView { tree: &tree, theme: &theme }.render(buffer.area, &mut buffer);
+    let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+    assert!(text.contains("▸ meta: {1 key}") && text.contains("slot: 8"), "{}", text);
+
+    assert_eq!(live::ws_url("https://rpc.devnet.soo.network/rpc").as_deref(), Some("wss://rpc.devnet.soo.network/rpc"));
+    assert_eq!(live::ws_url("ftp://host"), None);
+    assert!(live::backoff(0) < live::backoff(1));
+    assert_eq!(live::backoff(30), live::backoff(31));
+}